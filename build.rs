@@ -24,9 +24,20 @@ struct ToolInfo {
     name: String,
     description: String,
     args_type: String,
+    output_type: Option<String>,
+    annotations: ToolAnnotations,
+    group: String,
     function_path: String,
 }
 
+/// Behaviour hints of a tool, advertised to the clients to gate their confirmations
+#[derive(Debug)]
+struct ToolAnnotations {
+    read_only: bool,
+    destructive: bool,
+    idempotent: bool,
+}
+
 fn scan_for_tools(dir: &Path) -> Vec<ToolInfo> {
     let mut tools = Vec::new();
 
@@ -39,10 +50,10 @@ fn scan_for_tools(dir: &Path) -> Vec<ToolInfo> {
                 tools.extend(scan_for_tools(&path));
             } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
                 // Parse Rust files for #[mcp_tool] attributes
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Some(tool) = parse_mcp_tool_from_file(&content, &path) {
-                        tools.push(tool);
-                    }
+                if let Ok(content) = fs::read_to_string(&path)
+                    && let Some(tool) = parse_mcp_tool_from_file(&content, &path)
+                {
+                    tools.push(tool);
                 }
             }
         }
@@ -77,15 +88,41 @@ fn parse_mcp_tool_from_file(content: &str, file_path: &Path) -> Option<ToolInfo>
     // Extract args type (second parameter)
     let args_start = fn_sig.find("args:")? + "args:".len();
     let args_end = fn_sig[args_start..].find(')')?;
-    let args_type = fn_sig[args_start..args_start + args_end].trim().to_string();
+    let args_type = fn_sig[args_start..args_start + args_end].trim();
+
+    // Build function and args type paths from file path.
+    // The args type is declared in the same module as the tool function.
+    let module_path = build_module_path(file_path);
+    let function_path = format!("{}::{}", module_path, fn_name);
+    let args_type = format!("{}::{}", module_path, args_type);
+
+    // The output type is either a path or declared in the same module too
+    let output_type = extract_attribute_value(attr_content, "output").map(|output| {
+        if output.contains("::") {
+            output
+        } else {
+            format!("{}::{}", module_path, output)
+        }
+    });
 
-    // Build function path from file path
-    let function_path = build_function_path(file_path, fn_name);
+    // Read-only tools change nothing, so are neither destructive nor repeated with effect
+    let read_only = extract_bool_attribute(attr_content, "read_only")
+        .unwrap_or_else(|| panic!("Tool {} does not declare read_only", name));
+    let annotations = ToolAnnotations {
+        read_only,
+        destructive: !read_only
+            && extract_bool_attribute(attr_content, "destructive").unwrap_or(false),
+        idempotent: read_only
+            || extract_bool_attribute(attr_content, "idempotent").unwrap_or(false),
+    };
 
     Some(ToolInfo {
         name,
         description,
         args_type,
+        output_type,
+        annotations,
+        group: tool_group(file_path),
         function_path,
     })
 }
@@ -97,7 +134,32 @@ fn extract_attribute_value(attr: &str, key: &str) -> Option<String> {
     Some(attr[start..start + end].to_string())
 }
 
-fn build_function_path(file_path: &Path, fn_name: &str) -> String {
+fn extract_bool_attribute(attr: &str, key: &str) -> Option<bool> {
+    let pattern = format!("{} = ", key);
+    let start = attr.find(&pattern)? + pattern.len();
+    if attr[start..].starts_with("true") {
+        Some(true)
+    } else if attr[start..].starts_with("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Group of a tool, the directory under src/mcp/tools it is declared in
+fn tool_group(file_path: &Path) -> String {
+    let components: Vec<_> = file_path
+        .components()
+        .map(|c| c.as_os_str().to_str().unwrap())
+        .collect();
+    let tools_pos = components
+        .iter()
+        .position(|&c| c == "tools")
+        .expect("Path should contain 'tools'");
+    components[tools_pos + 1].to_string()
+}
+
+fn build_module_path(file_path: &Path) -> String {
     // Convert file path to module path using components for cross-platform compatibility
     let components: Vec<_> = file_path
         .components()
@@ -117,44 +179,24 @@ fn build_function_path(file_path: &Path, fn_name: &str) -> String {
         .collect();
 
     // Remove .rs extension from the last part
-    if let Some(last) = module_parts.last_mut() {
-        if last.ends_with(".rs") {
-            *last = last.trim_end_matches(".rs").to_string();
-        }
+    if let Some(last) = module_parts.last_mut()
+        && last.ends_with(".rs")
+    {
+        *last = last.trim_end_matches(".rs").to_string();
     }
 
     // Construct the full module path to the file
     let module_path = module_parts.join("::");
 
-    // The function is inside this module.
-    // We reference it as crate::module_path::fn_name
+    // Items inside this module are referenced as crate::module_path::item
     // This avoids relying on re-exports in parent modules
-    format!("crate::{}::{}", module_path, fn_name)
+    format!("crate::{}", module_path)
 }
 
 fn generate_tool_router_code(tools: &[ToolInfo]) -> String {
     let mut code = String::from("// Auto-generated by build.rs\n\n");
 
     // Add necessary imports
-    code.push_str("use crate::mcp::tools::classification_nodes::{ListAreaPathsArgs, ListIterationPathsArgs};\n");
-    code.push_str(
-        "use crate::mcp::tools::organizations::{GetCurrentUserArgs, ListOrganizationsArgs};\n",
-    );
-    code.push_str("use crate::mcp::tools::projects::ListProjectsArgs;\n");
-    code.push_str("use crate::mcp::tools::tags::ListTagsArgs;\n");
-    code.push_str("use crate::mcp::tools::teams::{\n");
-    code.push_str(
-        "    GetTeamArgs, GetTeamCurrentIterationArgs, ListTeamMembersArgs, ListTeamsArgs,\n",
-    );
-    code.push_str(
-        "    boards::{GetBoardArgs, ListBoardColumnsArgs, ListBoardRowsArgs, ListBoardsArgs},\n",
-    );
-    code.push_str("};\n");
-    code.push_str("use crate::mcp::tools::work_item_types::ListWorkItemTypesArgs;\n");
-    code.push_str("use crate::mcp::tools::work_items::{\n");
-    code.push_str("    AddCommentArgs, CreateWorkItemArgs, GetWorkItemArgs, GetWorkItemsArgs, LinkWorkItemsArgs,\n");
-    code.push_str("    QueryWorkItemsArgs, QueryWorkItemsArgsWiql, UpdateWorkItemArgs,\n");
-    code.push_str("};\n");
     code.push_str("use rmcp::{\n");
     code.push_str("    ErrorData as McpError,\n");
     code.push_str("    handler::server::wrapper::Parameters,\n");
@@ -167,25 +209,57 @@ fn generate_tool_router_code(tools: &[ToolInfo]) -> String {
     code.push_str("        Self {\n");
     code.push_str("            client: Arc::new(client),\n");
     code.push_str("            tool_router: Self::tool_router(),\n");
+    code.push_str("            tool_timeouts: Default::default(),\n");
+    code.push_str("            tool_access: Default::default(),\n");
+    code.push_str("            tool_defaults: Default::default(),\n");
+    code.push_str("            result_budget: Default::default(),\n");
+    code.push_str("            date_rendering: Default::default(),\n");
+    code.push_str("            session_client: Default::default(),\n");
+    code.push_str("            in_flight: Default::default(),\n");
+    code.push_str("            resource_subscriptions: Default::default(),\n");
+    code.push_str("            subscribed: Default::default(),\n");
+    code.push_str("            watches: Default::default(),\n");
+    code.push_str("            journal: Default::default(),\n");
+    code.push_str("            audit_log: None,\n");
+    code.push_str("            require_session_credentials: false,\n");
     code.push_str("        }\n");
     code.push_str("    }\n\n");
 
     for tool in tools {
-        code.push_str(&format!(
-            "    #[tool(description = \"{}\")]\n",
-            tool.description
+        let mut tool_attr = format!("description = \"{}\"", tool.description);
+        if let Some(output_type) = &tool.output_type {
+            tool_attr.push_str(&format!(
+                ", output_schema = rmcp::handler::server::tool::cached_schema_for_type::<{}>()",
+                output_type
+            ));
+        }
+        tool_attr.push_str(&format!(
+            ", annotations(read_only_hint = {}, destructive_hint = {}, idempotent_hint = {})",
+            tool.annotations.read_only, tool.annotations.destructive, tool.annotations.idempotent
         ));
+        code.push_str(&format!("    #[tool({})]\n", tool_attr));
         code.push_str(&format!("    async fn {}(\n", tool.name));
         code.push_str("        &self,\n");
         code.push_str(&format!("        args: Parameters<{}>,\n", tool.args_type));
+        code.push_str("        context: rmcp::service::RequestContext<rmcp::RoleServer>,\n");
         code.push_str("    ) -> Result<CallToolResult, McpError> {\n");
+        // Boxed, the futures of the tools being too large for the stack of a worker
+        // thread in debug builds
         code.push_str(&format!(
-            "        {}(&self.client, args.0).await\n",
-            tool.function_path
+            "        self.invoke(\"{}\", context, Box::pin({}(self.client(), args.0)))\n",
+            tool.name, tool.function_path
         ));
+        code.push_str("            .await\n");
         code.push_str("    }\n\n");
     }
 
-    code.push_str("}\n");
+    code.push_str("}\n\n");
+
+    // Group of each tool, to enable and disable them by group
+    code.push_str("const TOOL_GROUPS: &[(&str, &str)] = &[\n");
+    for tool in tools {
+        code.push_str(&format!("    (\"{}\", \"{}\"),\n", tool.name, tool.group));
+    }
+    code.push_str("];\n");
     code
 }
@@ -6,15 +6,23 @@ use syn::{parse_macro_input, ItemFn};
 ///
 /// This macro validates the required metadata and passes through the function.
 /// The build script will scan for these attributes and generate the router code.
+/// The optional `output` names the type whose JSON schema is advertised as the output
+/// schema of the tool, its value being returned as structured content.
+///
+/// Every tool declares whether it is `read_only`. The others may declare being
+/// `destructive` (overwriting or deleting data) and `idempotent` (calling them again with
+/// the same arguments has no further effect), both false by default. These are advertised
+/// as the annotations of the tool, for clients to decide which calls to confirm.
 ///
 /// Usage:
-/// ```rust
+/// ```rust,ignore
 /// #[mcp_tool(
 ///     name = "azdo_list_iteration_paths",
-///     description = "List iteration paths for a project or team"
+///     description = "List iteration paths for a project or team",
+///     read_only = true
 /// )]
 /// pub async fn list_iteration_paths(
-///     client: &AzureDevOpsClient,
+///     client: &dyn AzureBoardsApi,
 ///     args: ListIterationPathsArgs,
 /// ) -> Result<CallToolResult, McpError> {
 ///     // implementation
@@ -26,8 +34,11 @@ pub fn mcp_tool(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     // Validate that we have the required attributes
     let attr_str = attr.to_string();
-    if !attr_str.contains("name") || !attr_str.contains("description") {
-        panic!("mcp_tool attribute requires both 'name' and 'description' parameters");
+    if !attr_str.contains("name")
+        || !attr_str.contains("description")
+        || !attr_str.contains("read_only")
+    {
+        panic!("mcp_tool attribute requires the 'name', 'description' and 'read_only' parameters");
     }
 
     // Just pass through the function unchanged
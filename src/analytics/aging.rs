@@ -0,0 +1,184 @@
+use crate::analytics::burndown::RevisionSnapshot;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Age of a work item in progress
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgingItem {
+    pub id: u32,
+    pub title: String,
+    pub work_item_type: String,
+    pub state: String,
+    /// Board column, the state when the work item is not on a board
+    pub column: String,
+    pub assigned_to: String,
+    pub days_in_state: f64,
+    pub days_in_column: f64,
+    /// In its column for longer than the threshold
+    pub stale: bool,
+}
+
+/// Work items in progress of an assignee in a column
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgingGroup {
+    pub assigned_to: String,
+    pub column: String,
+    pub count: usize,
+    pub stale: usize,
+    pub oldest_days: f64,
+}
+
+/// When the work item got the value it has in its latest revision, the start of the
+/// latest run of revisions having it
+pub fn entered_at(
+    revisions: &[RevisionSnapshot],
+    value: impl Fn(&RevisionSnapshot) -> &str,
+) -> Option<DateTime<Utc>> {
+    let mut sorted: Vec<&RevisionSnapshot> = revisions.iter().collect();
+    sorted.sort_by_key(|r| r.changed_date);
+    let latest = value(sorted.last()?);
+    sorted
+        .iter()
+        .rev()
+        .take_while(|r| value(r) == latest)
+        .last()
+        .map(|r| r.changed_date)
+}
+
+/// Age of a work item from its revision history at the given instant, None without
+/// revisions
+pub fn aging_item(
+    id: u32,
+    title: &str,
+    work_item_type: &str,
+    revisions: &[RevisionSnapshot],
+    now: DateTime<Utc>,
+    threshold_days: f64,
+) -> Option<AgingItem> {
+    let latest = revisions.iter().max_by_key(|r| r.changed_date)?;
+    let days_since =
+        |instant: DateTime<Utc>| ((now - instant).num_seconds() as f64 / 86_400.0).max(0.0);
+    let days_in_state = days_since(entered_at(revisions, |r| r.state.as_str())?);
+    let (column, days_in_column) = if latest.board_column.is_empty() {
+        (latest.state.clone(), days_in_state)
+    } else {
+        let entered = entered_at(revisions, |r| r.board_column.as_str())?;
+        (latest.board_column.clone(), days_since(entered))
+    };
+    Some(AgingItem {
+        id,
+        title: title.to_string(),
+        work_item_type: work_item_type.to_string(),
+        state: latest.state.clone(),
+        column,
+        assigned_to: latest.assigned_to.clone(),
+        days_in_state,
+        days_in_column,
+        stale: days_in_column > threshold_days,
+    })
+}
+
+/// Group the work items by assignee and column, sorting them by assignee, column and
+/// age (oldest first)
+pub fn group_aging(items: &mut [AgingItem]) -> Vec<AgingGroup> {
+    items.sort_by(|a, b| {
+        (&a.assigned_to, &a.column)
+            .cmp(&(&b.assigned_to, &b.column))
+            .then(b.days_in_column.total_cmp(&a.days_in_column))
+    });
+    let mut groups: BTreeMap<(&str, &str), AgingGroup> = BTreeMap::new();
+    for item in items.iter() {
+        let group = groups
+            .entry((&item.assigned_to, &item.column))
+            .or_insert_with(|| AgingGroup {
+                assigned_to: item.assigned_to.clone(),
+                column: item.column.clone(),
+                count: 0,
+                stale: 0,
+                oldest_days: 0.0,
+            });
+        group.count += 1;
+        group.stale += usize::from(item.stale);
+        group.oldest_days = group.oldest_days.max(item.days_in_column);
+    }
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::burndown::parse_datetime;
+
+    fn revision(changed: &str, state: &str, column: &str) -> RevisionSnapshot {
+        RevisionSnapshot {
+            changed_date: parse_datetime(changed).unwrap(),
+            iteration_path: "Project".to_string(),
+            state: state.to_string(),
+            remaining_work: 0.0,
+            board_column: column.to_string(),
+            assigned_to: "Jane".to_string(),
+            points: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_aging_item() {
+        let now = parse_datetime("2025-01-21T00:00:00Z").unwrap();
+        let revisions = [
+            revision("2025-01-01T00:00:00Z", "New", "Backlog"),
+            revision("2025-01-06T00:00:00Z", "Active", "Development"),
+            revision("2025-01-10T00:00:00Z", "Active", "Development"),
+            revision("2025-01-16T00:00:00Z", "Active", "Review"),
+        ];
+        let item = aging_item(1, "Fix login", "Bug", &revisions, now, 3.0).unwrap();
+        assert_eq!(item.state, "Active");
+        assert_eq!(item.column, "Review");
+        assert_eq!(item.days_in_state, 15.0);
+        assert_eq!(item.days_in_column, 5.0);
+        assert!(item.stale);
+
+        // Off the boards, the state is the column
+        let revisions = [revision("2025-01-20T12:00:00Z", "Active", "")];
+        let item = aging_item(2, "Add export", "Task", &revisions, now, 3.0).unwrap();
+        assert_eq!(item.column, "Active");
+        assert_eq!(item.days_in_column, 0.5);
+        assert!(!item.stale);
+        assert_eq!(aging_item(3, "", "", &[], now, 3.0), None);
+    }
+
+    #[test]
+    fn test_group_aging() {
+        let item = |id: u32, assigned_to: &str, column: &str, days: f64| AgingItem {
+            id,
+            title: String::new(),
+            work_item_type: "Bug".to_string(),
+            state: "Active".to_string(),
+            column: column.to_string(),
+            assigned_to: assigned_to.to_string(),
+            days_in_state: days,
+            days_in_column: days,
+            stale: days > 10.0,
+        };
+        let mut items = vec![
+            item(1, "John", "Review", 2.0),
+            item(2, "Jane", "Review", 4.0),
+            item(3, "Jane", "Review", 12.0),
+            item(4, "Jane", "Development", 1.0),
+        ];
+        let groups = group_aging(&mut items);
+        let ids: Vec<u32> = items.iter().map(|item| item.id).collect();
+        assert_eq!(ids, [4, 3, 2, 1]);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[1],
+            AgingGroup {
+                assigned_to: "Jane".to_string(),
+                column: "Review".to_string(),
+                count: 2,
+                stale: 1,
+                oldest_days: 12.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// What the burndown series measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurndownMetric {
+    /// Sum of Microsoft.VSTS.Scheduling.RemainingWork
+    RemainingWork,
+    /// Number of work items not yet done
+    Count,
+}
+
+/// Fields sizing a work item, by process (Agile, Scrum, CMMI)
+const POINTS_FIELDS: [&str; 3] = [
+    "Microsoft.VSTS.Scheduling.StoryPoints",
+    "Microsoft.VSTS.Scheduling.Effort",
+    "Microsoft.VSTS.Scheduling.Size",
+];
+
+/// The state of a work item as of one of its revisions
+#[derive(Debug, Clone)]
+pub struct RevisionSnapshot {
+    pub changed_date: DateTime<Utc>,
+    pub iteration_path: String,
+    pub state: String,
+    pub remaining_work: f64,
+    /// Column of the work item on the board of its team, empty when not on a board
+    pub board_column: String,
+    /// Display name of the assignee, empty when unassigned
+    pub assigned_to: String,
+    /// Story points, effort or size, whichever the process has
+    pub points: f64,
+}
+
+impl RevisionSnapshot {
+    /// Build a snapshot from the fields of a work item revision
+    pub fn from_fields(fields: &HashMap<String, Value>) -> Option<Self> {
+        let changed_date = fields
+            .get("System.ChangedDate")
+            .and_then(|v| v.as_str())
+            .and_then(parse_datetime)?;
+
+        Some(Self {
+            changed_date,
+            iteration_path: fields
+                .get("System.IterationPath")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            state: fields
+                .get("System.State")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            remaining_work: fields
+                .get("Microsoft.VSTS.Scheduling.RemainingWork")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0),
+            board_column: fields
+                .get("System.BoardColumn")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            assigned_to: fields
+                .get("System.AssignedTo")
+                .and_then(|v| v.get("displayName").or(Some(v)))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            points: POINTS_FIELDS
+                .iter()
+                .find_map(|field| fields.get(*field).and_then(|v| v.as_f64()))
+                .unwrap_or(0.0),
+        })
+    }
+}
+
+/// Capacity of a single team member for the sprint
+#[derive(Debug, Clone)]
+pub struct MemberCapacity {
+    pub capacity_per_day: f64,
+    pub days_off: Vec<(NaiveDate, NaiveDate)>,
+}
+
+/// Working calendar of a sprint
+#[derive(Debug, Clone)]
+pub struct SprintCalendar {
+    pub start: NaiveDate,
+    pub finish: NaiveDate,
+    pub working_days: Vec<Weekday>,
+    pub team_days_off: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl SprintCalendar {
+    /// All the working dates of the sprint, excluding non-working weekdays and team days off
+    pub fn working_dates(&self) -> Vec<NaiveDate> {
+        self.start
+            .iter_days()
+            .take_while(|d| *d <= self.finish)
+            .filter(|d| self.working_days.contains(&d.weekday()))
+            .filter(|d| !in_ranges(*d, &self.team_days_off))
+            .collect()
+    }
+
+    /// Hours a team member can work in the sprint, excluding their days off
+    pub fn member_hours(&self, member: &MemberCapacity) -> f64 {
+        self.working_dates()
+            .iter()
+            .filter(|d| !in_ranges(**d, &member.days_off))
+            .count() as f64
+            * member.capacity_per_day
+    }
+}
+
+/// A single day of the burndown series
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Actual remaining at the end of the day, None for days in the future
+    pub remaining: Option<f64>,
+    /// Ideal trend, linear from the first day's remaining down to zero
+    pub ideal: f64,
+    /// Team capacity (hours) still available after this day
+    pub capacity: f64,
+}
+
+/// Parse an Azure DevOps datetime (RFC 3339) into UTC
+pub fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Parse an Azure DevOps datetime (or plain YYYY-MM-DD) into a date
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    parse_datetime(value)
+        .map(|d| d.date_naive())
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+}
+
+/// Parse team settings working days ("monday", ...) into weekdays
+pub fn parse_working_days(days: &[String]) -> Vec<Weekday> {
+    days.iter()
+        .filter_map(|d| d.parse::<Weekday>().ok())
+        .collect()
+}
+
+fn in_ranges(date: NaiveDate, ranges: &[(NaiveDate, NaiveDate)]) -> bool {
+    ranges
+        .iter()
+        .any(|(start, end)| date >= *start && date <= *end)
+}
+
+/// Whether a classification path is the parent path or under it, ignoring the case
+pub fn is_under_path(path: &str, parent: &str) -> bool {
+    let path = path.to_lowercase();
+    let parent = parent.to_lowercase();
+    path == parent || path.starts_with(&format!("{}\\", parent))
+}
+
+/// Latest revision before the given instant, None when the work item did not exist yet
+pub fn revision_at(
+    revisions: &[RevisionSnapshot],
+    instant: DateTime<Utc>,
+) -> Option<&RevisionSnapshot> {
+    revisions
+        .iter()
+        .filter(|r| r.changed_date < instant)
+        .max_by_key(|r| r.changed_date)
+}
+
+/// Value of the metric at the given instant, based on each item's revision history
+fn remaining_at(
+    items: &[Vec<RevisionSnapshot>],
+    instant: DateTime<Utc>,
+    iteration_path: &str,
+    done_states: &[String],
+    metric: BurndownMetric,
+) -> f64 {
+    items
+        .iter()
+        .filter_map(|revisions| revision_at(revisions, instant))
+        .filter(|r| is_under_path(&r.iteration_path, iteration_path))
+        .filter(|r| !done_states.iter().any(|s| s.eq_ignore_ascii_case(&r.state)))
+        .map(|r| match metric {
+            BurndownMetric::RemainingWork => r.remaining_work,
+            BurndownMetric::Count => 1.0,
+        })
+        .sum()
+}
+
+/// Compute the burndown series for every working date of the sprint.
+/// Only working dates up to `today` get an actual remaining value.
+pub fn compute_burndown(
+    calendar: &SprintCalendar,
+    members: &[MemberCapacity],
+    items: &[Vec<RevisionSnapshot>],
+    iteration_path: &str,
+    done_states: &[String],
+    metric: BurndownMetric,
+    today: NaiveDate,
+) -> Vec<BurndownPoint> {
+    let dates = calendar.working_dates();
+    if dates.is_empty() {
+        return Vec::new();
+    }
+
+    // Capacity available on each working date, accounting for personal days off
+    let daily_capacity: Vec<f64> = dates
+        .iter()
+        .map(|date| {
+            members
+                .iter()
+                .filter(|m| !in_ranges(*date, &m.days_off))
+                .map(|m| m.capacity_per_day)
+                .sum()
+        })
+        .collect();
+
+    let end_of_day = |date: NaiveDate| {
+        date.checked_add_days(Days::new(1))
+            .unwrap_or(date)
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    };
+
+    let initial = remaining_at(
+        items,
+        end_of_day(dates[0]),
+        iteration_path,
+        done_states,
+        metric,
+    );
+    let steps = (dates.len() - 1).max(1) as f64;
+
+    dates
+        .iter()
+        .enumerate()
+        .map(|(i, date)| BurndownPoint {
+            date: *date,
+            remaining: (*date <= today).then(|| {
+                remaining_at(
+                    items,
+                    end_of_day(*date),
+                    iteration_path,
+                    done_states,
+                    metric,
+                )
+            }),
+            ideal: initial * (1.0 - i as f64 / steps),
+            capacity: daily_capacity[i + 1..].iter().sum(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn snapshot(changed: &str, state: &str, remaining: f64) -> RevisionSnapshot {
+        RevisionSnapshot {
+            changed_date: parse_datetime(changed).unwrap(),
+            iteration_path: "Project\\Sprint 1".to_string(),
+            state: state.to_string(),
+            remaining_work: remaining,
+            board_column: String::new(),
+            assigned_to: String::new(),
+            points: 0.0,
+        }
+    }
+
+    fn calendar() -> SprintCalendar {
+        // Monday 2025-01-06 to Friday 2025-01-17, with Wednesday 2025-01-08 off
+        SprintCalendar {
+            start: date("2025-01-06"),
+            finish: date("2025-01-17"),
+            working_days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            team_days_off: vec![(date("2025-01-08"), date("2025-01-08"))],
+        }
+    }
+
+    #[test]
+    fn test_working_dates_skip_weekends_and_days_off() {
+        let dates = calendar().working_dates();
+        assert_eq!(dates.len(), 9);
+        assert!(!dates.contains(&date("2025-01-08")));
+        assert!(!dates.contains(&date("2025-01-11")));
+        let member = MemberCapacity {
+            capacity_per_day: 6.0,
+            days_off: vec![(date("2025-01-16"), date("2025-01-17"))],
+        };
+        assert_eq!(calendar().member_hours(&member), 42.0);
+    }
+
+    #[test]
+    fn test_burndown_remaining_work() {
+        let items = vec![
+            vec![
+                snapshot("2025-01-05T10:00:00Z", "Active", 8.0),
+                snapshot("2025-01-07T10:00:00Z", "Active", 4.0),
+                snapshot("2025-01-09T10:00:00Z", "Closed", 0.0),
+            ],
+            vec![snapshot("2025-01-05T10:00:00Z", "New", 6.0)],
+        ];
+        let members = vec![MemberCapacity {
+            capacity_per_day: 6.0,
+            days_off: vec![(date("2025-01-17"), date("2025-01-17"))],
+        }];
+
+        let points = compute_burndown(
+            &calendar(),
+            &members,
+            &items,
+            "Project\\Sprint 1",
+            &["Closed".to_string()],
+            BurndownMetric::RemainingWork,
+            date("2025-01-09"),
+        );
+
+        assert_eq!(points[0].remaining, Some(14.0));
+        assert_eq!(points[1].remaining, Some(10.0));
+        assert_eq!(points[2].remaining, Some(6.0));
+        assert_eq!(points[3].remaining, None);
+        assert_eq!(points[0].ideal, 14.0);
+        assert_eq!(points.last().unwrap().ideal, 0.0);
+        // 8 remaining working dates after the first, one of them is a personal day off
+        assert_eq!(points[0].capacity, 42.0);
+    }
+
+    #[test]
+    fn test_burndown_count_ignores_items_outside_iteration() {
+        let mut moved = snapshot("2025-01-05T10:00:00Z", "Active", 3.0);
+        moved.iteration_path = "Project\\Sprint 2".to_string();
+        let items = vec![
+            vec![snapshot("2025-01-05T10:00:00Z", "Active", 3.0)],
+            vec![moved],
+        ];
+
+        let points = compute_burndown(
+            &calendar(),
+            &[],
+            &items,
+            "project\\sprint 1",
+            &[],
+            BurndownMetric::Count,
+            date("2025-01-06"),
+        );
+
+        assert_eq!(points[0].remaining, Some(1.0));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::analytics::burndown::parse_datetime;
+use serde::Serialize;
+
+/// Summary statistics of a duration distribution, in days
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub p50: f64,
+    pub p85: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+/// Percentile of a sorted slice, linearly interpolated between closest ranks
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Compute the statistics of a set of durations, None when empty
+pub fn duration_stats(values: &[f64]) -> Option<DurationStats> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    Some(DurationStats {
+        count: sorted.len(),
+        mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+        min: sorted[0],
+        p50: percentile(&sorted, 50.0),
+        p85: percentile(&sorted, 85.0),
+        p95: percentile(&sorted, 95.0),
+        max: sorted[sorted.len() - 1],
+    })
+}
+
+/// Days elapsed between two Azure DevOps datetimes, None when missing or negative
+pub fn days_between(from: Option<&str>, to: Option<&str>) -> Option<f64> {
+    let from = parse_datetime(from?)?;
+    let to = parse_datetime(to?)?;
+    let days = (to - from).num_seconds() as f64 / 86_400.0;
+    (days >= 0.0).then_some(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_stats() {
+        let stats = duration_stats(&[5.0, 1.0, 3.0, 2.0, 4.0]).unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.p50, 3.0);
+        assert!((stats.p85 - 4.4).abs() < 1e-9);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(duration_stats(&[]), None);
+    }
+
+    #[test]
+    fn test_days_between() {
+        assert_eq!(
+            days_between(Some("2025-01-01T00:00:00Z"), Some("2025-01-03T12:00:00Z")),
+            Some(2.5)
+        );
+        assert_eq!(
+            days_between(Some("2025-01-03T00:00:00Z"), Some("2025-01-01T00:00:00Z")),
+            None
+        );
+        assert_eq!(days_between(None, Some("2025-01-01T00:00:00Z")), None);
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::NaiveDate;
+
+/// Weeks a simulation can run before giving up on completing the work items
+const MAX_WEEKS: u32 = 520;
+
+/// Work items completed in each week of the history, oldest first. The weeks are the
+/// whole weeks ending on `to`, the completions before the first one being ignored.
+pub fn weekly_throughput(completed: &[NaiveDate], to: NaiveDate, weeks: u32) -> Vec<u32> {
+    let mut throughput = vec![0; weeks as usize];
+    for date in completed {
+        let days_ago = (to - *date).num_days();
+        if days_ago < 0 {
+            continue;
+        }
+        let week = (days_ago / 7) as usize;
+        if week < throughput.len() {
+            let index = throughput.len() - 1 - week;
+            throughput[index] += 1;
+        }
+    }
+    throughput
+}
+
+/// Weeks needed to complete the work items in each trial, sorted, by sampling the
+/// weekly throughput of the history. Empty when nothing was completed in the history.
+pub fn simulate_weeks(
+    throughput: &[u32],
+    items: u32,
+    trials: usize,
+    rng: &mut fastrand::Rng,
+) -> Vec<u32> {
+    if throughput.iter().all(|count| *count == 0) {
+        return Vec::new();
+    }
+    let mut results: Vec<u32> = (0..trials)
+        .map(|_| {
+            let mut done = 0;
+            let mut weeks = 0;
+            while done < items && weeks < MAX_WEEKS {
+                done += throughput[rng.usize(..throughput.len())];
+                weeks += 1;
+            }
+            weeks
+        })
+        .collect();
+    results.sort_unstable();
+    results
+}
+
+/// Work items completed in the weeks in each trial, sorted, by sampling the weekly
+/// throughput of the history
+pub fn simulate_items(
+    throughput: &[u32],
+    weeks: u32,
+    trials: usize,
+    rng: &mut fastrand::Rng,
+) -> Vec<u32> {
+    if throughput.is_empty() {
+        return vec![0; trials];
+    }
+    let mut results: Vec<u32> = (0..trials)
+        .map(|_| {
+            (0..weeks)
+                .map(|_| throughput[rng.usize(..throughput.len())])
+                .sum()
+        })
+        .collect();
+    results.sort_unstable();
+    results
+}
+
+/// Value reached in at least the given share of the sorted trials: at most the value
+/// for the trials sorted ascending by what is minimized (weeks), at least the value for
+/// the ones maximized (items)
+pub fn at_confidence(sorted: &[u32], confidence: f64, maximized: bool) -> Option<u32> {
+    if sorted.is_empty() {
+        return None;
+    }
+    // Trials the value must hold for, the epsilon absorbing the rounding of the product
+    let needed = ((confidence * sorted.len() as f64 - 1e-9).ceil() as usize).clamp(1, sorted.len());
+    let index = if maximized {
+        sorted.len() - needed
+    } else {
+        needed - 1
+    };
+    Some(sorted[index])
+}
+
+/// Share of the trials completing the work items in the weeks
+pub fn probability_within(sorted_weeks: &[u32], weeks: u32) -> f64 {
+    if sorted_weeks.is_empty() {
+        return 0.0;
+    }
+    let within = sorted_weeks.partition_point(|needed| *needed <= weeks);
+    within as f64 / sorted_weeks.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_weekly_throughput() {
+        let completed = [
+            date("2025-01-20"),
+            date("2025-01-14"),
+            date("2025-01-13"),
+            date("2025-01-01"),
+            date("2024-12-01"),
+            date("2025-01-25"),
+        ];
+        assert_eq!(
+            weekly_throughput(&completed, date("2025-01-20"), 3),
+            [1, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_simulations() {
+        let mut rng = fastrand::Rng::with_seed(7);
+        // A constant throughput makes every trial the same
+        assert_eq!(simulate_weeks(&[5, 5], 12, 100, &mut rng), [3; 100]);
+        assert_eq!(simulate_items(&[5, 5], 4, 100, &mut rng), [20; 100]);
+        assert!(simulate_weeks(&[0, 0], 12, 100, &mut rng).is_empty());
+
+        let weeks = simulate_weeks(&[0, 10], 20, 1000, &mut rng);
+        assert_eq!(weeks[0], 2);
+        assert!(at_confidence(&weeks, 0.85, false).unwrap() > 2);
+        let probability = probability_within(&weeks, 4);
+        assert!(probability > 0.5 && probability < 1.0);
+    }
+
+    #[test]
+    fn test_at_confidence() {
+        let sorted: Vec<u32> = (1..=100).collect();
+        assert_eq!(at_confidence(&sorted, 0.85, false), Some(85));
+        assert_eq!(at_confidence(&sorted, 0.85, true), Some(16));
+        assert_eq!(at_confidence(&[], 0.85, true), None);
+        assert_eq!(probability_within(&sorted, 30), 0.3);
+    }
+}
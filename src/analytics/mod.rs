@@ -0,0 +1,6 @@
+// Analytics module: pure computations over data fetched from Azure DevOps
+pub mod aging;
+pub mod burndown;
+pub mod flow;
+pub mod forecast;
+pub mod sprint;
@@ -0,0 +1,290 @@
+use crate::analytics::burndown::{RevisionSnapshot, is_under_path, revision_at};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// State of work items moved out of the scope rather than done
+const REMOVED_STATE: &str = "Removed";
+
+/// Name of the people group of the unassigned work items
+const UNASSIGNED: &str = "(unassigned)";
+
+/// Work item that was in a sprint, with its revision history
+#[derive(Debug, Clone)]
+pub struct SprintItem {
+    pub id: u32,
+    pub title: String,
+    pub work_item_type: String,
+    pub revisions: Vec<RevisionSnapshot>,
+}
+
+/// Work item of a sprint report, as of the end of the sprint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ItemOutcome {
+    pub id: u32,
+    pub title: String,
+    pub work_item_type: String,
+    pub state: String,
+    pub assigned_to: String,
+    pub points: f64,
+}
+
+/// Work items of one category of a sprint report
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ItemSet {
+    pub count: usize,
+    pub points: f64,
+    pub items: Vec<ItemOutcome>,
+}
+
+impl ItemSet {
+    fn push(&mut self, item: ItemOutcome) {
+        self.count += 1;
+        self.points += item.points;
+        self.items.push(item);
+    }
+}
+
+/// Completion of the work items assigned to a person at the end of the sprint
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PersonCompletion {
+    pub assigned_to: String,
+    /// Work items committed or added, and not removed
+    pub planned: usize,
+    pub completed: usize,
+    pub carried_over: usize,
+    pub planned_points: f64,
+    pub completed_points: f64,
+    /// Hours the person could work in the sprint, None without a capacity set
+    pub capacity_hours: Option<f64>,
+}
+
+/// Retrospective of a sprint
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SprintReport {
+    /// In the sprint and not done when it started
+    pub committed: ItemSet,
+    /// Moved into the sprint, or created in it, after it started
+    pub added: ItemSet,
+    /// Committed or added, then moved out of the sprint or set to Removed
+    pub removed: ItemSet,
+    /// Committed or added, and done at the end of the sprint
+    pub completed: ItemSet,
+    /// Committed or added, and still open at the end of the sprint
+    pub carried_over: ItemSet,
+    /// Completed work items over the planned ones (committed + added - removed)
+    pub completion_rate: f64,
+    pub people: Vec<PersonCompletion>,
+}
+
+/// Compute the report of the sprint of an iteration path between its start and end
+/// (the finish of the sprint or now), from the revision history of the work items that
+/// were in it. The capacity is the hours of each person by display name.
+pub fn compute_sprint_report(
+    items: &[SprintItem],
+    iteration_path: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    done_states: &[String],
+    capacity: &BTreeMap<String, f64>,
+) -> SprintReport {
+    let in_sprint = |r: &RevisionSnapshot| is_under_path(&r.iteration_path, iteration_path);
+    let is_done =
+        |r: &RevisionSnapshot| done_states.iter().any(|s| s.eq_ignore_ascii_case(&r.state));
+    let is_removed = |r: &RevisionSnapshot| r.state.eq_ignore_ascii_case(REMOVED_STATE);
+
+    let mut report = SprintReport::default();
+    let mut people: BTreeMap<String, PersonCompletion> = capacity
+        .iter()
+        .map(|(name, hours)| {
+            let person = PersonCompletion {
+                assigned_to: name.clone(),
+                capacity_hours: Some(*hours),
+                ..Default::default()
+            };
+            (name.clone(), person)
+        })
+        .collect();
+
+    for item in items {
+        let at_start = revision_at(&item.revisions, start);
+        let committed = at_start.is_some_and(|r| in_sprint(r) && !is_done(r) && !is_removed(r));
+        let added = !committed
+            && item
+                .revisions
+                .iter()
+                .any(|r| r.changed_date >= start && r.changed_date < end && in_sprint(r));
+        if !committed && !added {
+            continue;
+        }
+        let Some(at_end) = revision_at(&item.revisions, end) else {
+            continue;
+        };
+
+        let outcome = ItemOutcome {
+            id: item.id,
+            title: item.title.clone(),
+            work_item_type: item.work_item_type.clone(),
+            state: at_end.state.clone(),
+            assigned_to: at_end.assigned_to.clone(),
+            points: at_end.points,
+        };
+        if committed {
+            report.committed.push(outcome.clone());
+        } else {
+            report.added.push(outcome.clone());
+        }
+        if !in_sprint(at_end) || is_removed(at_end) {
+            report.removed.push(outcome);
+            continue;
+        }
+
+        let name = if at_end.assigned_to.is_empty() {
+            UNASSIGNED.to_string()
+        } else {
+            at_end.assigned_to.clone()
+        };
+        let person = people
+            .entry(name.clone())
+            .or_insert_with(|| PersonCompletion {
+                assigned_to: name,
+                ..Default::default()
+            });
+        person.planned += 1;
+        person.planned_points += at_end.points;
+        if is_done(at_end) {
+            person.completed += 1;
+            person.completed_points += at_end.points;
+            report.completed.push(outcome);
+        } else {
+            person.carried_over += 1;
+            report.carried_over.push(outcome);
+        }
+    }
+
+    let planned = report.completed.count + report.carried_over.count;
+    if planned > 0 {
+        report.completion_rate = report.completed.count as f64 / planned as f64;
+    }
+    report.people = people.into_values().collect();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::burndown::parse_datetime;
+
+    fn revision(changed: &str, iteration: &str, state: &str) -> RevisionSnapshot {
+        RevisionSnapshot {
+            changed_date: parse_datetime(changed).unwrap(),
+            iteration_path: format!("Project\\{}", iteration),
+            state: state.to_string(),
+            remaining_work: 0.0,
+            board_column: String::new(),
+            assigned_to: "Jane".to_string(),
+            points: 3.0,
+        }
+    }
+
+    fn item(id: u32, revisions: Vec<RevisionSnapshot>) -> SprintItem {
+        SprintItem {
+            id,
+            title: format!("Item {}", id),
+            work_item_type: "User Story".to_string(),
+            revisions,
+        }
+    }
+
+    fn ids(set: &ItemSet) -> Vec<u32> {
+        set.items.iter().map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn test_compute_sprint_report() {
+        let items = vec![
+            // Committed and completed
+            item(
+                1,
+                vec![
+                    revision("2025-01-02T10:00:00Z", "Sprint 1", "New"),
+                    revision("2025-01-10T10:00:00Z", "Sprint 1", "Done"),
+                ],
+            ),
+            // Committed, still open and moved to the next sprint after the end
+            item(
+                2,
+                vec![
+                    revision("2025-01-02T10:00:00Z", "Sprint 1", "Active"),
+                    revision("2025-01-20T10:00:00Z", "Sprint 2", "Active"),
+                ],
+            ),
+            // Added during the sprint, then moved out of it
+            item(
+                3,
+                vec![
+                    revision("2025-01-02T10:00:00Z", "Backlog", "New"),
+                    revision("2025-01-08T10:00:00Z", "Sprint 1", "New"),
+                    revision("2025-01-09T10:00:00Z", "Sprint 2", "New"),
+                ],
+            ),
+            // Done before the sprint started
+            item(
+                4,
+                vec![revision("2025-01-02T10:00:00Z", "Sprint 1", "Done")],
+            ),
+        ];
+        let mut unassigned = item(
+            5,
+            vec![revision("2025-01-07T10:00:00Z", "Sprint 1", "Done")],
+        );
+        unassigned.revisions[0].assigned_to = String::new();
+        let items = [items, vec![unassigned]].concat();
+        let capacity = BTreeMap::from([("Jane".to_string(), 60.0), ("John".to_string(), 30.0)]);
+
+        let report = compute_sprint_report(
+            &items,
+            "project\\sprint 1",
+            parse_datetime("2025-01-06T00:00:00Z").unwrap(),
+            parse_datetime("2025-01-18T00:00:00Z").unwrap(),
+            &["Done".to_string()],
+            &capacity,
+        );
+
+        assert_eq!(ids(&report.committed), [1, 2]);
+        assert_eq!(ids(&report.added), [3, 5]);
+        assert_eq!(ids(&report.removed), [3]);
+        assert_eq!(ids(&report.completed), [1, 5]);
+        assert_eq!(ids(&report.carried_over), [2]);
+        assert_eq!(report.completed.points, 6.0);
+        assert!((report.completion_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            report.people,
+            [
+                PersonCompletion {
+                    assigned_to: "(unassigned)".to_string(),
+                    planned: 1,
+                    completed: 1,
+                    carried_over: 0,
+                    planned_points: 3.0,
+                    completed_points: 3.0,
+                    capacity_hours: None,
+                },
+                PersonCompletion {
+                    assigned_to: "Jane".to_string(),
+                    planned: 2,
+                    completed: 1,
+                    carried_over: 1,
+                    planned_points: 6.0,
+                    completed_points: 3.0,
+                    capacity_hours: Some(60.0),
+                },
+                PersonCompletion {
+                    assigned_to: "John".to_string(),
+                    capacity_hours: Some(30.0),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Paths of the requests sent with POST that only read, like queries and searches
+const READ_ONLY_POSTS: &[&str] = &[
+    "/wiql",
+    "/workitemsearchresults",
+    "/subjectlookup",
+    "/permissionevaluationbatch",
+];
+
+/// Suffixes of the argument names whose values are redacted, compared in lowercase
+/// without separators
+const SECRET_NAMES: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "pat",
+    "apikey",
+    "authorization",
+    "credential",
+    "privatekey",
+    "httpheaders",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Request to Azure DevOps changing something, made by a tool
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mutation {
+    pub method: String,
+    /// URL without its query string
+    pub url: String,
+    /// Status of the response, absent when no response was received
+    pub status: Option<u16>,
+}
+
+tokio::task_local! {
+    static MUTATIONS: RefCell<Vec<Mutation>>;
+}
+
+/// Run a future, collecting the Azure DevOps mutations it makes
+pub async fn scope<F: Future>(future: F) -> (F::Output, Vec<Mutation>) {
+    MUTATIONS
+        .scope(RefCell::default(), async {
+            let output = future.await;
+            (output, MUTATIONS.with(|mutations| mutations.take()))
+        })
+        .await
+}
+
+/// Whether the requests are collected, within a [`scope`]
+pub fn is_recording() -> bool {
+    MUTATIONS.try_with(|_| ()).is_ok()
+}
+
+/// Record a request sent to Azure DevOps when it is a mutation, does nothing outside
+/// of a [`scope`]
+pub fn record_request(method: &reqwest::Method, url: &reqwest::Url, status: Option<u16>) {
+    let reads = method == reqwest::Method::GET
+        || method == reqwest::Method::HEAD
+        || (method == reqwest::Method::POST
+            && (READ_ONLY_POSTS
+                .iter()
+                .any(|path| url.path().ends_with(path))
+                || url
+                    .query_pairs()
+                    .any(|(k, v)| k == "validateOnly" && v == "true")));
+    if reads {
+        return;
+    }
+    let mut url = url.clone();
+    url.set_query(None);
+    let _ = MUTATIONS.try_with(|mutations| {
+        mutations.borrow_mut().push(Mutation {
+            method: method.to_string(),
+            url: url.to_string(),
+            status,
+        })
+    });
+}
+
+/// Tool invocation written to the audit log
+#[derive(Debug, Serialize)]
+pub struct AuditRecord<'a> {
+    /// RFC 3339 UTC timestamp of the end of the invocation
+    pub time: String,
+    /// Identity of the MCP client authenticated by the HTTP server
+    pub caller: Option<&'a str>,
+    /// MCP session of the HTTP server
+    pub session: Option<&'a str>,
+    /// Name of the MCP client, as given when initializing
+    pub client: Option<&'a str>,
+    /// Azure DevOps credential the tool acted with: `session` for the one of the user
+    /// of the HTTP session, `server` for the one of the server
+    pub credential: &'static str,
+    pub tool: &'a str,
+    /// Arguments of the invocation, secrets redacted
+    pub arguments: Value,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a str>,
+    pub duration_ms: u64,
+    pub mutations: Vec<Mutation>,
+}
+
+/// Append-only JSON Lines log of the tool invocations
+pub struct AuditLog {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl AuditLog {
+    /// Append to a file, created if missing, or write to stdout for `-`
+    pub fn open(target: &Path) -> std::io::Result<Self> {
+        let writer: Box<dyn Write + Send> = if target == Path::new("-") {
+            Box::new(std::io::stdout())
+        } else {
+            Box::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(target)?,
+            )
+        };
+        Ok(Self {
+            writer: Mutex::new(writer),
+        })
+    }
+
+    /// Write a record on its own line, flushed right away
+    pub fn write(&self, record: &AuditRecord) {
+        let mut line = match serde_json::to_vec(record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!("Failed to serialize the audit record: {}", e);
+                return;
+            }
+        };
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap();
+        if let Err(e) = writer.write_all(&line).and_then(|()| writer.flush()) {
+            tracing::error!("Failed to write the audit log: {}", e);
+        }
+    }
+}
+
+/// Copy of tool arguments with the values of the secret-looking fields, and of the
+/// strings carrying credentials, redacted
+pub fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| {
+                    let redacted = if is_secret_name(name) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (name.clone(), redacted)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(redact).collect()),
+        Value::String(text) if text.starts_with("Bearer ") || text.starts_with("Basic ") => {
+            Value::String(REDACTED.to_string())
+        }
+        value => value.clone(),
+    }
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    SECRET_NAMES.iter().any(|secret| name.ends_with(secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact() {
+        let arguments = json!({
+            "url": "https://hooks.example.com",
+            "max_tokens": 100,
+            "consumer_inputs": {"basicAuthPassword": "hunter2", "httpHeaders": "X-Key: 1"},
+            "headers": ["Bearer abc"],
+            "api_token": null,
+        });
+        assert_eq!(
+            redact(&arguments),
+            json!({
+                "url": "https://hooks.example.com",
+                "max_tokens": 100,
+                "consumer_inputs": {"basicAuthPassword": REDACTED, "httpHeaders": REDACTED},
+                "headers": [REDACTED],
+                "api_token": null,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        let url = |url: &str| reqwest::Url::parse(url).unwrap();
+        record_request(
+            &reqwest::Method::DELETE,
+            &url("https://dev.azure.com/o"),
+            None,
+        );
+
+        let ((), mutations) = scope(async {
+            assert!(is_recording());
+            let org = "https://dev.azure.com/org/_apis";
+            record_request(
+                &reqwest::Method::GET,
+                &url(&format!("{org}/projects")),
+                Some(200),
+            );
+            record_request(
+                &reqwest::Method::POST,
+                &url(&format!("{org}/wit/wiql")),
+                Some(200),
+            );
+            record_request(
+                &reqwest::Method::PATCH,
+                &url(&format!("{org}/wit/workitems/1?api-version=7.1")),
+                Some(200),
+            );
+        })
+        .await;
+        assert_eq!(
+            mutations,
+            vec![Mutation {
+                method: "PATCH".to_string(),
+                url: "https://dev.azure.com/org/_apis/wit/workitems/1".to_string(),
+                status: Some(200),
+            }]
+        );
+        assert!(!is_recording());
+    }
+}
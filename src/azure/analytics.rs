@@ -0,0 +1,252 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Marker preceding the entity path in Analytics OData URLs
+const ODATA_ROOT: &str = "/_odata/v4.0-preview/";
+
+/// Upper bound of pages followed through `@odata.nextLink`
+const MAX_PAGES: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct ODataResponse<T> {
+    pub value: Vec<T>,
+    #[serde(default, rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+}
+
+/// OData query options, rendered as `$filter`, `$select`, `$apply`, `$orderby` and `$top`
+#[derive(Debug, Default, Clone)]
+pub struct ODataQuery {
+    pub filter: Option<String>,
+    pub select: Option<String>,
+    pub apply: Option<String>,
+    pub orderby: Option<String>,
+    pub top: Option<u32>,
+}
+
+impl ODataQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut params = Vec::new();
+        for (name, value) in [
+            ("$apply", &self.apply),
+            ("$filter", &self.filter),
+            ("$select", &self.select),
+            ("$orderby", &self.orderby),
+        ] {
+            if let Some(value) = value {
+                params.push(format!("{}={}", name, urlencoding::encode(value)));
+            }
+        }
+        if let Some(top) = self.top {
+            params.push(format!("$top={}", top));
+        }
+        params.join("&")
+    }
+}
+
+/// Quote a string literal for use in an OData expression
+pub fn odata_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Build a `Teams/any(...)` filter matching a team by GUID or by name
+pub fn team_filter(team: &str) -> String {
+    if team.len() == 36 && team.chars().filter(|c| *c == '-').count() == 4 {
+        format!("Teams/any(t:t/TeamId eq {})", team)
+    } else {
+        format!("Teams/any(t:t/TeamName eq {})", odata_string(team))
+    }
+}
+
+/// Row of the `WorkItemSnapshot` entity set (one row per work item per day)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemSnapshot {
+    #[serde(rename = "WorkItemId")]
+    pub work_item_id: u32,
+    #[serde(default, rename = "DateValue")]
+    pub date_value: Option<String>,
+    #[serde(default, rename = "Title")]
+    pub title: Option<String>,
+    #[serde(default, rename = "WorkItemType")]
+    pub work_item_type: Option<String>,
+    #[serde(default, rename = "State")]
+    pub state: Option<String>,
+    #[serde(default, rename = "StateCategory")]
+    pub state_category: Option<String>,
+    #[serde(default, rename = "StoryPoints")]
+    pub story_points: Option<f64>,
+    #[serde(default, rename = "Effort")]
+    pub effort: Option<f64>,
+    #[serde(default, rename = "RemainingWork")]
+    pub remaining_work: Option<f64>,
+}
+
+/// Row of the `WorkItems` entity set (current state of a work item)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalyticsWorkItem {
+    #[serde(rename = "WorkItemId")]
+    pub work_item_id: u32,
+    #[serde(default, rename = "Title")]
+    pub title: Option<String>,
+    #[serde(default, rename = "WorkItemType")]
+    pub work_item_type: Option<String>,
+    #[serde(default, rename = "State")]
+    pub state: Option<String>,
+    #[serde(default, rename = "StateCategory")]
+    pub state_category: Option<String>,
+    #[serde(default, rename = "CreatedDate")]
+    pub created_date: Option<String>,
+    #[serde(default, rename = "ActivatedDate")]
+    pub activated_date: Option<String>,
+    #[serde(default, rename = "ClosedDate")]
+    pub closed_date: Option<String>,
+    #[serde(default, rename = "CompletedDate")]
+    pub completed_date: Option<String>,
+    #[serde(default, rename = "StoryPoints")]
+    pub story_points: Option<f64>,
+    #[serde(default, rename = "Effort")]
+    pub effort: Option<f64>,
+    #[serde(default, rename = "CycleTimeDays")]
+    pub cycle_time_days: Option<f64>,
+    #[serde(default, rename = "LeadTimeDays")]
+    pub lead_time_days: Option<f64>,
+}
+
+/// Run an OData query against an entity set, following `@odata.nextLink` pagination
+pub async fn query<T: DeserializeOwned>(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+    entity_set: &str,
+    query: &ODataQuery,
+) -> Result<Vec<T>, AzureError> {
+    let mut path = query_path(entity_set, query);
+    let mut rows = Vec::new();
+
+    for page in 1..=MAX_PAGES {
+        let (page_rows, next) = query_page(client, organization, project, &path).await?;
+        rows.extend(page_rows);
+        crate::progress::report(page as u64, None, || {
+            format!(
+                "Fetched {} {} rows ({} pages)",
+                rows.len(),
+                entity_set,
+                page
+            )
+        });
+
+        match next {
+            Some(next) => path = next,
+            None => return Ok(rows),
+        }
+    }
+
+    tracing::warn!(
+        "Analytics query on {} truncated after {} pages",
+        entity_set,
+        MAX_PAGES
+    );
+    Ok(rows)
+}
+
+/// Path of the first page of an OData query against an entity set
+pub fn query_path(entity_set: &str, query: &ODataQuery) -> String {
+    // API: https://analytics.dev.azure.com/{organization}/{project}/_odata/v4.0-preview/{entitySet}?{query}
+    format!("{}?{}", entity_set, query.to_query_string())
+}
+
+/// Get a page of an OData query, with the path of the next page from `@odata.nextLink`
+pub async fn query_page<T: DeserializeOwned>(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+    path: &str,
+) -> Result<(Vec<T>, Option<String>), AzureError> {
+    let response: ODataResponse<T> = client
+        .analytics_request(organization, project, Method::GET, path, None::<&String>)
+        .await?;
+    let next = response
+        .next_link
+        .as_deref()
+        .and_then(|link| link.split_once(ODATA_ROOT))
+        .map(|(_, next)| next.to_string());
+    Ok((response.value, next))
+}
+
+/// Query daily work item snapshots
+pub async fn query_work_item_snapshots(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+    query_options: &ODataQuery,
+) -> Result<Vec<WorkItemSnapshot>, AzureError> {
+    query(
+        client,
+        organization,
+        project,
+        "WorkItemSnapshot",
+        query_options,
+    )
+    .await
+}
+
+/// Query the current state of work items
+pub async fn query_work_items(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+    query_options: &ODataQuery,
+) -> Result<Vec<AnalyticsWorkItem>, AzureError> {
+    query(client, organization, project, "WorkItems", query_options).await
+}
+
+/// Run an aggregation (`$apply=...groupby(...)/aggregate(...)`), rows are returned untyped
+pub async fn aggregate(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+    entity_set: &str,
+    apply: &str,
+) -> Result<Vec<Value>, AzureError> {
+    let query_options = ODataQuery {
+        apply: Some(apply.to_string()),
+        ..Default::default()
+    };
+    query(client, organization, project, entity_set, &query_options).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odata_query_string() {
+        let query = ODataQuery {
+            filter: Some("State eq 'Closed'".to_string()),
+            select: Some("WorkItemId,Title".to_string()),
+            top: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            query.to_query_string(),
+            "$filter=State%20eq%20%27Closed%27&$select=WorkItemId%2CTitle&$top=10"
+        );
+        assert_eq!(ODataQuery::default().to_query_string(), "");
+    }
+
+    #[test]
+    fn test_team_filter() {
+        assert_eq!(
+            team_filter("O'Brien Team"),
+            "Teams/any(t:t/TeamName eq 'O''Brien Team')"
+        );
+        assert_eq!(
+            team_filter("6f8a1c2e-3b4d-4e5f-9a0b-1c2d3e4f5a6b"),
+            "Teams/any(t:t/TeamId eq 6f8a1c2e-3b4d-4e5f-9a0b-1c2d3e4f5a6b)"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use crate::azure::client::AzureError;
+use crate::azure::cloud::Cloud;
+use async_trait::async_trait;
+use reqwest::Method;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Azure DevOps as the tools and the API modules reach it, implemented by
+/// [`AzureDevOpsClient`](crate::azure::client::AzureDevOpsClient). The typed requests of
+/// the API modules (`org_request`, `get`, `patch_patch`, ...) are built on the methods
+/// below, so another implementation, such as the mock of the tests or another backend,
+/// serves every tool unchanged. The settings and services the tools act with besides the
+/// requests, such as the write policy and the work item store, are given to them apart,
+/// in a [`ClientContext`](crate::azure::context::ClientContext).
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AzureBoardsApi: Send + Sync {
+    /// Send a request with a JSON body to a URL, returning the body of the successful
+    /// response
+    async fn send_json(
+        &self,
+        organization: Option<String>,
+        method: Method,
+        url: &str,
+        body: Option<String>,
+        content_type: &str,
+    ) -> Result<String, AzureError>;
+
+    /// Send a GET request to a URL, returning the body and headers of the successful
+    /// response
+    async fn send_get_with_headers(
+        &self,
+        organization: &str,
+        url: &str,
+    ) -> Result<(String, HeaderMap), AzureError>;
+
+    /// Send a request with a binary body to a URL, returning the body of the successful
+    /// response
+    async fn send_bytes(
+        &self,
+        organization: &str,
+        method: Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+        content_type: Option<&'static str>,
+    ) -> Result<Vec<u8>, AzureError>;
+
+    /// Token used for the requests to the organization, `user:password` for PATs
+    async fn access_token(&self, organization: &str) -> Result<String, AzureError>;
+
+    /// Cloud hosting the organizations
+    fn cloud(&self) -> Cloud;
+}
+
+/// Deserialize a response body, treating an empty body (e.g. 204 No Content) as null
+fn parse_response_body<T: DeserializeOwned>(text: &str) -> Result<T, AzureError> {
+    if text.trim().is_empty() {
+        return Ok(serde_json::from_str("null")?);
+    }
+    Ok(serde_json::from_str(text)?)
+}
+
+fn serialize_body(body: Option<&(impl Serialize + ?Sized)>) -> Result<Option<String>, AzureError> {
+    Ok(body.map(serde_json::to_string).transpose()?)
+}
+
+impl dyn AzureBoardsApi + '_ {
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        organization: Option<&str>,
+        method: Method,
+        url: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+        content_type: &str,
+    ) -> Result<T, AzureError> {
+        let body = serialize_body(body)?;
+        let text = self
+            .send_json(
+                organization.map(String::from),
+                method,
+                url,
+                body,
+                content_type,
+            )
+            .await?;
+        parse_response_body(&text)
+    }
+
+    pub async fn request_with_content_type<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+        content_type: &str,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            project,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, content_type)
+            .await
+    }
+
+    /// Make a request at the organization level (not project-scoped)
+    pub async fn org_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request to the VSSPS API (Visual Studio Services Platform Services)
+    /// URL format: https://app.vssps.visualstudio.com/_apis/{path}
+    pub async fn vssps_request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!("{}/_apis/{}", self.cloud().profile_base_url(), path);
+
+        self.execute(None, method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request to the organization scoped VSSPS API (identities, graph)
+    /// URL format: https://vssps.dev.azure.com/{organization}/_apis/{path}
+    pub async fn org_vssps_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/_apis/{}",
+            self.cloud().base_url(Some("vssps")),
+            organization,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request to the Search API
+    /// URL format: https://almsearch.dev.azure.com/{organization}/_apis/{path}
+    pub async fn search_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/_apis/{}",
+            self.cloud().base_url(Some("almsearch")),
+            organization,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request to the Audit API
+    /// URL format: https://auditservice.dev.azure.com/{organization}/_apis/{path}
+    pub async fn audit_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/_apis/{}",
+            self.cloud().base_url(Some("auditservice")),
+            organization,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request to the Analytics OData API, optionally project-scoped
+    /// URL format: https://analytics.dev.azure.com/{organization}/{project}/_odata/v4.0-preview/{path}
+    pub async fn analytics_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: Option<&str>,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = match project {
+            Some(project) => format!(
+                "{}/{}/{}/_odata/v4.0-preview/{}",
+                self.cloud().base_url(Some("analytics")),
+                organization,
+                project,
+                path
+            ),
+            None => format!(
+                "{}/{}/_odata/v4.0-preview/{}",
+                self.cloud().base_url(Some("analytics")),
+                organization,
+                path
+            ),
+        };
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    /// Make a request at the team level (team-scoped)
+    /// URL format: https://dev.azure.com/{organization}/{project}/{team}/_apis/{path}
+    pub async fn team_request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        method: Method,
+        team: &str,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            project,
+            team,
+            path
+        );
+
+        self.execute(Some(organization), method, &url, body, "application/json")
+            .await
+    }
+
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        method: Method,
+        path: &str,
+        body: Option<&(impl Serialize + ?Sized)>,
+    ) -> Result<T, AzureError> {
+        self.request_with_content_type(
+            organization,
+            project,
+            method,
+            path,
+            body,
+            "application/json",
+        )
+        .await
+    }
+
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+    ) -> Result<T, AzureError> {
+        self.request(organization, project, Method::GET, path, None::<&String>)
+            .await
+    }
+
+    /// GET request that returns both the response body and headers
+    pub async fn get_with_headers<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+    ) -> Result<(T, HeaderMap), AzureError> {
+        let url = format!(
+            "{}/{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            project,
+            path
+        );
+
+        let (text, headers) = self.send_get_with_headers(organization, &url).await?;
+        Ok((parse_response_body(&text)?, headers))
+    }
+
+    pub async fn post<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+        body: &(impl Serialize + ?Sized),
+    ) -> Result<T, AzureError> {
+        self.request(organization, project, Method::POST, path, Some(body))
+            .await
+    }
+
+    pub async fn patch<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+        body: &(impl Serialize + ?Sized),
+    ) -> Result<T, AzureError> {
+        self.request(organization, project, Method::PATCH, path, Some(body))
+            .await
+    }
+
+    pub async fn post_patch<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+        body: &(impl Serialize + ?Sized),
+    ) -> Result<T, AzureError> {
+        self.request_with_content_type(
+            organization,
+            project,
+            Method::POST,
+            path,
+            Some(body),
+            "application/json-patch+json",
+        )
+        .await
+    }
+
+    pub async fn patch_patch<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+        body: &(impl Serialize + ?Sized),
+    ) -> Result<T, AzureError> {
+        self.request_with_content_type(
+            organization,
+            project,
+            Method::PATCH,
+            path,
+            Some(body),
+            "application/json-patch+json",
+        )
+        .await
+    }
+
+    pub async fn post_binary<T: DeserializeOwned>(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<T, AzureError> {
+        let url = format!(
+            "{}/{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            project,
+            path
+        );
+
+        let bytes = self
+            .send_bytes(
+                organization,
+                Method::POST,
+                &url,
+                Some(body),
+                Some("application/octet-stream"),
+            )
+            .await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    pub async fn get_binary(
+        &self,
+        organization: &str,
+        project: &str,
+        path: &str,
+    ) -> Result<Vec<u8>, AzureError> {
+        let url = format!(
+            "{}/{}/{}/_apis/{}",
+            self.cloud().base_url(None),
+            organization,
+            project,
+            path
+        );
+
+        self.send_bytes(organization, Method::GET, &url, None, None)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::projects;
+    use mockall::predicate::{always, eq};
+
+    #[tokio::test]
+    async fn test_mock_api() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json()
+            .with(
+                eq(Some("contoso".to_string())),
+                eq(Method::GET),
+                eq("https://dev.azure.com/contoso/_apis/projects?api-version=7.1"),
+                always(),
+                eq("application/json"),
+            )
+            .times(1)
+            .returning(|_, _, _, _, _| {
+                Ok(
+                    r#"{"value":[{"id":"1","name":"Web","url":"u","state":"wellFormed"}]}"#
+                        .to_string(),
+                )
+            });
+
+        let projects = projects::list_projects(&api, "contoso").await.unwrap();
+        assert_eq!(projects[0].name, "Web");
+    }
+}
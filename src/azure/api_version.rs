@@ -0,0 +1,122 @@
+use reqwest::Url;
+
+/// Version the requests are written against
+pub const DEFAULT_API_VERSION: &str = "7.1";
+
+/// Overrides of the `api-version` of the requests, e.g. for older Azure DevOps Server
+/// releases or preview APIs
+#[derive(Debug, Clone, Default)]
+pub struct ApiVersionConfig {
+    /// Version of every request (e.g. `6.0`), keeping the preview suffix of the
+    /// endpoints that require one (`6.0-preview.3`)
+    pub default: Option<String>,
+    /// Versions by endpoint, used as is: the endpoint is matched as leading segments of
+    /// the path after `_apis/` (e.g. `wit/wiql`, not matching `wit/wiqlx`), the longest
+    /// match wins
+    pub overrides: Vec<(String, String)>,
+}
+
+impl ApiVersionConfig {
+    /// Version of a request to the endpoint written against the `requested` version
+    pub fn resolve(&self, endpoint: &str, requested: &str) -> String {
+        let endpoint = endpoint.trim_start_matches('/');
+        if let Some((_, version)) = self
+            .overrides
+            .iter()
+            .filter(|(prefix, _)| is_under(endpoint, prefix.trim_matches('/')))
+            .max_by_key(|(prefix, _)| prefix.len())
+        {
+            return version.clone();
+        }
+        match &self.default {
+            Some(version) => match requested.split_once("-preview") {
+                Some((_, preview)) if !version.contains("-preview") => {
+                    format!("{}-preview{}", version, preview)
+                }
+                _ => version.clone(),
+            },
+            None => requested.to_string(),
+        }
+    }
+
+    /// Rewrite the `api-version` query parameter of a request URL
+    pub fn apply(&self, url: &mut Url) {
+        if self.default.is_none() && self.overrides.is_empty() {
+            return;
+        }
+        let Some(query) = url.query() else {
+            return;
+        };
+        let endpoint = url
+            .path()
+            .split_once("/_apis/")
+            .map(|(_, endpoint)| endpoint.to_string())
+            .unwrap_or_default();
+        let query = query
+            .split('&')
+            .map(|pair| match pair.strip_prefix("api-version=") {
+                Some(requested) => format!("api-version={}", self.resolve(&endpoint, requested)),
+                None => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        url.set_query(Some(&query));
+    }
+}
+
+/// Whether an endpoint is the given one or under it, e.g. `wit/workitems/42` under
+/// `wit/workitems` but not `wit/workitemsbatch`
+fn is_under(endpoint: &str, prefix: &str) -> bool {
+    endpoint
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+}
+
+/// Compare two api-versions by their numeric part (`7.1-preview.3` is `7.1`)
+pub fn is_at_most(version: &str, max: &str) -> bool {
+    let number = |v: &str| -> Vec<u32> {
+        v.split('-')
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    number(version) <= number(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let config = ApiVersionConfig {
+            default: Some("6.0".to_string()),
+            overrides: vec![("wit/wiql".to_string(), "5.1".to_string())],
+        };
+        let mut url = Url::parse(
+            "https://dev.azure.com/org/_apis/graph/users?$top=1&api-version=7.1-preview.1",
+        )
+        .unwrap();
+        config.apply(&mut url);
+        assert_eq!(url.query(), Some("$top=1&api-version=6.0-preview.1"));
+
+        let mut url =
+            Url::parse("https://dev.azure.com/org/project/_apis/wit/wiql?api-version=7.1").unwrap();
+        config.apply(&mut url);
+        assert_eq!(url.query(), Some("api-version=5.1"));
+
+        let config = ApiVersionConfig {
+            default: None,
+            overrides: vec![("wit/workitems".to_string(), "5.1".to_string())],
+        };
+        assert_eq!(config.resolve("wit/workitems/42", "7.1"), "5.1");
+        assert_eq!(config.resolve("wit/workitems?ids=1", "7.1"), "5.1");
+        assert_eq!(config.resolve("wit/workitemsbatch", "7.1"), "7.1");
+        assert_eq!(config.resolve("wit/workitemtypes", "7.1"), "7.1");
+
+        assert!(is_at_most("6.0-preview.1", "7.1"));
+        assert!(!is_at_most("7.1", "6.0"));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::{builds, git};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+
+/// GitHub pull request and issue URLs, e.g. `https://github.com/owner/repo/pull/12`
+static RE_GITHUB_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^https?://(?:www\.)?github\.com/([^/\s]+)/([^/\s]+)/(pull|issues)/(\d+)(?:[/?#].*)?$",
+    )
+    .unwrap()
+});
+
+/// Development artifact referenced by an `ArtifactLink` work item relation
+#[derive(Debug, PartialEq, Eq)]
+pub enum Artifact {
+    PullRequest {
+        project_id: String,
+        repository_id: String,
+        pull_request_id: u32,
+    },
+    Branch {
+        project_id: String,
+        repository_id: String,
+        name: String,
+    },
+    Commit {
+        project_id: String,
+        repository_id: String,
+        commit_id: String,
+    },
+    Build {
+        build_id: u32,
+    },
+}
+
+/// Resolved, human readable artifact
+#[derive(Debug)]
+pub struct ResolvedArtifact {
+    pub kind: &'static str,
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub repository: String,
+    pub author: String,
+    pub date: String,
+}
+
+/// Parse a `vstfs:///` artifact URL, returns None for unsupported artifact types
+pub fn parse_artifact_url(url: &str) -> Option<Artifact> {
+    let rest = url.strip_prefix("vstfs:///")?;
+    let mut parts = rest.splitn(3, '/');
+    let tool = parts.next()?;
+    let artifact_type = parts.next()?;
+    let id = urlencoding::decode(parts.next()?).ok()?.into_owned();
+
+    match (tool, artifact_type) {
+        ("Build", "Build") => Some(Artifact::Build {
+            build_id: id.parse().ok()?,
+        }),
+        ("Git", kind) => {
+            let mut segments = id.splitn(3, '/');
+            let project_id = segments.next()?.to_string();
+            let repository_id = segments.next()?.to_string();
+            let value = segments.next()?.to_string();
+            match kind {
+                "PullRequestId" => Some(Artifact::PullRequest {
+                    project_id,
+                    repository_id,
+                    pull_request_id: value.parse().ok()?,
+                }),
+                // Branch refs are prefixed with "GB" (tags "GT", commits "GC")
+                "Ref" => Some(Artifact::Branch {
+                    project_id,
+                    repository_id,
+                    name: value.strip_prefix("GB").unwrap_or(&value).to_string(),
+                }),
+                "Commit" => Some(Artifact::Commit {
+                    project_id,
+                    repository_id,
+                    commit_id: value,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Resolve an artifact through the Git and Build APIs
+pub async fn resolve_artifact(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    artifact: &Artifact,
+) -> Result<ResolvedArtifact, AzureError> {
+    match artifact {
+        Artifact::PullRequest {
+            project_id,
+            repository_id,
+            pull_request_id,
+        } => {
+            let pr = git::get_pull_request(
+                client,
+                organization,
+                project_id,
+                repository_id,
+                *pull_request_id,
+            )
+            .await?;
+            Ok(ResolvedArtifact {
+                kind: "pull_request",
+                id: pr.pull_request_id.to_string(),
+                title: pr.title,
+                status: format!(
+                    "{} ({} -> {})",
+                    pr.status,
+                    pr.source_ref_name.as_deref().unwrap_or_default(),
+                    pr.target_ref_name.as_deref().unwrap_or_default()
+                ),
+                repository: pr.repository.map(|r| r.name).unwrap_or_default(),
+                author: pr
+                    .created_by
+                    .as_ref()
+                    .and_then(|c| c.get("displayName"))
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                date: pr.closed_date.or(pr.creation_date).unwrap_or_default(),
+            })
+        }
+        Artifact::Branch {
+            project_id,
+            repository_id,
+            name,
+        } => {
+            let repository =
+                git::get_repository(client, organization, project_id, repository_id).await?;
+            Ok(ResolvedArtifact {
+                kind: "branch",
+                id: name.clone(),
+                title: name.clone(),
+                status: String::new(),
+                repository: repository.name,
+                author: String::new(),
+                date: String::new(),
+            })
+        }
+        Artifact::Commit {
+            project_id,
+            repository_id,
+            commit_id,
+        } => {
+            let commit =
+                git::get_commit(client, organization, project_id, repository_id, commit_id).await?;
+            let repository =
+                git::get_repository(client, organization, project_id, repository_id).await?;
+            let (author, date) = commit.author.map(|a| (a.name, a.date)).unwrap_or_default();
+            Ok(ResolvedArtifact {
+                kind: "commit",
+                id: commit.commit_id,
+                title: commit
+                    .comment
+                    .as_deref()
+                    .and_then(|c| c.lines().next())
+                    .unwrap_or_default()
+                    .to_string(),
+                status: String::new(),
+                repository: repository.name,
+                author,
+                date,
+            })
+        }
+        Artifact::Build { build_id } => {
+            let build = builds::get_build(client, organization, project, *build_id).await?;
+            Ok(ResolvedArtifact {
+                kind: "build",
+                id: build.id.to_string(),
+                title: format!(
+                    "{} {}",
+                    build
+                        .definition
+                        .as_ref()
+                        .and_then(|d| d.get("name"))
+                        .and_then(|n| n.as_str())
+                        .unwrap_or_default(),
+                    build.build_number
+                ),
+                status: build.result.or(build.status).unwrap_or_default(),
+                repository: build.source_branch.unwrap_or_default(),
+                author: String::new(),
+                date: build.finish_time.unwrap_or_default(),
+            })
+        }
+    }
+}
+
+/// GitHub pull request or issue, linked to work items through the Boards GitHub
+/// connections of the project
+#[derive(Debug, PartialEq, Eq)]
+pub struct GitHubArtifact {
+    /// "PullRequest" or "Issue", as in the artifact URLs
+    pub kind: &'static str,
+    pub owner: String,
+    pub repository: String,
+    pub number: u32,
+}
+
+impl GitHubArtifact {
+    /// Name of the artifact link, as shown in the Development section of the work item
+    pub fn link_name(&self) -> &'static str {
+        match self.kind {
+            "PullRequest" => "GitHub Pull Request",
+            _ => "GitHub Issue",
+        }
+    }
+
+    pub fn repository_url(&self) -> String {
+        format!("https://github.com/{}/{}", self.owner, self.repository)
+    }
+}
+
+/// Parse a GitHub pull request or issue URL
+pub fn parse_github_url(url: &str) -> Option<GitHubArtifact> {
+    let captures = RE_GITHUB_URL.captures(url.trim())?;
+    Some(GitHubArtifact {
+        kind: match &captures[3] {
+            "pull" => "PullRequest",
+            _ => "Issue",
+        },
+        owner: captures[1].to_string(),
+        repository: captures[2].trim_end_matches(".git").to_string(),
+        number: captures[4].parse().ok()?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubConnectionList {
+    value: Vec<GitHubConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubConnection {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepositoryList {
+    value: Vec<GitHubRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GitHubRepository {
+    /// Internal ID of the repository in the connection, used by the artifact URLs
+    id: String,
+    git_hub_repository_url: String,
+}
+
+/// `vstfs:///` URL of a GitHub artifact, looking its repository up in the GitHub
+/// connections of the project, which must have it connected to Azure Boards
+pub async fn github_artifact_url(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    artifact: &GitHubArtifact,
+) -> Result<String, AzureError> {
+    // API: https://dev.azure.com/{organization}/{project}/_apis/githubconnections?api-version=7.1-preview
+    let connections: GitHubConnectionList = client
+        .get(
+            organization,
+            project,
+            "githubconnections?api-version=7.1-preview",
+        )
+        .await?;
+    let repository_url = artifact.repository_url();
+    for connection in connections.value {
+        // API: https://dev.azure.com/{organization}/{project}/_apis/githubconnections/{connectionId}/repos?api-version=7.1-preview
+        let path = format!(
+            "githubconnections/{}/repos?api-version=7.1-preview",
+            connection.id
+        );
+        let repositories: GitHubRepositoryList = client.get(organization, project, &path).await?;
+        if let Some(repository) = repositories.value.iter().find(|repository| {
+            repository
+                .git_hub_repository_url
+                .trim_end_matches('/')
+                .eq_ignore_ascii_case(&repository_url)
+        }) {
+            return Ok(format!(
+                "vstfs:///GitHub/{}/{}%2F{}",
+                artifact.kind, repository.id, artifact.number
+            ));
+        }
+    }
+    Err(AzureError::ApiError(format!(
+        "{} is not connected to the Azure Boards of project {}, add it to a GitHub connection in the project settings",
+        repository_url, project
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_artifact_url() {
+        assert_eq!(
+            parse_artifact_url("vstfs:///Git/PullRequestId/proj%2Frepo%2F42"),
+            Some(Artifact::PullRequest {
+                project_id: "proj".to_string(),
+                repository_id: "repo".to_string(),
+                pull_request_id: 42,
+            })
+        );
+        assert_eq!(
+            parse_artifact_url("vstfs:///Git/Ref/proj%2Frepo%2FGBfeature%2Flogin"),
+            Some(Artifact::Branch {
+                project_id: "proj".to_string(),
+                repository_id: "repo".to_string(),
+                name: "feature/login".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_artifact_url("vstfs:///Build/Build/123"),
+            Some(Artifact::Build { build_id: 123 })
+        );
+        assert_eq!(parse_artifact_url("vstfs:///Wiki/WikiPage/x"), None);
+    }
+
+    #[test]
+    fn test_parse_github_url() {
+        let artifact =
+            parse_github_url("https://github.com/contoso/web.git/pull/42/files").unwrap();
+        assert_eq!(
+            artifact,
+            GitHubArtifact {
+                kind: "PullRequest",
+                owner: "contoso".to_string(),
+                repository: "web".to_string(),
+                number: 42,
+            }
+        );
+        assert_eq!(artifact.repository_url(), "https://github.com/contoso/web");
+        assert_eq!(
+            parse_github_url("https://github.com/contoso/web/issues/7")
+                .unwrap()
+                .link_name(),
+            "GitHub Issue"
+        );
+        assert_eq!(parse_github_url("https://github.com/contoso/web"), None);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::context;
+use crate::azure::identities::{self, Identity};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Time the identities found for an input are reused
+const INDEX_TTL: Duration = Duration::from_secs(600);
+
+/// Candidates listed when an assignee is ambiguous
+const MAX_CANDIDATES: usize = 5;
+
+/// Users found by an identity search, with when it was made
+type Found = (Instant, Vec<Assignee>);
+
+/// Users found by the identity searches of a client, keyed by organization and
+/// lowercased input, kept by its [`ClientContext`](crate::azure::context::ClientContext)
+/// as the users of another credential may not see the same identities
+pub struct IdentityIndex {
+    ttl: Duration,
+    found: Mutex<HashMap<(String, String), Found>>,
+}
+
+impl Default for IdentityIndex {
+    fn default() -> Self {
+        Self {
+            ttl: INDEX_TTL,
+            found: Mutex::default(),
+        }
+    }
+}
+
+impl IdentityIndex {
+    fn get(&self, key: &(String, String)) -> Option<Vec<Assignee>> {
+        self.found
+            .lock()
+            .unwrap()
+            .get(key)
+            .filter(|(found_at, _)| found_at.elapsed() < self.ttl)
+            .map(|(_, users)| users.clone())
+    }
+
+    /// Index the users found for an input, dropping the expired searches
+    fn insert(&self, key: (String, String), users: Vec<Assignee>) {
+        let mut found = self.found.lock().unwrap();
+        found.retain(|_, (found_at, _)| found_at.elapsed() < self.ttl);
+        found.insert(key, (Instant::now(), users));
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.found.lock().unwrap().len()
+    }
+}
+
+/// User a work item can be assigned to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignee {
+    pub display_name: String,
+    pub email: Option<String>,
+    pub account: Option<String>,
+}
+
+impl Assignee {
+    /// Value of the `System.AssignedTo` field, the display name and email telling apart
+    /// the users with the same name, e.g. `Jane Doe <jane@contoso.com>`
+    pub fn field_value(&self) -> String {
+        match self.email.as_deref().or(self.account.as_deref()) {
+            Some(email) => format!("{} <{}>", self.display_name, email),
+            None => self.display_name.clone(),
+        }
+    }
+}
+
+impl From<&Identity> for Assignee {
+    fn from(identity: &Identity) -> Self {
+        Self {
+            display_name: identity.display_name().to_string(),
+            email: identity
+                .property("Mail")
+                .filter(|mail| !mail.is_empty())
+                .map(String::from),
+            account: identity.property("Account").map(String::from),
+        }
+    }
+}
+
+/// User an input stands for among the users found for it: the one with this email or
+/// account, else the one with this display name, else the only one whose name contains
+/// it or whose email starts with it. The candidates are returned when none or several
+/// match.
+pub fn match_assignee(input: &str, users: &[Assignee]) -> Result<Assignee, Vec<Assignee>> {
+    let wanted = input.trim().to_lowercase();
+    let is = |value: &Option<String>| {
+        value
+            .as_deref()
+            .is_some_and(|value| value.to_lowercase() == wanted)
+    };
+    if let Some(user) = users
+        .iter()
+        .find(|user| is(&user.email) || is(&user.account))
+    {
+        return Ok(user.clone());
+    }
+    let named: Vec<&Assignee> = users
+        .iter()
+        .filter(|user| user.display_name.to_lowercase() == wanted)
+        .collect();
+    let matching: Vec<&Assignee> = if named.is_empty() {
+        users
+            .iter()
+            .filter(|user| {
+                user.display_name.to_lowercase().contains(&wanted)
+                    || user
+                        .email
+                        .as_deref()
+                        .is_some_and(|email| email.to_lowercase().starts_with(&wanted))
+            })
+            .collect()
+    } else {
+        named
+    };
+    match matching.as_slice() {
+        [user] => Ok((*user).clone()),
+        _ => Err(matching.into_iter().take(MAX_CANDIDATES).cloned().collect()),
+    }
+}
+
+/// Users of the organization an input may stand for, searched by display name, account
+/// name or email and indexed for a while
+async fn find_users(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    input: &str,
+) -> Result<Vec<Assignee>, AzureError> {
+    let index = context::current().identity_index.clone();
+    let key = (organization.to_string(), input.trim().to_lowercase());
+    if let Some(users) = index.get(&key) {
+        return Ok(users);
+    }
+    let users: Vec<Assignee> = identities::search_identities(client, organization, input.trim())
+        .await?
+        .iter()
+        .filter(|identity| !identity.is_container && identity.is_active)
+        .map(Assignee::from)
+        .collect();
+    index.insert(key, users.clone());
+    Ok(users)
+}
+
+/// Resolve an assignee given by display name, part of it or email to the exact user,
+/// as the value of the `System.AssignedTo` field. Empty inputs, unassigning, and the
+/// values already naming an identity (`Name <email>`) are kept as they are, like the
+/// inputs when the identities cannot be searched, Azure DevOps reporting the unknown
+/// users.
+pub async fn resolve_assignee(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    input: &str,
+) -> Result<String, AzureError> {
+    if input.trim().is_empty() || (input.contains('<') && input.trim_end().ends_with('>')) {
+        return Ok(input.to_string());
+    }
+    let users = match find_users(client, organization, input).await {
+        Ok(users) => users,
+        Err(e) => {
+            tracing::warn!("Failed to search the identities matching {}: {}", input, e);
+            return Ok(input.to_string());
+        }
+    };
+    match_assignee(input, &users)
+        .map(|user| user.field_value())
+        .map_err(|candidates| AzureError::UnresolvedIdentity {
+            input: input.to_string(),
+            candidates: candidates.iter().map(Assignee::field_value).collect(),
+        })
+}
+
+/// Resolve the `System.AssignedTo` value of the fields of a work item, by reference
+/// name, in place, with [`resolve_assignee`]
+pub async fn resolve_field_assignee(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    fields: &mut [(String, Value)],
+) -> Result<(), AzureError> {
+    for (field, value) in fields {
+        if field == "System.AssignedTo"
+            && let Value::String(input) = value
+        {
+            *input = resolve_assignee(client, organization, input).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_assignee() {
+        let user = |name: &str, email: &str| Assignee {
+            display_name: name.to_string(),
+            email: Some(email.to_string()),
+            account: Some(email.to_string()),
+        };
+        let users = [
+            user("Jane Doe", "jane@contoso.com"),
+            user("Jane Roe", "jroe@contoso.com"),
+            user("John Smith", "jsmith@contoso.com"),
+        ];
+
+        assert_eq!(
+            match_assignee("JROE@contoso.com", &users)
+                .unwrap()
+                .field_value(),
+            "Jane Roe <jroe@contoso.com>"
+        );
+        assert_eq!(match_assignee("jane doe", &users).unwrap(), users[0]);
+        assert_eq!(match_assignee("smith", &users).unwrap(), users[2]);
+        assert_eq!(match_assignee("jsmith", &users).unwrap(), users[2]);
+        assert_eq!(match_assignee("Jane", &users).unwrap_err(), users[..2]);
+        assert!(match_assignee("Alex", &users).unwrap_err().is_empty());
+    }
+
+    #[test]
+    fn test_identity_index() {
+        let key = |input: &str| ("contoso".to_string(), input.to_string());
+        let users = vec![Assignee {
+            display_name: "Jane Doe".to_string(),
+            email: Some("jane@contoso.com".to_string()),
+            account: None,
+        }];
+        let index = IdentityIndex::default();
+        index.insert(key("jane"), users.clone());
+        assert_eq!(index.get(&key("jane")), Some(users.clone()));
+        assert_eq!(index.get(&key("john")), None);
+
+        let index = IdentityIndex {
+            ttl: Duration::ZERO,
+            ..Default::default()
+        };
+        index.insert(key("jane"), users.clone());
+        assert_eq!(index.get(&key("jane")), None);
+        index.insert(key("john"), Vec::new());
+        assert_eq!(index.len(), 1);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub timestamp: String,
+    #[serde(default, rename = "actorDisplayName")]
+    pub actor_display_name: Option<String>,
+    #[serde(default, rename = "actorUPN")]
+    pub actor_upn: Option<String>,
+    #[serde(default, rename = "actionId")]
+    pub action_id: Option<String>,
+    #[serde(default)]
+    pub area: Option<String>,
+    #[serde(default, rename = "categoryDisplayName")]
+    pub category_display_name: Option<String>,
+    #[serde(default, rename = "scopeDisplayName")]
+    pub scope_display_name: Option<String>,
+    #[serde(default, rename = "projectName")]
+    pub project_name: Option<String>,
+    #[serde(default, rename = "ipAddress")]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogQueryResult {
+    #[serde(rename = "decoratedAuditLogEntries")]
+    pub decorated_audit_log_entries: Vec<AuditLogEntry>,
+    #[serde(default, rename = "continuationToken")]
+    pub continuation_token: Option<String>,
+    #[serde(default, rename = "hasMore")]
+    pub has_more: bool,
+}
+
+/// Get a batch of the organization audit log between two RFC 3339 timestamps, newest
+/// first, with the continuation token of the next batch if there are more entries
+pub async fn query_audit_log(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    start_time: &str,
+    end_time: &str,
+    continuation_token: Option<&str>,
+) -> Result<(Vec<AuditLogEntry>, Option<String>), AzureError> {
+    // API: https://auditservice.dev.azure.com/{organization}/_apis/audit/auditlog?startTime={start}&endTime={end}&api-version=7.1-preview.1
+    let mut path = format!(
+        "audit/auditlog?startTime={}&endTime={}&batchSize=200&api-version=7.1-preview.1",
+        urlencoding::encode(start_time),
+        urlencoding::encode(end_time)
+    );
+    if let Some(token) = continuation_token {
+        path.push_str(&format!(
+            "&continuationToken={}",
+            urlencoding::encode(token)
+        ));
+    }
+
+    let result: AuditLogQueryResult = client
+        .audit_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    let continuation_token = result.continuation_token.filter(|_| result.has_more);
+    Ok((result.decorated_audit_log_entries, continuation_token))
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use azure_core::auth::{AccessToken, TokenCredential, TokenResponse};
+use time::OffsetDateTime;
+
+/// OAuth access token handed over by the caller, e.g. the user of an HTTP session.
+///
+/// The token cannot be refreshed: once it expires the requests are rejected, and the
+/// caller starts a new session with a new token.
+pub struct AccessTokenCredential {
+    token: String,
+}
+
+impl AccessTokenCredential {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenCredential for AccessTokenCredential {
+    async fn get_token(&self, _resource: &str) -> azure_core::Result<TokenResponse> {
+        // Its expiry is unknown and it cannot be renewed, report a far one so the token
+        // manager does not try to
+        Ok(TokenResponse::new(
+            AccessToken::new(self.token.clone()),
+            OffsetDateTime::now_utc() + time::Duration::days(365),
+        ))
+    }
+}
@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use azure_core::auth::{AccessToken, TokenCredential, TokenResponse};
+use azure_core::error::{Error, ErrorKind};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::Deserialize;
+use time::OffsetDateTime;
+use tokio::process::Command;
+
+#[derive(Debug, Deserialize)]
+struct CliTokenResponse {
+    #[serde(rename = "accessToken")]
+    access_token: String,
+    /// Local time, e.g. "2025-01-06 14:03:12.000000"
+    #[serde(default, rename = "expiresOn")]
+    expires_on_local: Option<String>,
+    /// Unix timestamp, only returned by recent Azure CLI versions
+    #[serde(default, rename = "expires_on")]
+    expires_on: Option<i64>,
+}
+
+/// Parse the token expiry returned by `az account get-access-token`
+fn parse_expiry(response: &CliTokenResponse) -> Option<OffsetDateTime> {
+    let timestamp = match response.expires_on {
+        Some(timestamp) => timestamp,
+        None => {
+            let local = NaiveDateTime::parse_from_str(
+                response.expires_on_local.as_deref()?,
+                "%Y-%m-%d %H:%M:%S%.f",
+            )
+            .ok()?;
+            Local.from_local_datetime(&local).single()?.timestamp()
+        }
+    };
+    OffsetDateTime::from_unix_timestamp(timestamp).ok()
+}
+
+/// Reuses the Azure CLI login by shelling out to `az account get-access-token`
+#[derive(Default)]
+pub struct AzureCliCredential;
+
+impl AzureCliCredential {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn request_token(resource: &str) -> azure_core::Result<TokenResponse> {
+        let program = if cfg!(windows) { "az.cmd" } else { "az" };
+        tracing::debug!("Requesting Azure CLI token for {}", resource);
+        let output = Command::new(program)
+            .args([
+                "account",
+                "get-access-token",
+                "--resource",
+                resource,
+                "--output",
+                "json",
+            ])
+            .output()
+            .await
+            .map_err(|e| Error::full(ErrorKind::Credential, e, "Failed to run the Azure CLI"))?;
+        if !output.status.success() {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "Azure CLI token request failed, run `az login`: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+            }));
+        }
+
+        let response: CliTokenResponse = serde_json::from_slice(&output.stdout)?;
+        let expires_on = parse_expiry(&response).ok_or_else(|| {
+            Error::message(
+                ErrorKind::Credential,
+                "Azure CLI returned a token without a valid expiry",
+            )
+        })?;
+        Ok(TokenResponse::new(
+            AccessToken::new(response.access_token),
+            expires_on,
+        ))
+    }
+}
+
+#[async_trait]
+impl TokenCredential for AzureCliCredential {
+    async fn get_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+        Self::request_token(resource).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expiry() {
+        let response: CliTokenResponse = serde_json::from_str(
+            r#"{"accessToken":"t","expiresOn":"2025-01-06 14:03:12.000000","expires_on":1736172192}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parse_expiry(&response).unwrap().unix_timestamp(),
+            1736172192
+        );
+
+        let response: CliTokenResponse =
+            serde_json::from_str(r#"{"accessToken":"t","expiresOn":"2025-01-06 14:03:12.000000"}"#)
+                .unwrap();
+        assert!(parse_expiry(&response).is_some());
+    }
+}
@@ -0,0 +1,102 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use rsa::RsaPrivateKey;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::{SignatureEncoding, Signer};
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::path::Path;
+use time::OffsetDateTime;
+
+/// Lifetime of the signed client assertions
+const ASSERTION_LIFETIME_SECS: i64 = 600;
+
+/// Signs Entra ID client assertions with the private key of an app registration certificate
+pub struct CertificateSigner {
+    key: SigningKey<Sha256>,
+    /// Base64url SHA-1 thumbprint of the certificate (`x5t` header)
+    thumbprint: String,
+}
+
+impl CertificateSigner {
+    /// Load a PEM file holding the certificate and its unencrypted private key
+    /// (PKCS#8 `PRIVATE KEY` or PKCS#1 `RSA PRIVATE KEY`)
+    pub fn from_pem_file(path: &Path) -> Result<Self, String> {
+        let pem = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_pem(&pem)
+    }
+
+    pub fn from_pem(pem: &str) -> Result<Self, String> {
+        let key = pem_block(pem, "PRIVATE KEY")
+            .map(|der| RsaPrivateKey::from_pkcs8_der(&der).map_err(|e| e.to_string()))
+            .or_else(|| {
+                pem_block(pem, "RSA PRIVATE KEY")
+                    .map(|der| RsaPrivateKey::from_pkcs1_der(&der).map_err(|e| e.to_string()))
+            })
+            .ok_or("No private key found in the certificate file")?
+            .map_err(|e| format!("Invalid private key: {}", e))?;
+        let certificate =
+            pem_block(pem, "CERTIFICATE").ok_or("No certificate found in the certificate file")?;
+        Ok(Self {
+            key: SigningKey::new(key),
+            thumbprint: URL_SAFE_NO_PAD.encode(Sha1::digest(&certificate)),
+        })
+    }
+
+    /// Signed JWT proving the identity of `client_id` to the token endpoint `audience`
+    pub fn client_assertion(&self, client_id: &str, audience: &str) -> String {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let header = serde_json::json!({
+            "alg": "RS256",
+            "typ": "JWT",
+            "x5t": self.thumbprint,
+        });
+        let claims = serde_json::json!({
+            "aud": audience,
+            "iss": client_id,
+            "sub": client_id,
+            "jti": uuid::Uuid::new_v4().to_string(),
+            "nbf": now,
+            "exp": now + ASSERTION_LIFETIME_SECS,
+        });
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let signature = self.key.sign(message.as_bytes());
+        format!(
+            "{}.{}",
+            message,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        )
+    }
+}
+
+/// DER content of the first PEM block with the given label
+fn pem_block(pem: &str, label: &str) -> Option<Vec<u8>> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let start = pem.find(&begin)? + begin.len();
+    let length = pem[start..].find(&end)?;
+    let body: String = pem[start..start + length]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    STANDARD.decode(body).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pem_block() {
+        let pem = "-----BEGIN CERTIFICATE-----\nAQID\nBA==\n-----END CERTIFICATE-----\n";
+        assert_eq!(pem_block(pem, "CERTIFICATE"), Some(vec![1, 2, 3, 4]));
+        assert_eq!(pem_block(pem, "PRIVATE KEY"), None);
+    }
+}
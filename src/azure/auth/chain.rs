@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use azure_core::auth::{TokenCredential, TokenResponse};
+use azure_core::error::{Error, ErrorKind};
+use std::sync::Arc;
+
+/// Tries each credential in order and returns the first token acquired
+pub struct ChainedCredential {
+    credentials: Vec<(String, Arc<dyn TokenCredential>)>,
+}
+
+impl ChainedCredential {
+    pub fn new(credentials: Vec<(String, Arc<dyn TokenCredential>)>) -> Self {
+        Self { credentials }
+    }
+}
+
+#[async_trait]
+impl TokenCredential for ChainedCredential {
+    async fn get_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+        let mut errors = Vec::new();
+        for (name, credential) in &self.credentials {
+            match credential.get_token(resource).await {
+                Ok(token) => return Ok(token),
+                Err(e) => {
+                    tracing::debug!("Credential {} failed: {}", name, e);
+                    errors.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+        Err(Error::with_message(ErrorKind::Credential, || {
+            format!(
+                "No credential could acquire a token ({})",
+                errors.join("; ")
+            )
+        }))
+    }
+}
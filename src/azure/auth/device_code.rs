@@ -0,0 +1,109 @@
+use crate::azure::auth::token_store::RefreshTokenStore;
+use crate::azure::client::AzureError;
+use crate::azure::cloud::Cloud;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Public client of Visual Studio, pre-authorized for Azure DevOps
+pub const DEFAULT_CLIENT_ID: &str = "872cd9fa-d31f-45e0-9eab-6e460a02d1f1";
+/// Accept work and school accounts from any tenant
+pub const DEFAULT_TENANT: &str = "organizations";
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    message: String,
+    expires_in: u64,
+    #[serde(default = "default_interval")]
+    interval: u64,
+}
+
+fn default_interval() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenPollResponse {
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Interactive device code sign-in: prints the code to stderr, polls until the user
+/// completes the sign-in and persists the refresh token in `store`
+pub async fn login(
+    http: &reqwest::Client,
+    cloud: Cloud,
+    tenant_id: &str,
+    client_id: &str,
+    resource: &str,
+    store: &RefreshTokenStore,
+) -> Result<(), AzureError> {
+    let scope = format!("{}/.default offline_access", resource);
+
+    let response = http
+        .post(format!(
+            "https://{}/{}/oauth2/v2.0/devicecode",
+            cloud.login_host(),
+            tenant_id
+        ))
+        .form(&[("client_id", client_id), ("scope", scope.as_str())])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(AzureError::ApiError(response.text().await?));
+    }
+    let device_code: DeviceCodeResponse = response.json().await?;
+
+    // stdout is reserved for the MCP protocol
+    eprintln!("{}", device_code.message);
+
+    let mut interval = Duration::from_secs(device_code.interval);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(device_code.expires_in);
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(interval).await;
+
+        let poll: TokenPollResponse = http
+            .post(format!(
+                "https://{}/{}/oauth2/v2.0/token",
+                cloud.login_host(),
+                tenant_id
+            ))
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", client_id),
+                ("device_code", device_code.device_code.as_str()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match (poll.refresh_token, poll.error.as_deref()) {
+            (Some(refresh_token), _) => {
+                store.save(&refresh_token).map_err(|e| {
+                    AzureError::ApiError(format!("Failed to store the refresh token: {}", e))
+                })?;
+                eprintln!("Signed in, credentials stored in {}", store);
+                return Ok(());
+            }
+            (None, Some("authorization_pending")) => {}
+            (None, Some("slow_down")) => interval += Duration::from_secs(5),
+            (None, _) => {
+                return Err(AzureError::ApiError(format!(
+                    "Device code sign-in failed: {}",
+                    poll.error_description
+                        .or(poll.error)
+                        .unwrap_or_else(|| "no refresh token returned".to_string())
+                )));
+            }
+        }
+    }
+
+    Err(AzureError::ApiError(
+        "Device code sign-in timed out".to_string(),
+    ))
+}
@@ -0,0 +1,175 @@
+use crate::azure::auth::certificate::CertificateSigner;
+use crate::azure::auth::token_store::RefreshTokenStore;
+use crate::azure::cloud::Cloud;
+use async_trait::async_trait;
+use azure_core::auth::{AccessToken, TokenCredential, TokenResponse};
+use azure_core::error::{Error, ErrorKind};
+use serde::Deserialize;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    expires_in: u64,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+/// How the credential proves its identity to the token endpoint
+enum Grant {
+    /// Confidential client (app registration with a secret)
+    ClientSecret(String),
+    /// Confidential client proving its identity with a certificate-signed assertion
+    ClientCertificate(Box<CertificateSigner>),
+    /// Public client holding a user refresh token, rotated on every exchange
+    /// and persisted when a store is set
+    RefreshToken(RwLock<String>, Option<RefreshTokenStore>),
+}
+
+/// Acquires Azure DevOps tokens from the Entra ID v2.0 token endpoint
+pub struct EntraIdCredential {
+    http: reqwest::Client,
+    cloud: Cloud,
+    tenant_id: String,
+    client_id: String,
+    grant: Grant,
+}
+
+impl EntraIdCredential {
+    pub fn client_secret(tenant_id: &str, client_id: &str, client_secret: &str) -> Self {
+        Self::new(
+            tenant_id,
+            client_id,
+            Grant::ClientSecret(client_secret.to_string()),
+        )
+    }
+
+    pub fn client_certificate(
+        tenant_id: &str,
+        client_id: &str,
+        certificate: CertificateSigner,
+    ) -> Self {
+        Self::new(
+            tenant_id,
+            client_id,
+            Grant::ClientCertificate(Box::new(certificate)),
+        )
+    }
+
+    pub fn refresh_token(
+        tenant_id: &str,
+        client_id: &str,
+        refresh_token: &str,
+        store: Option<RefreshTokenStore>,
+    ) -> Self {
+        Self::new(
+            tenant_id,
+            client_id,
+            Grant::RefreshToken(RwLock::new(refresh_token.to_string()), store),
+        )
+    }
+
+    fn new(tenant_id: &str, client_id: &str, grant: Grant) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            cloud: Cloud::default(),
+            tenant_id: tenant_id.to_string(),
+            client_id: client_id.to_string(),
+            grant,
+        }
+    }
+
+    /// Send the token requests with the given HTTP client (e.g. one using a proxy)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Request the tokens from the Entra ID authority of the given cloud
+    pub fn with_cloud(mut self, cloud: Cloud) -> Self {
+        self.cloud = cloud;
+        self
+    }
+
+    /// Current refresh token, None for confidential clients
+    pub async fn current_refresh_token(&self) -> Option<String> {
+        match &self.grant {
+            Grant::ClientSecret(_) | Grant::ClientCertificate(_) => None,
+            Grant::RefreshToken(token, _) => Some(token.read().await.clone()),
+        }
+    }
+
+    async fn request_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+        let url = format!(
+            "https://{}/{}/oauth2/v2.0/token",
+            self.cloud.login_host(),
+            self.tenant_id
+        );
+        let mut form = vec![("client_id", self.client_id.clone())];
+        match &self.grant {
+            Grant::ClientSecret(secret) => {
+                form.push(("grant_type", "client_credentials".to_string()));
+                form.push(("scope", format!("{}/.default", resource)));
+                form.push(("client_secret", secret.clone()));
+            }
+            Grant::ClientCertificate(certificate) => {
+                form.push(("grant_type", "client_credentials".to_string()));
+                form.push(("scope", format!("{}/.default", resource)));
+                form.push((
+                    "client_assertion_type",
+                    "urn:ietf:params:oauth:client-assertion-type:jwt-bearer".to_string(),
+                ));
+                form.push((
+                    "client_assertion",
+                    certificate.client_assertion(&self.client_id, &url),
+                ));
+            }
+            Grant::RefreshToken(token, _) => {
+                form.push(("grant_type", "refresh_token".to_string()));
+                form.push(("scope", format!("{}/.default offline_access", resource)));
+                form.push(("refresh_token", token.read().await.clone()));
+            }
+        }
+
+        tracing::debug!("Requesting Entra ID token for {}", resource);
+        let response =
+            self.http.post(&url).form(&form).send().await.map_err(|e| {
+                Error::full(ErrorKind::Credential, e, "Entra ID token request failed")
+            })?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Error::full(ErrorKind::Credential, e, "Entra ID token request failed"))?;
+        if !status.is_success() {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!("Entra ID token request failed ({}): {}", status, text)
+            }));
+        }
+
+        let token: OAuthTokenResponse = serde_json::from_str(&text)?;
+        if let (Grant::RefreshToken(current, store), Some(rotated)) =
+            (&self.grant, token.refresh_token)
+        {
+            if let Some(store) = store
+                && let Err(e) = store.save(&rotated)
+            {
+                tracing::warn!("Failed to persist the refreshed token: {}", e);
+            }
+            *current.write().await = rotated;
+        }
+        Ok(TokenResponse::new(
+            AccessToken::new(token.access_token),
+            OffsetDateTime::now_utc() + Duration::from_secs(token.expires_in),
+        ))
+    }
+}
+
+#[async_trait]
+impl TokenCredential for EntraIdCredential {
+    async fn get_token(&self, resource: &str) -> azure_core::Result<TokenResponse> {
+        self.request_token(resource).await
+    }
+}
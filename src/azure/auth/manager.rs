@@ -0,0 +1,257 @@
+use crate::azure::client::AZURE_DEVOPS_SCOPE;
+use azure_core::auth::{TokenCredential, TokenResponse};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Minimum delay between background refreshes, for credentials returning short-lived tokens
+const MIN_REFRESH_DELAY: Duration = Duration::from_secs(30);
+
+/// Whether a token must be refreshed before use
+fn needs_refresh(token: &TokenResponse, now: OffsetDateTime) -> bool {
+    token.expires_on <= now + REFRESH_MARGIN
+}
+
+struct State {
+    /// Named credentials, the active one is used until the server rejects its tokens
+    credentials: Vec<(String, Arc<dyn TokenCredential>)>,
+    active: AtomicUsize,
+    /// Token of the active credential, with the index of the credential
+    token: RwLock<Option<(usize, TokenResponse)>>,
+}
+
+impl State {
+    fn active(&self) -> &(String, Arc<dyn TokenCredential>) {
+        &self.credentials[self.active.load(Ordering::Acquire)]
+    }
+
+    /// Return the current token and the credential it is from, acquiring a new one when
+    /// missing or expiring
+    async fn token(&self) -> azure_core::Result<(usize, TokenResponse)> {
+        if let Some((used, token)) = self.token.read().await.as_ref()
+            && !needs_refresh(token, OffsetDateTime::now_utc())
+        {
+            return Ok((*used, token.clone()));
+        }
+        self.refresh(false).await
+    }
+
+    async fn refresh(&self, force: bool) -> azure_core::Result<(usize, TokenResponse)> {
+        let mut current = self.token.write().await;
+        // Another task may have refreshed the token while waiting for the lock
+        if !force
+            && let Some((used, token)) = current.as_ref()
+            && !needs_refresh(token, OffsetDateTime::now_utc())
+        {
+            return Ok((*used, token.clone()));
+        }
+        let used = self.active.load(Ordering::Acquire);
+        let token = self.credentials[used]
+            .1
+            .get_token(AZURE_DEVOPS_SCOPE)
+            .await?;
+        *current = Some((used, token.clone()));
+        Ok((used, token))
+    }
+
+    /// Discard the current token when it is from the given credential
+    async fn discard(&self, used: usize) {
+        let mut token = self.token.write().await;
+        if token.as_ref().is_some_and(|(index, _)| *index == used) {
+            *token = None;
+        }
+    }
+
+    /// Delay until the current token must be refreshed, None without a token
+    async fn refresh_delay(&self) -> Option<Duration> {
+        let token = self.token.read().await;
+        let remaining = token.as_ref()?.1.expires_on - OffsetDateTime::now_utc();
+        let delay = Duration::try_from(remaining)
+            .unwrap_or_default()
+            .saturating_sub(REFRESH_MARGIN);
+        Some(delay.max(MIN_REFRESH_DELAY))
+    }
+}
+
+/// Keeps an Azure DevOps token of a credential fresh: the token is refreshed by a
+/// background task before it expires, so long-running sessions never wait on or fail
+/// with an expired token, and can be invalidated when the server rejects it
+pub struct TokenManager {
+    state: Arc<State>,
+    refresher: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl TokenManager {
+    pub fn new(credential: Arc<dyn TokenCredential>) -> Self {
+        Self::with_fallbacks(vec![("default".to_string(), credential)])
+    }
+
+    /// Manage credentials used in turn: when the server rejects the tokens of the active
+    /// credential (e.g. a rotated PAT), the next one takes over
+    pub fn with_fallbacks(credentials: Vec<(String, Arc<dyn TokenCredential>)>) -> Self {
+        assert!(
+            !credentials.is_empty(),
+            "at least one credential is required"
+        );
+        Self {
+            state: Arc::new(State {
+                credentials,
+                active: AtomicUsize::new(0),
+                token: RwLock::new(None),
+            }),
+            refresher: Mutex::new(None),
+        }
+    }
+
+    /// Active credential
+    pub fn credential(&self) -> &Arc<dyn TokenCredential> {
+        &self.state.active().1
+    }
+
+    /// Current token secret, with the index of the credential it is from to pass to
+    /// [`reject`](Self::reject) or [`fail_over`](Self::fail_over)
+    pub async fn token(&self) -> azure_core::Result<(usize, String)> {
+        let (used, token) = self.state.token().await?;
+        self.start_refresher();
+        Ok((used, token.token.secret().to_string()))
+    }
+
+    /// Token secret of a credential, acquired without being kept, e.g. to try another
+    /// credential than the active one
+    pub async fn token_of(&self, index: usize) -> azure_core::Result<String> {
+        let token = self.state.credentials[index]
+            .1
+            .get_token(AZURE_DEVOPS_SCOPE)
+            .await?;
+        Ok(token.token.secret().to_string())
+    }
+
+    /// Discard the token of a credential after the server answered 401, so the next call
+    /// acquires a new one, failing over to the next credential when there is one
+    pub async fn reject(&self, used: usize) {
+        self.state.discard(used).await;
+        if self.state.credentials.len() > 1 {
+            self.fail_over(used).await;
+        }
+    }
+
+    /// Credential to try a request forbidden (403) to the given one with, without it
+    /// taking over, as the request may only be forbidden to this credential: None when
+    /// there is no other credential
+    pub fn next_credential(&self, used: usize) -> Option<usize> {
+        let count = self.state.credentials.len();
+        (count > 1).then(|| (used + 1) % count)
+    }
+
+    /// Make the credential after the given one the active one, unless the given one is
+    /// no longer active, e.g. when concurrent requests are rejected with its tokens, so
+    /// that a credential is only ever replaced once
+    pub async fn fail_over(&self, used: usize) {
+        let Some(next) = self.next_credential(used) else {
+            return;
+        };
+        if self
+            .state
+            .active
+            .compare_exchange(used, next, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+        self.state.discard(used).await;
+        tracing::warn!(
+            "Credential {} rejected, failing over to {}",
+            self.state.credentials[used].0,
+            self.state.credentials[next].0
+        );
+    }
+
+    fn start_refresher(&self) {
+        let mut refresher = self.refresher.lock().unwrap();
+        if refresher.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+        let state = self.state.clone();
+        *refresher = Some(tokio::spawn(async move {
+            while let Some(delay) = state.refresh_delay().await {
+                tokio::time::sleep(delay).await;
+                if let Err(e) = state.refresh(true).await {
+                    // The next request retries on demand and restarts the refresher
+                    tracing::warn!("Background token refresh failed: {}", e);
+                    *state.token.write().await = None;
+                    return;
+                }
+                tracing::debug!("Token refreshed in the background");
+            }
+        }));
+    }
+}
+
+impl Drop for TokenManager {
+    fn drop(&mut self) {
+        if let Some(task) = self.refresher.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::auth::PatCredential;
+    use azure_core::auth::AccessToken;
+
+    #[test]
+    fn test_needs_refresh() {
+        let now = OffsetDateTime::now_utc();
+        let token = |expires_in: u64| {
+            TokenResponse::new(
+                AccessToken::new("token"),
+                now + Duration::from_secs(expires_in),
+            )
+        };
+        assert!(!needs_refresh(&token(3600), now));
+        assert!(needs_refresh(&token(120), now));
+    }
+
+    #[tokio::test]
+    async fn test_reject_fails_over() {
+        let manager = TokenManager::with_fallbacks(vec![
+            ("first".to_string(), Arc::new(PatCredential::new("a"))),
+            ("second".to_string(), Arc::new(PatCredential::new("b"))),
+        ]);
+        assert_eq!(manager.token().await.unwrap(), (0, ":a".to_string()));
+        manager.reject(0).await;
+        assert_eq!(manager.token().await.unwrap(), (1, ":b".to_string()));
+        assert_eq!(manager.next_credential(1), Some(0));
+        assert_eq!(manager.token_of(0).await.unwrap(), ":a");
+        assert_eq!(manager.token().await.unwrap().0, 1);
+
+        let single = TokenManager::new(Arc::new(PatCredential::new("a")));
+        assert_eq!(single.next_credential(0), None);
+        single.reject(0).await;
+        assert_eq!(single.token().await.unwrap(), (0, ":a".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_rejections_fail_over_once() {
+        let manager = TokenManager::with_fallbacks(vec![
+            ("first".to_string(), Arc::new(PatCredential::new("a"))),
+            ("second".to_string(), Arc::new(PatCredential::new("b"))),
+        ]);
+        let (first, _) = manager.token().await.unwrap();
+        let (second, _) = manager.token().await.unwrap();
+        tokio::join!(manager.reject(first), manager.reject(second));
+        assert_eq!(manager.token().await.unwrap(), (1, ":b".to_string()));
+
+        // A late rejection of the replaced credential changes nothing
+        manager.reject(0).await;
+        assert_eq!(manager.token().await.unwrap(), (1, ":b".to_string()));
+    }
+}
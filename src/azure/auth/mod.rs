@@ -0,0 +1,282 @@
+pub mod access_token;
+pub mod azure_cli;
+pub mod certificate;
+pub mod chain;
+pub mod device_code;
+pub mod entra;
+pub mod manager;
+pub mod pat;
+pub mod pool;
+pub mod token_store;
+
+pub use access_token::AccessTokenCredential;
+pub use azure_cli::AzureCliCredential;
+pub use certificate::CertificateSigner;
+pub use chain::ChainedCredential;
+pub use entra::EntraIdCredential;
+pub use manager::TokenManager;
+pub use pat::PatCredential;
+pub use pool::CredentialPool;
+pub use token_store::{CredentialStore, RefreshTokenStore};
+
+use crate::azure::client::AzureError;
+use crate::azure::cloud::Cloud;
+use azure_core::auth::TokenCredential;
+use azure_identity::DefaultAzureCredential;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// How the server acquires Azure DevOps access tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthMethod {
+    /// Azure SDK default chain (environment, managed identity, Azure CLI)
+    #[default]
+    Default,
+    /// Entra ID OAuth with an app registration (client credentials)
+    Entra,
+    /// Token of the Azure CLI login (`az account get-access-token`)
+    AzureCli,
+    /// Refresh token persisted by the `--login` device code sign-in
+    DeviceCode,
+    /// Personal access token, given explicitly or saved in the OS keyring
+    Pat,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Methods tried in order until one acquires a token
+    #[serde(rename = "auth")]
+    pub methods: Vec<AuthMethod>,
+    pub tenant_id: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// PEM file with the certificate and private key of the app registration,
+    /// used instead of the client secret
+    pub client_certificate_path: Option<PathBuf>,
+    /// Personal access tokens, read from the OS keyring when not set.
+    /// Several tokens are used in turn, failing over when one is rejected.
+    pub pats: Vec<String>,
+    /// Where the device code refresh token is kept
+    pub credential_store: CredentialStore,
+    /// Organization of the profile, selects its own device code refresh token
+    #[serde(skip)]
+    pub organization: Option<String>,
+    /// Cloud of the Entra ID authority, shared by all the profiles
+    #[serde(skip)]
+    pub cloud: Cloud,
+    /// HTTP client of the token requests, shared by all the profiles
+    #[serde(skip)]
+    pub http: reqwest::Client,
+}
+
+/// Load per-organization authentication profiles from a JSON file
+/// mapping organization names to their `AuthConfig`
+pub fn load_profiles(path: &Path) -> Result<Vec<AuthConfig>, AzureError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AzureError::ApiError(format!("Failed to read {}: {}", path.display(), e)))?;
+    parse_profiles(&content)
+        .map_err(|e| AzureError::ApiError(format!("Invalid profiles {}: {}", path.display(), e)))
+}
+
+fn parse_profiles(content: &str) -> Result<Vec<AuthConfig>, serde_json::Error> {
+    let profiles: HashMap<String, AuthConfig> = serde_json::from_str(content)?;
+    Ok(profiles
+        .into_iter()
+        .map(|(organization, config)| AuthConfig {
+            organization: Some(organization),
+            ..config
+        })
+        .collect())
+}
+
+/// Build the credential pool from the default configuration and the organization profiles
+pub fn build_credential_pool(
+    default: &AuthConfig,
+    profiles: &[AuthConfig],
+) -> Result<CredentialPool, AzureError> {
+    let mut pool = CredentialPool::new(build_token_manager(default)?);
+    for profile in profiles {
+        if let Some(organization) = profile.organization.as_deref() {
+            let profile = AuthConfig {
+                cloud: default.cloud,
+                http: default.http.clone(),
+                ..profile.clone()
+            };
+            pool.insert(organization, build_token_manager(&profile)?);
+        }
+    }
+    Ok(pool)
+}
+
+/// Build the token manager of a configuration: with several PATs, one credential chain
+/// per PAT, so that a rotated PAT fails over to the next one
+fn build_token_manager(config: &AuthConfig) -> Result<TokenManager, AzureError> {
+    let pats = if config.methods.contains(&AuthMethod::Pat) {
+        load_pats(config)?
+    } else {
+        Vec::new()
+    };
+    if pats.len() <= 1 {
+        return Ok(TokenManager::new(build_credential(config)?));
+    }
+    let credentials = pats
+        .into_iter()
+        .enumerate()
+        .map(|(i, pat)| {
+            let config = AuthConfig {
+                pats: vec![pat],
+                ..config.clone()
+            };
+            Ok((format!("PAT #{}", i + 1), build_credential(&config)?))
+        })
+        .collect::<Result<Vec<_>, AzureError>>()?;
+    Ok(TokenManager::with_fallbacks(credentials))
+}
+
+/// Configured PATs, or the ones saved in the OS keyring (comma separated)
+fn load_pats(config: &AuthConfig) -> Result<Vec<String>, AzureError> {
+    if !config.pats.is_empty() {
+        return Ok(config.pats.clone());
+    }
+    let saved = PatCredential::load(config.organization.as_deref()).map_err(|e| {
+        AzureError::ApiError(format!(
+            "Failed to read the personal access token from the OS keyring: {}",
+            e
+        ))
+    })?;
+    Ok(saved
+        .iter()
+        .flat_map(|pats| pats.split(','))
+        .map(str::trim)
+        .filter(|pat| !pat.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Build the credential chain for the configured authentication methods
+pub fn build_credential(config: &AuthConfig) -> Result<Arc<dyn TokenCredential>, AzureError> {
+    let mut credentials = config
+        .methods
+        .iter()
+        .map(|method| {
+            Ok((
+                format!("{:?}", method),
+                build_method_credential(config, *method)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, AzureError>>()?;
+    match credentials.len() {
+        0 => Ok(Arc::new(DefaultAzureCredential::default())),
+        1 => Ok(credentials.remove(0).1),
+        _ => Ok(Arc::new(ChainedCredential::new(credentials))),
+    }
+}
+
+fn build_method_credential(
+    config: &AuthConfig,
+    method: AuthMethod,
+) -> Result<Arc<dyn TokenCredential>, AzureError> {
+    match method {
+        AuthMethod::Default => Ok(Arc::new(DefaultAzureCredential::default())),
+        AuthMethod::Entra => {
+            let (Some(tenant_id), Some(client_id)) =
+                (config.tenant_id.as_deref(), config.client_id.as_deref())
+            else {
+                return Err(AzureError::ApiError(
+                    "Entra ID authentication requires tenant ID and client ID".to_string(),
+                ));
+            };
+            match (&config.client_certificate_path, &config.client_secret) {
+                (Some(path), _) => {
+                    let certificate =
+                        CertificateSigner::from_pem_file(path).map_err(AzureError::ApiError)?;
+                    Ok(Arc::new(
+                        EntraIdCredential::client_certificate(tenant_id, client_id, certificate)
+                            .with_cloud(config.cloud)
+                            .with_http_client(config.http.clone()),
+                    ))
+                }
+                (None, Some(client_secret)) => Ok(Arc::new(
+                    EntraIdCredential::client_secret(tenant_id, client_id, client_secret)
+                        .with_cloud(config.cloud)
+                        .with_http_client(config.http.clone()),
+                )),
+                (None, None) => Err(AzureError::ApiError(
+                    "Entra ID authentication requires a client secret or certificate".to_string(),
+                )),
+            }
+        }
+        AuthMethod::AzureCli => Ok(Arc::new(AzureCliCredential::new())),
+        AuthMethod::DeviceCode => {
+            let store =
+                RefreshTokenStore::open(config.credential_store, config.organization.as_deref())
+                    .ok_or_else(|| {
+                        AzureError::ApiError(
+                            "Unable to locate the configuration directory".to_string(),
+                        )
+                    })?;
+            let refresh_token = store
+                .load()
+                .map_err(|e| AzureError::ApiError(format!("Failed to read {}: {}", store, e)))?
+                .ok_or_else(|| {
+                    AzureError::ApiError("Not signed in, run with --login first".to_string())
+                })?;
+            Ok(Arc::new(
+                EntraIdCredential::refresh_token(
+                    config
+                        .tenant_id
+                        .as_deref()
+                        .unwrap_or(device_code::DEFAULT_TENANT),
+                    config
+                        .client_id
+                        .as_deref()
+                        .unwrap_or(device_code::DEFAULT_CLIENT_ID),
+                    &refresh_token,
+                    Some(store),
+                )
+                .with_cloud(config.cloud)
+                .with_http_client(config.http.clone()),
+            ))
+        }
+        AuthMethod::Pat => {
+            let token = load_pats(config)?.into_iter().next().ok_or_else(|| {
+                AzureError::ApiError(
+                    "No personal access token, set AZDO_PAT or run with --store-credentials"
+                        .to_string(),
+                )
+            })?;
+            Ok(Arc::new(PatCredential::new(&token)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profiles = parse_profiles(
+            r#"{"contoso": {"auth": ["entra", "azure-cli"], "tenant_id": "t"}, "fabrikam": {}}"#,
+        )
+        .unwrap();
+        let contoso = profiles
+            .iter()
+            .find(|p| p.organization.as_deref() == Some("contoso"))
+            .unwrap();
+        assert_eq!(
+            contoso.methods,
+            vec![AuthMethod::Entra, AuthMethod::AzureCli]
+        );
+        assert_eq!(contoso.tenant_id.as_deref(), Some("t"));
+        let fabrikam = profiles
+            .iter()
+            .find(|p| p.organization.as_deref() == Some("fabrikam"))
+            .unwrap();
+        assert!(fabrikam.methods.is_empty());
+    }
+}
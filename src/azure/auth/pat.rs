@@ -0,0 +1,45 @@
+use crate::azure::auth::token_store::{account_name, load_keyring, save_keyring};
+use async_trait::async_trait;
+use azure_core::auth::{AccessToken, TokenCredential, TokenResponse};
+use std::io;
+use time::OffsetDateTime;
+
+/// Keyring account name of personal access tokens
+const PAT_ACCOUNT: &str = "pat";
+
+/// Personal access token, sent with basic authentication.
+///
+/// The token is returned as `:<pat>` (basic credentials with an empty user name),
+/// which the client tells apart from OAuth bearer tokens by the colon.
+pub struct PatCredential {
+    token: String,
+}
+
+impl PatCredential {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+
+    /// Load the personal access token saved in the OS keyring for the organization
+    pub fn load(organization: Option<&str>) -> io::Result<Option<String>> {
+        load_keyring(&account_name(PAT_ACCOUNT, organization))
+    }
+
+    /// Save a personal access token in the OS keyring for the organization
+    pub fn save(organization: Option<&str>, token: &str) -> io::Result<()> {
+        save_keyring(&account_name(PAT_ACCOUNT, organization), token)
+    }
+}
+
+#[async_trait]
+impl TokenCredential for PatCredential {
+    async fn get_token(&self, _resource: &str) -> azure_core::Result<TokenResponse> {
+        // PATs expire server side only, report a far expiry so they are never refreshed
+        Ok(TokenResponse::new(
+            AccessToken::new(format!(":{}", self.token)),
+            OffsetDateTime::now_utc() + time::Duration::days(365),
+        ))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::azure::auth::manager::TokenManager;
+use std::collections::HashMap;
+
+/// Credentials keyed by organization, with a default for organizations without a profile.
+/// Each credential has its own token manager keeping its token fresh.
+pub struct CredentialPool {
+    default: TokenManager,
+    by_organization: HashMap<String, TokenManager>,
+}
+
+impl CredentialPool {
+    pub fn new(default: TokenManager) -> Self {
+        Self {
+            default,
+            by_organization: HashMap::new(),
+        }
+    }
+
+    /// Use a dedicated credential for the organization (case insensitive)
+    pub fn insert(&mut self, organization: &str, credential: TokenManager) {
+        self.by_organization
+            .insert(organization.to_lowercase(), credential);
+    }
+
+    pub fn organizations(&self) -> impl Iterator<Item = &str> {
+        self.by_organization.keys().map(String::as_str)
+    }
+
+    /// Credential for the organization, falling back to the default one
+    pub fn credential_for(&self, organization: Option<&str>) -> &TokenManager {
+        organization
+            .and_then(|org| self.by_organization.get(&org.to_lowercase()))
+            .unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::auth::AzureCliCredential;
+    use azure_core::auth::TokenCredential;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_credential_for() {
+        let default: Arc<dyn TokenCredential> = Arc::new(AzureCliCredential::new());
+        let contoso: Arc<dyn TokenCredential> = Arc::new(AzureCliCredential::new());
+        let mut pool = CredentialPool::new(TokenManager::new(default.clone()));
+        pool.insert("Contoso", TokenManager::new(contoso.clone()));
+        assert!(Arc::ptr_eq(
+            pool.credential_for(Some("contoso")).credential(),
+            &contoso
+        ));
+        assert!(Arc::ptr_eq(
+            pool.credential_for(Some("fabrikam")).credential(),
+            &default
+        ));
+        assert!(Arc::ptr_eq(
+            pool.credential_for(None).credential(),
+            &default
+        ));
+    }
+}
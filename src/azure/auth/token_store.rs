@@ -0,0 +1,162 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the persisted refresh token
+const REFRESH_TOKEN_FILE: &str = "refresh_token";
+
+/// Where credentials saved by the server are kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialStore {
+    /// File in the user configuration directory, readable only by the current user
+    #[default]
+    File,
+    /// OS keyring (Keychain, Credential Manager, Secret Service)
+    Keyring,
+}
+
+/// Keyring entry of the server for the given account name
+pub(crate) fn keyring_entry(account: &str) -> io::Result<keyring::Entry> {
+    keyring::Entry::new(env!("CARGO_PKG_NAME"), account).map_err(io::Error::other)
+}
+
+/// Account name of a secret, one per organization profile
+pub(crate) fn account_name(name: &str, organization: Option<&str>) -> String {
+    match organization {
+        Some(org) => format!("{}.{}", name, org.to_lowercase()),
+        None => name.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Backend {
+    File(PathBuf),
+    Keyring(String),
+}
+
+/// Persists the device code refresh token in a file only readable by the current user
+/// or in the OS keyring
+#[derive(Debug, Clone)]
+pub struct RefreshTokenStore {
+    backend: Backend,
+}
+
+impl fmt::Display for RefreshTokenStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.backend {
+            Backend::File(path) => write!(f, "{}", path.display()),
+            Backend::Keyring(account) => write!(f, "the OS keyring ({})", account),
+        }
+    }
+}
+
+impl RefreshTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            backend: Backend::File(path.into()),
+        }
+    }
+
+    /// Store in the OS keyring, one entry per organization profile
+    pub fn keyring(organization: Option<&str>) -> Self {
+        Self {
+            backend: Backend::Keyring(account_name(REFRESH_TOKEN_FILE, organization)),
+        }
+    }
+
+    /// Store in the given backend, one entry per organization profile
+    pub fn open(store: CredentialStore, organization: Option<&str>) -> Option<Self> {
+        match store {
+            CredentialStore::File => Self::default_location_for(organization),
+            CredentialStore::Keyring => Some(Self::keyring(organization)),
+        }
+    }
+
+    /// Store in the user configuration directory
+    /// (`$XDG_CONFIG_HOME`, `~/.config` or `%APPDATA%`)
+    pub fn default_location() -> Option<Self> {
+        Self::default_location_for(None)
+    }
+
+    /// Store in the user configuration directory, one file per organization profile
+    pub fn default_location_for(organization: Option<&str>) -> Option<Self> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|h| Path::new(&h).join(".config")))?;
+        Some(Self::new(
+            base.join(env!("CARGO_PKG_NAME"))
+                .join(account_name(REFRESH_TOKEN_FILE, organization)),
+        ))
+    }
+
+    pub fn load(&self) -> io::Result<Option<String>> {
+        match &self.backend {
+            Backend::File(path) => match std::fs::read_to_string(path) {
+                Ok(token) if !token.trim().is_empty() => Ok(Some(token.trim().to_string())),
+                Ok(_) => Ok(None),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e),
+            },
+            Backend::Keyring(account) => load_keyring(account),
+        }
+    }
+
+    pub fn save(&self, token: &str) -> io::Result<()> {
+        let path = match &self.backend {
+            Backend::File(path) => path,
+            Backend::Keyring(account) => return save_keyring(account, token),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path)?;
+        io::Write::write_all(&mut file, token.as_bytes())
+    }
+}
+
+/// Read a secret from the OS keyring, None when there is no entry
+pub(crate) fn load_keyring(account: &str) -> io::Result<Option<String>> {
+    match keyring_entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(io::Error::other(e)),
+    }
+}
+
+pub(crate) fn save_keyring(account: &str, secret: &str) -> io::Result<()> {
+    keyring_entry(account)?
+        .set_password(secret)
+        .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("azdo-token-store-{}", std::process::id()))
+            .join(REFRESH_TOKEN_FILE);
+        let store = RefreshTokenStore::new(&path);
+        assert_eq!(store.load().unwrap(), None);
+        store.save("secret").unwrap();
+        assert_eq!(store.load().unwrap(), Some("secret".to_string()));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
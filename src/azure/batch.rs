@@ -0,0 +1,485 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::api_version::DEFAULT_API_VERSION;
+use crate::azure::client::AzureError;
+use crate::azure::context;
+use crate::azure::models::WorkItem;
+use crate::azure::work_items::{self, IRREVERSIBLE_FIELDS, JsonPatchOperation, rev_of};
+use crate::azure::write_policy;
+use crate::journal::{self, Change};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Requests a `$batch` call can carry
+pub const MAX_BATCH_REQUESTS: usize = 200;
+
+/// Operation of a batch, the negative work item IDs referring to the work items created
+/// by the batch, -1 being the first one
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchOperation {
+    Create {
+        work_item_type: String,
+        fields: Vec<(String, Value)>,
+    },
+    Update {
+        id: u32,
+        fields: Vec<(String, Value)>,
+    },
+    Link {
+        source_id: i64,
+        target_id: i64,
+        link_type: String,
+    },
+}
+
+impl BatchOperation {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Create { .. } => "create",
+            Self::Update { .. } => "update",
+            Self::Link { .. } => "link",
+        }
+    }
+}
+
+/// Request of the batch, a work item creation or change
+#[derive(Debug, PartialEq)]
+struct BatchRequest {
+    /// Work item changed, negative when created by the request
+    id: i64,
+    work_item_type: Option<String>,
+    fields: Vec<(String, Value)>,
+    /// Links added, by type and target
+    links: Vec<(String, i64)>,
+}
+
+/// Operations of a batch grouped in requests, the links from the created work items
+/// being sent with their creation
+#[derive(Debug, PartialEq)]
+pub struct BatchPlan {
+    requests: Vec<BatchRequest>,
+    /// Request each operation is sent with
+    request_of: Vec<usize>,
+}
+
+/// Result of an operation of a batch
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    pub index: usize,
+    pub operation: &'static str,
+    /// HTTP status of the request the operation was sent with
+    pub status: u16,
+    /// Work item created or changed
+    pub work_item_id: Option<u32>,
+    pub rev: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchResponse {
+    value: Vec<BatchResponseItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchResponseItem {
+    code: u16,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Group the operations of a batch in requests, checking the created work items they
+/// refer to
+pub fn plan(operations: &[BatchOperation]) -> Result<BatchPlan, String> {
+    let mut requests: Vec<BatchRequest> = Vec::new();
+    let mut request_of = Vec::new();
+    // Request creating each work item, by temporary ID
+    let mut creations: HashMap<i64, usize> = HashMap::new();
+    for (index, operation) in operations.iter().enumerate() {
+        let created_before = |id: i64, request: usize| {
+            if id > 0 {
+                return Ok(());
+            }
+            match creations.get(&id) {
+                Some(creation) if *creation < request => Ok(()),
+                _ => Err(format!(
+                    "Operation {}: work item {} is not created by an earlier operation",
+                    index, id
+                )),
+            }
+        };
+        match operation {
+            BatchOperation::Create {
+                work_item_type,
+                fields,
+            } => {
+                let id = -(creations.len() as i64) - 1;
+                creations.insert(id, requests.len());
+                request_of.push(requests.len());
+                requests.push(BatchRequest {
+                    id,
+                    work_item_type: Some(work_item_type.clone()),
+                    fields: fields.clone(),
+                    links: Vec::new(),
+                });
+            }
+            BatchOperation::Update { id, fields } => {
+                request_of.push(requests.len());
+                requests.push(BatchRequest {
+                    id: i64::from(*id),
+                    work_item_type: None,
+                    fields: fields.clone(),
+                    links: Vec::new(),
+                });
+            }
+            BatchOperation::Link {
+                source_id,
+                target_id,
+                link_type,
+            } => {
+                if *source_id == 0 || *target_id == 0 || source_id == target_id {
+                    return Err(format!(
+                        "Operation {}: invalid link from {} to {}",
+                        index, source_id, target_id
+                    ));
+                }
+                if *source_id < 0 {
+                    created_before(*source_id, usize::MAX)?;
+                    let creation = creations[source_id];
+                    created_before(*target_id, creation)?;
+                    requests[creation]
+                        .links
+                        .push((link_type.clone(), *target_id));
+                    request_of.push(creation);
+                } else {
+                    created_before(*target_id, requests.len())?;
+                    request_of.push(requests.len());
+                    requests.push(BatchRequest {
+                        id: *source_id,
+                        work_item_type: None,
+                        fields: Vec::new(),
+                        links: vec![(link_type.clone(), *target_id)],
+                    });
+                }
+            }
+        }
+    }
+    if requests.len() > MAX_BATCH_REQUESTS {
+        return Err(format!(
+            "{} requests in the batch, at most {} can be sent at once",
+            requests.len(),
+            MAX_BATCH_REQUESTS
+        ));
+    }
+    Ok(BatchPlan {
+        requests,
+        request_of,
+    })
+}
+
+fn work_item_url(client: &dyn AzureBoardsApi, organization: &str, id: i64) -> String {
+    format!(
+        "{}/{}/_apis/wit/workItems/{}",
+        client.cloud().base_url(None),
+        organization,
+        id
+    )
+}
+
+fn patch_operations(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    request: &BatchRequest,
+) -> Vec<JsonPatchOperation> {
+    let mut operations = Vec::new();
+    if request.id < 0 {
+        operations.push(JsonPatchOperation {
+            op: "add".to_string(),
+            path: "/id".to_string(),
+            value: Some(request.id.into()),
+            from: None,
+        });
+    }
+    operations.extend(
+        request
+            .fields
+            .iter()
+            .map(|(field, value)| JsonPatchOperation {
+                op: "add".to_string(),
+                path: format!("/fields/{}", field),
+                value: Some(value.clone()),
+                from: None,
+            }),
+    );
+    operations.extend(
+        request
+            .links
+            .iter()
+            .map(|(link_type, target)| JsonPatchOperation {
+                op: "add".to_string(),
+                path: "/relations/-".to_string(),
+                value: Some(json!({
+                    "rel": link_type,
+                    "url": work_item_url(client, organization, *target),
+                })),
+                from: None,
+            }),
+    );
+    operations
+}
+
+/// Error message of a failed request of the batch
+fn error_message(body: Option<&str>) -> String {
+    let body = body.unwrap_or_default();
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|body| {
+            body["value"]["Message"]
+                .as_str()
+                .or(body["message"].as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// Run the operations of a batch in one `wit/$batch` round trip. The requests are not a
+/// transaction: each one succeeds or fails on its own, the ones referring to a work item
+/// whose creation failed failing too.
+pub async fn run_batch(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    operations: &[BatchOperation],
+    plan: &BatchPlan,
+) -> Result<Vec<BatchResult>, AzureError> {
+    // The values of the existing work items the updates overwrite, for the journal to
+    // restore them, and the ones the write policy checks
+    let policy = context::current().write_policy.clone();
+    let existing: BTreeSet<u32> = plan
+        .requests
+        .iter()
+        .filter_map(|request| u32::try_from(request.id).ok())
+        .collect();
+    let mut names = policy.checked_fields();
+    if journal::is_recording() {
+        names.extend(
+            plan.requests
+                .iter()
+                .filter(|request| request.id > 0)
+                .flat_map(|request| request.fields.iter().map(|(field, _)| field.clone())),
+        );
+        names.sort();
+        names.dedup();
+    }
+    let current: HashMap<u32, HashMap<String, Value>> = if names.is_empty() {
+        HashMap::new()
+    } else {
+        let ids: Vec<u32> = existing.iter().copied().collect();
+        work_items::get_work_items(client, organization, project, &ids, None, &names)
+            .await?
+            .into_iter()
+            .map(|work_item| (work_item.id, work_item.fields))
+            .collect()
+    };
+    if !policy.is_empty() {
+        let created = plan.requests.iter().filter(|r| r.id < 0).count();
+        policy.check_bulk_writes(existing.iter().copied(), created)?;
+        for request in &plan.requests {
+            let mut after = u32::try_from(request.id)
+                .ok()
+                .and_then(|id| current.get(&id))
+                .cloned()
+                .unwrap_or_default();
+            if request.id > 0 && !after.is_empty() {
+                policy.check_area_path(project, &after)?;
+            }
+            if request.id < 0 || !request.fields.is_empty() {
+                after.extend(request.fields.iter().cloned());
+                policy.check_fields(project, &after)?;
+            }
+        }
+    }
+
+    let api_version = context::current()
+        .api_versions
+        .resolve("wit/workitems", DEFAULT_API_VERSION);
+    let patches: Vec<Vec<JsonPatchOperation>> = plan
+        .requests
+        .iter()
+        .map(|request| patch_operations(client, organization, request))
+        .collect();
+    let body: Vec<Value> = plan
+        .requests
+        .iter()
+        .zip(&patches)
+        .map(|(request, operations)| {
+            let target = match &request.work_item_type {
+                Some(work_item_type) => format!("${}", urlencoding::encode(work_item_type)),
+                None => request.id.to_string(),
+            };
+            json!({
+                "method": "PATCH",
+                "uri": format!(
+                    "/{}/_apis/wit/workitems/{}?api-version={}",
+                    urlencoding::encode(project),
+                    target,
+                    api_version
+                ),
+                "headers": { "Content-Type": "application/json-patch+json" },
+                "body": operations,
+            })
+        })
+        .collect();
+    // API: https://dev.azure.com/{organization}/_apis/wit/$batch?api-version=7.1
+    let response: BatchResponse = client
+        .org_request(
+            organization,
+            Method::POST,
+            "wit/$batch?api-version=7.1",
+            Some(&body),
+        )
+        .await?;
+
+    // Work item and error of each request, the created IDs replacing the temporary ones
+    let mut outcomes: Vec<(u16, Option<WorkItem>, Option<String>)> = Vec::new();
+    let mut created: HashMap<i64, u32> = HashMap::new();
+    for (index, request) in plan.requests.iter().enumerate() {
+        let Some(item) = response.value.get(index) else {
+            outcomes.push((0, None, Some("No response to the request".to_string())));
+            continue;
+        };
+        if !(200..300).contains(&item.code) {
+            outcomes.push((item.code, None, Some(error_message(item.body.as_deref()))));
+            continue;
+        }
+        match serde_json::from_str::<WorkItem>(item.body.as_deref().unwrap_or_default()) {
+            Ok(work_item) => {
+                if request.id < 0 {
+                    created.insert(request.id, work_item.id);
+                }
+                outcomes.push((item.code, Some(work_item), None));
+            }
+            Err(e) => outcomes.push((item.code, None, Some(e.to_string()))),
+        }
+    }
+
+    for ((request, operations), (_, work_item, _)) in
+        plan.requests.iter().zip(&patches).zip(&outcomes)
+    {
+        let Some(work_item) = work_item else {
+            continue;
+        };
+        if let Some(store) = context::current().work_item_store.clone() {
+            store
+                .record(organization, project, std::slice::from_ref(work_item))
+                .await;
+        }
+        let request_body = serde_json::to_value(operations).unwrap();
+        if request.id < 0 {
+            write_policy::count_created(work_item.id);
+            journal::record(
+                organization,
+                project,
+                work_item.id,
+                rev_of(work_item),
+                Change::Created,
+                request_body,
+            );
+            continue;
+        }
+        if !request.fields.is_empty() {
+            let fields = current.get(&work_item.id);
+            let previous: BTreeMap<String, Value> = request
+                .fields
+                .iter()
+                .filter(|(field, _)| !IRREVERSIBLE_FIELDS.contains(&field.as_str()))
+                .map(|(field, _)| {
+                    let value = fields.and_then(|fields| fields.get(field)).cloned();
+                    (field.clone(), value.unwrap_or(Value::Null))
+                })
+                .collect();
+            journal::record(
+                organization,
+                project,
+                work_item.id,
+                rev_of(work_item),
+                Change::Updated { previous },
+                request_body.clone(),
+            );
+        }
+        for (link_type, target) in &request.links {
+            let target = created.get(target).map_or(*target, |id| i64::from(*id));
+            journal::record(
+                organization,
+                project,
+                work_item.id,
+                rev_of(work_item),
+                Change::Linked {
+                    rel: link_type.clone(),
+                    url: work_item_url(client, organization, target),
+                },
+                request_body.clone(),
+            );
+        }
+    }
+
+    Ok(operations
+        .iter()
+        .zip(&plan.request_of)
+        .enumerate()
+        .map(|(index, (operation, request))| {
+            let (status, work_item, error) = &outcomes[*request];
+            BatchResult {
+                index,
+                operation: operation.kind(),
+                status: *status,
+                work_item_id: work_item.as_ref().map(|work_item| work_item.id),
+                rev: work_item.as_ref().and_then(rev_of),
+                error: error.clone(),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let create = |title: &str| BatchOperation::Create {
+            work_item_type: "Task".to_string(),
+            fields: vec![("System.Title".to_string(), title.into())],
+        };
+        let link = |source_id, target_id| BatchOperation::Link {
+            source_id,
+            target_id,
+            link_type: "System.LinkTypes.Related".to_string(),
+        };
+        let operations = [
+            create("A"),
+            create("B"),
+            BatchOperation::Update {
+                id: 7,
+                fields: vec![("System.State".to_string(), "Active".into())],
+            },
+            link(-2, -1),
+            link(7, -2),
+        ];
+        let plan = plan(&operations).unwrap();
+        assert_eq!(plan.request_of, [0, 1, 2, 1, 3]);
+        assert_eq!(
+            plan.requests[1].links,
+            [("System.LinkTypes.Related".to_string(), -1)]
+        );
+        assert_eq!(plan.requests[3].id, 7);
+
+        // A work item can only link to the ones created before it
+        let error = super::plan(&[create("A"), create("B"), link(-1, -2)]).unwrap_err();
+        assert_eq!(
+            error,
+            "Operation 2: work item -2 is not created by an earlier operation"
+        );
+        assert!(super::plan(&[link(-1, 7)]).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
@@ -147,7 +148,7 @@ pub struct BoardListResponse {
 
 /// List all teams in the project
 pub async fn list_teams(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
 ) -> Result<Vec<Team>, AzureError> {
@@ -162,7 +163,7 @@ pub async fn list_teams(
 
 /// Get a specific team by ID or name
 pub async fn get_team(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -174,9 +175,65 @@ pub async fn get_team(
         .await
 }
 
+/// Create a new team in the project
+pub async fn create_team(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    name: &str,
+    description: Option<&str>,
+) -> Result<Team, AzureError> {
+    // Team API: POST https://dev.azure.com/{organization}/_apis/projects/{project}/teams
+    let path = format!("projects/{}/teams?api-version=7.1", project);
+    let body = serde_json::json!({
+        "name": name,
+        "description": description.unwrap_or_default(),
+    });
+    client
+        .org_request(organization, Method::POST, &path, Some(&body))
+        .await
+}
+
+/// Rename a team and/or change its description
+pub async fn update_team(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    name: Option<&str>,
+    description: Option<&str>,
+) -> Result<Team, AzureError> {
+    // Team API: PATCH https://dev.azure.com/{organization}/_apis/projects/{project}/teams/{teamId}
+    let path = format!("projects/{}/teams/{}?api-version=7.1", project, team_id);
+    let mut body = serde_json::Map::new();
+    if let Some(name) = name {
+        body.insert("name".to_string(), serde_json::json!(name));
+    }
+    if let Some(description) = description {
+        body.insert("description".to_string(), serde_json::json!(description));
+    }
+    client
+        .org_request(organization, Method::PATCH, &path, Some(&body))
+        .await
+}
+
+/// Delete a team
+pub async fn delete_team(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+) -> Result<(), AzureError> {
+    // Team API: DELETE https://dev.azure.com/{organization}/_apis/projects/{project}/teams/{teamId}
+    let path = format!("projects/{}/teams/{}?api-version=7.1", project, team_id);
+    client
+        .org_request(organization, Method::DELETE, &path, None::<&String>)
+        .await
+}
+
 /// List all work item types (Stories, Epics, Features, Bugs, etc.)
 pub async fn list_work_item_types(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
 ) -> Result<Vec<WorkItemType>, AzureError> {
@@ -188,7 +245,7 @@ pub async fn list_work_item_types(
 /// List boards for a specific team
 /// Note: In Azure DevOps, boards are team-specific Kanban boards
 pub async fn list_boards(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -210,7 +267,7 @@ pub async fn list_boards(
 
 /// Get a specific board (requires team context)
 pub async fn get_board(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -237,7 +294,7 @@ pub struct BoardColumnsResponse {
 
 /// List columns for a specific board
 pub async fn list_board_columns(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -265,7 +322,7 @@ pub struct BoardRowsResponse {
 
 /// List rows (swimlanes) for a specific board
 pub async fn list_board_rows(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
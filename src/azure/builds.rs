@@ -0,0 +1,33 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Build {
+    pub id: u32,
+    #[serde(rename = "buildNumber")]
+    pub build_number: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default)]
+    pub definition: Option<Value>,
+    #[serde(default, rename = "sourceBranch")]
+    pub source_branch: Option<String>,
+    #[serde(default, rename = "finishTime")]
+    pub finish_time: Option<String>,
+}
+
+/// Get a build
+pub async fn get_build(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    build_id: u32,
+) -> Result<Build, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/build/builds/{buildId}?api-version=7.1
+    let path = format!("build/builds/{}?api-version=7.1", build_id);
+    client.get(organization, project, &path).await
+}
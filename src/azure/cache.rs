@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rarely changing metadata whose GET responses are cached by the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceClass {
+    Projects,
+    Teams,
+    ClassificationNodes,
+    WorkItemTypes,
+}
+
+impl ResourceClass {
+    /// Class of the resource addressed by a REST API URL, None for uncached resources
+    pub fn of_url(url: &str) -> Option<Self> {
+        let (_, path) = url.split_once("/_apis/")?;
+        let path = path.split('?').next().unwrap_or_default();
+        if path == "teams" || (path.starts_with("projects/") && path.contains("/teams")) {
+            Some(ResourceClass::Teams)
+        } else if path == "projects" || path.starts_with("projects/") {
+            Some(ResourceClass::Projects)
+        } else if path.starts_with("wit/classificationnodes") {
+            Some(ResourceClass::ClassificationNodes)
+        } else if path.starts_with("wit/workitemtype") {
+            Some(ResourceClass::WorkItemTypes)
+        } else {
+            None
+        }
+    }
+}
+
+/// Time to live of the cached responses per resource class, zero disables caching
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub projects: Duration,
+    pub teams: Duration,
+    pub classification_nodes: Duration,
+    pub work_item_types: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            projects: Duration::from_secs(600),
+            teams: Duration::from_secs(600),
+            classification_nodes: Duration::from_secs(300),
+            work_item_types: Duration::from_secs(1800),
+        }
+    }
+}
+
+impl CacheConfig {
+    fn ttl(&self, class: ResourceClass) -> Duration {
+        match class {
+            ResourceClass::Projects => self.projects,
+            ResourceClass::Teams => self.teams,
+            ResourceClass::ClassificationNodes => self.classification_nodes,
+            ResourceClass::WorkItemTypes => self.work_item_types,
+        }
+    }
+}
+
+/// Maximum number of stored responses, the oldest are evicted first
+const MAX_ENTRIES: usize = 1000;
+
+struct Entry {
+    class: Option<ResourceClass>,
+    stored_at: Instant,
+    /// Validator of the body, kept after expiry to revalidate with a conditional request
+    etag: Option<String>,
+    body: String,
+}
+
+/// In-memory cache of response bodies keyed by URL: metadata responses are served
+/// until their time to live expires, responses with an ETag are revalidated with
+/// conditional requests
+#[derive(Default)]
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl ResponseCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    fn ttl(&self, class: Option<ResourceClass>) -> Duration {
+        class.map_or(Duration::ZERO, |class| self.config.ttl(class))
+    }
+
+    /// Fresh cached body of the URL, unless bypassed by the current task
+    pub fn get(&self, url: &str) -> Option<String> {
+        if bypassed() {
+            return None;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get(url)?;
+        if entry.stored_at.elapsed() < self.ttl(entry.class) {
+            return Some(entry.body.clone());
+        }
+        if entry.etag.is_none() {
+            entries.remove(url);
+        }
+        None
+    }
+
+    /// ETag of the stored body of the URL, for an `If-None-Match` request
+    pub fn etag(&self, url: &str) -> Option<String> {
+        self.entries.lock().unwrap().get(url)?.etag.clone()
+    }
+
+    /// Stored body of the URL after the server answered 304 Not Modified
+    pub fn revalidated(&self, url: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(url)?;
+        entry.stored_at = Instant::now();
+        Some(entry.body.clone())
+    }
+
+    /// Store a response body, if it is cacheable metadata or it has an ETag
+    pub fn insert(&self, url: &str, class: Option<ResourceClass>, etag: Option<&str>, body: &str) {
+        if self.ttl(class).is_zero() && etag.is_none() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES
+            && !entries.contains_key(url)
+            && let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(url, _)| url.clone())
+        {
+            entries.remove(&oldest);
+        }
+        entries.insert(
+            url.to_string(),
+            Entry {
+                class,
+                stored_at: Instant::now(),
+                etag: etag.map(String::from),
+                body: body.to_string(),
+            },
+        );
+    }
+
+    /// Drop the cached responses of a class, after a change to one of its resources
+    pub fn invalidate(&self, class: ResourceClass) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, entry| entry.class != Some(class));
+    }
+}
+
+tokio::task_local! {
+    static BYPASS: bool;
+}
+
+/// Run `future` fetching fresh responses instead of the cached ones when `bypass` is set;
+/// the fresh responses still refresh the cache
+pub async fn bypass<F: Future>(bypass: bool, future: F) -> F::Output {
+    BYPASS.scope(bypass, future).await
+}
+
+fn bypassed() -> bool {
+    BYPASS.try_with(|bypass| *bypass).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_class_of_url() {
+        let class =
+            |path: &str| ResourceClass::of_url(&format!("https://dev.azure.com/org/{}", path));
+        assert_eq!(
+            class("_apis/projects?api-version=7.1"),
+            Some(ResourceClass::Projects)
+        );
+        assert_eq!(
+            class("_apis/projects/p/teams?api-version=7.1"),
+            Some(ResourceClass::Teams)
+        );
+        assert_eq!(
+            class("p/_apis/wit/classificationnodes/areas?$depth=2"),
+            Some(ResourceClass::ClassificationNodes)
+        );
+        assert_eq!(
+            class("p/_apis/wit/workitemtypes?api-version=7.1"),
+            Some(ResourceClass::WorkItemTypes)
+        );
+        assert_eq!(class("p/_apis/wit/workitems/1"), None);
+    }
+
+    #[test]
+    fn test_etag_revalidation() {
+        let cache = ResponseCache::new(CacheConfig::default());
+        let url = "https://dev.azure.com/org/p/_apis/wit/workitems/1";
+        cache.insert(url, None, None, "{}");
+        assert_eq!(cache.etag(url), None);
+
+        cache.insert(url, None, Some("\"7\""), "{\"rev\":7}");
+        assert_eq!(cache.get(url), None);
+        assert_eq!(cache.etag(url).as_deref(), Some("\"7\""));
+        assert_eq!(cache.revalidated(url).as_deref(), Some("{\"rev\":7}"));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// When to stop sending requests to an organization that keeps failing
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed requests opening the circuit, 0 disables the breaker
+    pub failure_threshold: u32,
+    /// How long requests fail fast before a probe request is let through
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A probe request is in flight since the given instant
+    HalfOpen {
+        since: Instant,
+    },
+}
+
+/// Per-organization circuit breaker: after repeated failures (connection errors and
+/// 5xx responses, retries included) the requests to the organization fail fast until
+/// a probe request succeeds
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    states: Mutex<HashMap<String, State>>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Whether a request to the organization can be sent, or how long until it can
+    pub fn check(&self, organization: Option<&str>) -> Result<(), Duration> {
+        self.check_at(organization, Instant::now())
+    }
+
+    fn check_at(&self, organization: Option<&str>, now: Instant) -> Result<(), Duration> {
+        if self.config.failure_threshold == 0 {
+            return Ok(());
+        }
+        let mut states = self.states.lock().unwrap();
+        let Some(state) = states.get_mut(organization.unwrap_or_default()) else {
+            return Ok(());
+        };
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until } if now < until => Err(until - now),
+            // A probe that never reported back (e.g. cancelled) is replaced after a while
+            State::HalfOpen { since } if now < since + self.config.open_duration => {
+                Err(since + self.config.open_duration - now)
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                tracing::info!(
+                    "Probing Azure DevOps availability for organization {}",
+                    organization.unwrap_or("<none>")
+                );
+                *state = State::HalfOpen { since: now };
+                Ok(())
+            }
+        }
+    }
+
+    /// Record the outcome of a request to the organization
+    pub fn record(&self, organization: Option<&str>, success: bool) {
+        self.record_at(organization, success, Instant::now());
+    }
+
+    fn record_at(&self, organization: Option<&str>, success: bool, now: Instant) {
+        if self.config.failure_threshold == 0 {
+            return;
+        }
+        let mut states = self.states.lock().unwrap();
+        let key = organization.unwrap_or_default();
+        if success {
+            if let Some(State::Open { .. } | State::HalfOpen { .. }) = states.remove(key) {
+                tracing::info!(
+                    "Azure DevOps available again for organization {}",
+                    organization.unwrap_or("<none>")
+                );
+            }
+            return;
+        }
+
+        let state = states
+            .entry(key.to_string())
+            .or_insert(State::Closed { failures: 0 });
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            State::Open { .. } | State::HalfOpen { .. } => self.config.failure_threshold,
+        };
+        *state = if failures >= self.config.failure_threshold {
+            tracing::warn!(
+                "Azure DevOps appears unavailable for organization {}, failing fast for {}s",
+                organization.unwrap_or("<none>"),
+                self.config.open_duration.as_secs()
+            );
+            State::Open {
+                until: now + self.config.open_duration,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_and_probe() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(10),
+        });
+        let now = Instant::now();
+        breaker.record_at(Some("org"), false, now);
+        assert_eq!(breaker.check_at(Some("org"), now), Ok(()));
+        breaker.record_at(Some("org"), false, now);
+        assert_eq!(
+            breaker.check_at(Some("org"), now),
+            Err(Duration::from_secs(10))
+        );
+        assert_eq!(breaker.check_at(Some("other"), now), Ok(()));
+
+        // Only one probe goes through, a failed probe opens the circuit again
+        let later = now + Duration::from_secs(10);
+        assert_eq!(breaker.check_at(Some("org"), later), Ok(()));
+        assert!(breaker.check_at(Some("org"), later).is_err());
+        breaker.record_at(Some("org"), false, later);
+        assert!(breaker.check_at(Some("org"), later).is_err());
+
+        let later = later + Duration::from_secs(10);
+        assert_eq!(breaker.check_at(Some("org"), later), Ok(()));
+        breaker.record_at(Some("org"), true, later);
+        assert_eq!(breaker.check_at(Some("org"), later), Ok(()));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,6 +14,9 @@ pub struct ClassificationNode {
     pub children: Option<Vec<ClassificationNode>>,
     #[serde(default, rename = "hasChildren")]
     pub has_children: Option<bool>,
+    /// Iteration dates (startDate, finishDate), only set on iteration nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<serde_json::Value>,
 }
 
 impl ClassificationNode {
@@ -29,7 +33,7 @@ impl ClassificationNode {
 
 /// List area paths for a project
 pub async fn list_area_paths(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     parent_path: Option<&str>,
@@ -55,7 +59,7 @@ pub async fn list_area_paths(
 
 /// List iteration paths for a project
 pub async fn list_iteration_paths(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     parent_path: Option<&str>,
@@ -78,3 +82,217 @@ pub async fn list_iteration_paths(
 
     client.get(organization, project, &path).await
 }
+
+/// Convert a full classification path ("Project\\Area\\Sub" or "\\Project\\Area\\Sub")
+/// into the URL path relative to the structure root ("Area/Sub"), encoding every segment
+pub fn relative_node_path(full_path: &str) -> String {
+    full_path
+        .trim_matches('\\')
+        .split('\\')
+        .skip(1) // project name
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Map a user supplied structure kind ("area", "iteration", ...) to its API structure group
+pub fn structure_group(kind: &str) -> Option<&'static str> {
+    match kind.to_lowercase().as_str() {
+        "area" | "areas" => Some("areas"),
+        "iteration" | "iterations" => Some("iterations"),
+        _ => None,
+    }
+}
+
+fn node_api_path(structure_group: &str, full_path: &str) -> String {
+    let relative = relative_node_path(full_path);
+    if relative.is_empty() {
+        format!("wit/classificationnodes/{}", structure_group)
+    } else {
+        format!("wit/classificationnodes/{}/{}", structure_group, relative)
+    }
+}
+
+/// Get a single classification node ("areas" or "iterations") by its full path
+pub async fn get_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    full_path: &str,
+) -> Result<ClassificationNode, AzureError> {
+    // API: https://dev.azure.com/{organization}/{project}/_apis/wit/classificationnodes/{structureGroup}/{path}?api-version=7.1
+    let path = format!(
+        "{}?api-version=7.1",
+        node_api_path(structure_group, full_path)
+    );
+    client.get(organization, project, &path).await
+}
+
+/// Get a single area node by its full path
+pub async fn get_area_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    area_path: &str,
+) -> Result<ClassificationNode, AzureError> {
+    get_node(client, organization, project, "areas", area_path).await
+}
+
+/// Create a classification node ("areas" or "iterations") under the given parent path,
+/// or directly under the project root when no parent is given
+pub async fn create_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    parent_path: Option<&str>,
+    body: &serde_json::Value,
+) -> Result<ClassificationNode, AzureError> {
+    // API: POST https://dev.azure.com/{organization}/{project}/_apis/wit/classificationnodes/{structureGroup}/{path}?api-version=7.1
+    let path = format!(
+        "{}?api-version=7.1",
+        node_api_path(structure_group, parent_path.unwrap_or_default())
+    );
+    client.post(organization, project, &path, body).await
+}
+
+/// Create an area node
+pub async fn create_area_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    parent_path: Option<&str>,
+    name: &str,
+) -> Result<ClassificationNode, AzureError> {
+    let body = serde_json::json!({ "name": name });
+    create_node(client, organization, project, "areas", parent_path, &body).await
+}
+
+/// Create an iteration node, optionally with start and finish dates
+pub async fn create_iteration_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    parent_path: Option<&str>,
+    name: &str,
+    start_date: Option<&str>,
+    finish_date: Option<&str>,
+) -> Result<ClassificationNode, AzureError> {
+    let mut body = serde_json::json!({ "name": name });
+    if start_date.is_some() || finish_date.is_some() {
+        body["attributes"] = serde_json::json!({
+            "startDate": start_date,
+            "finishDate": finish_date,
+        });
+    }
+    create_node(
+        client,
+        organization,
+        project,
+        "iterations",
+        parent_path,
+        &body,
+    )
+    .await
+}
+
+/// Rename a classification node and/or change its attributes (iteration dates)
+pub async fn update_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    full_path: &str,
+    body: &serde_json::Value,
+) -> Result<ClassificationNode, AzureError> {
+    // API: PATCH https://dev.azure.com/{organization}/{project}/_apis/wit/classificationnodes/{structureGroup}/{path}?api-version=7.1
+    let path = format!(
+        "{}?api-version=7.1",
+        node_api_path(structure_group, full_path)
+    );
+    client.patch(organization, project, &path, body).await
+}
+
+/// Move a classification node (by ID) under a new parent path
+pub async fn move_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    node_id: i32,
+    new_parent_path: &str,
+) -> Result<ClassificationNode, AzureError> {
+    // API: POST https://dev.azure.com/{organization}/{project}/_apis/wit/classificationnodes/{structureGroup}/{newParentPath}?api-version=7.1
+    // with the ID of an existing node in the body moves it instead of creating a new one
+    let body = serde_json::json!({ "id": node_id });
+    create_node(
+        client,
+        organization,
+        project,
+        structure_group,
+        Some(new_parent_path),
+        &body,
+    )
+    .await
+}
+
+/// Delete a classification node, reclassifying its work items to another node
+pub async fn delete_node(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    full_path: &str,
+    reclassify_id: i32,
+) -> Result<(), AzureError> {
+    // API: DELETE https://dev.azure.com/{organization}/{project}/_apis/wit/classificationnodes/{structureGroup}/{path}?$reclassifyId={id}&api-version=7.1
+    let path = format!(
+        "{}?$reclassifyId={}&api-version=7.1",
+        node_api_path(structure_group, full_path),
+        reclassify_id
+    );
+    client
+        .request(
+            organization,
+            project,
+            reqwest::Method::DELETE,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_node_path() {
+        assert_eq!(relative_node_path("MyProject"), "");
+        assert_eq!(relative_node_path("MyProject\\Team1"), "Team1");
+        assert_eq!(
+            relative_node_path("\\MyProject\\Team 1\\Sub"),
+            "Team%201/Sub"
+        );
+    }
+
+    #[test]
+    fn test_node_api_path() {
+        assert_eq!(
+            node_api_path("areas", "MyProject"),
+            "wit/classificationnodes/areas"
+        );
+        assert_eq!(
+            node_api_path("iterations", "MyProject\\2025\\Sprint 1"),
+            "wit/classificationnodes/iterations/2025/Sprint%201"
+        );
+    }
+
+    #[test]
+    fn test_structure_group() {
+        assert_eq!(structure_group("Area"), Some("areas"));
+        assert_eq!(structure_group("iterations"), Some("iterations"));
+        assert_eq!(structure_group("team"), None);
+    }
+}
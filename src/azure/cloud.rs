@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+/// Azure cloud hosting the Azure DevOps organizations, selects the service hostnames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cloud {
+    /// Azure public cloud (dev.azure.com)
+    #[default]
+    Public,
+    /// Azure US Government (dev.azure.us)
+    UsGov,
+    /// Azure China (dev.azure.cn)
+    China,
+}
+
+impl Cloud {
+    /// Domain of the Azure DevOps services, prefixed by the service name
+    /// (e.g. `vssps.dev.azure.com`)
+    pub fn domain(self) -> &'static str {
+        match self {
+            Cloud::Public => "dev.azure.com",
+            Cloud::UsGov => "dev.azure.us",
+            Cloud::China => "dev.azure.cn",
+        }
+    }
+
+    /// Base URL of an Azure DevOps service, the core REST API when `service` is None
+    pub fn base_url(self, service: Option<&str>) -> String {
+        match service {
+            Some(service) => format!("https://{}.{}", service, self.domain()),
+            None => format!("https://{}", self.domain()),
+        }
+    }
+
+    /// Base URL of the organization independent profile and accounts APIs
+    pub fn profile_base_url(self) -> String {
+        match self {
+            Cloud::Public => "https://app.vssps.visualstudio.com".to_string(),
+            _ => self.base_url(Some("vssps")),
+        }
+    }
+
+    /// Entra ID authority issuing the tokens
+    pub fn login_host(self) -> &'static str {
+        match self {
+            Cloud::Public => "login.microsoftonline.com",
+            Cloud::UsGov => "login.microsoftonline.us",
+            Cloud::China => "login.chinacloudapi.cn",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url() {
+        assert_eq!(Cloud::Public.base_url(None), "https://dev.azure.com");
+        assert_eq!(
+            Cloud::UsGov.base_url(Some("vssps")),
+            "https://vssps.dev.azure.us"
+        );
+        assert_eq!(
+            Cloud::Public.profile_base_url(),
+            "https://app.vssps.visualstudio.com"
+        );
+    }
+}
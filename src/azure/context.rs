@@ -0,0 +1,70 @@
+use crate::azure::api_version::ApiVersionConfig;
+use crate::azure::assignees::IdentityIndex;
+use crate::azure::named_queries::NamedQueries;
+use crate::azure::service_hooks::ServiceHookReceiver;
+use crate::azure::write_policy::WritePolicy;
+use crate::store::WorkItemStore;
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Settings and services the API modules and the tools act with, besides the requests
+/// of [`AzureBoardsApi`](crate::azure::api::AzureBoardsApi), kept by the client they
+/// were configured on and given to its tool invocations with [`scope`]
+#[derive(Clone, Default)]
+pub struct ClientContext {
+    /// api-versions the requests are sent with instead of the ones they are written
+    /// against
+    pub api_versions: ApiVersionConfig,
+    /// Local store mirroring the work items fetched, if any
+    pub work_item_store: Option<Arc<WorkItemStore>>,
+    /// Receiver of the events of the service hooks created for the change notifications
+    pub service_hook_receiver: Option<Arc<ServiceHookReceiver>>,
+    /// Library of WIQL queries offered to the tools
+    pub named_queries: Arc<NamedQueries>,
+    /// Policy the work item changes are checked against before being sent
+    pub write_policy: Arc<WritePolicy>,
+    /// Users found by the searches of the assignees
+    pub identity_index: Arc<IdentityIndex>,
+}
+
+static DEFAULT: Lazy<Arc<ClientContext>> = Lazy::new(Arc::default);
+
+tokio::task_local! {
+    static CONTEXT: Arc<ClientContext>;
+}
+
+/// Run a future, e.g. a tool invocation, acting with a context
+pub async fn scope<F: Future>(context: Arc<ClientContext>, future: F) -> F::Output {
+    CONTEXT.scope(context, future).await
+}
+
+/// Context of the running future, the default one (no store, no policy) outside of a
+/// [`scope`]
+pub fn current() -> Arc<ClientContext> {
+    CONTEXT
+        .try_with(Arc::clone)
+        .unwrap_or_else(|_| Arc::clone(&DEFAULT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope() {
+        assert!(current().write_policy.is_empty());
+        let context = ClientContext {
+            write_policy: Arc::new(WritePolicy {
+                max_bulk_items: Some(5),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let max = scope(Arc::new(context), async {
+            current().write_policy.max_bulk_items
+        })
+        .await;
+        assert_eq!(max, Some(5));
+    }
+}
@@ -0,0 +1,396 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::api_version::{self, DEFAULT_API_VERSION};
+use crate::azure::client::AzureError;
+use crate::azure::context;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Tokens expiring within this many days get a rotation reminder
+const EXPIRY_WARNING_DAYS: i64 = 7;
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct TokenDetails {
+    /// "oauth" or "pat"
+    pub kind: &'static str,
+    pub user: Option<String>,
+    /// Scopes granted to OAuth tokens, PAT scopes can only be probed
+    pub scopes: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScopeProbe {
+    pub scope: &'static str,
+    pub description: &'static str,
+    /// None when the probe was skipped
+    pub granted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiVersionCheck {
+    /// api-version sent to the work item endpoints
+    pub configured: String,
+    /// Highest api-version of the work item endpoints the organization supports
+    pub supported_max: Option<String>,
+    /// None when the supported versions could not be read
+    pub supported: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialCheck {
+    pub organization: String,
+    pub valid: bool,
+    pub authenticated_user: Option<String>,
+    pub token: TokenDetails,
+    pub scopes: Vec<ScopeProbe>,
+    pub api_version: ApiVersionCheck,
+    pub remediation: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtClaims {
+    exp: Option<i64>,
+    upn: Option<String>,
+    unique_name: Option<String>,
+    scp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectionData {
+    #[serde(rename = "authenticatedUser")]
+    authenticated_user: ConnectionUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceLocations {
+    value: Vec<ResourceLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceLocation {
+    area: String,
+    resource_name: String,
+    max_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectionUser {
+    id: String,
+    #[serde(rename = "providerDisplayName")]
+    provider_display_name: Option<String>,
+}
+
+/// Describe a token: PATs are opaque, OAuth tokens are JWTs carrying user, scopes and expiry
+pub fn parse_token(token: &str) -> TokenDetails {
+    if token.contains(':') {
+        return TokenDetails {
+            kind: "pat",
+            ..Default::default()
+        };
+    }
+    let claims = token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(payload.trim_end_matches('='))
+                .ok()
+        })
+        .and_then(|json| serde_json::from_slice::<JwtClaims>(&json).ok());
+    match claims {
+        Some(claims) => TokenDetails {
+            kind: "oauth",
+            user: claims.upn.or(claims.unique_name),
+            scopes: claims.scp,
+            expires_at: claims.exp.and_then(|exp| DateTime::from_timestamp(exp, 0)),
+        },
+        None => TokenDetails {
+            kind: "oauth",
+            ..Default::default()
+        },
+    }
+}
+
+fn probe(
+    scope: &'static str,
+    description: &'static str,
+    result: Option<Result<Value, AzureError>>,
+) -> ScopeProbe {
+    let (granted, error) = match result {
+        None => (None, None),
+        Some(Ok(_)) => (Some(true), None),
+        Some(Err(e)) => {
+            let mut error = e.to_string();
+            error.truncate(200);
+            (Some(false), Some(error))
+        }
+    };
+    ScopeProbe {
+        scope,
+        description,
+        granted,
+        error,
+    }
+}
+
+/// Display name of the identity the requests to an organization are authenticated as
+pub async fn authenticated_user(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+) -> Result<String, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/connectionData
+    let connection: ConnectionData = client
+        .org_request(organization, Method::GET, "connectionData", None::<&String>)
+        .await?;
+    Ok(connection
+        .authenticated_user
+        .provider_display_name
+        .unwrap_or(connection.authenticated_user.id))
+}
+
+/// Check which organization the token is valid for and which scopes it grants, by
+/// probing a representative read endpoint per scope (and a validate-only work item
+/// creation for write access when a project is given)
+pub async fn check_credentials(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+) -> Result<CredentialCheck, AzureError> {
+    let token = parse_token(&client.access_token(organization).await?);
+
+    // API: https://dev.azure.com/{organization}/_apis/connectionData
+    // Rejected tokens get a sign-in page instead of JSON, failing the request
+    let connection: Result<ConnectionData, AzureError> = client
+        .org_request(organization, Method::GET, "connectionData", None::<&String>)
+        .await;
+    let authenticated_user = connection.ok().map(|c| {
+        c.authenticated_user
+            .provider_display_name
+            .unwrap_or(c.authenticated_user.id)
+    });
+
+    let mut scopes = Vec::new();
+    if authenticated_user.is_some() {
+        // API: https://dev.azure.com/{organization}/_apis/projects?$top=1&api-version=7.1
+        let result = client
+            .org_request(
+                organization,
+                Method::GET,
+                "projects?$top=1&api-version=7.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.project", "Project and Team: Read", Some(result)));
+
+        // API: https://dev.azure.com/{organization}/_apis/wit/fields?api-version=7.1
+        let result = client
+            .org_request(
+                organization,
+                Method::GET,
+                "wit/fields?api-version=7.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.work", "Work Items: Read", Some(result)));
+
+        let result = match project {
+            // API: https://dev.azure.com/{organization}/{project}/_apis/wit/workitems/$Task?validateOnly=true&api-version=7.1
+            Some(project) => Some(
+                client
+                    .post_patch(
+                        organization,
+                        project,
+                        "wit/workitems/$Task?validateOnly=true&api-version=7.1",
+                        &serde_json::json!([{
+                            "op": "add",
+                            "path": "/fields/System.Title",
+                            "value": "Credential check"
+                        }]),
+                    )
+                    .await,
+            ),
+            None => None,
+        };
+        scopes.push(probe("vso.work_write", "Work Items: Read & write", result));
+
+        // API: https://vssps.dev.azure.com/{organization}/_apis/graph/users?api-version=7.1-preview.1
+        let result = client
+            .org_vssps_request(
+                organization,
+                Method::GET,
+                "graph/users?api-version=7.1-preview.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.graph", "Graph: Read", Some(result)));
+
+        // API: https://analytics.dev.azure.com/{organization}/_odata/v4.0-preview/Projects?$top=1
+        let result = client
+            .analytics_request(
+                organization,
+                None,
+                Method::GET,
+                "Projects?$top=1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.analytics", "Analytics: Read", Some(result)));
+
+        // API: https://dev.azure.com/{organization}/_apis/notification/subscriptions?api-version=7.1
+        let result = client
+            .org_request(
+                organization,
+                Method::GET,
+                "notification/subscriptions?api-version=7.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe(
+            "vso.notification",
+            "Notifications: Read",
+            Some(result),
+        ));
+
+        // API: https://dev.azure.com/{organization}/_apis/hooks/subscriptions?api-version=7.1
+        let result = client
+            .org_request(
+                organization,
+                Method::GET,
+                "hooks/subscriptions?api-version=7.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.hooks", "Service Hooks: Read", Some(result)));
+
+        // API: https://auditservice.dev.azure.com/{organization}/_apis/audit/auditlog?batchSize=1&api-version=7.1-preview.1
+        let result = client
+            .audit_request(
+                organization,
+                Method::GET,
+                "audit/auditlog?batchSize=1&api-version=7.1-preview.1",
+                None::<&String>,
+            )
+            .await;
+        scopes.push(probe("vso.auditlog", "Audit Log: Read", Some(result)));
+    }
+
+    let api_version = check_api_version(client, organization).await;
+    let remediation = remediation(
+        organization,
+        &authenticated_user,
+        &token,
+        &scopes,
+        &api_version,
+        Utc::now(),
+    );
+    Ok(CredentialCheck {
+        organization: organization.to_string(),
+        valid: authenticated_user.is_some(),
+        authenticated_user,
+        token,
+        scopes,
+        api_version,
+        remediation,
+    })
+}
+
+/// Compare the api-version of the work item requests with the highest one the
+/// organization (or Azure DevOps Server collection) supports
+async fn check_api_version(client: &dyn AzureBoardsApi, organization: &str) -> ApiVersionCheck {
+    let configured = context::current()
+        .api_versions
+        .resolve("wit/workitems", DEFAULT_API_VERSION);
+
+    // API: OPTIONS https://dev.azure.com/{organization}/_apis/
+    // Lists the resource locations with the versions they support
+    let locations: Result<ResourceLocations, AzureError> = client
+        .org_request(organization, Method::OPTIONS, "", None::<&String>)
+        .await;
+    let supported_max = locations.ok().and_then(|locations| {
+        locations
+            .value
+            .into_iter()
+            .find(|l| l.area.eq_ignore_ascii_case("wit") && l.resource_name == "workItems")
+            .and_then(|l| l.max_version)
+    });
+    ApiVersionCheck {
+        supported: supported_max
+            .as_deref()
+            .map(|max| api_version::is_at_most(&configured, max)),
+        configured,
+        supported_max,
+    }
+}
+
+fn remediation(
+    organization: &str,
+    authenticated_user: &Option<String>,
+    token: &TokenDetails,
+    scopes: &[ScopeProbe],
+    api_version: &ApiVersionCheck,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let mut remediation = Vec::new();
+    if authenticated_user.is_none() {
+        remediation.push(format!(
+            "The token is not valid for organization '{}': check the organization name, \
+             that the PAT was created for this organization (or all accessible organizations) \
+             and that it has not expired or been revoked",
+            organization
+        ));
+    }
+    for probe in scopes {
+        match probe.granted {
+            Some(false) => remediation.push(format!(
+                "Missing {} ({}): add this scope to the PAT, or check the user's permissions",
+                probe.scope, probe.description
+            )),
+            None => remediation.push(format!(
+                "{} ({}) not checked: pass a project to probe it",
+                probe.scope, probe.description
+            )),
+            Some(true) => {}
+        }
+    }
+    if let (Some(false), Some(max)) = (api_version.supported, &api_version.supported_max) {
+        remediation.push(format!(
+            "api-version {} is not supported (up to {}), set --api-version {}",
+            api_version.configured, max, max
+        ));
+    }
+    if let Some(expires_at) = token.expires_at {
+        if expires_at <= now {
+            remediation.push(format!("The token expired on {}", expires_at));
+        } else if expires_at - now < chrono::Duration::days(EXPIRY_WARNING_DAYS) {
+            remediation.push(format!(
+                "The token expires on {}, rotate it soon",
+                expires_at
+            ));
+        }
+    }
+    remediation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token() {
+        assert_eq!(parse_token(":secret").kind, "pat");
+
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"exp":1736172192,"upn":"jane@contoso.com","scp":"vso.work"}"#);
+        let token = parse_token(&format!("eyJhbGciOiJSUzI1NiJ9.{}.sig", payload));
+        assert_eq!(token.kind, "oauth");
+        assert_eq!(token.user.as_deref(), Some("jane@contoso.com"));
+        assert_eq!(token.scopes.as_deref(), Some("vso.work"));
+        assert_eq!(token.expires_at.unwrap().timestamp(), 1736172192);
+    }
+}
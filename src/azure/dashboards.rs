@@ -0,0 +1,179 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardSummary {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, rename = "dashboardScope")]
+    pub dashboard_scope: Option<String>,
+    #[serde(default, rename = "refreshInterval")]
+    pub refresh_interval: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardListResponse {
+    pub value: Vec<DashboardSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dashboard {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub widgets: Vec<Widget>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WidgetPosition {
+    pub row: i32,
+    pub column: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WidgetSize {
+    #[serde(rename = "rowSpan")]
+    pub row_span: i32,
+    #[serde(rename = "columnSpan")]
+    pub column_span: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Widget {
+    pub id: String,
+    pub name: String,
+    #[serde(default, rename = "contributionId")]
+    pub contribution_id: Option<String>,
+    /// Widget configuration, a JSON document serialized as string
+    #[serde(default)]
+    pub settings: Option<String>,
+    #[serde(default)]
+    pub position: Option<WidgetPosition>,
+    #[serde(default)]
+    pub size: Option<WidgetSize>,
+}
+
+impl Widget {
+    /// Short widget type, e.g. "Microsoft.VisualStudioOnline.Dashboards.QueryScalarWidget"
+    /// becomes "QueryScalarWidget"
+    pub fn widget_type(&self) -> &str {
+        self.contribution_id
+            .as_deref()
+            .and_then(|c| c.rsplit('.').next())
+            .unwrap_or_default()
+    }
+
+    /// Saved query ids referenced by the widget settings
+    pub fn query_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        if let Some(settings) = self
+            .settings
+            .as_deref()
+            .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        {
+            collect_query_ids(&settings, &mut ids);
+        }
+        ids
+    }
+}
+
+fn collect_query_ids(value: &Value, ids: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                if key.eq_ignore_ascii_case("queryId")
+                    && let Some(id) = v.as_str()
+                    && !id.is_empty()
+                    && !ids.iter().any(|x| x == id)
+                {
+                    ids.push(id.to_string());
+                } else {
+                    collect_query_ids(v, ids);
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_query_ids(v, ids)),
+        _ => {}
+    }
+}
+
+/// List the dashboards of a team
+pub async fn list_dashboards(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+) -> Result<Vec<DashboardSummary>, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/dashboard/dashboards?api-version=7.1-preview.3
+    let path = "dashboard/dashboards?api-version=7.1-preview.3";
+    let response: DashboardListResponse = client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            path,
+            None::<&String>,
+        )
+        .await?;
+    Ok(response.value)
+}
+
+/// Get a dashboard with its widgets
+pub async fn get_dashboard(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    dashboard_id: &str,
+) -> Result<Dashboard, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/dashboard/dashboards/{dashboardId}?api-version=7.1-preview.3
+    let path = format!(
+        "dashboard/dashboards/{}?api-version=7.1-preview.3",
+        dashboard_id
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widget_query_ids() {
+        let widget = Widget {
+            id: "1".to_string(),
+            name: "Active bugs".to_string(),
+            contribution_id: Some(
+                "ms.vss-dashboards-web.Microsoft.VisualStudioOnline.Dashboards.QueryScalarWidget"
+                    .to_string(),
+            ),
+            settings: Some(
+                r#"{"query":{"queryId":"abc","queryName":"Bugs"},"groups":[{"queryId":"def"},{"queryId":"abc"}]}"#
+                    .to_string(),
+            ),
+            position: None,
+            size: None,
+        };
+        assert_eq!(widget.widget_type(), "QueryScalarWidget");
+        let mut ids = widget.query_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["abc", "def"]);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::azure::client::AzureError;
+use crate::azure::transport::{Network, Transport, build_response};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::{Request, Response};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Placeholders of the fixture bodies, `{{name}}`
+static RE_PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+/// Directory of JSON fixtures answering the requests instead of Azure DevOps, to demo the
+/// server or develop a client without an organization nor credentials.
+///
+/// The response to a request is the file at `<METHOD>/<URL path>.json`, e.g.
+/// `GET/contoso/Web/_apis/wit/workitems/42.json`, the host and the query being ignored.
+/// A directory or file named `{name}` matches any path segment, the exact names first,
+/// and the `{{name}}` placeholders of the body are replaced by the segment it matched
+/// or by the query parameter of this name. A body of the form
+/// `{"$status": 404, "$body": {...}}` answers with another status.
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+}
+
+impl Fixtures {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Status and body answering a request, 404 when no fixture matches it
+    pub fn lookup(&self, method: &str, url: &reqwest::Url) -> (u16, String) {
+        let segments: Vec<String> = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                urlencoding::decode(segment)
+                    .map(|segment| segment.into_owned())
+                    .unwrap_or_else(|_| segment.to_string())
+            })
+            .collect();
+        let mut values = HashMap::new();
+        let Some(file) = find(
+            &self.dir.join(method.to_uppercase()),
+            &segments,
+            &mut values,
+        ) else {
+            tracing::warn!("No fixture for {} {}", method, url.path());
+            let body = json!({
+                "message": format!("No fixture for {} {}", method, url.path()),
+                "typeKey": "FixtureNotFoundException",
+            });
+            return (404, body.to_string());
+        };
+        for (name, value) in url.query_pairs() {
+            values
+                .entry(name.into_owned())
+                .or_insert(value.into_owned());
+        }
+
+        let template = match std::fs::read_to_string(&file) {
+            Ok(template) => template,
+            Err(e) => {
+                let body =
+                    json!({ "message": format!("Failed to read {}: {}", file.display(), e) });
+                return (500, body.to_string());
+            }
+        };
+        let body = RE_PLACEHOLDER.replace_all(&template, |captures: &regex::Captures| {
+            values
+                .get(&captures[1])
+                .cloned()
+                .unwrap_or_else(|| captures[0].to_string())
+        });
+        match serde_json::from_str::<Value>(&body) {
+            Ok(Value::Object(mut envelope)) if envelope.contains_key("$status") => {
+                let status = envelope["$status"].as_u64().unwrap_or(200) as u16;
+                let body = envelope
+                    .remove("$body")
+                    .map(|body| body.to_string())
+                    .unwrap_or_default();
+                (status, body)
+            }
+            _ => (200, body.into_owned()),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for Fixtures {
+    async fn send(&self, request: Request, _network: Network<'_>) -> Result<Response, AzureError> {
+        let (status, body) = self.lookup(request.method().as_str(), request.url());
+        Ok(build_response(
+            request.url(),
+            status,
+            [("content-type", "application/json; charset=utf-8")],
+            body,
+        ))
+    }
+}
+
+/// Fixture file of the remaining path segments under a directory, recording the values
+/// of the placeholders it matched
+fn find(dir: &Path, segments: &[String], values: &mut HashMap<String, String>) -> Option<PathBuf> {
+    let (segment, rest) = segments.split_first()?;
+    let mut entries: Vec<(String, PathBuf)> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            )
+        })
+        .collect();
+    // The exact names first, then the placeholders
+    entries.sort_by_key(|(name, _)| name.starts_with('{'));
+
+    for (name, path) in entries {
+        let name = match (rest.is_empty(), name.strip_suffix(".json")) {
+            (true, Some(stem)) if path.is_file() => stem.to_string(),
+            (false, None) if path.is_dir() => name,
+            _ => continue,
+        };
+        let placeholder = name
+            .strip_prefix('{')
+            .and_then(|name| name.strip_suffix('}'));
+        if placeholder.is_none() && !name.eq_ignore_ascii_case(segment) {
+            continue;
+        }
+        if rest.is_empty() {
+            if let Some(placeholder) = placeholder {
+                values.insert(placeholder.to_string(), segment.clone());
+            }
+            return Some(path);
+        }
+        if let Some(file) = find(&path, rest, values) {
+            if let Some(placeholder) = placeholder {
+                values.insert(placeholder.to_string(), segment.clone());
+            }
+            return Some(file);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let dir = std::env::temp_dir().join(format!("azdo-fixtures-{}", uuid::Uuid::new_v4()));
+        let items = dir.join("GET/{organization}/{project}/_apis/wit/workitems");
+        std::fs::create_dir_all(&items).unwrap();
+        std::fs::write(
+            items.join("{id}.json"),
+            r#"{"id": {{id}}, "fields": {"System.TeamProject": "{{project}}"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            items.join("404.json"),
+            r#"{"$status": 404, "$body": {"message": "Work item 404 does not exist"}}"#,
+        )
+        .unwrap();
+        let fixtures = Fixtures::new(&dir);
+        let url =
+            |path: &str| reqwest::Url::parse(&format!("https://dev.azure.com{}", path)).unwrap();
+
+        let (status, body) = fixtures.lookup(
+            "GET",
+            &url("/contoso/Fabrikam%20Web/_apis/wit/workitems/42?api-version=7.1"),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({"id": 42, "fields": {"System.TeamProject": "Fabrikam Web"}})
+        );
+        let (status, body) = fixtures.lookup("GET", &url("/contoso/Web/_apis/wit/workitems/404"));
+        assert_eq!(status, 404);
+        assert!(body.contains("does not exist"));
+        assert_eq!(
+            fixtures
+                .lookup("PATCH", &url("/contoso/Web/_apis/wit/workitems/42"))
+                .0,
+            404
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
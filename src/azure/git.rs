@@ -0,0 +1,94 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Repository {
+    pub id: String,
+    pub name: String,
+    #[serde(default, rename = "webUrl")]
+    pub web_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullRequest {
+    #[serde(rename = "pullRequestId")]
+    pub pull_request_id: u32,
+    pub title: String,
+    pub status: String,
+    #[serde(default, rename = "createdBy")]
+    pub created_by: Option<Value>,
+    #[serde(default, rename = "creationDate")]
+    pub creation_date: Option<String>,
+    #[serde(default, rename = "closedDate")]
+    pub closed_date: Option<String>,
+    #[serde(default, rename = "sourceRefName")]
+    pub source_ref_name: Option<String>,
+    #[serde(default, rename = "targetRefName")]
+    pub target_ref_name: Option<String>,
+    #[serde(default)]
+    pub repository: Option<Repository>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitUserDate {
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    pub date: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Commit {
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub author: Option<GitUserDate>,
+}
+
+/// Get a Git repository by ID or name
+pub async fn get_repository(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    repository_id: &str,
+) -> Result<Repository, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/git/repositories/{repositoryId}?api-version=7.1
+    let path = format!("git/repositories/{}?api-version=7.1", repository_id);
+    client.get(organization, project, &path).await
+}
+
+/// Get a pull request
+pub async fn get_pull_request(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    repository_id: &str,
+    pull_request_id: u32,
+) -> Result<PullRequest, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/git/repositories/{repositoryId}/pullrequests/{pullRequestId}?api-version=7.1
+    let path = format!(
+        "git/repositories/{}/pullrequests/{}?api-version=7.1",
+        repository_id, pull_request_id
+    );
+    client.get(organization, project, &path).await
+}
+
+/// Get a commit
+pub async fn get_commit(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    repository_id: &str,
+    commit_id: &str,
+) -> Result<Commit, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/git/repositories/{repositoryId}/commits/{commitId}?api-version=7.1
+    let path = format!(
+        "git/repositories/{}/commits/{}?api-version=7.1",
+        repository_id, commit_id
+    );
+    client.get(organization, project, &path).await
+}
@@ -0,0 +1,94 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Identity {
+    pub id: String,
+    #[serde(default)]
+    pub descriptor: Option<String>,
+    #[serde(default, rename = "subjectDescriptor")]
+    pub subject_descriptor: Option<String>,
+    #[serde(default, rename = "providerDisplayName")]
+    pub provider_display_name: Option<String>,
+    #[serde(default, rename = "customDisplayName")]
+    pub custom_display_name: Option<String>,
+    #[serde(default, rename = "isActive")]
+    pub is_active: bool,
+    #[serde(default, rename = "isContainer")]
+    pub is_container: bool,
+    #[serde(default)]
+    pub properties: Option<Value>,
+}
+
+impl Identity {
+    pub fn display_name(&self) -> &str {
+        self.custom_display_name
+            .as_deref()
+            .or(self.provider_display_name.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Read a string identity property (e.g. "Mail", "Account")
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.as_ref()?.get(name)?.get("$value")?.as_str()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdentityListResponse {
+    pub value: Vec<Identity>,
+}
+
+/// Search users and groups by display name, account name or email
+pub async fn search_identities(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    query: &str,
+) -> Result<Vec<Identity>, AzureError> {
+    // API: https://vssps.dev.azure.com/{organization}/_apis/identities?searchFilter=General&filterValue={query}&api-version=7.1
+    let path = format!(
+        "identities?searchFilter=General&filterValue={}&queryMembership=None&api-version=7.1",
+        urlencoding::encode(query)
+    );
+    let response: IdentityListResponse = client
+        .org_vssps_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
+
+/// Read identities by their GUIDs
+pub async fn get_identities_by_ids(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    ids: &[&str],
+) -> Result<Vec<Identity>, AzureError> {
+    // API: https://vssps.dev.azure.com/{organization}/_apis/identities?identityIds={ids}&api-version=7.1
+    let path = format!(
+        "identities?identityIds={}&queryMembership=None&api-version=7.1",
+        urlencoding::encode(&ids.join(","))
+    );
+    let response: IdentityListResponse = client
+        .org_vssps_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
+
+/// Read identities by their identity descriptors
+pub async fn get_identities_by_descriptors(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    descriptors: &[&str],
+) -> Result<Vec<Identity>, AzureError> {
+    // API: https://vssps.dev.azure.com/{organization}/_apis/identities?descriptors={descriptors}&api-version=7.1
+    let path = format!(
+        "identities?descriptors={}&queryMembership=None&api-version=7.1",
+        urlencoding::encode(&descriptors.join(","))
+    );
+    let response: IdentityListResponse = client
+        .org_vssps_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
@@ -1,4 +1,6 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::models::WorkItemReference;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
@@ -28,7 +30,7 @@ pub struct IterationListResponse {
 
 /// Get the current iteration for a team
 pub async fn get_team_current_iteration(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -51,16 +53,14 @@ pub async fn get_team_current_iteration(
             // The API returns a list, but with $timeframe=current there should only be one
             Ok(response.value.into_iter().next())
         }
-        Err(AzureError::ApiError(msg)) if msg.contains("CurrentIterationDoesNotExistException") => {
-            Ok(None)
-        }
+        Err(e) if e.is_type("CurrentIterationDoesNotExistException") => Ok(None),
         Err(e) => Err(e),
     }
 }
 
 /// Get all iterations for a team, optionally filtered by timeframe
 pub async fn get_team_iterations(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     team_id: &str,
@@ -89,3 +89,207 @@ pub async fn get_team_iterations(
 
     Ok(response.value)
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamDaysOff {
+    #[serde(rename = "daysOff")]
+    pub days_off: Vec<DateRange>,
+}
+
+/// Get the team days off for an iteration
+pub async fn get_team_days_off(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<TeamDaysOff, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/teamdaysoff?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/teamdaysoff?api-version=7.1",
+        iteration_id
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+/// Replace the team days off for an iteration
+pub async fn update_team_days_off(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+    days_off: &TeamDaysOff,
+) -> Result<TeamDaysOff, AzureError> {
+    // API: PATCH https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/teamdaysoff?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/teamdaysoff?api-version=7.1",
+        iteration_id
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::PATCH,
+            team_id,
+            &path,
+            Some(days_off),
+        )
+        .await
+}
+
+/// Find a team iteration by ID, name or path, defaulting to the current iteration
+pub async fn find_team_iteration(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration: Option<&str>,
+) -> Result<TeamSettingsIteration, AzureError> {
+    let Some(iteration) = iteration else {
+        return get_team_current_iteration(client, organization, project, team_id)
+            .await?
+            .ok_or_else(|| AzureError::ApiError("Team has no current iteration".to_string()));
+    };
+
+    let iterations = get_team_iterations(client, organization, project, team_id, None).await?;
+    iterations
+        .into_iter()
+        .find(|i| {
+            i.id.eq_ignore_ascii_case(iteration)
+                || i.name.eq_ignore_ascii_case(iteration)
+                || i.path.eq_ignore_ascii_case(iteration)
+        })
+        .ok_or_else(|| {
+            AzureError::ApiError(format!(
+                "Iteration '{}' is not assigned to team '{}'",
+                iteration, team_id
+            ))
+        })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemLink {
+    #[serde(default)]
+    pub rel: Option<String>,
+    #[serde(default)]
+    pub source: Option<WorkItemReference>,
+    pub target: WorkItemReference,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IterationWorkItems {
+    #[serde(rename = "workItemRelations")]
+    pub work_item_relations: Vec<WorkItemLink>,
+}
+
+/// Get the work items committed to a team iteration, with their parent/child relations
+pub async fn get_iteration_work_items(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<IterationWorkItems, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/workitems?api-version=7.1-preview.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/workitems?api-version=7.1-preview.1",
+        iteration_id
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Activity {
+    #[serde(rename = "capacityPerDay", default)]
+    pub capacity_per_day: f64,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamMemberCapacity {
+    #[serde(rename = "teamMember")]
+    pub team_member: serde_json::Value,
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    #[serde(rename = "daysOff", default)]
+    pub days_off: Vec<DateRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamCapacity {
+    #[serde(rename = "teamMembers", default)]
+    pub team_members: Vec<TeamMemberCapacity>,
+}
+
+/// Get the capacity of every team member for an iteration
+pub async fn get_team_capacity(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<TeamCapacity, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/capacities?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/capacities?api-version=7.1",
+        iteration_id
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+/// Add an existing iteration node to the team's iterations
+pub async fn add_team_iteration(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<TeamSettingsIteration, AzureError> {
+    // API: POST https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations?api-version=7.1
+    let body = serde_json::json!({ "id": iteration_id });
+    client
+        .team_request(
+            organization,
+            project,
+            Method::POST,
+            team_id,
+            "work/teamsettings/iterations?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
@@ -1,10 +1,41 @@
+pub mod analytics;
+pub mod api;
+pub mod api_version;
+pub mod artifact_links;
+pub mod assignees;
+pub mod audit;
+pub mod auth;
+pub mod batch;
 pub mod boards;
+pub mod builds;
+pub mod cache;
+pub mod circuit_breaker;
 pub mod classification_nodes;
 pub mod client;
+pub mod cloud;
+pub mod context;
+pub mod credentials;
+pub mod dashboards;
+pub mod fixtures;
+pub mod git;
+pub mod identities;
 pub mod iterations;
 pub mod models;
+pub mod named_queries;
+pub mod notifications;
 pub mod organizations;
+pub mod path_resolver;
+pub mod plans;
+pub mod processes;
 pub mod projects;
+pub mod rate_limit;
+pub mod retry;
+pub mod search;
+pub mod security;
+pub mod service_hooks;
 pub mod tags;
 pub mod teams;
+pub mod transport;
+pub mod users;
 pub mod work_items;
+pub mod write_policy;
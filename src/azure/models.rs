@@ -27,6 +27,16 @@ pub struct WorkItem {
     pub url: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub comments: Option<Vec<Comment>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relations: Option<Vec<WorkItemRelation>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemRelation {
+    pub rel: String,
+    pub url: String,
+    #[serde(default)]
+    pub attributes: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,8 +66,18 @@ pub struct WiqlQuery {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WiqlResponse {
-    #[serde(rename = "workItems")]
+    /// Results of flat queries
+    #[serde(rename = "workItems", default)]
     pub work_items: Vec<WorkItemReference>,
+    /// Results of tree and one-hop queries
+    #[serde(rename = "workItemRelations", default)]
+    pub work_item_relations: Vec<WorkItemLink>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemLink {
+    pub source: Option<WorkItemReference>,
+    pub target: Option<WorkItemReference>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
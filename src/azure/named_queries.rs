@@ -0,0 +1,211 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Placeholders of the parameters, `{name}` or `{name=default}`
+static RE_PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)(?:=([^{}]*))?\}").unwrap());
+
+/// Values inserted as they are outside of the string literals, numbers and macros (e.g.
+/// `@Me`, `@CurrentIteration`), the others being quoted
+static RE_LITERAL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:-?\d+(?:\.\d+)?|@[A-Za-z]+)$").unwrap());
+
+/// WIQL query of the library defined in the config, whose `{name}` or `{name=default}`
+/// placeholders are replaced by the values of its parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedQuery {
+    pub name: String,
+    pub wiql: String,
+    /// Parameters in order of appearance, with their default value
+    pub parameters: Vec<(String, Option<String>)>,
+}
+
+impl NamedQuery {
+    pub fn new(name: &str, wiql: &str) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "invalid query name {}, expected letters, digits, - and _",
+                name
+            ));
+        }
+        let wiql = wiql.trim();
+        if wiql.is_empty() {
+            return Err(format!("query {} has no WIQL", name));
+        }
+        let mut parameters: Vec<(String, Option<String>)> = Vec::new();
+        for captures in RE_PLACEHOLDER.captures_iter(wiql) {
+            let parameter = &captures[1];
+            let default = captures.get(2).map(|default| default.as_str().to_string());
+            match parameters.iter_mut().find(|(known, _)| known == parameter) {
+                Some((_, known)) => {
+                    if known.is_none() {
+                        *known = default;
+                    }
+                }
+                None => parameters.push((parameter.to_string(), default)),
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            wiql: wiql.to_string(),
+            parameters,
+        })
+    }
+
+    /// Parameters with their defaults, e.g. `iteration, type=Bug`
+    pub fn parameter_list(&self) -> String {
+        self.parameters
+            .iter()
+            .map(|(name, default)| match default {
+                Some(default) => format!("{}={}", name, default),
+                None => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Name with the parameters, e.g. `sprint_scope{iteration, type=Bug}`
+    pub fn signature(&self) -> String {
+        if self.parameters.is_empty() {
+            return self.name.clone();
+        }
+        format!("{}{{{}}}", self.name, self.parameter_list())
+    }
+
+    /// WIQL with the placeholders replaced by the values, escaped within the string
+    /// literals and quoted outside of them unless numbers or macros
+    pub fn render(&self, values: &BTreeMap<String, String>) -> Result<String, String> {
+        if let Some(unknown) = values
+            .keys()
+            .find(|name| !self.parameters.iter().any(|(known, _)| known == *name))
+        {
+            return Err(format!(
+                "Unknown parameter {} of query {}, expected: {}",
+                unknown,
+                self.name,
+                self.signature()
+            ));
+        }
+        let missing: Vec<&str> = self
+            .parameters
+            .iter()
+            .filter(|(name, default)| default.is_none() && !values.contains_key(name))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Missing parameters {} of query {}",
+                missing.join(", "),
+                self.signature()
+            ));
+        }
+
+        let mut wiql = String::new();
+        let mut last = 0;
+        for captures in RE_PLACEHOLDER.captures_iter(&self.wiql) {
+            let placeholder = captures.get(0).unwrap();
+            let value = values
+                .get(&captures[1])
+                .map(String::as_str)
+                .or_else(|| {
+                    self.parameters
+                        .iter()
+                        .find(|(name, _)| name == &captures[1])
+                        .and_then(|(_, default)| default.as_deref())
+                })
+                .unwrap_or_default();
+            let escaped = value.replace('\'', "''");
+            // Within a string literal when an odd number of quotes precede it
+            let quoted = self.wiql[..placeholder.start()].matches('\'').count() % 2 == 1;
+            wiql.push_str(&self.wiql[last..placeholder.start()]);
+            if quoted || RE_LITERAL.is_match(value) {
+                wiql.push_str(&escaped);
+            } else {
+                wiql.push_str(&format!("'{}'", escaped));
+            }
+            last = placeholder.end();
+        }
+        wiql.push_str(&self.wiql[last..]);
+        Ok(wiql)
+    }
+}
+
+/// Named queries of the config, which teams ship as a curated vocabulary to their agents
+#[derive(Debug, Clone, Default)]
+pub struct NamedQueries {
+    queries: BTreeMap<String, NamedQuery>,
+}
+
+impl NamedQueries {
+    pub fn new(queries: impl IntoIterator<Item = NamedQuery>) -> Self {
+        Self {
+            queries: queries
+                .into_iter()
+                .map(|query| (query.name.clone(), query))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NamedQuery> {
+        self.queries.get(name)
+    }
+
+    /// Queries sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = &NamedQuery> {
+        self.queries.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let query = NamedQuery::new(
+            "sprint_scope",
+            "SELECT [System.Id] FROM WorkItems WHERE [System.IterationPath] UNDER {iteration} \
+             AND [System.Title] CONTAINS '{text=}' AND [System.AssignedTo] = {assignee=@Me} \
+             AND [System.ChangedDate] >= @today - {days=30} AND [System.AreaPath] UNDER {iteration}",
+        )
+        .unwrap();
+        assert_eq!(
+            query.signature(),
+            "sprint_scope{iteration, text=, assignee=@Me, days=30}"
+        );
+        let values = BTreeMap::from([
+            ("iteration".to_string(), "Fabrikam\\Sprint 'A'".to_string()),
+            ("text".to_string(), "it's".to_string()),
+        ]);
+        assert_eq!(
+            query.render(&values).unwrap(),
+            "SELECT [System.Id] FROM WorkItems WHERE [System.IterationPath] UNDER \
+             'Fabrikam\\Sprint ''A''' AND [System.Title] CONTAINS 'it''s' AND \
+             [System.AssignedTo] = @Me AND [System.ChangedDate] >= @today - 30 AND \
+             [System.AreaPath] UNDER 'Fabrikam\\Sprint ''A'''"
+        );
+
+        assert_eq!(
+            query.render(&BTreeMap::new()).unwrap_err(),
+            "Missing parameters iteration of query sprint_scope{iteration, text=, assignee=@Me, days=30}"
+        );
+        let unknown = BTreeMap::from([("team".to_string(), "Web".to_string())]);
+        assert!(
+            query
+                .render(&unknown)
+                .unwrap_err()
+                .starts_with("Unknown parameter team")
+        );
+        assert!(NamedQuery::new("my bugs", "SELECT").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Event type raised when a work item is created or updated
+pub const WORK_ITEM_CHANGED_EVENT: &str = "ms.vss-work.workitem-changed-event";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub filter: Option<Value>,
+    #[serde(default)]
+    pub subscriber: Option<Value>,
+    #[serde(default)]
+    pub channel: Option<Value>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+impl Subscription {
+    pub fn event_type(&self) -> &str {
+        self.filter
+            .as_ref()
+            .and_then(|f| f.get("eventType"))
+            .and_then(|e| e.as_str())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionListResponse {
+    pub value: Vec<Subscription>,
+}
+
+/// Expression filter clause, e.g. field "Area path", operator "Under", value "Project\\Team"
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionClause {
+    #[serde(rename = "fieldName")]
+    pub field_name: String,
+    pub operator: String,
+    pub value: String,
+}
+
+/// List the notification subscriptions of the caller, or of a team when `target_id` is set
+pub async fn list_subscriptions(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    target_id: Option<&str>,
+) -> Result<Vec<Subscription>, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/notification/subscriptions?targetId={id}&api-version=7.1
+    let path = match target_id {
+        Some(id) => format!("notification/subscriptions?targetId={}&api-version=7.1", id),
+        None => "notification/subscriptions?api-version=7.1".to_string(),
+    };
+    let response: SubscriptionListResponse = client
+        .org_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
+
+/// Create an email subscription for the caller, or for a team when `subscriber_id` is set
+pub async fn create_subscription(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    description: &str,
+    event_type: &str,
+    clauses: &[SubscriptionClause],
+    project_id: Option<&str>,
+    subscriber_id: Option<&str>,
+) -> Result<Subscription, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/notification/subscriptions?api-version=7.1
+    let clauses: Vec<Value> = clauses
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            serde_json::json!({
+                "fieldName": c.field_name,
+                "operator": c.operator,
+                "value": c.value,
+                "logicalOperator": if i == 0 { "" } else { "And" },
+                "index": i + 1,
+            })
+        })
+        .collect();
+    let mut body = serde_json::json!({
+        "description": description,
+        "filter": {
+            "type": "Expression",
+            "eventType": event_type,
+            "criteria": {
+                "clauses": clauses,
+                "groups": [],
+                "maxGroupLevel": 0,
+            },
+        },
+        "channel": { "type": "EmailHtml", "useCustomAddress": false },
+    });
+    if let Some(id) = project_id {
+        body["scope"] = serde_json::json!({ "id": id });
+    }
+    if let Some(id) = subscriber_id {
+        body["subscriber"] = serde_json::json!({ "id": id });
+    }
+    client
+        .org_request(
+            organization,
+            Method::POST,
+            "notification/subscriptions?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
+
+/// Delete a notification subscription
+pub async fn delete_subscription(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    subscription_id: &str,
+) -> Result<(), AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/notification/subscriptions/{id}?api-version=7.1
+    let path = format!(
+        "notification/subscriptions/{}?api-version=7.1",
+        subscription_id
+    );
+    client
+        .org_request(organization, Method::DELETE, &path, None::<&String>)
+        .await
+}
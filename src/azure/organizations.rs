@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
@@ -29,7 +30,7 @@ pub struct OrganizationListResponse {
 }
 
 /// Get the current user's profile
-pub async fn get_profile(client: &AzureDevOpsClient) -> Result<Profile, AzureError> {
+pub async fn get_profile(client: &dyn AzureBoardsApi) -> Result<Profile, AzureError> {
     let path = "profile/profiles/me?api-version=7.1";
     client
         .vssps_request(Method::GET, path, None::<&String>)
@@ -38,7 +39,7 @@ pub async fn get_profile(client: &AzureDevOpsClient) -> Result<Profile, AzureErr
 
 /// List all organizations the current user has access to
 pub async fn list_organizations(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     member_id: &str,
 ) -> Result<Vec<Organization>, AzureError> {
     let path = format!("accounts?memberId={}&api-version=7.1", member_id);
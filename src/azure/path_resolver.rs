@@ -0,0 +1,333 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::classification_nodes::{self, ClassificationNode};
+use crate::azure::client::AzureError;
+use serde_json::Value;
+
+/// Depth of the classification trees fetched, as the path tools do
+const TREE_DEPTH: i32 = 10;
+
+/// Similarity of the segments for a misspelled path to match, from 0 to 1
+const MIN_SIMILARITY: f64 = 0.75;
+
+/// Lead the best fuzzy match needs over the next one to be picked
+const MIN_LEAD: f64 = 0.1;
+
+/// Candidates listed when a path is ambiguous
+const MAX_CANDIDATES: usize = 5;
+
+/// Paths of a classification tree as the work item fields take them (e.g.
+/// `Project\Team\Sub`), without the `Area` or `Iteration` segment of the node paths
+pub fn canonical_paths(root: &ClassificationNode) -> Vec<String> {
+    fn collect(node: &ClassificationNode, path: String, paths: &mut Vec<String>) {
+        paths.push(path.clone());
+        if let Some(children) = &node.children {
+            for child in children {
+                collect(child, format!("{}\\{}", path, child.name), paths);
+            }
+        }
+    }
+    let mut paths = Vec::new();
+    collect(root, root.name.clone(), &mut paths);
+    paths
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split(['\\', '/'])
+        .map(|segment| segment.trim().to_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Similarity of two segments from 0 to 1, from their edit distance
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    let longest = a.len().max(b.len()).max(1);
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+/// Runs of digits of a segment, e.g. `["2025", "42"]` for `2025 sprint 42`
+fn digit_runs(segment: &str) -> Vec<&str> {
+    segment
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .collect()
+}
+
+/// Canonical path a partial or misspelled input stands for: the one it is, relative to
+/// the project or not, else the ones it ends, else the ones whose last segments are
+/// close to its segments with the same numbers (`Sprint 24` is no misspelling of
+/// `Sprint 42`). The candidates are returned when none or several match.
+pub fn match_path(input: &str, paths: &[String]) -> Result<String, Vec<String>> {
+    let wanted = segments(input);
+    if wanted.is_empty() {
+        return Err(Vec::new());
+    }
+    let paths: Vec<(&String, Vec<String>)> =
+        paths.iter().map(|path| (path, segments(path))).collect();
+
+    if let Some((path, _)) = paths.iter().find(|(_, segments)| *segments == wanted) {
+        return Ok(path.to_string());
+    }
+    if let Some((path, _)) = paths
+        .iter()
+        .find(|(_, segments)| segments.len() > 1 && segments[1..] == wanted[..])
+    {
+        return Ok(path.to_string());
+    }
+    let ending: Vec<&String> = paths
+        .iter()
+        .filter(|(_, segments)| segments.ends_with(&wanted))
+        .map(|(path, _)| *path)
+        .collect();
+    match ending.as_slice() {
+        [path] => return Ok(path.to_string()),
+        [] => {}
+        _ => {
+            return Err(ending.into_iter().take(MAX_CANDIDATES).cloned().collect());
+        }
+    }
+
+    let mut scored: Vec<(f64, &String)> = paths
+        .iter()
+        .filter(|(_, segments)| segments.len() >= wanted.len())
+        .map(|(path, segments)| {
+            let last = &segments[segments.len() - wanted.len()..];
+            let score = last
+                .iter()
+                .zip(&wanted)
+                .map(|(a, b)| similarity(a, b))
+                .sum::<f64>()
+                / wanted.len() as f64;
+            (score, *path)
+        })
+        .filter(|(score, _)| *score >= MIN_SIMILARITY)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    let same_numbers = |path: &String| {
+        let segments = segments(path);
+        segments[segments.len() - wanted.len()..]
+            .iter()
+            .zip(&wanted)
+            .all(|(a, b)| digit_runs(a) == digit_runs(b))
+    };
+    let matching: Vec<&(f64, &String)> = scored
+        .iter()
+        .filter(|(_, path)| same_numbers(path))
+        .collect();
+    match matching.as_slice() {
+        [(_, path)] => Ok(path.to_string()),
+        [(best, path), (next, _), ..] if best - next >= MIN_LEAD => Ok(path.to_string()),
+        _ => Err(scored
+            .into_iter()
+            .take(MAX_CANDIDATES)
+            .map(|(_, path)| path.clone())
+            .collect()),
+    }
+}
+
+/// Resolve an area ("areas") or iteration ("iterations") path given by the user to its
+/// canonical path, from the classification nodes the client caches. The input is kept
+/// as is when the nodes cannot be read, Azure DevOps reporting the invalid paths.
+pub async fn resolve_path(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    input: &str,
+) -> Result<String, AzureError> {
+    let root = match structure_group {
+        "iterations" => {
+            classification_nodes::list_iteration_paths(
+                client,
+                organization,
+                project,
+                None,
+                TREE_DEPTH,
+            )
+            .await
+        }
+        _ => {
+            classification_nodes::list_area_paths(client, organization, project, None, TREE_DEPTH)
+                .await
+        }
+    };
+    let root = match root {
+        Ok(root) => root,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to read the {} of {}: {}",
+                structure_group,
+                project,
+                e
+            );
+            return Ok(input.to_string());
+        }
+    };
+    match_path(input, &canonical_paths(&root)).map_err(|candidates| AzureError::UnresolvedPath {
+        kind: if structure_group == "iterations" {
+            "iteration"
+        } else {
+            "area"
+        },
+        input: input.to_string(),
+        candidates,
+    })
+}
+
+/// Resolve the area and iteration paths given to a tool, in place, with [`resolve_path`]
+pub async fn resolve_area_and_iteration(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    area_path: &mut Option<String>,
+    iteration_path: &mut Option<String>,
+) -> Result<(), AzureError> {
+    for (structure_group, path) in [("areas", area_path), ("iterations", iteration_path)] {
+        if let Some(path) = path {
+            *path = resolve_path(client, organization, project, structure_group, path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the `System.AreaPath` and `System.IterationPath` values of the fields of a
+/// work item, by reference name, in place, with [`resolve_path`]
+pub async fn resolve_field_paths(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    fields: &mut [(String, Value)],
+) -> Result<(), AzureError> {
+    for (field, value) in fields {
+        let structure_group = match field.as_str() {
+            "System.AreaPath" => "areas",
+            "System.IterationPath" => "iterations",
+            _ => continue,
+        };
+        if let Value::String(path) = value {
+            *path = resolve_path(client, organization, project, structure_group, path).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use serde_json::json;
+
+    #[test]
+    fn test_match_path() {
+        let paths: Vec<String> = [
+            "Web",
+            "Web\\Payments",
+            "Web\\Payments\\Backend",
+            "Web\\Checkout\\Backend",
+            "Web\\2025\\Sprint 42",
+            "Web\\2025\\Sprint 43",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect();
+
+        assert_eq!(
+            match_path("web\\payments", &paths).unwrap(),
+            "Web\\Payments"
+        );
+        assert_eq!(
+            match_path("Payments/Backend", &paths).unwrap(),
+            "Web\\Payments\\Backend"
+        );
+        assert_eq!(
+            match_path(" Sprint 42 ", &paths).unwrap(),
+            "Web\\2025\\Sprint 42"
+        );
+        assert_eq!(
+            match_path("Paymnts\\Backend", &paths).unwrap(),
+            "Web\\Payments\\Backend"
+        );
+        assert_eq!(
+            match_path("Backend", &paths).unwrap_err(),
+            ["Web\\Payments\\Backend", "Web\\Checkout\\Backend"]
+        );
+        assert_eq!(
+            match_path("Sprint 4", &paths).unwrap_err(),
+            ["Web\\2025\\Sprint 42", "Web\\2025\\Sprint 43"]
+        );
+        assert!(match_path("Marketing", &paths).unwrap_err().is_empty());
+
+        let sprints: Vec<String> = ["Web\\Sprint 1", "Web\\Sprint 24"]
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        assert!(match_path("Sprint 42", &sprints).is_err());
+        assert!(match_path("Sprint 3", &sprints).is_err());
+        assert!(match_path("Sprint 3", &sprints[..1]).is_err());
+        assert_eq!(match_path("Sprnt 24", &sprints).unwrap(), "Web\\Sprint 24");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_field_paths() {
+        let node = |name: &str, path: &str, children: Vec<Value>| {
+            json!({
+                "id": 1,
+                "identifier": name,
+                "name": name,
+                "path": path,
+                "structureType": "area",
+                "children": children,
+            })
+        };
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json()
+            .withf(|_, _, url, _, _| url.contains("/classificationnodes/areas?"))
+            .returning(move |_, _, _, _, _| {
+                let payments = node("Payments", "\\Web\\Area\\Payments", vec![]);
+                Ok(node("Web", "\\Web\\Area", vec![payments]).to_string())
+            });
+        api.expect_send_json()
+            .withf(|_, _, url, _, _| url.contains("/classificationnodes/iterations?"))
+            .returning(move |_, _, _, _, _| {
+                let sprint = node("Sprint 42", "\\Web\\Iteration\\Sprint 42", vec![]);
+                Ok(node("Web", "\\Web\\Iteration", vec![sprint]).to_string())
+            });
+
+        let mut fields = vec![
+            ("System.Title".to_string(), json!("payments")),
+            ("System.AreaPath".to_string(), json!("payments")),
+            ("System.IterationPath".to_string(), json!("sprint 42")),
+        ];
+        resolve_field_paths(&api, "contoso", "Web", &mut fields)
+            .await
+            .unwrap();
+        assert_eq!(fields[0].1, "payments");
+        assert_eq!(fields[1].1, "Web\\Payments");
+        assert_eq!(fields[2].1, "Web\\Sprint 42");
+
+        let (mut area_path, mut iteration_path) = (Some("Paymnts".to_string()), None);
+        resolve_area_and_iteration(&api, "contoso", "Web", &mut area_path, &mut iteration_path)
+            .await
+            .unwrap();
+        assert_eq!(area_path.as_deref(), Some("Web\\Payments"));
+        assert_eq!(iteration_path, None);
+
+        let mut fields = vec![("System.AreaPath".to_string(), json!("Marketing"))];
+        assert!(matches!(
+            resolve_field_paths(&api, "contoso", "Web", &mut fields).await,
+            Err(AzureError::UnresolvedPath { kind: "area", .. })
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Plan {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub plan_type: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub properties: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlanListResponse {
+    pub value: Vec<Plan>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlanMarker {
+    pub date: String,
+    pub label: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineIteration {
+    pub name: String,
+    pub path: String,
+    #[serde(default, rename = "startDate")]
+    pub start_date: Option<String>,
+    #[serde(default, rename = "finishDate")]
+    pub finish_date: Option<String>,
+    /// Work item rows, values ordered as the team `field_reference_names`
+    #[serde(default, rename = "workItems")]
+    pub work_items: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineTeam {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, rename = "backlog")]
+    pub backlog: Option<Value>,
+    #[serde(default, rename = "fieldReferenceNames")]
+    pub field_reference_names: Vec<String>,
+    #[serde(default)]
+    pub iterations: Vec<TimelineIteration>,
+    #[serde(default, rename = "status")]
+    pub status: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryTimeline {
+    #[serde(rename = "startDate")]
+    pub start_date: String,
+    #[serde(rename = "endDate")]
+    pub end_date: String,
+    #[serde(default)]
+    pub teams: Vec<TimelineTeam>,
+}
+
+/// List the plans (Delivery Plans) of a project
+pub async fn list_plans(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+) -> Result<Vec<Plan>, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/work/plans?api-version=7.1
+    let response: PlanListResponse = client
+        .request(
+            organization,
+            project,
+            Method::GET,
+            "work/plans?api-version=7.1",
+            None::<&String>,
+        )
+        .await?;
+    Ok(response.value)
+}
+
+/// Get a plan with its properties (team backlog mappings, markers, criteria)
+pub async fn get_plan(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    plan_id: &str,
+) -> Result<Plan, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/work/plans/{id}?api-version=7.1
+    let path = format!("work/plans/{}?api-version=7.1", plan_id);
+    client
+        .request(organization, project, Method::GET, &path, None::<&String>)
+        .await
+}
+
+/// Get the delivery timeline of a plan between two dates (YYYY-MM-DD)
+pub async fn get_delivery_timeline(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    plan_id: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<DeliveryTimeline, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/_apis/work/plans/{id}/deliverytimeline?startDate={start}&endDate={end}&api-version=7.1
+    let path = format!(
+        "work/plans/{}/deliverytimeline?startDate={}&endDate={}&api-version=7.1",
+        plan_id, start_date, end_date
+    );
+    client
+        .request(organization, project, Method::GET, &path, None::<&String>)
+        .await
+}
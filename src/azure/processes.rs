@@ -0,0 +1,54 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Process {
+    #[serde(rename = "typeId")]
+    pub type_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, rename = "parentProcessTypeId")]
+    pub parent_process_type_id: Option<String>,
+    #[serde(default, rename = "referenceName")]
+    pub reference_name: Option<String>,
+    #[serde(default, rename = "customizationType")]
+    pub customization_type: Option<String>,
+    #[serde(default, rename = "isDefault")]
+    pub is_default: bool,
+    #[serde(default, rename = "isEnabled")]
+    pub is_enabled: Option<bool>,
+    #[serde(default)]
+    pub projects: Option<Vec<ProcessProject>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessProject {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessListResponse {
+    pub value: Vec<Process>,
+}
+
+/// List all processes (system and inherited) in an organization
+pub async fn list_processes(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    include_projects: bool,
+) -> Result<Vec<Process>, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/work/processes?$expand=projects&api-version=7.1
+    let path = if include_projects {
+        "work/processes?$expand=projects&api-version=7.1"
+    } else {
+        "work/processes?api-version=7.1"
+    };
+    let response: ProcessListResponse = client
+        .org_request(organization, Method::GET, path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
@@ -21,7 +22,7 @@ pub struct ProjectListResponse {
 
 /// List all projects in an organization
 pub async fn list_projects(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
 ) -> Result<Vec<Project>, AzureError> {
     let path = "projects?api-version=7.1";
@@ -30,3 +31,128 @@ pub async fn list_projects(
         .await?;
     Ok(response.value)
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamReference {
+    pub id: String,
+    pub name: String,
+}
+
+/// Detailed project information (used in get operations)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectDetail {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub visibility: Option<String>,
+    #[serde(default, rename = "lastUpdateTime")]
+    pub last_update_time: Option<String>,
+    #[serde(default, rename = "defaultTeam")]
+    pub default_team: Option<TeamReference>,
+    /// processTemplate (templateName, templateTypeId) and versioncontrol (sourceControlType)
+    #[serde(default)]
+    pub capabilities: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectProperty {
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectPropertyListResponse {
+    pub value: Vec<ProjectProperty>,
+}
+
+/// Get a single project, including its capabilities (process template, version control)
+pub async fn get_project(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+) -> Result<ProjectDetail, AzureError> {
+    let path = format!(
+        "projects/{}?includeCapabilities=true&api-version=7.1",
+        project
+    );
+    client
+        .org_request(organization, Method::GET, &path, None::<&String>)
+        .await
+}
+
+/// Get the properties of a project
+pub async fn get_project_properties(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project_id: &str,
+) -> Result<Vec<ProjectProperty>, AzureError> {
+    let path = format!(
+        "projects/{}/properties?api-version=7.1-preview.1",
+        project_id
+    );
+    let response: ProjectPropertyListResponse = client
+        .org_request(organization, Method::GET, &path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperationReference {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default, rename = "resultMessage")]
+    pub result_message: Option<String>,
+}
+
+impl OperationReference {
+    /// Whether the operation reached a final status
+    pub fn is_completed(&self) -> bool {
+        matches!(self.status.as_str(), "succeeded" | "failed" | "cancelled")
+    }
+}
+
+/// Queue the creation of a new project
+pub async fn create_project(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    name: &str,
+    description: Option<&str>,
+    visibility: &str,
+    process_type_id: &str,
+    source_control_type: &str,
+) -> Result<OperationReference, AzureError> {
+    let body = serde_json::json!({
+        "name": name,
+        "description": description.unwrap_or_default(),
+        "visibility": visibility,
+        "capabilities": {
+            "versioncontrol": { "sourceControlType": source_control_type },
+            "processTemplate": { "templateTypeId": process_type_id },
+        },
+    });
+    client
+        .org_request(
+            organization,
+            Method::POST,
+            "projects?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
+
+/// Get the status of a long running operation
+pub async fn get_operation(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    operation_id: &str,
+) -> Result<OperationReference, AzureError> {
+    let path = format!("operations/{}?api-version=7.1", operation_id);
+    client
+        .org_request(organization, Method::GET, &path, None::<&String>)
+        .await
+}
@@ -0,0 +1,163 @@
+use reqwest::header::HeaderMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Below this fraction of the remaining Azure DevOps quota, requests wait for its reset
+const LOW_QUOTA_FRACTION: f64 = 0.1;
+
+/// Longest pause applied from the rate limit headers
+const MAX_PAUSE: Duration = Duration::from_secs(30);
+
+/// Client side rate limit of the outgoing requests
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second, 0 disables the limit
+    pub requests_per_second: f64,
+    /// Requests that can be sent at once after an idle period
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10.0,
+            burst: 20,
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set when Azure DevOps reports the quota is almost exhausted
+    paused_until: Option<Instant>,
+}
+
+/// Token bucket throttling the requests to the configured rate, slowed down further
+/// when the `X-RateLimit-*` headers report the Azure DevOps quota running low
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                tokens: config.burst as f64,
+                refilled_at: Instant::now(),
+                paused_until: None,
+            }),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Wait until a request can be sent
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take a token, or return how long to wait for one
+    fn try_acquire(&self, now: Instant) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        if let Some(until) = bucket.paused_until {
+            if until > now {
+                return Some(until - now);
+            }
+            bucket.paused_until = None;
+        }
+
+        let rate = self.config.requests_per_second;
+        if rate <= 0.0 {
+            return None;
+        }
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(self.config.burst.max(1) as f64);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+    }
+
+    /// Slow down according to the rate limit headers of a response: honor the delay
+    /// Azure DevOps applied, and wait for the quota reset when it is almost exhausted
+    pub fn observe(&self, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+        let mut pause = header("x-ratelimit-delay").map(Duration::from_secs_f64);
+
+        if let (Some(remaining), Some(limit)) =
+            (header("x-ratelimit-remaining"), header("x-ratelimit-limit"))
+            && limit > 0.0
+            && remaining / limit < LOW_QUOTA_FRACTION
+        {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64();
+            let until_reset = header("x-ratelimit-reset")
+                .map(|reset| Duration::from_secs_f64((reset - now).max(0.0)))
+                .unwrap_or(Duration::from_secs(1));
+            tracing::warn!(
+                "Azure DevOps rate limit almost exhausted ({}/{} remaining), slowing down",
+                remaining,
+                limit
+            );
+            pause = pause.max(Some(until_reset));
+        }
+
+        if let Some(pause) = pause.filter(|pause| !pause.is_zero()) {
+            let until = Instant::now() + pause.min(MAX_PAUSE);
+            let mut bucket = self.bucket.lock().unwrap();
+            bucket.paused_until = bucket.paused_until.max(Some(until));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: 2.0,
+            burst: 2,
+        });
+        let now = Instant::now();
+        assert_eq!(limiter.try_acquire(now), None);
+        assert_eq!(limiter.try_acquire(now), None);
+        assert_eq!(limiter.try_acquire(now), Some(Duration::from_millis(500)));
+        assert_eq!(limiter.try_acquire(now + Duration::from_millis(500)), None);
+    }
+
+    #[test]
+    fn test_observe_low_quota() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: 0.0,
+            burst: 1,
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "50".parse().unwrap());
+        headers.insert("x-ratelimit-limit", "200".parse().unwrap());
+        limiter.observe(&headers);
+        assert_eq!(limiter.try_acquire(Instant::now()), None);
+
+        headers.insert("x-ratelimit-remaining", "5".parse().unwrap());
+        limiter.observe(&headers);
+        assert!(limiter.try_acquire(Instant::now()).is_some());
+    }
+}
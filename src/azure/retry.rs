@@ -0,0 +1,94 @@
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Retry policy for throttled (429) and transiently failing (5xx) requests
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Attempts including the first one, 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub base_delay: Duration,
+    /// Upper bound of a single delay, also applied to server provided delays
+    pub max_delay: Duration,
+    /// Random fraction of the delay added to spread out concurrent retries
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before retrying after `attempt` failed attempts, preferring the server delay
+    pub fn delay(&self, attempt: u32, server_delay: Option<Duration>) -> Duration {
+        let delay = server_delay
+            .unwrap_or_else(|| {
+                self.base_delay
+                    .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            })
+            .min(self.max_delay);
+        delay + delay.mul_f64(self.jitter.max(0.0) * fastrand::f64())
+    }
+}
+
+/// Whether a response status is worth retrying
+pub fn is_retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Delay requested by the server: `Retry-After` seconds, or the time until the
+/// `X-RateLimit-Reset` Unix timestamp
+pub fn server_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+    if let Some(seconds) = header(RETRY_AFTER.as_str()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    let reset = header("x-ratelimit-reset")?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let config = RetryConfig {
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(config.delay(1, None), Duration::from_secs(1));
+        assert_eq!(config.delay(3, None), Duration::from_secs(4));
+        assert_eq!(config.delay(10, None), Duration::from_secs(60));
+        assert_eq!(
+            config.delay(1, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn test_server_delay() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(server_delay(&headers), None);
+        headers.insert("x-ratelimit-reset", "0".parse().unwrap());
+        assert_eq!(server_delay(&headers), Some(Duration::ZERO));
+        headers.insert(RETRY_AFTER, "30".parse().unwrap());
+        assert_eq!(server_delay(&headers), Some(Duration::from_secs(30)));
+    }
+}
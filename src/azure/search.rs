@@ -0,0 +1,77 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct WorkItemSearchFilters {
+    pub projects: Vec<String>,
+    pub work_item_types: Vec<String>,
+    pub states: Vec<String>,
+    pub assigned_to: Vec<String>,
+    pub area_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemHit {
+    #[serde(rename = "fieldReferenceName")]
+    pub field_reference_name: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemSearchResult {
+    /// Field values keyed by lowercase reference name (e.g. "system.title")
+    pub fields: HashMap<String, Value>,
+    #[serde(default)]
+    pub hits: Vec<WorkItemHit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemSearchResponse {
+    pub count: u32,
+    pub results: Vec<WorkItemSearchResult>,
+}
+
+/// Relevance ranked full-text search over work items
+pub async fn search_work_items(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    search_text: &str,
+    filters: &WorkItemSearchFilters,
+    top: u32,
+    skip: u32,
+) -> Result<WorkItemSearchResponse, AzureError> {
+    // API: https://almsearch.dev.azure.com/{organization}/_apis/search/workitemsearchresults?api-version=7.1
+    let mut filter_map = serde_json::Map::new();
+    for (name, values) in [
+        ("System.TeamProject", &filters.projects),
+        ("System.WorkItemType", &filters.work_item_types),
+        ("System.State", &filters.states),
+        ("System.AssignedTo", &filters.assigned_to),
+        ("System.AreaPath", &filters.area_paths),
+    ] {
+        if !values.is_empty() {
+            filter_map.insert(name.to_string(), serde_json::json!(values));
+        }
+    }
+
+    let body = serde_json::json!({
+        "searchText": search_text,
+        "$top": top,
+        "$skip": skip,
+        "filters": filter_map,
+        "includeFacets": false,
+    });
+    client
+        .search_request(
+            organization,
+            Method::POST,
+            "search/workitemsearchresults?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
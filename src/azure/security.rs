@@ -0,0 +1,301 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::classification_nodes;
+use crate::azure::client::AzureError;
+use crate::azure::credentials;
+use crate::azure::projects;
+use futures::future::try_join_all;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Security namespace of the project permissions
+const PROJECT_NAMESPACE: &str = "52d39943-cb85-4d7f-8fa8-c6baac873819";
+/// Security namespace of the area path permissions (CSS)
+const AREA_NAMESPACE: &str = "83e28ad4-2d72-4ceb-97b0-c7726d5502c3";
+/// Security namespace of the iteration path permissions
+const ITERATION_NAMESPACE: &str = "bf7bfa03-b2b7-47db-8113-fa2e002cc5b1";
+
+/// Permission of a security namespace, a bit of its permission mask
+struct Permission {
+    bit: u32,
+    name: &'static str,
+    description: &'static str,
+}
+
+const PROJECT_PERMISSIONS: &[Permission] = &[
+    Permission {
+        bit: 1,
+        name: "view_project",
+        description: "View project-level information",
+    },
+    Permission {
+        bit: 2,
+        name: "edit_project",
+        description: "Edit project-level information",
+    },
+    Permission {
+        bit: 8192,
+        name: "delete_work_items",
+        description: "Delete and restore work items",
+    },
+    Permission {
+        bit: 16384,
+        name: "move_work_items",
+        description: "Move work items out of this project",
+    },
+    Permission {
+        bit: 32768,
+        name: "permanently_delete_work_items",
+        description: "Permanently delete work items",
+    },
+    Permission {
+        bit: 1048576,
+        name: "bypass_rules",
+        description: "Bypass rules on work item updates",
+    },
+    Permission {
+        bit: 2097152,
+        name: "suppress_notifications",
+        description: "Suppress notifications for work item updates",
+    },
+];
+
+const AREA_PERMISSIONS: &[Permission] = &[
+    Permission {
+        bit: 1,
+        name: "view_area",
+        description: "View permissions for this node",
+    },
+    Permission {
+        bit: 2,
+        name: "edit_area",
+        description: "Edit this node",
+    },
+    Permission {
+        bit: 4,
+        name: "create_child_areas",
+        description: "Create child nodes",
+    },
+    Permission {
+        bit: 8,
+        name: "delete_area",
+        description: "Delete this node",
+    },
+    Permission {
+        bit: 16,
+        name: "view_work_items",
+        description: "View work items in this node",
+    },
+    Permission {
+        bit: 32,
+        name: "edit_work_items",
+        description: "Edit work items in this node",
+    },
+    Permission {
+        bit: 512,
+        name: "comment_work_items",
+        description: "Edit work item comments in this node",
+    },
+];
+
+const ITERATION_PERMISSIONS: &[Permission] = &[
+    Permission {
+        bit: 1,
+        name: "view_iteration",
+        description: "View permissions for this node",
+    },
+    Permission {
+        bit: 2,
+        name: "edit_iteration",
+        description: "Edit this node",
+    },
+    Permission {
+        bit: 4,
+        name: "create_child_iterations",
+        description: "Create child nodes",
+    },
+    Permission {
+        bit: 8,
+        name: "delete_iteration",
+        description: "Delete this node",
+    },
+];
+
+#[derive(Debug, Serialize)]
+pub struct PermissionResult {
+    /// "project", "area" or "iteration"
+    pub scope: &'static str,
+    /// Project name or classification path the permission was evaluated on
+    pub target: String,
+    pub permission: &'static str,
+    pub description: &'static str,
+    pub allowed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PermissionReport {
+    pub organization: String,
+    pub project: String,
+    pub authenticated_user: Option<String>,
+    /// Names of the permissions granted
+    pub can: Vec<&'static str>,
+    /// Names of the permissions denied or not set
+    pub cannot: Vec<&'static str>,
+    pub permissions: Vec<PermissionResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EvaluationBatch {
+    evaluations: Vec<Evaluation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Evaluation {
+    #[serde(default)]
+    value: bool,
+}
+
+/// Token of a classification node, the tokens of its ancestors from the root joined
+/// with `:`
+fn node_token(identifiers: &[String]) -> String {
+    identifiers
+        .iter()
+        .map(|identifier| format!("vstfs:///Classification/Node/{}", identifier))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Token of a classification path, built from the identifiers of the nodes along it
+async fn path_token(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    structure_group: &str,
+    path: &str,
+) -> Result<String, AzureError> {
+    let segments: Vec<&str> = path
+        .trim_matches('\\')
+        .split('\\')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    // The root node is the one of the project, whatever the path starts with
+    let prefixes: Vec<String> = (1..=segments.len().max(1))
+        .map(|end| {
+            std::iter::once(project)
+                .chain(segments.iter().skip(1).take(end - 1).copied())
+                .collect::<Vec<_>>()
+                .join("\\")
+        })
+        .collect();
+    let nodes = try_join_all(prefixes.iter().map(|prefix| {
+        classification_nodes::get_node(client, organization, project, structure_group, prefix)
+    }))
+    .await?;
+    let identifiers: Vec<String> = nodes.into_iter().map(|node| node.identifier).collect();
+    Ok(node_token(&identifiers))
+}
+
+/// Evaluate the permissions the authenticated identity has on a project and on one of
+/// its area paths (the root one when not given) and iteration paths, as Azure DevOps
+/// resolves them from the groups, inheritance and explicit deny
+pub async fn check_permissions(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    area_path: Option<&str>,
+    iteration_path: Option<&str>,
+) -> Result<PermissionReport, AzureError> {
+    let project_id = projects::get_project(client, organization, project)
+        .await?
+        .id;
+    let area_path = area_path.unwrap_or(project);
+
+    let mut targets = vec![(
+        "project",
+        project.to_string(),
+        PROJECT_NAMESPACE,
+        format!(
+            "$PROJECT:vstfs:///Classification/TeamProject/{}",
+            project_id
+        ),
+        PROJECT_PERMISSIONS,
+    )];
+    let token = path_token(client, organization, project, "areas", area_path).await?;
+    targets.push((
+        "area",
+        area_path.to_string(),
+        AREA_NAMESPACE,
+        token,
+        AREA_PERMISSIONS,
+    ));
+    if let Some(iteration_path) = iteration_path {
+        let token = path_token(client, organization, project, "iterations", iteration_path).await?;
+        targets.push((
+            "iteration",
+            iteration_path.to_string(),
+            ITERATION_NAMESPACE,
+            token,
+            ITERATION_PERMISSIONS,
+        ));
+    }
+
+    let evaluations: Vec<_> = targets
+        .iter()
+        .flat_map(|(_, _, namespace, token, permissions)| {
+            permissions.iter().map(move |permission| {
+                json!({
+                    "securityNamespaceId": namespace,
+                    "token": token,
+                    "permissions": permission.bit,
+                })
+            })
+        })
+        .collect();
+    let body = json!({
+        "evaluations": evaluations,
+        "alwaysAllowAdministrators": false,
+    });
+    // API: https://dev.azure.com/{organization}/_apis/security/permissionevaluationbatch?api-version=7.1
+    // The permissions are evaluated for the identity the request is authenticated as
+    let batch: EvaluationBatch = client
+        .org_request(
+            organization,
+            Method::POST,
+            "security/permissionevaluationbatch?api-version=7.1",
+            Some(&body),
+        )
+        .await?;
+
+    let mut results = batch.evaluations.into_iter();
+    let mut permissions = Vec::new();
+    for (scope, target, _, _, definitions) in &targets {
+        for permission in definitions.iter() {
+            permissions.push(PermissionResult {
+                scope,
+                target: target.clone(),
+                permission: permission.name,
+                description: permission.description,
+                allowed: results.next().is_some_and(|evaluation| evaluation.value),
+            });
+        }
+    }
+
+    Ok(PermissionReport {
+        organization: organization.to_string(),
+        project: project.to_string(),
+        authenticated_user: credentials::authenticated_user(client, organization)
+            .await
+            .ok(),
+        can: permissions
+            .iter()
+            .filter(|result| result.allowed)
+            .map(|result| result.permission)
+            .collect(),
+        cannot: permissions
+            .iter()
+            .filter(|result| !result.allowed)
+            .map(|result| result.permission)
+            .collect(),
+        permissions,
+    })
+}
@@ -0,0 +1,142 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Publisher of Boards events
+pub const TFS_PUBLISHER: &str = "tfs";
+/// Consumer and action posting the event to a URL
+pub const WEBHOOKS_CONSUMER: &str = "webHooks";
+pub const HTTP_REQUEST_ACTION: &str = "httpRequest";
+
+/// Header carrying the shared secret of the service hooks posting to the server
+pub const SECRET_HEADER: &str = "X-AzDO-Hook-Secret";
+
+/// Endpoint of the server receiving the service hook events, and the secret they carry
+#[derive(Debug, Clone)]
+pub struct ServiceHookReceiver {
+    pub url: String,
+    pub secret: String,
+}
+
+impl ServiceHookReceiver {
+    /// Inputs of a webhook posting the events to the receiver with its secret
+    pub fn consumer_inputs(&self) -> ConsumerInputs {
+        ConsumerInputs {
+            url: self.url.clone(),
+            http_headers: Some(format!("{}:{}", SECRET_HEADER, self.secret)),
+            resource_details_to_send: None,
+        }
+    }
+}
+
+/// Inputs of the "tfs" publisher, restricting the events that trigger the hook
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PublisherInputs {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "areaPath", skip_serializing_if = "Option::is_none")]
+    pub area_path: Option<String>,
+    #[serde(rename = "workItemType", skip_serializing_if = "Option::is_none")]
+    pub work_item_type: Option<String>,
+    /// Only for "workitem.updated": trigger only when this field changes
+    #[serde(rename = "changedFields", skip_serializing_if = "Option::is_none")]
+    pub changed_fields: Option<String>,
+}
+
+/// Inputs of the "webHooks" consumer
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ConsumerInputs {
+    pub url: String,
+    /// Extra headers, one "Name:Value" per line
+    #[serde(rename = "httpHeaders", skip_serializing_if = "Option::is_none")]
+    pub http_headers: Option<String>,
+    #[serde(
+        rename = "resourceDetailsToSend",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_details_to_send: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceHookSubscription {
+    pub id: String,
+    #[serde(rename = "publisherId")]
+    pub publisher_id: String,
+    #[serde(rename = "eventType")]
+    pub event_type: String,
+    #[serde(rename = "consumerId")]
+    pub consumer_id: String,
+    #[serde(rename = "consumerActionId")]
+    pub consumer_action_id: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, rename = "publisherInputs")]
+    pub publisher_inputs: Option<Value>,
+    #[serde(default, rename = "consumerInputs")]
+    pub consumer_inputs: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceHookSubscriptionListResponse {
+    pub value: Vec<ServiceHookSubscription>,
+}
+
+/// List service hook subscriptions of an organization
+pub async fn list_subscriptions(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+) -> Result<Vec<ServiceHookSubscription>, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/hooks/subscriptions?api-version=7.1
+    let response: ServiceHookSubscriptionListResponse = client
+        .org_request(
+            organization,
+            Method::GET,
+            "hooks/subscriptions?api-version=7.1",
+            None::<&String>,
+        )
+        .await?;
+    Ok(response.value)
+}
+
+/// Create a webhook subscription posting Boards events to a URL
+pub async fn create_webhook_subscription(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    event_type: &str,
+    publisher_inputs: &PublisherInputs,
+    consumer_inputs: &ConsumerInputs,
+) -> Result<ServiceHookSubscription, AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/hooks/subscriptions?api-version=7.1
+    let body = serde_json::json!({
+        "publisherId": TFS_PUBLISHER,
+        "eventType": event_type,
+        "resourceVersion": "1.0",
+        "consumerId": WEBHOOKS_CONSUMER,
+        "consumerActionId": HTTP_REQUEST_ACTION,
+        "publisherInputs": publisher_inputs,
+        "consumerInputs": consumer_inputs,
+    });
+    client
+        .org_request(
+            organization,
+            Method::POST,
+            "hooks/subscriptions?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
+
+/// Delete a service hook subscription
+pub async fn delete_subscription(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    subscription_id: &str,
+) -> Result<(), AzureError> {
+    // API: https://dev.azure.com/{organization}/_apis/hooks/subscriptions/{id}?api-version=7.1
+    let path = format!("hooks/subscriptions/{}?api-version=7.1", subscription_id);
+    client
+        .org_request(organization, Method::DELETE, &path, None::<&String>)
+        .await
+}
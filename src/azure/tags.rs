@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,7 +20,7 @@ pub struct TagListResponse {
 
 /// Get all tags in a project
 pub async fn list_tags(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
 ) -> Result<Vec<TagDefinition>, AzureError> {
@@ -1,4 +1,5 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
@@ -21,7 +22,7 @@ struct TeamMembersResponse {
     value: Vec<TeamMember>,
 }
 
-impl AzureDevOpsClient {
+impl dyn AzureBoardsApi + '_ {
     pub async fn list_team_members(
         &self,
         organization: &str,
@@ -39,3 +40,93 @@ impl AzureDevOpsClient {
         Ok(response.value)
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamSettings {
+    #[serde(rename = "workingDays", default)]
+    pub working_days: Vec<String>,
+    #[serde(rename = "bugsBehavior", default)]
+    pub bugs_behavior: Option<String>,
+}
+
+/// Get the team settings (working days, bugs behavior, ...)
+pub async fn get_team_settings(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+) -> Result<TeamSettings, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings?api-version=7.1
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            "work/teamsettings?api-version=7.1",
+            None::<&String>,
+        )
+        .await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamFieldValue {
+    pub value: String,
+    #[serde(rename = "includeChildren", default)]
+    pub include_children: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamFieldValues {
+    #[serde(rename = "defaultValue")]
+    pub default_value: String,
+    #[serde(default)]
+    pub field: Option<serde_json::Value>,
+    pub values: Vec<TeamFieldValue>,
+}
+
+/// Get the team field values (the area paths owned by the team, and the default one)
+pub async fn get_team_field_values(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+) -> Result<TeamFieldValues, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/teamfieldvalues?api-version=7.1
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            "work/teamsettings/teamfieldvalues?api-version=7.1",
+            None::<&String>,
+        )
+        .await
+}
+
+/// Replace the team field values (area paths owned by the team and the default one)
+pub async fn update_team_field_values(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    default_value: &str,
+    values: &[TeamFieldValue],
+) -> Result<TeamFieldValues, AzureError> {
+    // API: PATCH https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/teamfieldvalues?api-version=7.1
+    let body = serde_json::json!({
+        "defaultValue": default_value,
+        "values": values,
+    });
+    client
+        .team_request(
+            organization,
+            project,
+            Method::PATCH,
+            team_id,
+            "work/teamsettings/teamfieldvalues?api-version=7.1",
+            Some(&body),
+        )
+        .await
+}
@@ -0,0 +1,468 @@
+use crate::azure::client::AzureError;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::{CONTENT_TYPE, HeaderName, HeaderValue};
+use reqwest::{Request, Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Response headers recorded, the ones the client reads
+const RECORDED_HEADERS: &[&str] = &["content-type", "etag", "x-ms-continuationtoken"];
+
+/// Body keys whose values are redacted from the recordings
+const SECRET_KEYS: &[&str] = &[
+    "token",
+    "accesstoken",
+    "access_token",
+    "refreshtoken",
+    "refresh_token",
+    "authorization",
+    "cookie",
+];
+
+static RE_EMAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+
+/// Identity as text, `Display Name <email>`
+static RE_NAMED_EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"([^<>,;:\x22\n]*[^<>,;:\x22\s])\s*<([A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,})>",
+    )
+    .unwrap()
+});
+
+/// Keys of the identity objects telling that their `displayName` is the one of a person
+const IDENTITY_KEYS: &[&str] = &["uniqueName", "descriptor", "imageUrl"];
+
+/// Sends a request to Azure DevOps, within the rate limit, retries and authentication of
+/// the client
+pub type Network<'a> =
+    Box<dyn FnOnce(Request) -> BoxFuture<'a, Result<Response, AzureError>> + Send + 'a>;
+
+/// Way the requests of the client reach Azure DevOps, or are answered without it
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Response to a request, from the network or not
+    async fn send(&self, request: Request, network: Network<'_>) -> Result<Response, AzureError>;
+
+    /// Whether the requests are answered without Azure DevOps, the credentials being unused
+    fn is_offline(&self) -> bool {
+        true
+    }
+}
+
+/// Response as received from Azure DevOps, for the transports answering the requests
+pub(crate) fn build_response<'a>(
+    url: &Url,
+    status: u16,
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    body: impl Into<reqwest::Body>,
+) -> Response {
+    let mut builder = http::Response::builder().status(status).url(url.clone());
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    Response::from(builder.body(body.into()).unwrap())
+}
+
+/// Request and response exchanged with Azure DevOps, sanitized, one line of a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON body, or the text of the other bodies
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl Interaction {
+    fn is_json(&self) -> bool {
+        self.headers
+            .get("content-type")
+            .is_some_and(|content_type| content_type.contains("json"))
+    }
+}
+
+/// Body as recorded, JSON when it parses, else its text
+fn body_value(bytes: &[u8]) -> Value {
+    if bytes.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+/// Replaces the email addresses, display names and secrets of the recordings, each
+/// address by the same `userN@example.com` and each name by the same `User N`
+/// throughout a recording
+#[derive(Debug, Default)]
+struct Sanitizer {
+    emails: HashMap<String, String>,
+    names: HashMap<String, String>,
+}
+
+impl Sanitizer {
+    fn name(&mut self, name: &str) -> String {
+        if name.is_empty() || self.names.values().any(|known| known == name) {
+            return name.to_string();
+        }
+        let count = self.names.len();
+        self.names
+            .entry(name.to_string())
+            .or_insert_with(|| format!("User {}", count + 1))
+            .clone()
+    }
+
+    fn text(&mut self, text: &str) -> String {
+        let text = RE_NAMED_EMAIL.replace_all(text, |captures: &regex::Captures| {
+            format!("{} <{}>", self.name(&captures[1]), &captures[2])
+        });
+        // The names of the identities met so far, wherever they appear
+        let mut text = text.into_owned();
+        let mut names: Vec<(&String, &String)> = self.names.iter().collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        for (name, replacement) in names {
+            if name != replacement {
+                text = text.replace(name.as_str(), replacement);
+            }
+        }
+        RE_EMAIL
+            .replace_all(&text, |captures: &regex::Captures| {
+                let email = captures[0].to_lowercase();
+                if email.ends_with("@example.com") {
+                    return email;
+                }
+                let count = self.emails.len();
+                self.emails
+                    .entry(email)
+                    .or_insert_with(|| format!("user{}@example.com", count + 1))
+                    .clone()
+            })
+            .into_owned()
+    }
+
+    /// Record the display names of the identities of a value, to be replaced wherever
+    /// they appear
+    fn collect_names(&mut self, value: &Value) {
+        match value {
+            Value::String(text) => {
+                for captures in RE_NAMED_EMAIL.captures_iter(text) {
+                    self.name(&captures[1]);
+                }
+            }
+            Value::Array(values) => values.iter().for_each(|value| self.collect_names(value)),
+            Value::Object(map) => {
+                if IDENTITY_KEYS.iter().any(|key| map.contains_key(*key))
+                    && let Some(Value::String(name)) = map.get("displayName")
+                {
+                    self.name(name);
+                }
+                map.values().for_each(|value| self.collect_names(value));
+            }
+            _ => {}
+        }
+    }
+
+    fn value(&mut self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.text(text),
+            Value::Array(values) => values.iter_mut().for_each(|value| self.value(value)),
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_lowercase();
+                    if key.contains("password")
+                        || key.contains("secret")
+                        || SECRET_KEYS.contains(&key.as_str())
+                    {
+                        *value = Value::String("REDACTED".to_string());
+                    } else {
+                        self.value(value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Sends the requests to Azure DevOps, appending the exchanges to a JSON Lines file
+/// without the request headers, with the email addresses and secrets replaced, to be
+/// replayed by [`Replay`]
+pub struct Recorder {
+    file: Mutex<File>,
+    sanitizer: Mutex<Sanitizer>,
+}
+
+impl Recorder {
+    /// Record to a file, replacing it
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: Mutex::new(File::create(path)?),
+            sanitizer: Mutex::default(),
+        })
+    }
+
+    fn record(&self, mut interaction: Interaction) {
+        {
+            let mut sanitizer = self.sanitizer.lock().unwrap();
+            if let Some(body) = &interaction.request_body {
+                sanitizer.collect_names(body);
+            }
+            sanitizer.collect_names(&interaction.body);
+            interaction.url = sanitizer.text(&interaction.url);
+            if let Some(body) = &mut interaction.request_body {
+                sanitizer.value(body);
+            }
+            sanitizer.value(&mut interaction.body);
+        }
+        let line = serde_json::to_string(&interaction).unwrap();
+        let mut file = self.file.lock().unwrap();
+        if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+            tracing::warn!(
+                "Failed to record {} {}: {}",
+                interaction.method,
+                interaction.url,
+                e
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for Recorder {
+    async fn send(&self, request: Request, network: Network<'_>) -> Result<Response, AzureError> {
+        let method = request.method().to_string();
+        let url = request.url().clone();
+        let request_body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(body_value);
+        let response = network(request).await?;
+
+        let status = response.status().as_u16();
+        let headers: Vec<(HeaderName, HeaderValue)> = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| RECORDED_HEADERS.contains(&name.as_str()))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let bytes = response.bytes().await?;
+        self.record(Interaction {
+            method,
+            url: url.to_string(),
+            request_body,
+            status,
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: body_value(&bytes),
+        });
+
+        let mut builder = http::Response::builder().status(status).url(url);
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        Ok(Response::from(builder.body(bytes).unwrap()))
+    }
+
+    fn is_offline(&self) -> bool {
+        false
+    }
+}
+
+/// Answers the requests from a recording of [`Recorder`], deterministically: the requests
+/// with the same method and URL get the responses recorded for them in turn, the last
+/// one being repeated, and the requests not recorded get a 404
+#[derive(Debug)]
+pub struct Replay {
+    interactions: Mutex<HashMap<(String, String), VecDeque<Interaction>>>,
+}
+
+impl Replay {
+    pub fn new(interactions: impl IntoIterator<Item = Interaction>) -> Self {
+        let mut by_request: HashMap<(String, String), VecDeque<Interaction>> = HashMap::new();
+        for interaction in interactions {
+            by_request
+                .entry((interaction.method.clone(), interaction.url.clone()))
+                .or_default()
+                .push_back(interaction);
+        }
+        Self {
+            interactions: Mutex::new(by_request),
+        }
+    }
+
+    /// Replay a recording, one interaction per line
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut interactions = Vec::new();
+        for (number, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let interaction = serde_json::from_str(&line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} line {}: {}", path.display(), number + 1, e),
+                )
+            })?;
+            interactions.push(interaction);
+        }
+        Ok(Self::new(interactions))
+    }
+
+    fn next(&self, method: &str, url: &str) -> Option<Interaction> {
+        let mut interactions = self.interactions.lock().unwrap();
+        let queue = interactions.get_mut(&(method.to_string(), url.to_string()))?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for Replay {
+    async fn send(&self, request: Request, _network: Network<'_>) -> Result<Response, AzureError> {
+        let method = request.method().as_str();
+        let url = request.url();
+        let Some(interaction) = self.next(method, url.as_str()) else {
+            tracing::warn!("No recorded response for {} {}", method, url);
+            let body = serde_json::json!({
+                "message": format!("No recorded response for {} {}", method, url),
+            });
+            return Ok(build_response(
+                url,
+                StatusCode::NOT_FOUND.as_u16(),
+                [(CONTENT_TYPE.as_str(), "application/json; charset=utf-8")],
+                body.to_string(),
+            ));
+        };
+        let body = match &interaction.body {
+            Value::Null => String::new(),
+            Value::String(text) if !interaction.is_json() => text.clone(),
+            body => body.to_string(),
+        };
+        Ok(build_response(
+            url,
+            interaction.status,
+            interaction
+                .headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+            body,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::client::AzureDevOpsClient;
+    use crate::azure::projects;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sanitizer() {
+        let mut sanitizer = Sanitizer::default();
+        let mut value = serde_json::json!({
+            "System.History": "Assigned by John Smith",
+            "System.CreatedBy": "John Smith <john@contoso.com>",
+            "System.AssignedTo": {"displayName": "Jane Doe", "uniqueName": "jane@contoso.com"},
+            "System.Title": "Sync with Jane Doe",
+        });
+        sanitizer.collect_names(&value);
+        sanitizer.value(&mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "System.History": "Assigned by User 2",
+                "System.CreatedBy": "User 2 <user2@example.com>",
+                "System.AssignedTo": {"displayName": "User 1", "uniqueName": "user1@example.com"},
+                "System.Title": "Sync with User 1",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let path =
+            std::env::temp_dir().join(format!("azdo-recording-{}.jsonl", uuid::Uuid::new_v4()));
+        let url = "https://dev.azure.com/contoso/_apis/projects?api-version=7.1";
+        let recorder = Recorder::create(&path).unwrap();
+        let request = Request::new(reqwest::Method::GET, Url::parse(url).unwrap());
+        let response = recorder
+            .send(
+                request,
+                Box::new(|request| {
+                    Box::pin(async move {
+                        let body = serde_json::json!({
+                            "value": [{
+                                "id": "1",
+                                "name": "Web",
+                                "description": "Owned by Jane Doe (jane@contoso.com)",
+                                "owner": {
+                                    "displayName": "Jane Doe",
+                                    "uniqueName": "jane@contoso.com",
+                                },
+                                "url": "https://dev.azure.com/contoso/_apis/projects/1",
+                                "state": "wellFormed",
+                                "accessToken": "secret",
+                            }],
+                        });
+                        Ok(build_response(
+                            request.url(),
+                            200,
+                            [("content-type", "application/json"), ("x-request-id", "1")],
+                            body.to_string(),
+                        ))
+                    })
+                }),
+            )
+            .await
+            .unwrap();
+        // The live response is untouched
+        assert!(response.text().await.unwrap().contains("Jane Doe"));
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        let interaction: Interaction = serde_json::from_str(recording.trim()).unwrap();
+        assert_eq!(interaction.url, url);
+        assert_eq!(
+            interaction.headers,
+            BTreeMap::from([("content-type".to_string(), "application/json".to_string())])
+        );
+        assert_eq!(
+            interaction.body["value"][0]["description"],
+            "Owned by User 1 (user1@example.com)"
+        );
+        assert_eq!(
+            interaction.body["value"][0]["owner"]["displayName"],
+            "User 1"
+        );
+        assert_eq!(interaction.body["value"][0]["accessToken"], "REDACTED");
+
+        let client =
+            AzureDevOpsClient::new().with_transport(Arc::new(Replay::open(&path).unwrap()));
+        let projects = projects::list_projects(&client, "contoso").await.unwrap();
+        assert_eq!(projects[0].name, "Web");
+        assert!(projects::list_projects(&client, "fabrikam").await.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
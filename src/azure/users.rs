@@ -0,0 +1,208 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::identities::{self, Identity};
+use once_cell::sync::Lazy;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Resolved profiles, keyed by organization and the identifier they were resolved from
+static PROFILE_CACHE: Lazy<Mutex<HashMap<(String, String), UserProfile>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub display_name: String,
+    pub email: Option<String>,
+    pub unique_name: Option<String>,
+    pub kind: String,
+}
+
+impl From<&Identity> for UserProfile {
+    fn from(identity: &Identity) -> Self {
+        Self {
+            display_name: identity.display_name().to_string(),
+            email: identity.property("Mail").map(String::from),
+            unique_name: identity.property("Account").map(String::from),
+            kind: if identity.is_container {
+                "group".to_string()
+            } else {
+                "user".to_string()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphSubject {
+    #[serde(default, rename = "displayName")]
+    pub display_name: Option<String>,
+    #[serde(default, rename = "mailAddress")]
+    pub mail_address: Option<String>,
+    #[serde(default, rename = "principalName")]
+    pub principal_name: Option<String>,
+    #[serde(default, rename = "subjectKind")]
+    pub subject_kind: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphSubjectLookupResponse {
+    pub value: HashMap<String, GraphSubject>,
+}
+
+/// Kind of identifier accepted by `resolve_users`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdentifierKind {
+    /// Identity GUID
+    Id,
+    /// Identity descriptor, e.g. "Microsoft.IdentityModel.Claims.ClaimsIdentity;..."
+    IdentityDescriptor,
+    /// Graph subject descriptor, e.g. "aad.xxx" or "vssgp.xxx"
+    SubjectDescriptor,
+}
+
+fn identifier_kind(value: &str) -> IdentifierKind {
+    let is_guid = value.len() == 36
+        && value.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    if is_guid {
+        IdentifierKind::Id
+    } else if value.contains(';') {
+        IdentifierKind::IdentityDescriptor
+    } else {
+        IdentifierKind::SubjectDescriptor
+    }
+}
+
+/// Look up Graph subjects by subject descriptor
+pub async fn lookup_subjects(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    descriptors: &[&str],
+) -> Result<HashMap<String, GraphSubject>, AzureError> {
+    // API: https://vssps.dev.azure.com/{organization}/_apis/graph/subjectlookup?api-version=7.1-preview.1
+    let body = serde_json::json!({
+        "lookupKeys": descriptors
+            .iter()
+            .map(|d| serde_json::json!({ "descriptor": d }))
+            .collect::<Vec<_>>(),
+    });
+    let response: GraphSubjectLookupResponse = client
+        .org_vssps_request(
+            organization,
+            Method::POST,
+            "graph/subjectlookup?api-version=7.1-preview.1",
+            Some(&body),
+        )
+        .await?;
+    Ok(response.value)
+}
+
+/// Resolve identity GUIDs, identity descriptors and subject descriptors to profiles.
+/// Results are cached per organization, unknown identifiers are omitted from the result.
+pub async fn resolve_users(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    identifiers: &[String],
+) -> Result<HashMap<String, UserProfile>, AzureError> {
+    let mut resolved = HashMap::new();
+    let mut ids = Vec::new();
+    let mut identity_descriptors = Vec::new();
+    let mut subject_descriptors = Vec::new();
+
+    {
+        let cache = PROFILE_CACHE.lock().unwrap();
+        for identifier in identifiers {
+            let key = (organization.to_string(), identifier.clone());
+            if let Some(profile) = cache.get(&key) {
+                resolved.insert(identifier.clone(), profile.clone());
+                continue;
+            }
+            match identifier_kind(identifier) {
+                IdentifierKind::Id => ids.push(identifier.as_str()),
+                IdentifierKind::IdentityDescriptor => {
+                    identity_descriptors.push(identifier.as_str())
+                }
+                IdentifierKind::SubjectDescriptor => subject_descriptors.push(identifier.as_str()),
+            }
+        }
+    }
+
+    if !ids.is_empty() {
+        for identity in identities::get_identities_by_ids(client, organization, &ids).await? {
+            if let Some(id) = ids.iter().find(|id| id.eq_ignore_ascii_case(&identity.id)) {
+                resolved.insert(id.to_string(), UserProfile::from(&identity));
+            }
+        }
+    }
+
+    if !identity_descriptors.is_empty() {
+        let found =
+            identities::get_identities_by_descriptors(client, organization, &identity_descriptors)
+                .await?;
+        for identity in found {
+            if let Some(descriptor) = identity_descriptors
+                .iter()
+                .find(|d| identity.descriptor.as_deref() == Some(**d))
+            {
+                resolved.insert(descriptor.to_string(), UserProfile::from(&identity));
+            }
+        }
+    }
+
+    if !subject_descriptors.is_empty() {
+        for (descriptor, subject) in
+            lookup_subjects(client, organization, &subject_descriptors).await?
+        {
+            let kind = match subject.subject_kind.as_deref() {
+                Some("group") => "group",
+                _ => "user",
+            };
+            resolved.insert(
+                descriptor,
+                UserProfile {
+                    display_name: subject.display_name.unwrap_or_default(),
+                    email: subject.mail_address.filter(|m| !m.is_empty()),
+                    unique_name: subject.principal_name,
+                    kind: kind.to_string(),
+                },
+            );
+        }
+    }
+
+    let mut cache = PROFILE_CACHE.lock().unwrap();
+    for (identifier, profile) in &resolved {
+        cache.insert(
+            (organization.to_string(), identifier.clone()),
+            profile.clone(),
+        );
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_kind() {
+        assert_eq!(
+            identifier_kind("6f8a1c2e-3b4d-4e5f-9a0b-1c2d3e4f5a6b"),
+            IdentifierKind::Id
+        );
+        assert_eq!(
+            identifier_kind(
+                "Microsoft.IdentityModel.Claims.ClaimsIdentity;tenant\\user@example.com"
+            ),
+            IdentifierKind::IdentityDescriptor
+        );
+        assert_eq!(
+            identifier_kind("aad.ZmYxMjM0NTYtNzg5MC0xMjM0"),
+            IdentifierKind::SubjectDescriptor
+        );
+    }
+}
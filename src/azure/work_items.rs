@@ -1,9 +1,22 @@
-use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::context;
 use crate::azure::models::{
     Comment, CommentListResponse, WiqlQuery, WiqlResponse, WorkItem, WorkItemListResponse,
+    WorkItemRelation,
 };
+use crate::azure::write_policy;
+use crate::journal::{self, Change};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{StreamExt, TryStreamExt, stream};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Fields whose changes cannot be undone, the comments of the history being permanent
+pub const IRREVERSIBLE_FIELDS: &[&str] = &["System.History"];
 
 #[derive(Serialize)]
 pub struct JsonPatchOperation {
@@ -16,11 +29,12 @@ pub struct JsonPatchOperation {
 }
 
 pub async fn get_work_item(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     id: u32,
     include_latest_n_comments: Option<i32>,
+    fields: &[String],
 ) -> Result<Option<WorkItem>, AzureError> {
     let result = get_work_items(
         client,
@@ -28,23 +42,32 @@ pub async fn get_work_item(
         project,
         &[id],
         include_latest_n_comments,
+        fields,
     )
     .await;
 
     match result {
         Ok(items) => Ok(items.into_iter().next()),
-        Err(AzureError::ApiError(msg))
-            if msg.contains("WorkItemUnauthorizedAccessException")
-                || msg.contains("Work item does not exist") =>
-        {
-            Ok(None)
-        }
+        Err(AzureError::NotFound(_)) => Ok(None),
+        Err(e) if e.is_type("WorkItemUnauthorizedAccessException") => Ok(None),
         Err(e) => Err(e),
     }
 }
 
+/// Get the relations (links) of a work item
+pub async fn get_work_item_relations(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<Vec<WorkItemRelation>, AzureError> {
+    let path = format!("wit/workitems/{}?$expand=relations&api-version=7.1", id);
+    let work_item: WorkItem = client.get(organization, project, &path).await?;
+    Ok(work_item.relations.unwrap_or_default())
+}
+
 pub async fn get_comments(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     work_item_id: u32,
@@ -98,46 +121,155 @@ pub async fn get_comments(
     Ok(all_comments)
 }
 
+/// Maximum number of work items fetched in one call, but for the exports
+const MAX_WORK_ITEMS: usize = 5000;
+
+/// Maximum number of results of a WIQL query
+const MAX_WIQL_RESULTS: usize = 20000;
+
+static RE_WIQL_WHERE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bWHERE\b").unwrap());
+static RE_WIQL_ORDER_BY: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bORDER\s+BY\b").unwrap());
+static RE_WIQL_ASOF: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bASOF\b").unwrap());
+
+/// Maximum number of IDs the batch API accepts per request
+const WORK_ITEMS_BATCH_SIZE: usize = 200;
+
+/// Batches (and comment requests) in flight at the same time
+pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Get work items by ID, in the order of `ids`. Large ID lists are split in batches
+/// fetched concurrently. Only the fields given by reference name are returned, all of
+/// them when none is given.
 pub async fn get_work_items(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     ids: &[u32],
     include_latest_n_comments: Option<i32>,
+    fields: &[String],
 ) -> Result<Vec<WorkItem>, AzureError> {
-    if ids.is_empty() {
-        return Ok(vec![]);
-    }
-
-    let max_items = 1000;
-    let ids_to_fetch = if ids.len() > max_items {
-        log::warn!(
+    let ids = if ids.len() > MAX_WORK_ITEMS {
+        tracing::warn!(
             "Requested {} work items, limiting to {} items",
             ids.len(),
-            max_items
+            MAX_WORK_ITEMS
         );
-        &ids[..max_items]
+        &ids[..MAX_WORK_ITEMS]
     } else {
         ids
     };
+    fetch_work_items(
+        client,
+        organization,
+        project,
+        ids,
+        include_latest_n_comments,
+        fields,
+    )
+    .await
+}
 
-    let batch_size = 200;
-    let mut all_work_items = Vec::new();
+/// Get work items by ID like [`get_work_items`], all of them however many, for the
+/// exports
+pub async fn get_all_work_items(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    ids: &[u32],
+    fields: &[String],
+) -> Result<Vec<WorkItem>, AzureError> {
+    fetch_work_items(client, organization, project, ids, None, fields).await
+}
 
-    for chunk in ids_to_fetch.chunks(batch_size) {
-        let ids_str = chunk
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<_>>()
-            .join(",");
-        let path = format!("wit/workitems?ids={}&api-version=7.1", ids_str);
-        let response: WorkItemListResponse = client.get(organization, project, &path).await?;
-        all_work_items.extend(response.value);
+async fn fetch_work_items(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    ids_to_fetch: &[u32],
+    include_latest_n_comments: Option<i32>,
+    fields: &[String],
+) -> Result<Vec<WorkItem>, AzureError> {
+    if ids_to_fetch.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let fields = if fields.is_empty() {
+        String::new()
+    } else {
+        format!("&fields={}", urlencoding::encode(&fields.join(",")))
+    };
+    let fields = fields.as_str();
+
+    // Collect the requests first: a lazily mapped iterator held across the awaits
+    // would make the future not Send
+    let requests: Vec<_> = ids_to_fetch
+        .chunks(WORK_ITEMS_BATCH_SIZE)
+        .map(|chunk| {
+            let ids_str = chunk
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let path = format!("wit/workitems?ids={}{}&api-version=7.1", ids_str, fields);
+            async move {
+                let response: WorkItemListResponse =
+                    client.get(organization, project, &path).await?;
+                Ok::<_, AzureError>(response.value)
+            }
+        })
+        .collect();
+    // With comments, fetching them is the second half of the progress
+    let total = ids_to_fetch.len() as u64 * (1 + include_latest_n_comments.is_some() as u64);
+    let mut fetched = 0;
+    let batches: Vec<Vec<WorkItem>> = stream::iter(requests)
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .inspect_ok(|batch| {
+            fetched += batch.len() as u64;
+            crate::progress::report(fetched, Some(total), || {
+                format!("Fetched {} of {} work items", fetched, ids_to_fetch.len())
+            });
+        })
+        .try_collect()
+        .await?;
+
+    // Batches come back in order, but restore the input order within them too
+    let mut by_id: HashMap<u32, WorkItem> = batches
+        .into_iter()
+        .flatten()
+        .map(|work_item| (work_item.id, work_item))
+        .collect();
+    let mut all_work_items: Vec<WorkItem> = ids_to_fetch
+        .iter()
+        .filter_map(|id| by_id.remove(id))
+        .collect();
+    // Only the work items with all their fields are mirrored
+    if let Some(store) = context::current().work_item_store.clone()
+        && fields.is_empty()
+    {
+        store.record(organization, project, &all_work_items).await;
     }
 
     if let Some(n) = include_latest_n_comments {
-        for work_item in &mut all_work_items {
-            let comments = get_comments(client, organization, project, work_item.id, n).await?;
+        let requests: Vec<_> = all_work_items
+            .iter()
+            .map(|work_item| get_comments(client, organization, project, work_item.id, n))
+            .collect();
+        let work_items = all_work_items.len() as u64;
+        let mut done = 0;
+        let comments: Vec<Vec<Comment>> = stream::iter(requests)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .inspect_ok(|_| {
+                done += 1;
+                crate::progress::report(total - work_items + done, Some(total), || {
+                    format!(
+                        "Fetched the comments of {} of {} work items",
+                        done, work_items
+                    )
+                });
+            })
+            .try_collect()
+            .await?;
+        for (work_item, comments) in all_work_items.iter_mut().zip(comments) {
             work_item.comments = Some(comments);
         }
     }
@@ -145,14 +277,60 @@ pub async fn get_work_items(
     Ok(all_work_items)
 }
 
+/// Get work items with all their fields and their relations (links)
+pub async fn get_work_items_with_relations(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    ids: &[u32],
+) -> Result<Vec<WorkItem>, AzureError> {
+    // The fields cannot be selected when expanding the relations
+    let requests: Vec<_> = ids
+        .chunks(WORK_ITEMS_BATCH_SIZE)
+        .map(|chunk| {
+            let ids_str = chunk
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let path = format!(
+                "wit/workitems?ids={}&$expand=relations&api-version=7.1",
+                ids_str
+            );
+            async move {
+                let response: WorkItemListResponse =
+                    client.get(organization, project, &path).await?;
+                Ok::<_, AzureError>(response.value)
+            }
+        })
+        .collect();
+    let batches: Vec<Vec<WorkItem>> = stream::iter(requests)
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .try_collect()
+        .await?;
+    Ok(batches.into_iter().flatten().collect())
+}
+
 pub async fn create_work_item(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     work_item_type: &str,
     fields: &[(&str, Value)],
 ) -> Result<WorkItem, AzureError> {
-    let operations: Vec<JsonPatchOperation> = fields
+    create_work_item_under(client, organization, project, work_item_type, fields, None).await
+}
+
+/// Create a work item, linked to its parent in the same request when given
+pub async fn create_work_item_under(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    work_item_type: &str,
+    fields: &[(&str, Value)],
+    parent_id: Option<u32>,
+) -> Result<WorkItem, AzureError> {
+    let mut operations: Vec<JsonPatchOperation> = fields
         .iter()
         .map(|(k, v)| JsonPatchOperation {
             op: "add".to_string(),
@@ -161,15 +339,52 @@ pub async fn create_work_item(
             from: None,
         })
         .collect();
+    if let Some(parent_id) = parent_id {
+        operations.push(JsonPatchOperation {
+            op: "add".to_string(),
+            path: "/relations/-".to_string(),
+            value: Some(serde_json::json!({
+                "rel": "System.LinkTypes.Hierarchy-Reverse",
+                "url": format!(
+                    "{}/{}/_apis/wit/workItems/{}",
+                    client.cloud().base_url(None),
+                    organization,
+                    parent_id
+                ),
+            })),
+            from: None,
+        });
+    }
+
+    let created: HashMap<String, Value> = fields
+        .iter()
+        .map(|(field, value)| (field.to_string(), value.clone()))
+        .collect();
+    check_policy(project, None, &created)?;
 
     let path = format!("wit/workitems/${}?api-version=7.1", work_item_type);
-    client
+    let work_item: WorkItem = client
         .post_patch(organization, project, &path, &operations)
-        .await
+        .await?;
+    write_policy::count_created(work_item.id);
+    if let Some(store) = context::current().work_item_store.clone() {
+        store
+            .record(organization, project, std::slice::from_ref(&work_item))
+            .await;
+    }
+    journal::record(
+        organization,
+        project,
+        work_item.id,
+        rev_of(&work_item),
+        Change::Created,
+        serde_json::to_value(&operations).unwrap(),
+    );
+    Ok(work_item)
 }
 
 pub async fn update_work_item(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     id: u32,
@@ -184,15 +399,196 @@ pub async fn update_work_item(
             from: None,
         })
         .collect();
+    // The values the update overwrites, for the journal to restore them, and the ones
+    // the write policy checks
+    let policy = context::current().write_policy.clone();
+    let overwritten: Vec<String> = fields
+        .iter()
+        .map(|(field, _)| field.to_string())
+        .filter(|field| !IRREVERSIBLE_FIELDS.contains(&field.as_str()))
+        .collect();
+    let mut names = policy.checked_fields();
+    if journal::is_recording() {
+        names.extend(overwritten.iter().cloned());
+    }
+    let current = if names.is_empty() {
+        HashMap::new()
+    } else {
+        get_work_item(client, organization, project, id, None, &names)
+            .await?
+            .map(|work_item| work_item.fields)
+            .unwrap_or_default()
+    };
+    if !policy.is_empty() {
+        // Neither out of the allowed area paths nor into them
+        if !current.is_empty() {
+            policy.check_area_path(project, &current)?;
+        }
+        let mut after = current.clone();
+        after.extend(
+            fields
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.clone())),
+        );
+        check_policy(project, Some(id), &after)?;
+    }
+    let previous = journal::is_recording().then(|| {
+        overwritten
+            .into_iter()
+            .map(|field| {
+                let value = current.get(&field).cloned().unwrap_or(Value::Null);
+                (field, value)
+            })
+            .collect::<BTreeMap<_, _>>()
+    });
 
     let path = format!("wit/workitems/{}?api-version=7.1", id);
-    client
+    let work_item: WorkItem = client
         .patch_patch(organization, project, &path, &operations)
-        .await
+        .await?;
+    if let Some(store) = context::current().work_item_store.clone() {
+        store
+            .record(organization, project, std::slice::from_ref(&work_item))
+            .await;
+    }
+    if let Some(previous) = previous {
+        journal::record(
+            organization,
+            project,
+            id,
+            rev_of(&work_item),
+            Change::Updated { previous },
+            serde_json::to_value(&operations).unwrap(),
+        );
+    }
+    Ok(work_item)
+}
+
+/// Restore the values of fields overwritten by an update, removing the ones that were
+/// empty. With the revision the work item had after the update, it fails when changed
+/// since.
+pub async fn restore_work_item_fields(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+    previous: &BTreeMap<String, Value>,
+    rev: Option<i64>,
+) -> Result<WorkItem, AzureError> {
+    check_policy_of(client, organization, project, id).await?;
+    // Nor moved back out of the allowed area paths
+    if let Some(area_path) = previous
+        .get("System.AreaPath")
+        .filter(|value| !value.is_null())
+    {
+        let fields = HashMap::from([("System.AreaPath".to_string(), area_path.clone())]);
+        context::current()
+            .write_policy
+            .check_area_path(project, &fields)?;
+    }
+    let mut operations: Vec<JsonPatchOperation> = rev
+        .map(|rev| JsonPatchOperation {
+            op: "test".to_string(),
+            path: "/rev".to_string(),
+            value: Some(rev.into()),
+            from: None,
+        })
+        .into_iter()
+        .collect();
+    operations.extend(previous.iter().map(|(field, value)| JsonPatchOperation {
+        op: if value.is_null() { "remove" } else { "add" }.to_string(),
+        path: format!("/fields/{}", field),
+        value: (!value.is_null()).then(|| value.clone()),
+        from: None,
+    }));
+
+    let path = format!("wit/workitems/{}?api-version=7.1", id);
+    let work_item: WorkItem = client
+        .patch_patch(organization, project, &path, &operations)
+        .await?;
+    if let Some(store) = context::current().work_item_store.clone() {
+        store
+            .record(organization, project, std::slice::from_ref(&work_item))
+            .await;
+    }
+    Ok(work_item)
+}
+
+/// Move a work item to the recycle bin, removing it from the work item store
+pub async fn delete_work_item(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<Value, AzureError> {
+    check_policy_of(client, organization, project, id).await?;
+    let path = format!("wit/workitems/{}?api-version=7.1", id);
+    let deleted = client
+        .request(
+            organization,
+            project,
+            reqwest::Method::DELETE,
+            &path,
+            None::<&String>,
+        )
+        .await?;
+    if let Some(store) = context::current().work_item_store.clone() {
+        store.remove(organization, project, &[id]).await;
+    }
+    Ok(deleted)
+}
+
+/// Check the fields a work item would have after a change against the write policy,
+/// with the work items the tool invocation changed so far, None creating one
+fn check_policy(
+    project: &str,
+    id: Option<u32>,
+    fields: &HashMap<String, Value>,
+) -> Result<(), AzureError> {
+    let policy = context::current().write_policy.clone();
+    policy.check_fields(project, fields)?;
+    policy.check_bulk_writes(id, usize::from(id.is_none()))
+}
+
+/// Check a change of a work item leaving its fields as they are, e.g. a link or a
+/// comment, against the allowed area paths and the bulk limit of the write policy
+async fn check_policy_of(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<(), AzureError> {
+    let policy = context::current().write_policy.clone();
+    if !policy.allowed_area_paths.is_empty() {
+        let fields = ["System.AreaPath".to_string()];
+        let fields = get_work_item(client, organization, project, id, None, &fields)
+            .await?
+            .map(|work_item| work_item.fields)
+            .unwrap_or_default();
+        policy.check_area_path(project, &fields)?;
+    }
+    policy.check_bulk_writes([id], 0)
+}
+
+/// Whether two link URLs target the same artifact, the work item URLs being compared by
+/// ID as Azure DevOps rewrites them with the organization
+fn same_link_target(a: &str, b: &str) -> bool {
+    let work_item_id = |url: &str| {
+        let url = url.to_ascii_lowercase();
+        url.contains("/_apis/wit/workitems/")
+            .then(|| url.rsplit('/').next().map(str::to_string))
+            .flatten()
+    };
+    a.eq_ignore_ascii_case(b) || work_item_id(a).is_some_and(|id| Some(id) == work_item_id(b))
+}
+
+/// Revision of a work item, as returned by its fields
+pub fn rev_of(work_item: &WorkItem) -> Option<i64> {
+    work_item.fields.get("System.Rev").and_then(Value::as_i64)
 }
 
 pub async fn add_comment(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     work_item_id: u32,
@@ -202,43 +598,209 @@ pub async fn add_comment(
         "wit/workitems/{}/comments?api-version=7.1-preview.3",
         work_item_id
     );
+    check_policy_of(client, organization, project, work_item_id).await?;
     let body = serde_json::json!({
         "text": text
     });
-    client.post(organization, project, &path, &body).await
+    let comment: Value = client.post(organization, project, &path, &body).await?;
+    if let Some(comment_id) = comment.get("id").and_then(Value::as_u64) {
+        journal::record(
+            organization,
+            project,
+            work_item_id,
+            None,
+            Change::Commented { comment_id },
+            body,
+        );
+    }
+    Ok(comment)
+}
+
+/// Delete a comment of a work item. The work item store keeps the fields only, its
+/// `System.CommentCount` being updated when the work item is fetched again.
+pub async fn delete_comment(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    work_item_id: u32,
+    comment_id: u64,
+) -> Result<(), AzureError> {
+    check_policy_of(client, organization, project, work_item_id).await?;
+    let path = format!(
+        "wit/workitems/{}/comments/{}?api-version=7.1-preview.3",
+        work_item_id, comment_id
+    );
+    client
+        .request(
+            organization,
+            project,
+            reqwest::Method::DELETE,
+            &path,
+            None::<&String>,
+        )
+        .await
 }
 
 pub async fn link_work_items(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     source_id: u32,
     target_id: u32,
     link_type: &str,
 ) -> Result<Value, AzureError> {
+    check_policy_of(client, organization, project, source_id).await?;
+    let url = format!(
+        "{}/_apis/wit/workitems/{}",
+        client.cloud().base_url(None),
+        target_id
+    );
     let operations = vec![JsonPatchOperation {
         op: "add".to_string(),
         path: "/relations/-".to_string(),
         value: Some(serde_json::json!({
             "rel": link_type,
-            "url": format!("https://dev.azure.com/_apis/wit/workitems/{}", target_id),
+            "url": url,
         })),
         from: None,
     }];
 
     let path = format!("wit/workitems/{}?api-version=7.1", source_id);
-    client
+    let work_item: Value = client
         .patch_patch(organization, project, &path, &operations)
-        .await
+        .await?;
+    journal::record(
+        organization,
+        project,
+        source_id,
+        work_item["fields"]["System.Rev"].as_i64(),
+        Change::Linked {
+            rel: link_type.to_string(),
+            url,
+        },
+        serde_json::to_value(&operations).unwrap(),
+    );
+    Ok(work_item)
+}
+
+/// Add an artifact link (pull request, commit, GitHub artifact, ...) to a work item,
+/// returning false without changing it when already linked
+pub async fn add_artifact_link(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+    url: &str,
+    name: &str,
+) -> Result<bool, AzureError> {
+    let relations = get_work_item_relations(client, organization, project, id).await?;
+    if relations
+        .iter()
+        .any(|relation| relation.rel == "ArtifactLink" && relation.url.eq_ignore_ascii_case(url))
+    {
+        return Ok(false);
+    }
+    check_policy_of(client, organization, project, id).await?;
+    let operations = vec![JsonPatchOperation {
+        op: "add".to_string(),
+        path: "/relations/-".to_string(),
+        value: Some(serde_json::json!({
+            "rel": "ArtifactLink",
+            "url": url,
+            "attributes": { "name": name },
+        })),
+        from: None,
+    }];
+    let path = format!("wit/workitems/{}?api-version=7.1", id);
+    let work_item: Value = client
+        .patch_patch(organization, project, &path, &operations)
+        .await?;
+    journal::record(
+        organization,
+        project,
+        id,
+        work_item["fields"]["System.Rev"].as_i64(),
+        Change::Linked {
+            rel: "ArtifactLink".to_string(),
+            url: url.to_string(),
+        },
+        serde_json::to_value(&operations).unwrap(),
+    );
+    Ok(true)
+}
+
+/// Remove a link of a work item, found by type and URL, returning whether it existed.
+/// Unless forced, it fails when the work item changed since its links were read, as the
+/// link is removed by its index.
+pub async fn remove_link(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+    rel: &str,
+    url: &str,
+    force: bool,
+) -> Result<bool, AzureError> {
+    let path = format!("wit/workitems/{}?$expand=relations&api-version=7.1", id);
+    let work_item: WorkItem = client.get(organization, project, &path).await?;
+    let Some(index) = work_item
+        .relations
+        .iter()
+        .flatten()
+        .position(|relation| relation.rel == rel && same_link_target(&relation.url, url))
+    else {
+        return Ok(false);
+    };
+    check_policy_of(client, organization, project, id).await?;
+    let mut operations: Vec<JsonPatchOperation> = rev_of(&work_item)
+        .filter(|_| !force)
+        .map(|rev| JsonPatchOperation {
+            op: "test".to_string(),
+            path: "/rev".to_string(),
+            value: Some(rev.into()),
+            from: None,
+        })
+        .into_iter()
+        .collect();
+    operations.push(JsonPatchOperation {
+        op: "remove".to_string(),
+        path: format!("/relations/{}", index),
+        value: None,
+        from: None,
+    });
+    let path = format!("wit/workitems/{}?api-version=7.1", id);
+    let _: Value = client
+        .patch_patch(organization, project, &path, &operations)
+        .await?;
+    Ok(true)
 }
 
 pub async fn query_work_items(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     organization: &str,
     project: &str,
     query: &str,
     include_latest_n_comments: Option<i32>,
 ) -> Result<Vec<WorkItem>, AzureError> {
+    let ids = query_work_item_ids(client, organization, project, query).await?;
+    get_work_items(
+        client,
+        organization,
+        project,
+        &ids,
+        include_latest_n_comments,
+        &[],
+    )
+    .await
+}
+
+/// Run a WIQL query and get the IDs of the work items it returns, in order
+pub async fn query_work_item_ids(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    query: &str,
+) -> Result<Vec<u32>, AzureError> {
     let wiql = WiqlQuery {
         query: query.to_string(),
     };
@@ -246,17 +808,404 @@ pub async fn query_work_items(
         .post(organization, project, "wit/wiql?api-version=7.1", &wiql)
         .await?;
 
-    if response.work_items.is_empty() {
-        return Ok(vec![]);
+    Ok(response.work_items.iter().map(|wi| wi.id).collect())
+}
+
+/// Run a WIQL query and get the IDs of all the work items it returns, however many.
+/// Past the results a query can return at once, the query is run again by pages of
+/// increasing IDs, the IDs being then in ascending order rather than in the order of
+/// the query.
+pub async fn query_all_work_item_ids(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    query: &str,
+) -> Result<Vec<u32>, AzureError> {
+    let path = format!("wit/wiql?$top={}&api-version=7.1", MAX_WIQL_RESULTS);
+    let run = |query: String| async {
+        let response: WiqlResponse = client
+            .post(organization, project, &path, &WiqlQuery { query })
+            .await?;
+        Ok::<_, AzureError>(
+            response
+                .work_items
+                .iter()
+                .map(|wi| wi.id)
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let ids = run(query.to_string()).await?;
+    if ids.len() < MAX_WIQL_RESULTS {
+        return Ok(ids);
     }
+    let mut ids = Vec::new();
+    loop {
+        let page = run(wiql_after_id(query, ids.last().copied().unwrap_or(0))).await?;
+        let complete = page.len() < MAX_WIQL_RESULTS;
+        ids.extend(page);
+        crate::progress::report(ids.len() as u64, None, || {
+            format!("Queried {} work items", ids.len())
+        });
+        if complete {
+            return Ok(ids);
+        }
+    }
+}
 
-    let ids: Vec<u32> = response.work_items.iter().map(|wi| wi.id).collect();
+/// Query of the work items of a WIQL query with an ID above one, by ascending ID
+fn wiql_after_id(query: &str, last: u32) -> String {
+    let (query, as_of) = match RE_WIQL_ASOF.find(query) {
+        Some(found) => (&query[..found.start()], &query[found.start()..]),
+        None => (query, ""),
+    };
+    let query = RE_WIQL_ORDER_BY
+        .find(query)
+        .map_or(query, |found| &query[..found.start()])
+        .trim_end();
+    let query = match RE_WIQL_WHERE.find(query) {
+        Some(found) => format!(
+            "{} [System.Id] > {} AND ({})",
+            &query[..found.end()],
+            last,
+            query[found.end()..].trim()
+        ),
+        None => format!("{} WHERE [System.Id] > {}", query, last),
+    };
+    format!("{} ORDER BY [System.Id] {}", query, as_of)
+        .trim_end()
+        .to_string()
+}
+
+/// IDs of the work items of a project changed since a time, at most `top`, the least
+/// recently changed first
+pub async fn query_changed_work_item_ids(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    since: DateTime<Utc>,
+    top: usize,
+) -> Result<Vec<u32>, AzureError> {
+    let wiql = WiqlQuery {
+        query: format!(
+            "SELECT [System.Id] FROM WorkItems WHERE [System.TeamProject] = @project \
+             AND [System.ChangedDate] >= '{}' ORDER BY [System.ChangedDate] ASC",
+            since.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ),
+    };
+    // The time precision compares the times of the dates, not only their days
+    let path = format!("wit/wiql?timePrecision=true&$top={}&api-version=7.1", top);
+    let response: WiqlResponse = client.post(organization, project, &path, &wiql).await?;
+
+    Ok(response.work_items.iter().map(|wi| wi.id).collect())
+}
+
+/// Run a saved query and get the work items it returns, the targets of the links
+/// for tree and one-hop queries
+pub async fn run_saved_query(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    query_id: &str,
+    include_latest_n_comments: Option<i32>,
+) -> Result<Vec<WorkItem>, AzureError> {
+    let (ids, _) = saved_query_ids(client, organization, project, query_id).await?;
     get_work_items(
         client,
         organization,
         project,
         &ids,
         include_latest_n_comments,
+        &[],
     )
     .await
 }
+
+/// Run a saved query and get all the work items it returns like [`run_saved_query`],
+/// however many, for the exports
+pub async fn run_saved_query_all(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    query_id: &str,
+    fields: &[String],
+) -> Result<Vec<WorkItem>, AzureError> {
+    let (mut ids, flat) = saved_query_ids(client, organization, project, query_id).await?;
+    // The results are truncated, page the WIQL of the query
+    if flat && ids.len() >= MAX_WIQL_RESULTS {
+        let path = format!("wit/queries/{}?$expand=wiql&api-version=7.1", query_id);
+        let query: Value = client.get(organization, project, &path).await?;
+        if let Some(wiql) = query["wiql"].as_str() {
+            ids = query_all_work_item_ids(client, organization, project, wiql).await?;
+        }
+    }
+    get_all_work_items(client, organization, project, &ids, fields).await
+}
+
+/// IDs of the work items a saved query returns, and whether it is a flat query
+async fn saved_query_ids(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    query_id: &str,
+) -> Result<(Vec<u32>, bool), AzureError> {
+    // API: https://dev.azure.com/{organization}/{project}/_apis/wit/wiql/{id}?api-version=7.1
+    let path = format!("wit/wiql/{}?api-version=7.1", query_id);
+    let response: WiqlResponse = client.get(organization, project, &path).await?;
+
+    let mut ids: Vec<u32> = response.work_items.iter().map(|wi| wi.id).collect();
+    for link in &response.work_item_relations {
+        if let Some(target) = &link.target
+            && !ids.contains(&target.id)
+        {
+            ids.push(target.id);
+        }
+    }
+    Ok((ids, response.work_item_relations.is_empty()))
+}
+
+/// Get the full revision history of a work item (oldest first)
+pub async fn get_work_item_revisions(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<Vec<WorkItem>, AzureError> {
+    let page_size = 200;
+    let mut revisions = Vec::new();
+
+    loop {
+        let path = format!(
+            "wit/workitems/{}/revisions?$top={}&$skip={}&api-version=7.1",
+            id,
+            page_size,
+            revisions.len()
+        );
+        let response: WorkItemListResponse = client.get(organization, project, &path).await?;
+        let fetched = response.value.len();
+        revisions.extend(response.value);
+        crate::progress::report(revisions.len() as u64, None, || {
+            format!("Fetched {} revisions", revisions.len())
+        });
+
+        if fetched < page_size {
+            break;
+        }
+    }
+
+    Ok(revisions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// Client answering the batch requests with the work items they ask for, recording
+    /// their IDs
+    fn work_items_api(requested: Arc<Mutex<Vec<u32>>>) -> MockAzureBoardsApi {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json().returning(move |_, _, url, _, _| {
+            let url = reqwest::Url::parse(url).unwrap();
+            let ids: Vec<u32> = url
+                .query_pairs()
+                .find(|(name, _)| name == "ids")
+                .unwrap()
+                .1
+                .split(',')
+                .map(|id| id.parse().unwrap())
+                .collect();
+            requested.lock().unwrap().extend(&ids);
+            let value: Vec<Value> = ids
+                .iter()
+                .map(|id| json!({"id": id, "fields": {"System.Title": "Item"}}))
+                .collect();
+            Ok(json!({"count": value.len(), "value": value}).to_string())
+        });
+        api
+    }
+
+    #[tokio::test]
+    async fn test_get_all_work_items() {
+        let ids: Vec<u32> = (1..=MAX_WORK_ITEMS as u32 + 1234).collect();
+
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let api = work_items_api(requested.clone());
+        let work_items = get_all_work_items(&api, "contoso", "Web", &ids, &[])
+            .await
+            .unwrap();
+        assert_eq!(work_items.iter().map(|w| w.id).collect::<Vec<_>>(), ids);
+        assert_eq!(*requested.lock().unwrap(), ids);
+
+        // Capped otherwise
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let api = work_items_api(requested.clone());
+        let work_items = get_work_items(&api, "contoso", "Web", &ids, None, &[])
+            .await
+            .unwrap();
+        assert_eq!(work_items.len(), MAX_WORK_ITEMS);
+    }
+
+    #[tokio::test]
+    async fn test_query_all_work_item_ids() {
+        let total = MAX_WIQL_RESULTS as u32 * 2 + 10;
+        let queries = Arc::new(Mutex::new(Vec::new()));
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        let recorded = queries.clone();
+        api.expect_send_json().returning(move |_, _, _, body, _| {
+            let query = serde_json::from_str::<Value>(&body.unwrap()).unwrap()["query"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            let after = query
+                .split("[System.Id] > ")
+                .nth(1)
+                .map_or(0, |rest| rest.split(' ').next().unwrap().parse().unwrap());
+            recorded.lock().unwrap().push(query);
+            let work_items: Vec<Value> = (after + 1..=total)
+                .take(MAX_WIQL_RESULTS)
+                .map(|id| json!({"id": id, "url": "u"}))
+                .collect();
+            Ok(json!({"workItems": work_items}).to_string())
+        });
+
+        let query = "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'Active' \
+                     ORDER BY [System.ChangedDate] DESC";
+        let ids = query_all_work_item_ids(&api, "contoso", "Web", query)
+            .await
+            .unwrap();
+        assert_eq!(ids, (1..=total).collect::<Vec<_>>());
+        let queries = queries.lock().unwrap();
+        assert_eq!(queries.len(), 4);
+        assert_eq!(
+            queries[2],
+            format!(
+                "SELECT [System.Id] FROM WorkItems WHERE [System.Id] > {} AND \
+                 ([System.State] = 'Active') ORDER BY [System.Id]",
+                MAX_WIQL_RESULTS
+            )
+        );
+    }
+
+    #[test]
+    fn test_wiql_after_id() {
+        assert_eq!(
+            wiql_after_id("SELECT [System.Id] FROM WorkItems", 5),
+            "SELECT [System.Id] FROM WorkItems WHERE [System.Id] > 5 ORDER BY [System.Id]"
+        );
+        assert_eq!(
+            wiql_after_id(
+                "select [System.Id] from WorkItems where [System.State] = 'New' or \
+                 [System.State] = 'Active' order by [System.Title] asof '2025-01-01'",
+                7
+            ),
+            "select [System.Id] from WorkItems where [System.Id] > 7 AND ([System.State] = \
+             'New' or [System.State] = 'Active') ORDER BY [System.Id] asof '2025-01-01'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_link() {
+        let patches = Arc::new(Mutex::new(Vec::new()));
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        let sent = patches.clone();
+        api.expect_send_json()
+            .returning(move |_, method, _, body, _| {
+                if method == reqwest::Method::PATCH {
+                    sent.lock()
+                        .unwrap()
+                        .push(serde_json::from_str::<Value>(&body.unwrap()).unwrap());
+                    return Ok(json!({"id": 42, "fields": {"System.Rev": 4}}).to_string());
+                }
+                let relation = |id: u32| {
+                    json!({
+                        "rel": "System.LinkTypes.Related",
+                        "url": format!("https://dev.azure.com/contoso/_apis/wit/workItems/{}", id),
+                    })
+                };
+                Ok(json!({
+                    "id": 42,
+                    "fields": {"System.Rev": 3},
+                    "relations": [relation(7), relation(8)],
+                })
+                .to_string())
+            });
+
+        let url = "https://dev.azure.com/contoso/_apis/wit/workitems/8";
+        for force in [false, true] {
+            let removed = remove_link(
+                &api,
+                "contoso",
+                "Web",
+                42,
+                "System.LinkTypes.Related",
+                url,
+                force,
+            )
+            .await
+            .unwrap();
+            assert!(removed);
+        }
+        let patches = patches.lock().unwrap();
+        assert_eq!(
+            patches[0],
+            json!([
+                {"op": "test", "path": "/rev", "value": 3},
+                {"op": "remove", "path": "/relations/1"},
+            ])
+        );
+        assert_eq!(
+            patches[1],
+            json!([{"op": "remove", "path": "/relations/1"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undo_against_policy() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json()
+            .withf(|_, method, _, _, _| method == reqwest::Method::GET)
+            .returning(|_, _, _, _, _| {
+                let work_item = json!({"id": 42, "fields": {"System.AreaPath": "Web\\Payments"}});
+                Ok(json!({"count": 1, "value": [work_item]}).to_string())
+            });
+        api.expect_send_json()
+            .withf(|_, method, _, _, _| method != reqwest::Method::GET)
+            .never();
+        let context = context::ClientContext {
+            write_policy: Arc::new(crate::azure::write_policy::WritePolicy {
+                allowed_area_paths: vec!["Web\\Checkout".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        context::scope(Arc::new(context), async {
+            let violation = |result: Result<(), AzureError>| {
+                matches!(result, Err(AzureError::PolicyViolation { .. }))
+            };
+            assert!(violation(
+                delete_work_item(&api, "contoso", "Web", 42)
+                    .await
+                    .map(|_| ())
+            ));
+            assert!(violation(
+                delete_comment(&api, "contoso", "Web", 42, 7).await
+            ));
+            let previous = BTreeMap::from([("System.Title".to_string(), json!("Old"))]);
+            assert!(violation(
+                restore_work_item_fields(&api, "contoso", "Web", 42, &previous, None)
+                    .await
+                    .map(|_| ())
+            ));
+        })
+        .await;
+    }
+}
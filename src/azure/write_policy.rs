@@ -0,0 +1,287 @@
+use crate::azure::client::AzureError;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+/// Field required by the policy, when another field has a value if conditional
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequiredField {
+    /// Reference name of the required field
+    pub field: String,
+    /// Reference name and value of the field the requirement applies on, compared
+    /// ignoring the case
+    pub condition: Option<(String, String)>,
+}
+
+/// Rules the work item changes of the tools must follow, checked before the changes are
+/// sent so that the agents get a clear error rather than a partial change
+#[derive(Debug, Clone, Default)]
+pub struct WritePolicy {
+    /// Area paths the work items must be under to be created or changed, child paths
+    /// included, any when empty
+    pub allowed_area_paths: Vec<String>,
+    pub required_fields: Vec<RequiredField>,
+    /// Work items a tool invocation can change
+    pub max_bulk_items: Option<usize>,
+}
+
+/// Work items a tool invocation changed so far, counted against the bulk limit
+#[derive(Debug, Default)]
+struct BulkCount {
+    changed: HashSet<u32>,
+    created: usize,
+}
+
+impl BulkCount {
+    fn len(&self) -> usize {
+        self.changed.len() + self.created
+    }
+}
+
+tokio::task_local! {
+    static BULK_COUNT: RefCell<BulkCount>;
+}
+
+/// Run a tool invocation, counting the work items it changes against the bulk limit
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    BULK_COUNT.scope(RefCell::default(), future).await
+}
+
+impl WritePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allowed_area_paths.is_empty()
+            && self.required_fields.is_empty()
+            && self.max_bulk_items.is_none()
+    }
+
+    /// Fields whose current values a change of a work item is checked against
+    pub fn checked_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        if !self.allowed_area_paths.is_empty() {
+            fields.push("System.AreaPath".to_string());
+        }
+        for rule in &self.required_fields {
+            fields.push(rule.field.clone());
+            if let Some((field, _)) = &rule.condition {
+                fields.push(field.clone());
+            }
+        }
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+
+    /// Check the fields a work item would have after a change, given with the ones of
+    /// the project when created
+    pub fn check_fields(
+        &self,
+        project: &str,
+        fields: &HashMap<String, Value>,
+    ) -> Result<(), AzureError> {
+        self.check_area_path(project, fields)?;
+        for rule in &self.required_fields {
+            let applies = match &rule.condition {
+                Some((field, value)) => text(fields, field).eq_ignore_ascii_case(value),
+                None => true,
+            };
+            // The root of the classification trees, the project, counts as unset
+            let value = text(fields, &rule.field);
+            let is_root = matches!(
+                rule.field.as_str(),
+                "System.AreaPath" | "System.IterationPath"
+            ) && value.eq_ignore_ascii_case(project);
+            if applies && (value.is_empty() || is_root) {
+                let when = rule
+                    .condition
+                    .as_ref()
+                    .map(|(field, value)| format!(" when {} is {}", field, value))
+                    .unwrap_or_default();
+                return Err(violation(
+                    "required_fields",
+                    format!("{} must be set{}", rule.field, when),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that a work item is under the allowed area paths, given its fields
+    pub fn check_area_path(
+        &self,
+        project: &str,
+        fields: &HashMap<String, Value>,
+    ) -> Result<(), AzureError> {
+        if self.allowed_area_paths.is_empty() {
+            return Ok(());
+        }
+        // The work items created without an area path get the one of the project
+        let area_path = match text(fields, "System.AreaPath") {
+            area_path if area_path.is_empty() => project.to_string(),
+            area_path => area_path,
+        };
+        if self
+            .allowed_area_paths
+            .iter()
+            .any(|allowed| is_under(&area_path, allowed))
+        {
+            return Ok(());
+        }
+        Err(violation(
+            "allowed_area_paths",
+            format!(
+                "work item under area path {}, only the ones under {} can be changed",
+                area_path,
+                self.allowed_area_paths.join(", ")
+            ),
+        ))
+    }
+
+    /// Check that a tool invocation changing `count` work items stays within the bulk
+    /// limit
+    pub fn check_bulk(&self, count: usize) -> Result<(), AzureError> {
+        match self.max_bulk_items {
+            Some(max) if count > max => Err(violation(
+                "max_bulk_items",
+                format!(
+                    "{} work items changed by a tool call, at most {} can be",
+                    count, max
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check, before writing them, that changing work items and creating `created` more
+    /// keeps the running tool invocation within the bulk limit, with the ones it changed
+    /// so far within a [`scope`], then count them
+    pub fn check_bulk_writes(
+        &self,
+        ids: impl IntoIterator<Item = u32>,
+        created: usize,
+    ) -> Result<(), AzureError> {
+        let ids: HashSet<u32> = ids.into_iter().collect();
+        let check = |count: &mut BulkCount| {
+            let new = ids.difference(&count.changed).count();
+            self.check_bulk(count.len() + new + created)?;
+            count.changed.extend(&ids);
+            count.created += created;
+            Ok(())
+        };
+        BULK_COUNT
+            .try_with(|count| check(&mut count.borrow_mut()))
+            .unwrap_or_else(|_| check(&mut BulkCount::default()))
+    }
+}
+
+/// Count a work item created by the running invocation by its ID, so that changing it
+/// afterwards doesn't count it again
+pub fn count_created(id: u32) {
+    let _ = BULK_COUNT.try_with(|count| {
+        let mut count = count.borrow_mut();
+        if count.changed.insert(id) {
+            count.created = count.created.saturating_sub(1);
+        }
+    });
+}
+
+/// Value of a field as text, empty when unset
+fn text(fields: &HashMap<String, Value>, field: &str) -> String {
+    match fields.get(field) {
+        Some(Value::String(value)) => value.trim().to_string(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn violation(rule: &str, message: String) -> AzureError {
+    AzureError::PolicyViolation {
+        rule: rule.to_string(),
+        message,
+    }
+}
+
+/// Whether an area path is the given one or one of its children
+fn is_under(path: &str, parent: &str) -> bool {
+    let (path, parent) = (
+        path.trim_matches('\\').to_lowercase(),
+        parent.trim_matches('\\').to_lowercase(),
+    );
+    path == parent || path.starts_with(&format!("{}\\", parent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_fields() {
+        let policy = WritePolicy {
+            allowed_area_paths: vec!["Web\\Payments".to_string()],
+            required_fields: vec![RequiredField {
+                field: "System.IterationPath".to_string(),
+                condition: Some(("System.State".to_string(), "Active".to_string())),
+            }],
+            max_bulk_items: Some(25),
+        };
+        let fields = |pairs: &[(&str, &str)]| -> HashMap<String, Value> {
+            pairs
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.to_string().into()))
+                .collect()
+        };
+
+        let valid = fields(&[
+            ("System.AreaPath", "Web\\payments\\Backend"),
+            ("System.State", "Active"),
+            ("System.IterationPath", "Web\\Sprint 1"),
+        ]);
+        assert!(policy.check_fields("Web", &valid).is_ok());
+        let outside = fields(&[("System.AreaPath", "Web\\PaymentsOld")]);
+        assert_eq!(
+            policy
+                .check_fields("Web", &outside)
+                .unwrap_err()
+                .to_string(),
+            "Policy violation: work item under area path Web\\PaymentsOld, only the ones under Web\\Payments can be changed"
+        );
+        assert!(policy.check_fields("Web", &HashMap::new()).is_err());
+        let unscheduled = fields(&[
+            ("System.AreaPath", "Web\\Payments"),
+            ("System.State", "active"),
+            ("System.IterationPath", "Web"),
+        ]);
+        assert_eq!(
+            policy
+                .check_fields("Web", &unscheduled)
+                .unwrap_err()
+                .to_string(),
+            "Policy violation: System.IterationPath must be set when System.State is Active"
+        );
+
+        assert!(policy.check_bulk(25).is_ok());
+        assert!(policy.check_bulk(26).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_bulk_writes() {
+        let policy = WritePolicy {
+            max_bulk_items: Some(3),
+            ..Default::default()
+        };
+        scope(async {
+            policy.check_bulk_writes([1, 2], 0).unwrap();
+            // Changing a work item again doesn't count it twice
+            policy.check_bulk_writes([2], 0).unwrap();
+            policy.check_bulk_writes(None, 1).unwrap();
+            count_created(10);
+            policy.check_bulk_writes([10], 0).unwrap();
+            assert!(policy.check_bulk_writes([3], 0).is_err());
+        })
+        .await;
+        // Each invocation counts its own, only the given ones outside of a scope
+        scope(async { policy.check_bulk_writes([1, 2, 3], 0).unwrap() }).await;
+        assert!(policy.check_bulk_writes([1, 2, 3], 0).is_ok());
+        assert!(policy.check_bulk_writes([1, 2, 3, 4], 0).is_err());
+    }
+}
@@ -1,57 +1,289 @@
 use serde::Serialize;
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json::{Map, Number, Value};
+
+/// Characters delimiting the structure, the strings containing them being quoted
+const DELIMITERS: &[char] = &[':', ',', '{', '}', '[', ']', '"'];
 
 /// Serializes a value to a compact representation optimized for LLM consumption.
-/// This format removes all unnecessary whitespace and quotes, only escaping newlines.
+/// This format removes all unnecessary whitespace and quotes: strings are written
+/// bare, with their newlines escaped, unless they contain a structural delimiter
+/// (`:,{}[]"\`), are empty, start or end with whitespace, or read as `null`, a
+/// boolean or a number, in which case they are quoted as in JSON.
 ///
-/// Example output: {id:123,name:John Doe,tags:[tag1,tag2],active:true}
+/// Example output: {id:123,name:John Doe,tags:[tag1,tag2],active:true,path:"a:b"}
 pub fn to_compact_string<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    to_compact_string_with(value, &CompactOptions::default())
+}
+
+/// Options of [`to_compact_string_with`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactOptions<'a> {
+    /// Drop the fields of the objects that are null, empty strings or empty arrays
+    pub omit_empty: bool,
+    /// Keys written first in the objects, in this order, the others following sorted
+    pub key_order: &'a [&'a str],
+}
+
+/// [`to_compact_string`] with options to save the tokens of the empty fields and to
+/// put the important ones first. The keys are always written in the same order, so
+/// that the outputs can be compared.
+pub fn to_compact_string_with<T: Serialize>(
+    value: &T,
+    options: &CompactOptions,
+) -> Result<String, serde_json::Error> {
     // First serialize to standard JSON to get the structure
     let json_value = serde_json::to_value(value)?;
 
     // Then convert to compact format
     let mut output = String::new();
-    write_compact_value(&json_value, &mut output);
+    write_compact_value(&json_value, options, &mut output);
 
     Ok(output)
 }
 
-fn write_compact_value(value: &serde_json::Value, output: &mut String) {
+/// Renders a list of objects as a markdown table of the given columns, in their order.
+/// The cells escape their pipes and break their lines with `<br>`, the arrays and
+/// objects being written in the compact representation, and the missing or null
+/// values are left empty.
+///
+/// Example output:
+/// | id | Title |
+/// | --- | --- |
+/// | 123 | Fix login |
+pub fn to_markdown_table(items: &[Value], columns: &[&str]) -> String {
+    let mut output = String::new();
+    write_markdown_row(columns.iter().map(|column| column.to_string()), &mut output);
+    write_markdown_row(columns.iter().map(|_| "---".to_string()), &mut output);
+    for item in items {
+        let cells = columns.iter().map(|column| match item.get(column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(value @ (Value::Array(_) | Value::Object(_))) => {
+                let mut cell = String::new();
+                write_compact_value(value, &CompactOptions::default(), &mut cell);
+                cell
+            }
+            Some(value) => value.to_string(),
+        });
+        write_markdown_row(cells, &mut output);
+    }
+    output.pop();
+    output
+}
+
+fn write_markdown_row(cells: impl Iterator<Item = String>, output: &mut String) {
+    output.push('|');
+    for cell in cells {
+        let cell = cell
+            .replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace(['\r', '\n'], "<br>");
+        output.push(' ');
+        output.push_str(cell.trim());
+        output.push_str(" |");
+    }
+    output.push('\n');
+}
+
+/// Parses the representation written by [`to_compact_string`]
+pub fn from_compact_string<T: DeserializeOwned>(input: &str) -> Result<T, serde_json::Error> {
+    let mut parser = Parser { input, position: 0 };
+    let value = parser.value()?;
+    if parser.position < input.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    serde_json::from_value(value)
+}
+
+fn write_compact_value(value: &Value, options: &CompactOptions, output: &mut String) {
     match value {
-        serde_json::Value::Null => output.push_str("null"),
-        serde_json::Value::Bool(b) => output.push_str(if *b { "true" } else { "false" }),
-        serde_json::Value::Number(n) => output.push_str(&n.to_string()),
-        serde_json::Value::String(s) => {
-            // Always write strings without quotes, only escape newlines
-            let escaped = s.replace('\n', "\\n").replace('\r', "\\r");
-            output.push_str(&escaped);
-        }
-        serde_json::Value::Array(arr) => {
+        Value::Null => output.push_str("null"),
+        Value::Bool(b) => output.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => output.push_str(&n.to_string()),
+        Value::String(s) => write_compact_string(s, false, output),
+        Value::Array(arr) => {
             output.push('[');
             for (i, item) in arr.iter().enumerate() {
                 if i > 0 {
                     output.push(',');
                 }
-                write_compact_value(item, output);
+                write_compact_value(item, options, output);
             }
             output.push(']');
         }
-        serde_json::Value::Object(obj) => {
+        Value::Object(obj) => {
+            // The map keeps its keys sorted
+            let mut fields: Vec<(&String, &Value)> = obj
+                .iter()
+                .filter(|(_, val)| !(options.omit_empty && is_empty(val)))
+                .collect();
+            fields.sort_by_key(|(key, _)| {
+                options
+                    .key_order
+                    .iter()
+                    .position(|first| first == key)
+                    .unwrap_or(options.key_order.len())
+            });
             output.push('{');
-            for (i, (key, val)) in obj.iter().enumerate() {
+            for (i, (key, val)) in fields.into_iter().enumerate() {
                 if i > 0 {
                     output.push(',');
                 }
-                // Write key without quotes
-                let escaped_key = key.replace('\n', "\\n").replace('\r', "\\r");
-                output.push_str(&escaped_key);
+                write_compact_string(key, true, output);
                 output.push(':');
-                write_compact_value(val, output);
+                write_compact_value(val, options, output);
             }
             output.push('}');
         }
     }
 }
 
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Write a string bare, only escaping newlines, or quoted when it would be ambiguous.
+/// Keys are always strings, so they can read as other values.
+fn write_compact_string(s: &str, key: bool, output: &mut String) {
+    let quoted = s.is_empty()
+        || s.contains(DELIMITERS)
+        // Bare strings escape their newlines with it
+        || s.contains('\\')
+        || s.starts_with(char::is_whitespace)
+        || s.ends_with(char::is_whitespace)
+        || (!key && bare_literal(s).is_some());
+    if quoted {
+        output.push_str(&Value::String(s.to_string()).to_string());
+    } else {
+        output.push_str(&s.replace('\n', "\\n").replace('\r', "\\r"));
+    }
+}
+
+/// Value other than a string a bare token reads as
+fn bare_literal(token: &str) -> Option<Value> {
+    match token {
+        "null" => Some(Value::Null),
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        _ => serde_json::from_str::<Number>(token)
+            .ok()
+            .map(Value::Number),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    /// Byte offset of the next character, the delimiters being ASCII
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> serde_json::Error {
+        serde_json::Error::custom(format!("{} at offset {}", message, self.position))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn expect(&mut self, delimiter: u8) -> Result<(), serde_json::Error> {
+        if self.peek() != Some(delimiter) {
+            return Err(self.error(&format!("expected '{}'", delimiter as char)));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value, serde_json::Error> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.quoted().map(Value::String),
+            _ => {
+                let token = self.bare()?;
+                Ok(bare_literal(&token).unwrap_or(Value::String(token)))
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, serde_json::Error> {
+        self.expect(b'{')?;
+        let mut object = Map::new();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Value::Object(object));
+        }
+        loop {
+            let key = match self.peek() {
+                Some(b'"') => self.quoted()?,
+                _ => self.bare()?,
+            };
+            self.expect(b':')?;
+            object.insert(key, self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                _ => break,
+            }
+        }
+        self.expect(b'}')?;
+        Ok(Value::Object(object))
+    }
+
+    fn array(&mut self) -> Result<Value, serde_json::Error> {
+        self.expect(b'[')?;
+        let mut array = Vec::new();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Value::Array(array));
+        }
+        loop {
+            array.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                _ => break,
+            }
+        }
+        self.expect(b']')?;
+        Ok(Value::Array(array))
+    }
+
+    /// JSON string, up to its closing quote
+    fn quoted(&mut self) -> Result<String, serde_json::Error> {
+        let start = self.position;
+        let mut escaped = false;
+        for (offset, byte) in self.input.as_bytes()[start + 1..].iter().enumerate() {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    self.position = start + offset + 2;
+                    return serde_json::from_str(&self.input[start..self.position]);
+                }
+                _ => {}
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    /// Bare token, up to the next delimiter, with its newlines unescaped
+    fn bare(&mut self) -> Result<String, serde_json::Error> {
+        let rest = &self.input[self.position..];
+        let length = rest.find(DELIMITERS).unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error("expected a value"));
+        }
+        self.position += length;
+        Ok(rest[..length].replace("\\n", "\n").replace("\\r", "\r"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +368,128 @@ mod tests {
         assert!(result.contains("bool_false:false"));
         assert!(result.contains("number:42.5"));
     }
+
+    #[test]
+    fn test_quoting() {
+        use serde_json::json;
+
+        let data = json!({
+            "path": "Project\\Area: Web, {old}",
+            "id": "42",
+            "state": "null",
+            "empty": "",
+            "padded": " x",
+            "a:b": [1, "two words", "[x]"]
+        });
+
+        let result = to_compact_string(&data).unwrap();
+        assert_eq!(
+            result,
+            r#"{"a:b":[1,two words,"[x]"],empty:"",id:"42",padded:" x",path:"Project\\Area: Web, {old}",state:"null"}"#
+        );
+        assert_eq!(from_compact_string::<Value>(&result).unwrap(), data);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for input in ["{a:1", "[1,2", "{a}", "\"open", "[1]x", "{a:1,}", ""] {
+            assert!(
+                from_compact_string::<Value>(input).is_err(),
+                "{} should not parse",
+                input
+            );
+        }
+    }
+
+    /// Deterministic xorshift generator of the values of the round-trip property
+    struct Generator(u64);
+
+    impl Generator {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        fn string(&mut self) -> String {
+            const TOKENS: &[&str] = &[
+                "a", "Z", "7", "-", ".", "e", " ", "\n", "\r", "\t", "\\", "\"", ":", ",", "{",
+                "}", "[", "]", "é", "日", "null", "true", "1.5", "\\n",
+            ];
+            (0..self.next(6))
+                .map(|_| TOKENS[self.next(TOKENS.len() as u64) as usize])
+                .collect()
+        }
+
+        fn value(&mut self, depth: u32) -> Value {
+            let kinds = if depth == 0 { 5 } else { 7 };
+            match self.next(kinds) {
+                0 => Value::Null,
+                1 => Value::Bool(self.next(2) == 0),
+                2 => Value::from(self.next(2000) as i64 - 1000),
+                3 => Value::from(self.next(1000) as f64 / 8.0),
+                4 => Value::String(self.string()),
+                5 => Value::Array((0..self.next(4)).map(|_| self.value(depth - 1)).collect()),
+                _ => Value::Object(
+                    (0..self.next(4))
+                        .map(|_| (self.string(), self.value(depth - 1)))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_options() {
+        let value = serde_json::json!({
+            "Tags": [],
+            "Title": "Fix login",
+            "AssignedTo": null,
+            "Description": "",
+            "Priority": 2,
+            "id": 42,
+            "comments": [{"text": "Done", "author": null}],
+        });
+        let options = CompactOptions {
+            omit_empty: true,
+            key_order: &["id", "Title"],
+        };
+        assert_eq!(
+            to_compact_string_with(&value, &options).unwrap(),
+            "{id:42,Title:Fix login,Priority:2,comments:[{text:Done}]}"
+        );
+        assert_eq!(
+            to_compact_string(&value).unwrap(),
+            "{AssignedTo:null,Description:\"\",Priority:2,Tags:[],Title:Fix login,\
+             comments:[{author:null,text:Done}],id:42}"
+        );
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let items = [
+            serde_json::json!({"id": 1, "Title": "Fix a|b", "Tags": ["x", "y"]}),
+            serde_json::json!({"id": 2, "Title": "Line 1\nLine 2", "Tags": null}),
+        ];
+        assert_eq!(
+            to_markdown_table(&items, &["id", "Title", "Tags"]),
+            "| id | Title | Tags |\n\
+             | --- | --- | --- |\n\
+             | 1 | Fix a\\|b | [x,y] |\n\
+             | 2 | Line 1<br>Line 2 |  |"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut generator = Generator(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..5000 {
+            let value = generator.value(3);
+            let compact = to_compact_string(&value).unwrap();
+            let parsed: Value = from_compact_string(&compact)
+                .unwrap_or_else(|e| panic!("{} does not parse: {}", compact, e));
+            assert_eq!(parsed, value, "{} does not round-trip", compact);
+        }
+    }
 }
@@ -0,0 +1,993 @@
+use crate::azure::api_version::ApiVersionConfig;
+use crate::azure::auth::{AuthMethod, CredentialStore};
+use crate::azure::cache::CacheConfig;
+use crate::azure::circuit_breaker::CircuitBreakerConfig;
+use crate::azure::client::{HttpConfig, ProxyConfig};
+use crate::azure::cloud::Cloud;
+use crate::azure::named_queries::{NamedQueries, NamedQuery};
+use crate::azure::rate_limit::RateLimitConfig;
+use crate::azure::retry::RetryConfig;
+use crate::azure::service_hooks::ServiceHookReceiver;
+use crate::azure::write_policy::{RequiredField, WritePolicy};
+use crate::mcp::date_rendering::DateRendering;
+use crate::mcp::result_budget::ResultBudget;
+use crate::mcp::server::ToolTimeouts;
+use crate::mcp::tool_access::ToolAccess;
+use crate::mcp::tool_defaults::ToolDefaults;
+use crate::mcp::tools::support::reference_names;
+use crate::server::auth::HttpAuthConfig;
+use crate::server::cors::CorsConfig;
+use crate::server::rate_limit::ClientLimitConfig;
+use crate::server::service_hooks::SERVICE_HOOK_PATH;
+use crate::server::tls::TlsConfig;
+use crate::store::StoreConfig;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+use tz::TimeZone;
+
+/// Arguments that are actions rather than settings, not accepted in the config file
+const NOT_SETTINGS: &[&str] = &["config", "login", "store_credentials", "help", "version"];
+
+/// Settings given in the config file as a table of `name = value` entries, passed as
+/// `name=value` pairs
+const TABLE_SETTINGS: &[&str] = &["queries"];
+
+/// Settings of the server, from the command line, the environment and the config file,
+/// in that order of precedence
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct AppConfig {
+    /// TOML file with the settings not given on the command line or in the environment,
+    /// named after their flag (e.g. `cache_ttl_projects = 300`)
+    #[arg(long, env = "AZDO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// What to run, serving MCP over stdio by default
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Sign in with the device code flow and store the credentials for `--auth device-code`,
+    /// optionally for the profile of the given organization
+    #[arg(long, value_name = "ORGANIZATION", num_args = 0..=1)]
+    pub login: Option<Option<String>>,
+
+    /// Save the personal access token (`--pat`, or read from stdin) in the OS keyring
+    /// for `--auth pat`, optionally for the profile of the given organization
+    #[arg(long, value_name = "ORGANIZATION", num_args = 0..=1)]
+    pub store_credentials: Option<Option<String>>,
+
+    #[command(flatten)]
+    pub auth: AuthOptions,
+
+    #[command(flatten)]
+    pub cache: CacheOptions,
+
+    #[command(flatten)]
+    pub requests: RequestOptions,
+
+    #[command(flatten)]
+    pub tools: ToolOptions,
+
+    #[command(flatten)]
+    pub http_server: HttpServerOptions,
+
+    #[command(flatten)]
+    pub output: OutputOptions,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Serve MCP to the clients
+    Serve {
+        #[command(subcommand)]
+        transport: Transport,
+    },
+    /// Inspect the tools
+    Tools {
+        #[command(subcommand)]
+        command: ToolsCommand,
+    },
+    /// Invoke a tool once, printing its result, e.g. to debug it without an MCP client
+    Call {
+        /// Name of the tool (e.g. `azdo_list_projects`)
+        tool: String,
+        /// Arguments of the tool, as a JSON object
+        #[arg(long, default_value = "{}")]
+        args: String,
+    },
+    /// Check the credentials, the organizations and projects they reach, their scopes
+    /// and the latency, printing a report
+    Doctor {
+        /// Organizations to check, comma separated, the default organization or else the
+        /// ones of the user by default
+        #[arg(long, value_delimiter = ',')]
+        organization: Vec<String>,
+        /// Project to probe the work item write access in, with a validate-only creation,
+        /// the default project by default
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum Transport {
+    /// Serve a single client over stdin and stdout
+    Stdio,
+    /// Serve the clients over streamable HTTP and HTTP+SSE
+    Http,
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq)]
+pub enum ToolsCommand {
+    /// Print the tools offered, with their input schemas, as JSON
+    List,
+}
+
+/// Credentials the server acts with
+#[derive(Args, Debug)]
+#[command(next_help_heading = "Authentication")]
+pub struct AuthOptions {
+    /// Authentication methods, comma separated, tried in order
+    #[arg(
+        long = "auth",
+        value_enum,
+        env = "AZDO_AUTH",
+        value_delimiter = ',',
+        default_value = "default"
+    )]
+    pub methods: Vec<AuthMethod>,
+
+    /// Entra ID tenant ID (entra auth)
+    #[arg(long, env = "AZURE_TENANT_ID")]
+    pub tenant_id: Option<String>,
+
+    /// Entra ID application (client) ID (entra auth)
+    #[arg(long, env = "AZURE_CLIENT_ID")]
+    pub client_id: Option<String>,
+
+    /// Entra ID client secret (entra auth)
+    #[arg(long, env = "AZURE_CLIENT_SECRET", hide_env_values = true)]
+    pub client_secret: Option<String>,
+
+    /// PEM file with the certificate and private key of the app registration (entra auth),
+    /// used instead of the client secret
+    #[arg(long, env = "AZURE_CLIENT_CERTIFICATE_PATH")]
+    pub client_certificate: Option<PathBuf>,
+
+    /// Personal access tokens (pat auth), comma separated, read from the OS keyring when
+    /// not set. Several tokens are used in turn, failing over when one is rejected.
+    #[arg(long, env = "AZDO_PAT", hide_env_values = true, value_delimiter = ',')]
+    pub pat: Vec<String>,
+
+    /// Where the device code sign-in keeps its refresh token
+    #[arg(
+        long,
+        value_enum,
+        env = "AZDO_CREDENTIAL_STORE",
+        default_value = "file"
+    )]
+    pub credential_store: CredentialStore,
+
+    /// Azure cloud hosting the organizations
+    #[arg(long, value_enum, env = "AZDO_CLOUD", default_value = "public")]
+    pub cloud: Cloud,
+
+    /// JSON file with per-organization authentication profiles
+    #[arg(long, env = "AZDO_PROFILES")]
+    pub profiles: Option<PathBuf>,
+}
+
+/// Time to live of the cached Azure DevOps metadata
+#[derive(Args, Debug)]
+#[command(next_help_heading = "Caching")]
+pub struct CacheOptions {
+    /// Cache time to live of projects, in seconds (0 disables caching)
+    #[arg(
+        long = "cache-ttl-projects",
+        env = "AZDO_CACHE_TTL_PROJECTS",
+        default_value_t = 600
+    )]
+    pub ttl_projects: u64,
+
+    /// Cache time to live of teams, in seconds (0 disables caching)
+    #[arg(
+        long = "cache-ttl-teams",
+        env = "AZDO_CACHE_TTL_TEAMS",
+        default_value_t = 600
+    )]
+    pub ttl_teams: u64,
+
+    /// Cache time to live of area and iteration paths, in seconds (0 disables caching)
+    #[arg(
+        long = "cache-ttl-classification-nodes",
+        env = "AZDO_CACHE_TTL_CLASSIFICATION_NODES",
+        default_value_t = 300
+    )]
+    pub ttl_classification_nodes: u64,
+
+    /// Cache time to live of work item types, in seconds (0 disables caching)
+    #[arg(
+        long = "cache-ttl-work-item-types",
+        env = "AZDO_CACHE_TTL_WORK_ITEM_TYPES",
+        default_value_t = 1800
+    )]
+    pub ttl_work_item_types: u64,
+
+    /// SQLite database mirroring the work items fetched, searched by
+    /// `azdo_search_local_work_items` when Azure DevOps is slow or throttling, disabled
+    /// when absent
+    #[arg(long, env = "AZDO_WORK_ITEM_STORE")]
+    pub work_item_store: Option<PathBuf>,
+
+    /// Work items kept in the store, the least recently fetched evicted first
+    #[arg(long, env = "AZDO_WORK_ITEM_STORE_MAX_ITEMS", default_value_t = 20000)]
+    pub work_item_store_max_items: usize,
+}
+
+impl CacheOptions {
+    pub fn cache_config(&self) -> CacheConfig {
+        CacheConfig {
+            projects: Duration::from_secs(self.ttl_projects),
+            teams: Duration::from_secs(self.ttl_teams),
+            classification_nodes: Duration::from_secs(self.ttl_classification_nodes),
+            work_item_types: Duration::from_secs(self.ttl_work_item_types),
+        }
+    }
+
+    pub fn store_config(&self) -> Option<StoreConfig> {
+        Some(StoreConfig {
+            path: self.work_item_store.clone()?,
+            max_items: self.work_item_store_max_items,
+        })
+    }
+}
+
+/// How the requests to Azure DevOps are sent
+#[derive(Args, Debug)]
+#[command(next_help_heading = "Azure DevOps requests")]
+pub struct RequestOptions {
+    /// Attempts of throttled (429) and transiently failing (5xx) requests, 1 disables retries
+    #[arg(long, env = "AZDO_RETRY_MAX_ATTEMPTS", default_value_t = 4)]
+    pub retry_max_attempts: u32,
+
+    /// Random fraction of the retry delay added to spread out concurrent retries
+    #[arg(long, env = "AZDO_RETRY_JITTER", default_value_t = 0.2)]
+    pub retry_jitter: f64,
+
+    /// Maximum sustained requests per second to Azure DevOps, 0 disables the limit
+    #[arg(long, env = "AZDO_RATE_LIMIT", default_value_t = 10.0)]
+    pub rate_limit: f64,
+
+    /// Requests that can be sent at once after an idle period
+    #[arg(long, env = "AZDO_RATE_LIMIT_BURST", default_value_t = 20)]
+    pub rate_limit_burst: u32,
+
+    /// Consecutive failed requests (connection errors and 5xx responses) after which the
+    /// requests to an organization fail fast, 0 disables the circuit breaker
+    #[arg(long, env = "AZDO_CIRCUIT_BREAKER_THRESHOLD", default_value_t = 5)]
+    pub circuit_breaker_threshold: u32,
+
+    /// Seconds the requests fail fast before a probe request is let through
+    #[arg(long, env = "AZDO_CIRCUIT_BREAKER_OPEN_DURATION", default_value_t = 30)]
+    pub circuit_breaker_open_duration: u64,
+
+    /// Idle connections to Azure DevOps kept open for reuse
+    #[arg(long, env = "AZDO_HTTP_POOL_MAX_IDLE", default_value_t = usize::MAX, hide_default_value = true)]
+    pub http_pool_max_idle: usize,
+
+    /// Seconds an idle connection is kept open, 0 keeps it indefinitely
+    #[arg(long, env = "AZDO_HTTP_POOL_IDLE_TIMEOUT", default_value_t = 90)]
+    pub http_pool_idle_timeout: u64,
+
+    /// Interval of the TCP keep-alive probes, in seconds, 0 disables them
+    #[arg(long, env = "AZDO_HTTP_KEEPALIVE", default_value_t = 0)]
+    pub http_keepalive: u64,
+
+    /// Use HTTP/2 without negotiating it first
+    #[arg(long, env = "AZDO_HTTP2")]
+    pub http2: bool,
+
+    /// Timeout of each request to Azure DevOps, in seconds, 0 disables it
+    #[arg(long, env = "AZDO_HTTP_TIMEOUT", default_value_t = 0)]
+    pub http_timeout: u64,
+
+    /// Proxy of the requests (e.g. `http://proxy.example.com:8080`), overriding
+    /// `HTTP_PROXY` and `HTTPS_PROXY`
+    #[arg(long, env = "AZDO_PROXY")]
+    pub proxy: Option<String>,
+
+    /// User name of the authenticated proxy
+    #[arg(long, env = "AZDO_PROXY_USER")]
+    pub proxy_user: Option<String>,
+
+    /// Password of the authenticated proxy
+    #[arg(long, env = "AZDO_PROXY_PASSWORD", hide_env_values = true)]
+    pub proxy_password: Option<String>,
+
+    /// Comma separated hosts and domains reached without the proxy, overriding `NO_PROXY`
+    #[arg(long, env = "AZDO_NO_PROXY")]
+    pub no_proxy: Option<String>,
+
+    /// PEM bundle of root certificates trusted in addition to the system ones (e.g. of a
+    /// TLS-inspecting proxy or an on-premises server)
+    #[arg(long, env = "AZDO_CA_CERTIFICATES")]
+    pub ca_certificates: Option<PathBuf>,
+
+    /// PEM file with the client certificate presented for mutual TLS
+    #[arg(long, env = "AZDO_TLS_CLIENT_CERTIFICATE")]
+    pub tls_client_certificate: Option<PathBuf>,
+
+    /// PEM file with the PKCS#8 private key of the client certificate, when it is not in
+    /// the certificate file
+    #[arg(long, env = "AZDO_TLS_CLIENT_KEY")]
+    pub tls_client_key: Option<PathBuf>,
+
+    /// api-version of every request (e.g. `6.0` for older Azure DevOps Server releases),
+    /// keeping the preview suffix of the endpoints that require one
+    #[arg(long, env = "AZDO_API_VERSION")]
+    pub api_version: Option<String>,
+
+    /// api-versions of specific endpoints, comma separated `endpoint=version` pairs where
+    /// the endpoint is the leading segments of the path after `_apis/` (e.g.
+    /// `wit/wiql=7.2-preview.2`)
+    #[arg(long, env = "AZDO_API_VERSIONS", value_delimiter = ',', value_parser = parse_api_version)]
+    pub api_versions: Vec<(String, String)>,
+
+    /// Directory of JSON fixtures answering the requests instead of Azure DevOps, to demo
+    /// the server or develop a client without an organization nor credentials
+    #[arg(long, env = "AZDO_MOCK", conflicts_with_all = ["record", "replay"])]
+    pub mock: Option<PathBuf>,
+
+    /// JSON Lines file the requests and responses are recorded to, sanitized, to be
+    /// replayed with `--replay`
+    #[arg(long, env = "AZDO_RECORD", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Recording of `--record` answering the requests instead of Azure DevOps
+    #[arg(long, env = "AZDO_REPLAY")]
+    pub replay: Option<PathBuf>,
+}
+
+impl RequestOptions {
+    pub fn http_config(&self) -> HttpConfig {
+        HttpConfig {
+            pool_max_idle_per_host: self.http_pool_max_idle,
+            pool_idle_timeout: seconds(self.http_pool_idle_timeout),
+            tcp_keepalive: seconds(self.http_keepalive),
+            http2_prior_knowledge: self.http2,
+            timeout: seconds(self.http_timeout),
+            proxy: self.proxy.clone().map(|url| ProxyConfig {
+                url,
+                username: self.proxy_user.clone(),
+                password: self.proxy_password.clone(),
+                no_proxy: self.no_proxy.clone(),
+            }),
+            ca_certificates: self.ca_certificates.clone(),
+            client_certificate: self.tls_client_certificate.clone(),
+            client_key: self.tls_client_key.clone(),
+        }
+    }
+
+    pub fn retry_config(&self) -> RetryConfig {
+        RetryConfig {
+            max_attempts: self.retry_max_attempts.max(1),
+            jitter: self.retry_jitter,
+            ..Default::default()
+        }
+    }
+
+    pub fn rate_limit_config(&self) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_second: self.rate_limit,
+            burst: self.rate_limit_burst,
+        }
+    }
+
+    pub fn circuit_breaker_config(&self) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: self.circuit_breaker_threshold,
+            open_duration: Duration::from_secs(self.circuit_breaker_open_duration),
+        }
+    }
+
+    pub fn api_version_config(&self) -> ApiVersionConfig {
+        ApiVersionConfig {
+            default: self.api_version.clone(),
+            overrides: self.api_versions.clone(),
+        }
+    }
+}
+
+/// Tools offered and how long they can run
+#[derive(Args, Debug)]
+#[command(next_help_heading = "Tools")]
+pub struct ToolOptions {
+    /// Seconds a tool can run before it fails and its requests are stopped, 0 disables it
+    #[arg(long, env = "AZDO_TOOL_TIMEOUT", default_value_t = 300)]
+    pub tool_timeout: u64,
+
+    /// Timeouts of specific tools, comma separated `tool=seconds` pairs, 0 disables it
+    /// (e.g. `azdo_query_work_items=600`)
+    #[arg(long, env = "AZDO_TOOL_TIMEOUTS", value_delimiter = ',', value_parser = parse_tool_timeout)]
+    pub tool_timeouts: Vec<(String, u64)>,
+
+    /// Estimated tokens of a tool result before it is truncated, with a marker telling
+    /// how to get the rest, 0 disables it
+    #[arg(long, env = "AZDO_MAX_RESULT_TOKENS", default_value_t = 20000)]
+    pub max_result_tokens: usize,
+
+    /// Time zone the timestamps of the tool results are written in, an IANA name (e.g.
+    /// `Europe/Rome`), `local` or a POSIX TZ string, as returned by Azure DevOps (UTC)
+    /// when absent
+    #[arg(long, env = "AZDO_TIMEZONE", value_parser = parse_timezone)]
+    pub timezone: Option<TimeZone>,
+
+    /// Append how long ago the timestamps of the tool results are, e.g. `(3 days ago)`
+    #[arg(long, env = "AZDO_RELATIVE_DATES")]
+    pub relative_dates: bool,
+
+    /// Offer only the read-only tools, until changed through the admin endpoint
+    #[arg(long, env = "AZDO_READ_ONLY")]
+    pub read_only: bool,
+
+    /// Tool groups not offered, comma separated (e.g. `service_hooks,notifications`),
+    /// until changed through the admin endpoint
+    #[arg(long, env = "AZDO_DISABLED_TOOL_GROUPS", value_delimiter = ',')]
+    pub disabled_tool_groups: Vec<String>,
+
+    /// Tools offered, comma separated name globs where `*` matches any text (e.g.
+    /// `azdo_query_*,azdo_*_board*`), all of them when absent, until changed through
+    /// the admin endpoint
+    #[arg(long, env = "AZDO_ALLOW_TOOLS", value_delimiter = ',')]
+    pub allow_tools: Vec<String>,
+
+    /// Tools not offered, comma separated name globs (e.g. `azdo_delete_*`), even when
+    /// allowed, until changed through the admin endpoint
+    #[arg(long, env = "AZDO_DENY_TOOLS", value_delimiter = ',')]
+    pub deny_tools: Vec<String>,
+
+    /// Organization the tools act on when their arguments omit it
+    #[arg(long, env = "AZDO_DEFAULT_ORGANIZATION")]
+    pub default_organization: Option<String>,
+
+    /// Project the tools act on when their arguments omit it
+    #[arg(long, env = "AZDO_DEFAULT_PROJECT")]
+    pub default_project: Option<String>,
+
+    /// Team the tools act on when their arguments omit it, except the tools deleting or
+    /// overwriting data
+    #[arg(long, env = "AZDO_DEFAULT_TEAM")]
+    pub default_team: Option<String>,
+
+    /// Named WIQL queries the agents can list and run, `name=WIQL` pairs whose `{param}`
+    /// or `{param=default}` placeholders are replaced by the given values, usually a
+    /// `[queries]` table of the config file (e.g. `my_bugs = "SELECT [System.Id] FROM
+    /// WorkItems WHERE [System.AssignedTo] = @Me AND [System.WorkItemType] = 'Bug'"`)
+    #[arg(long, value_parser = parse_named_query)]
+    pub queries: Vec<NamedQuery>,
+
+    /// Area paths the tools can create and change work items under, comma separated,
+    /// child paths included (e.g. `Web\\Payments`), any when absent
+    #[arg(long, env = "AZDO_POLICY_ALLOWED_AREA_PATHS", value_delimiter = ',')]
+    pub policy_allowed_area_paths: Vec<String>,
+
+    /// Fields the work items changed by the tools must have, comma separated `Field` or
+    /// `Field if Field=value` rules by simplified or reference name (e.g.
+    /// `IterationPath if State=Active`)
+    #[arg(long, env = "AZDO_POLICY_REQUIRED_FIELDS", value_delimiter = ',', value_parser = parse_required_field)]
+    pub policy_required_fields: Vec<(String, Option<(String, String)>)>,
+
+    /// Work items a tool call can create or change, 0 disables it
+    #[arg(long, env = "AZDO_POLICY_MAX_BULK_ITEMS", default_value_t = 0)]
+    pub policy_max_bulk_items: usize,
+}
+
+impl ToolOptions {
+    pub fn tool_timeouts(&self) -> ToolTimeouts {
+        ToolTimeouts {
+            default: seconds(self.tool_timeout),
+            overrides: self
+                .tool_timeouts
+                .iter()
+                .map(|(tool, secs)| (tool.clone(), seconds(*secs)))
+                .collect(),
+        }
+    }
+
+    pub fn tool_access(&self) -> ToolAccess {
+        ToolAccess {
+            read_only: self.read_only,
+            disabled_groups: self.disabled_tool_groups.iter().cloned().collect(),
+            allowed_tools: self.allow_tools.iter().cloned().collect(),
+            denied_tools: self.deny_tools.iter().cloned().collect(),
+        }
+    }
+
+    pub fn result_budget(&self) -> ResultBudget {
+        ResultBudget {
+            max_tokens: self.max_result_tokens,
+        }
+    }
+
+    pub fn date_rendering(&self) -> DateRendering {
+        DateRendering {
+            timezone: self.timezone.clone(),
+            relative: self.relative_dates,
+        }
+    }
+
+    pub fn tool_defaults(&self) -> ToolDefaults {
+        ToolDefaults {
+            organization: self.default_organization.clone(),
+            project: self.default_project.clone(),
+            team: self.default_team.clone(),
+        }
+    }
+
+    pub fn write_policy(&self) -> WritePolicy {
+        let reference_name = |field: &str| reference_names(&[field.to_string()]).remove(0);
+        WritePolicy {
+            allowed_area_paths: self.policy_allowed_area_paths.clone(),
+            required_fields: self
+                .policy_required_fields
+                .iter()
+                .map(|(field, condition)| RequiredField {
+                    field: reference_name(field),
+                    condition: condition
+                        .as_ref()
+                        .map(|(field, value)| (reference_name(field), value.clone())),
+                })
+                .collect(),
+            max_bulk_items: (self.policy_max_bulk_items > 0).then_some(self.policy_max_bulk_items),
+        }
+    }
+
+    pub fn named_queries(&self) -> NamedQueries {
+        NamedQueries::new(self.queries.iter().cloned())
+    }
+}
+
+/// HTTP server of `serve http`
+#[derive(Args, Debug)]
+#[command(next_help_heading = "HTTP server")]
+pub struct HttpServerOptions {
+    /// Port to run the server on
+    #[arg(long, default_value_t = 3000)]
+    pub port: u16,
+
+    /// PEM file with the certificate chain to serve HTTPS with, with `serve http` (requires
+    /// `--https-key`)
+    #[arg(long, env = "AZDO_HTTPS_CERTIFICATE", requires = "https_key")]
+    pub https_certificate: Option<PathBuf>,
+
+    /// PEM file with the private key of the HTTPS certificate (PKCS#8, PKCS#1 or SEC1)
+    #[arg(long, env = "AZDO_HTTPS_KEY", requires = "https_certificate")]
+    pub https_key: Option<PathBuf>,
+
+    /// Seconds between checks of the HTTPS certificate files, reloading them when they
+    /// change, 0 disables it
+    #[arg(long, env = "AZDO_HTTPS_RELOAD_INTERVAL", default_value_t = 0)]
+    pub https_reload_interval: u64,
+
+    /// Bearer tokens the MCP clients of the HTTP server must present, comma separated
+    #[arg(
+        long,
+        env = "AZDO_HTTP_TOKENS",
+        hide_env_values = true,
+        value_delimiter = ','
+    )]
+    pub http_tokens: Vec<String>,
+
+    /// Issuer of the JWTs the MCP clients of the HTTP server may present instead, e.g.
+    /// `https://login.microsoftonline.com/{tenant}/v2.0` (requires `--http-jwt-audience`)
+    #[arg(long, env = "AZDO_HTTP_JWT_ISSUER")]
+    pub http_jwt_issuer: Option<String>,
+
+    /// Audience of the JWTs the MCP clients of the HTTP server may present
+    #[arg(long, env = "AZDO_HTTP_JWT_AUDIENCE")]
+    pub http_jwt_audience: Option<String>,
+
+    /// Refuse the HTTP sessions whose user does not give their own Azure DevOps
+    /// credential (`X-AzDO-PAT` or `X-AzDO-Token` header)
+    #[arg(long, env = "AZDO_REQUIRE_SESSION_CREDENTIALS")]
+    pub require_session_credentials: bool,
+
+    /// Browser origins allowed to call the HTTP server (e.g. `https://app.example.com`),
+    /// comma separated, `*` allowing any, none allowed by default
+    #[arg(long, env = "AZDO_CORS_ORIGINS", value_delimiter = ',')]
+    pub cors_origins: Vec<String>,
+
+    /// Request headers browsers may send, comma separated, besides those of MCP and of
+    /// the authentication
+    #[arg(long, env = "AZDO_CORS_HEADERS", value_delimiter = ',')]
+    pub cors_headers: Vec<String>,
+
+    /// Let browsers send their credentials (cookies, HTTP authentication) to the
+    /// allowed origins
+    #[arg(long, env = "AZDO_CORS_CREDENTIALS")]
+    pub cors_credentials: bool,
+
+    /// Sustained requests per second each client of the HTTP server (by bearer token,
+    /// else session, else IP address) can send, 0 disables the limit
+    #[arg(long, env = "AZDO_CLIENT_RATE_LIMIT", default_value_t = 0.0)]
+    pub client_rate_limit: f64,
+
+    /// Requests each client of the HTTP server can send at once after an idle period
+    #[arg(long, env = "AZDO_CLIENT_RATE_LIMIT_BURST", default_value_t = 20)]
+    pub client_rate_limit_burst: u32,
+
+    /// Requests, such as tool calls, each client of the HTTP server can have processed at
+    /// once, 0 disables the cap
+    #[arg(long, env = "AZDO_CLIENT_MAX_CONCURRENT", default_value_t = 0)]
+    pub client_max_concurrent: usize,
+
+    /// Seconds the tool calls running on SIGTERM or SIGINT have to finish before the
+    /// server stops
+    #[arg(long, env = "AZDO_DRAIN_TIMEOUT", default_value_t = 30)]
+    pub drain_timeout: u64,
+
+    /// Bearer token of the admin endpoint changing the tools offered at runtime, in server
+    /// mode, the endpoint being disabled without it
+    #[arg(long, env = "AZDO_ADMIN_TOKEN", hide_env_values = true)]
+    pub admin_token: Option<String>,
+
+    /// Shared secret of the service hooks posting work item events to the HTTP server,
+    /// which forwards them to the subscribed sessions, the endpoint being disabled
+    /// without it
+    #[arg(long, env = "AZDO_SERVICE_HOOK_SECRET", hide_env_values = true)]
+    pub service_hook_secret: Option<String>,
+
+    /// URL Azure DevOps reaches the HTTP server at (e.g. `https://mcp.contoso.com`), for
+    /// the service hooks created by `azdo_subscribe_work_item_notifications`
+    #[arg(long, env = "AZDO_PUBLIC_URL", requires = "service_hook_secret")]
+    pub public_url: Option<String>,
+}
+
+impl HttpServerOptions {
+    pub fn tls_config(&self) -> Option<TlsConfig> {
+        self.https_certificate
+            .clone()
+            .zip(self.https_key.clone())
+            .map(|(certificate, key)| TlsConfig {
+                certificate,
+                key,
+                reload_interval: seconds(self.https_reload_interval),
+            })
+    }
+
+    pub fn http_auth_config(&self) -> HttpAuthConfig {
+        HttpAuthConfig {
+            tokens: self.http_tokens.clone(),
+            jwt_issuer: self.http_jwt_issuer.clone(),
+            jwt_audience: self.http_jwt_audience.clone(),
+        }
+    }
+
+    pub fn cors_config(&self) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: self.cors_origins.clone(),
+            allowed_headers: self.cors_headers.clone(),
+            allow_credentials: self.cors_credentials,
+        }
+    }
+
+    /// Receiver of the service hooks created for the change notifications, when the
+    /// server is reachable by Azure DevOps
+    pub fn service_hook_receiver(&self) -> Option<ServiceHookReceiver> {
+        let url = self.public_url.as_ref()?;
+        Some(ServiceHookReceiver {
+            url: format!("{}{}", url.trim_end_matches('/'), SERVICE_HOOK_PATH),
+            secret: self.service_hook_secret.clone()?,
+        })
+    }
+
+    pub fn client_limit_config(&self) -> ClientLimitConfig {
+        ClientLimitConfig {
+            requests_per_second: self.client_rate_limit,
+            burst: self.client_rate_limit_burst,
+            max_concurrent: self.client_max_concurrent,
+        }
+    }
+}
+
+/// Records of what the server does
+#[derive(Args, Debug)]
+#[command(next_help_heading = "Output")]
+pub struct OutputOptions {
+    /// JSON Lines file the tool invocations are appended to, with their caller, redacted
+    /// arguments and the Azure DevOps changes they made, `-` writing to stdout in server
+    /// mode
+    #[arg(long, env = "AZDO_AUDIT_LOG")]
+    pub audit_log: Option<PathBuf>,
+
+    /// OTLP/HTTP collector the tool and request spans are exported to (requires the
+    /// `otlp` feature)
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl AppConfig {
+    /// Settings of the process, exiting with the usage on invalid ones
+    pub fn load() -> Self {
+        Self::try_load_from(std::env::args_os()).unwrap_or_else(|e| e.exit())
+    }
+
+    /// What to run, serving MCP over stdio without a command
+    pub fn subcommand(&self) -> Command {
+        self.command.clone().unwrap_or(Command::Serve {
+            transport: Transport::Stdio,
+        })
+    }
+
+    /// Command line parser, whose settings can also be given after the subcommands
+    fn parser() -> clap::Command {
+        <Self as CommandFactory>::command().mut_args(|arg| arg.global(true))
+    }
+
+    /// Parse the command line, falling back on the environment and then on the config
+    /// file, whose settings are passed as the flags they are named after
+    pub fn try_load_from<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        let mut command = Self::parser();
+        // The settings already given, and the config file, are found ignoring the errors,
+        // which are reported by the final parse
+        let given = command
+            .clone()
+            .ignore_errors(true)
+            .try_get_matches_from(&args)?;
+        let Some(path) = given.get_one::<PathBuf>("config") else {
+            return Self::from_arg_matches(&command.try_get_matches_from(args)?);
+        };
+        let file = std::fs::read_to_string(path)
+            .map_err(|e| command.error(ErrorKind::Io, format!("{}: {}", path.display(), e)))?;
+        let settings = parse_settings(&file).map_err(|e| {
+            command.error(
+                ErrorKind::InvalidValue,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+
+        let mut file_args = Vec::new();
+        for (name, values) in settings {
+            let Some(arg) = command.get_arguments().find(|arg| {
+                !NOT_SETTINGS.contains(&arg.get_id().as_str())
+                    && arg.get_long().map(|long| long.replace('-', "_")) == Some(name.clone())
+            }) else {
+                return Err(command.error(
+                    ErrorKind::UnknownArgument,
+                    format!("{}: unknown setting {}", path.display(), name),
+                ));
+            };
+            if matches!(
+                given.value_source(arg.get_id().as_str()),
+                Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+            ) {
+                continue;
+            }
+            let flag = format!("--{}", arg.get_long().unwrap_or_default());
+            if arg.get_action().takes_values() {
+                file_args.extend(values.iter().map(|value| format!("{}={}", flag, value)));
+            } else if values == ["true"] {
+                file_args.push(flag);
+            } else if values != ["false"] {
+                return Err(command.error(
+                    ErrorKind::InvalidValue,
+                    format!("{}: {} expects true or false", path.display(), name),
+                ));
+            }
+        }
+
+        let args = args
+            .iter()
+            .take(1)
+            .cloned()
+            .chain(file_args.into_iter().map(OsString::from))
+            .chain(args.iter().skip(1).cloned());
+        Self::from_arg_matches(&command.try_get_matches_from(args)?)
+    }
+}
+
+/// Settings of a TOML config file, with their values as given on the command line. The
+/// names of the tables prefix the settings they contain, `[cache] ttl_teams = 60`
+/// setting `cache_ttl_teams`, except the tables of `name = value` entries.
+fn parse_settings(file: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let document = toml_edit::Document::parse(file).map_err(|e| e.to_string())?;
+    let mut settings = Vec::new();
+    collect_settings("", document.as_table(), &mut settings)?;
+    Ok(settings)
+}
+
+fn collect_settings(
+    prefix: &str,
+    table: &dyn toml_edit::TableLike,
+    settings: &mut Vec<(String, Vec<String>)>,
+) -> Result<(), String> {
+    for (key, item) in table.iter() {
+        let name = format!("{}{}", prefix, key.replace('-', "_"));
+        if let Some(table) = item.as_table_like()
+            && TABLE_SETTINGS.contains(&name.as_str())
+        {
+            let values = table
+                .iter()
+                .map(|(key, item)| match item.as_value() {
+                    Some(value) => Ok(format!("{}={}", key, scalar(&name, value)?)),
+                    None => Err(format!("invalid value of {}.{}", name, key)),
+                })
+                .collect::<Result<_, _>>()?;
+            settings.push((name, values));
+            continue;
+        }
+        if let Some(table) = item.as_table_like() {
+            collect_settings(&format!("{}_", name), table, settings)?;
+            continue;
+        }
+        let values = match item.as_value() {
+            Some(toml_edit::Value::Array(array)) => array
+                .iter()
+                .map(|value| scalar(&name, value))
+                .collect::<Result<_, _>>()?,
+            Some(value) => vec![scalar(&name, value)?],
+            None => return Err(format!("invalid value of {}", name)),
+        };
+        settings.push((name, values));
+    }
+    Ok(())
+}
+
+fn scalar(name: &str, value: &toml_edit::Value) -> Result<String, String> {
+    match value {
+        toml_edit::Value::String(value) => Ok(value.value().clone()),
+        toml_edit::Value::Integer(value) => Ok(value.value().to_string()),
+        toml_edit::Value::Float(value) => Ok(value.value().to_string()),
+        toml_edit::Value::Boolean(value) => Ok(value.value().to_string()),
+        toml_edit::Value::Datetime(value) => Ok(value.value().to_string()),
+        _ => Err(format!("invalid value of {}", name)),
+    }
+}
+
+fn seconds(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+fn parse_tool_timeout(value: &str) -> Result<(String, u64), String> {
+    let (tool, seconds) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected tool=seconds, got {}", value))?;
+    let seconds = seconds
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid timeout of {}: {}", tool, e))?;
+    Ok((tool.trim().to_string(), seconds))
+}
+
+fn parse_timezone(value: &str) -> Result<TimeZone, String> {
+    let timezone = match value.trim() {
+        "local" => TimeZone::local(),
+        "UTC" | "utc" => Ok(TimeZone::utc()),
+        name => TimeZone::from_posix_tz(name),
+    };
+    timezone.map_err(|_| {
+        format!(
+            "unknown time zone {}, expected an IANA name (e.g. Europe/Rome), local or a POSIX TZ string",
+            value
+        )
+    })
+}
+
+fn parse_required_field(value: &str) -> Result<(String, Option<(String, String)>), String> {
+    let (field, condition) = match value.split_once(" if ") {
+        Some((field, condition)) => {
+            let (other, expected) = condition
+                .split_once('=')
+                .ok_or_else(|| format!("expected Field if Field=value, got {}", value))?;
+            (
+                field,
+                Some((other.trim().to_string(), expected.trim().to_string())),
+            )
+        }
+        None => (value, None),
+    };
+    match field.trim() {
+        "" => Err(format!("no required field in {}", value)),
+        field => Ok((field.to_string(), condition)),
+    }
+}
+
+fn parse_named_query(value: &str) -> Result<NamedQuery, String> {
+    let (name, wiql) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=WIQL, got {}", value))?;
+    NamedQuery::new(name, wiql)
+}
+
+fn parse_api_version(value: &str) -> Result<(String, String), String> {
+    let (endpoint, version) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected endpoint=version, got {}", value))?;
+    Ok((endpoint.trim().to_string(), version.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings() {
+        let settings = parse_settings(
+            r#"
+            auth = ["pat"]
+            read_only = true
+            [cache]
+            ttl-teams = 60
+            [queries]
+            my_bugs = "SELECT [System.Id] FROM WorkItems WHERE [System.AssignedTo] = @Me"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings,
+            vec![
+                ("auth".to_string(), vec!["pat".to_string()]),
+                ("read_only".to_string(), vec!["true".to_string()]),
+                ("cache_ttl_teams".to_string(), vec!["60".to_string()]),
+                (
+                    "queries".to_string(),
+                    vec![
+                        "my_bugs=SELECT [System.Id] FROM WorkItems WHERE [System.AssignedTo] = @Me"
+                            .to_string()
+                    ]
+                ),
+            ]
+        );
+        assert!(parse_settings("auth = [[1]]").is_err());
+    }
+
+    #[test]
+    fn test_try_load_from() {
+        let path = std::env::temp_dir().join(format!("azdo-config-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "rate_limit = 5.0\ntool_timeout = 60\nread_only = true\n[cache]\nttl_teams = 60\n\
+             [queries]\nsprint = \"SELECT [System.Id] FROM WorkItems WHERE [System.IterationPath] = {iteration}\"\n\
+             [policy]\nallowed_area_paths = [\"Web\\\\Payments\"]\nrequired_fields = [\"IterationPath if State=Active\"]\n",
+        )
+        .unwrap();
+        let config = path.to_str().unwrap();
+
+        let loaded =
+            AppConfig::try_load_from(["azdo", "--config", config, "--tool-timeout", "120"])
+                .unwrap();
+        assert_eq!(loaded.requests.rate_limit, 5.0);
+        assert_eq!(loaded.tools.tool_timeout, 120);
+        assert!(loaded.tools.read_only);
+        assert_eq!(loaded.cache.ttl_teams, 60);
+        assert_eq!(loaded.cache.ttl_projects, 600);
+        let queries = loaded.tools.named_queries();
+        assert_eq!(
+            queries.get("sprint").unwrap().signature(),
+            "sprint{iteration}"
+        );
+        let policy = loaded.tools.write_policy();
+        assert_eq!(policy.allowed_area_paths, ["Web\\Payments"]);
+        assert_eq!(
+            policy.required_fields,
+            [RequiredField {
+                field: "System.IterationPath".to_string(),
+                condition: Some(("System.State".to_string(), "Active".to_string())),
+            }]
+        );
+        assert_eq!(policy.max_bulk_items, None);
+
+        std::fs::write(&path, "unknown = 1\n").unwrap();
+        assert!(AppConfig::try_load_from(["azdo", "--config", config]).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::credentials::{self, CredentialCheck};
+use crate::azure::{organizations, projects};
+use reqwest::Method;
+use serde_json::Value;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Projects named in the report of an organization, the others being counted
+const LISTED_PROJECTS: usize = 10;
+
+/// Round trips timed to measure the latency of an organization
+const LATENCY_SAMPLES: usize = 3;
+
+/// Findings of the self-test of the setup
+#[derive(Debug)]
+pub struct DoctorReport {
+    /// User the credentials act as, from their profile
+    pub user: Result<String, String>,
+    pub organizations: Vec<OrganizationReport>,
+    /// Problems found before checking the organizations
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct OrganizationReport {
+    pub organization: String,
+    /// Validity of the credentials, scopes probed and api-version support
+    pub check: Result<CredentialCheck, String>,
+    /// Names of the projects accessible
+    pub projects: Result<Vec<String>, String>,
+    /// Median round trip of a small request to the organization
+    pub latency: Option<Duration>,
+}
+
+impl DoctorReport {
+    /// Whether the setup works: every organization accepts the credentials with all the
+    /// scopes probed granted, and supports the api-version
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+            && !self.organizations.is_empty()
+            && self.organizations.iter().all(|org| {
+                org.check.as_ref().is_ok_and(|check| {
+                    check.valid
+                        && check.scopes.iter().all(|s| s.granted != Some(false))
+                        && check.api_version.supported != Some(false)
+                })
+            })
+    }
+}
+
+/// Check the credentials against the given organizations, or against the ones the
+/// user is a member of when none is given. The write access is probed with a
+/// validate-only work item creation in the project, when one is given, so nothing is
+/// left to roll back.
+pub async fn run(
+    client: &dyn AzureBoardsApi,
+    organizations: Vec<String>,
+    project: Option<&str>,
+) -> DoctorReport {
+    let mut problems = Vec::new();
+    let profile = organizations::get_profile(client).await;
+    let user = match &profile {
+        Ok(profile) => Ok(format!(
+            "{} <{}>",
+            profile.display_name, profile.email_address
+        )),
+        Err(e) => Err(e.to_string()),
+    };
+
+    let organizations = if !organizations.is_empty() {
+        organizations
+    } else if let Ok(profile) = &profile {
+        match organizations::list_organizations(client, &profile.id).await {
+            Ok(found) if found.is_empty() => {
+                problems.push("The user is not a member of any organization".to_string());
+                Vec::new()
+            }
+            Ok(found) => found.into_iter().map(|org| org.account_name).collect(),
+            Err(e) => {
+                problems.push(format!(
+                    "Unable to list the organizations, pass --organization: {}",
+                    e
+                ));
+                Vec::new()
+            }
+        }
+    } else {
+        problems.push(
+            "Unable to read the user profile to find the organizations, pass --organization"
+                .to_string(),
+        );
+        Vec::new()
+    };
+
+    let mut reports = Vec::new();
+    for organization in &organizations {
+        reports.push(check_organization(client, organization, project).await);
+    }
+    DoctorReport {
+        user,
+        organizations: reports,
+        problems,
+    }
+}
+
+async fn check_organization(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: Option<&str>,
+) -> OrganizationReport {
+    let check = credentials::check_credentials(client, organization, project)
+        .await
+        .map_err(|e| e.to_string());
+    let projects = projects::list_projects(client, organization)
+        .await
+        .map(|projects| projects.into_iter().map(|project| project.name).collect())
+        .map_err(|e| e.to_string());
+    OrganizationReport {
+        organization: organization.to_string(),
+        check,
+        projects,
+        latency: measure_latency(client, organization).await,
+    }
+}
+
+/// Median round trip of the connection data request, None when it fails
+async fn measure_latency(client: &dyn AzureBoardsApi, organization: &str) -> Option<Duration> {
+    let mut samples = Vec::with_capacity(LATENCY_SAMPLES);
+    for _ in 0..LATENCY_SAMPLES {
+        let started = Instant::now();
+        // API: https://dev.azure.com/{organization}/_apis/connectionData
+        let result: Result<Value, AzureError> = client
+            .org_request(organization, Method::GET, "connectionData", None::<&String>)
+            .await;
+        result.ok()?;
+        samples.push(started.elapsed());
+    }
+    samples.sort();
+    Some(samples[samples.len() / 2])
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.user {
+            Ok(user) => writeln!(f, "User: {}", user)?,
+            Err(e) => writeln!(f, "User: unknown ({})", e)?,
+        }
+        for problem in &self.problems {
+            writeln!(f, "[FAIL] {}", problem)?;
+        }
+        for org in &self.organizations {
+            writeln!(f)?;
+            write!(f, "{}", org)?;
+        }
+        writeln!(f)?;
+        if self.is_healthy() {
+            writeln!(f, "No problem found")
+        } else {
+            writeln!(f, "Problems found, see the fixes above")
+        }
+    }
+}
+
+impl fmt::Display for OrganizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Organization {}", self.organization)?;
+        let check = match &self.check {
+            Ok(check) => check,
+            Err(e) => return writeln!(f, "  [FAIL] Unable to check the credentials: {}", e),
+        };
+        match &check.authenticated_user {
+            Some(user) => writeln!(f, "  [ OK ] Credentials accepted, as {}", user)?,
+            None => writeln!(f, "  [FAIL] Credentials rejected")?,
+        }
+        let token = &check.token;
+        match token.expires_at {
+            Some(expires_at) => writeln!(f, "  Token: {}, expires {}", token.kind, expires_at)?,
+            None => writeln!(f, "  Token: {}", token.kind)?,
+        }
+        if let Some(latency) = self.latency {
+            writeln!(f, "  Latency: {} ms", latency.as_millis())?;
+        }
+        match &self.projects {
+            Ok(projects) if projects.len() > LISTED_PROJECTS => writeln!(
+                f,
+                "  Projects: {} and {} more",
+                projects[..LISTED_PROJECTS].join(", "),
+                projects.len() - LISTED_PROJECTS
+            )?,
+            Ok(projects) if projects.is_empty() => writeln!(f, "  Projects: none")?,
+            Ok(projects) => writeln!(f, "  Projects: {}", projects.join(", "))?,
+            Err(e) => writeln!(f, "  Projects: unable to list them ({})", e)?,
+        }
+        for probe in &check.scopes {
+            let status = match probe.granted {
+                Some(true) => "[ OK ]",
+                Some(false) => "[FAIL]",
+                None => "[SKIP]",
+            };
+            writeln!(f, "  {} {} ({})", status, probe.description, probe.scope)?;
+        }
+        let api_version = &check.api_version;
+        match (api_version.supported, &api_version.supported_max) {
+            (Some(false), Some(max)) => writeln!(
+                f,
+                "  [FAIL] api-version {} (supported up to {})",
+                api_version.configured, max
+            )?,
+            (Some(true), _) => writeln!(f, "  [ OK ] api-version {}", api_version.configured)?,
+            _ => writeln!(f, "  [SKIP] api-version {}", api_version.configured)?,
+        }
+        for fix in &check.remediation {
+            writeln!(f, "  Fix: {}", fix)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::credentials::{ApiVersionCheck, ScopeProbe, TokenDetails};
+
+    fn report(granted: Option<bool>) -> DoctorReport {
+        DoctorReport {
+            user: Ok("Jane <jane@contoso.com>".to_string()),
+            organizations: vec![OrganizationReport {
+                organization: "contoso".to_string(),
+                check: Ok(CredentialCheck {
+                    organization: "contoso".to_string(),
+                    valid: true,
+                    authenticated_user: Some("Jane".to_string()),
+                    token: TokenDetails {
+                        kind: "pat",
+                        ..Default::default()
+                    },
+                    scopes: vec![ScopeProbe {
+                        scope: "vso.work_write",
+                        description: "Work Items: Read & write",
+                        granted,
+                        error: None,
+                    }],
+                    api_version: ApiVersionCheck {
+                        configured: "7.1".to_string(),
+                        supported_max: Some("7.2".to_string()),
+                        supported: Some(true),
+                    },
+                    remediation: Vec::new(),
+                }),
+                projects: Ok(vec!["Fabrikam".to_string()]),
+                latency: Some(Duration::from_millis(42)),
+            }],
+            problems: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        assert!(report(Some(true)).is_healthy());
+        assert!(report(None).is_healthy());
+        let unhealthy = report(Some(false));
+        assert!(!unhealthy.is_healthy());
+        assert_eq!(
+            unhealthy.to_string(),
+            "User: Jane <jane@contoso.com>\n\
+             \n\
+             Organization contoso\n  \
+             [ OK ] Credentials accepted, as Jane\n  \
+             Token: pat\n  \
+             Latency: 42 ms\n  \
+             Projects: Fabrikam\n  \
+             [FAIL] Work Items: Read & write (vso.work_write)\n  \
+             [ OK ] api-version 7.1\n\
+             \n\
+             Problems found, see the fixes above\n"
+        );
+    }
+}
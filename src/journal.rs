@@ -0,0 +1,206 @@
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Changes a session keeps, the oldest being forgotten
+const MAX_ENTRIES: usize = 500;
+
+/// Change made to a work item, with what its undoing needs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// Work item created, undone by deleting it
+    Created,
+    /// Fields set, undone by restoring their previous values, null when they were empty
+    Updated { previous: BTreeMap<String, Value> },
+    /// Link added, undone by removing it
+    Linked { rel: String, url: String },
+    /// Comment added, undone by deleting it
+    Commented { comment_id: u64 },
+}
+
+/// Change recorded in the journal of a session
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalEntry {
+    pub id: u64,
+    /// Tool invocation that made the change, shared by the changes of a bulk tool
+    pub invocation: u64,
+    pub tool: String,
+    /// RFC 3339 UTC timestamp of the change
+    pub time: String,
+    pub organization: String,
+    pub project: String,
+    pub work_item_id: u32,
+    /// Revision of the work item after the change, telling whether it changed since
+    pub rev: Option<i64>,
+    #[serde(flatten)]
+    pub change: Change,
+    /// JSON patch or body sent
+    pub request: Value,
+    pub undone: bool,
+}
+
+/// Work item changes made by the tools of a session, most recent last
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: VecDeque<JournalEntry>,
+    next_id: u64,
+    next_invocation: u64,
+}
+
+impl Journal {
+    /// Most recent changes first
+    pub fn recent(&self, max: usize) -> Vec<JournalEntry> {
+        self.entries.iter().rev().take(max).cloned().collect()
+    }
+
+    /// Changes of the last tool invocation having changes not undone yet, most recent
+    /// first
+    pub fn last_invocation(&self) -> Vec<JournalEntry> {
+        let Some(invocation) = self
+            .entries
+            .iter()
+            .rev()
+            .find(|entry| !entry.undone)
+            .map(|entry| entry.invocation)
+        else {
+            return Vec::new();
+        };
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.invocation == invocation && !entry.undone)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<JournalEntry> {
+        self.entries.iter().find(|entry| entry.id == id).cloned()
+    }
+
+    pub fn mark_undone(&mut self, id: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.undone = true;
+        }
+    }
+
+    fn push(&mut self, mut entry: JournalEntry) {
+        self.next_id += 1;
+        entry.id = self.next_id;
+        self.entries.push_back(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Journal of a session, shared by its tool invocations
+pub type SessionJournal = Arc<Mutex<Journal>>;
+
+/// Journal and tool of an invocation
+#[derive(Clone)]
+struct Recorder {
+    journal: SessionJournal,
+    tool: String,
+    invocation: u64,
+}
+
+tokio::task_local! {
+    static RECORDER: Recorder;
+}
+
+/// Run a tool invocation, recording the work item changes it makes in the journal
+pub async fn scope<F: Future>(journal: SessionJournal, tool: &str, future: F) -> F::Output {
+    let invocation = {
+        let mut journal = journal.lock().unwrap();
+        journal.next_invocation += 1;
+        journal.next_invocation
+    };
+    let recorder = Recorder {
+        journal,
+        tool: tool.to_string(),
+        invocation,
+    };
+    RECORDER.scope(recorder, future).await
+}
+
+/// Whether the changes are recorded, within a [`scope`], so that the previous values
+/// are only fetched when needed
+pub fn is_recording() -> bool {
+    RECORDER.try_with(|_| ()).is_ok()
+}
+
+/// Journal of the running invocation, within a [`scope`]
+pub fn current() -> Option<SessionJournal> {
+    RECORDER.try_with(|recorder| recorder.journal.clone()).ok()
+}
+
+/// Record a change of a work item, does nothing outside of a [`scope`]
+pub fn record(
+    organization: &str,
+    project: &str,
+    work_item_id: u32,
+    rev: Option<i64>,
+    change: Change,
+    request: Value,
+) {
+    let _ = RECORDER.try_with(|recorder| {
+        recorder.journal.lock().unwrap().push(JournalEntry {
+            id: 0,
+            invocation: recorder.invocation,
+            tool: recorder.tool.clone(),
+            time: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            organization: organization.to_string(),
+            project: project.to_string(),
+            work_item_id,
+            rev,
+            change,
+            request,
+            undone: false,
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope() {
+        let journal = SessionJournal::default();
+        record("contoso", "Web", 1, Some(1), Change::Created, Value::Null);
+        assert!(journal.lock().unwrap().recent(10).is_empty());
+
+        scope(journal.clone(), "azdo_create_work_item", async {
+            record("contoso", "Web", 1, Some(1), Change::Created, Value::Null);
+        })
+        .await;
+        scope(journal.clone(), "azdo_update_work_item", async {
+            for id in [1, 2] {
+                let previous = BTreeMap::from([("System.State".to_string(), "New".into())]);
+                record(
+                    "contoso",
+                    "Web",
+                    id,
+                    Some(2),
+                    Change::Updated { previous },
+                    Value::Null,
+                );
+            }
+        })
+        .await;
+
+        let mut journal = journal.lock().unwrap();
+        let last: Vec<_> = journal.last_invocation().iter().map(|e| e.id).collect();
+        assert_eq!(last, [3, 2]);
+        journal.mark_undone(3);
+        journal.mark_undone(2);
+        let last = journal.last_invocation();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].tool, "azdo_create_work_item");
+        assert_eq!(journal.recent(2)[0].id, 3);
+    }
+}
@@ -1,4 +1,12 @@
+pub mod analytics;
+pub mod audit;
 pub mod azure;
 pub mod compact_llm;
+pub mod config;
+pub mod doctor;
+pub mod journal;
 pub mod mcp;
+pub mod progress;
 pub mod server;
+pub mod store;
+pub mod telemetry;
@@ -1,38 +1,323 @@
-use clap::Parser;
-use mcp_for_azure_devops_boards::azure::client::AzureDevOpsClient;
+use mcp_for_azure_devops_boards::audit::AuditLog;
+use mcp_for_azure_devops_boards::azure::auth::{self, AuthConfig};
+use mcp_for_azure_devops_boards::azure::client::{AZURE_DEVOPS_SCOPE, AzureDevOpsClient};
+use mcp_for_azure_devops_boards::azure::context;
+use mcp_for_azure_devops_boards::azure::fixtures::Fixtures;
+use mcp_for_azure_devops_boards::azure::transport::{Recorder, Replay};
+use mcp_for_azure_devops_boards::config::{AppConfig, Command, ToolsCommand, Transport};
+use mcp_for_azure_devops_boards::doctor;
 use mcp_for_azure_devops_boards::mcp::server::AzureMcpServer;
-use mcp_for_azure_devops_boards::server::http;
-use rmcp::ServiceExt;
+use mcp_for_azure_devops_boards::server::auth::HttpAuth;
+use mcp_for_azure_devops_boards::server::http::{self, HttpServerConfig};
+use mcp_for_azure_devops_boards::server::service_hooks::SERVICE_HOOK_PATH;
+use mcp_for_azure_devops_boards::store::WorkItemStore;
+use mcp_for_azure_devops_boards::telemetry;
+use rmcp::model::{CallToolRequestParam, RawContent};
 use rmcp::transport::stdio;
-
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Run in server mode
-    #[arg(long)]
-    server: bool,
-
-    /// Port to run the server on
-    #[arg(long, default_value_t = 3000)]
-    port: u16,
-}
+use rmcp::{ServiceError, ServiceExt};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    env_logger::init();
-    let args = Args::parse();
+    let config = AppConfig::load();
+    let command = config.subcommand();
+    let _telemetry = telemetry::init(config.output.otlp_endpoint.as_deref())?;
 
-    let client = AzureDevOpsClient::new();
-    let mcp_server = AzureMcpServer::new(client);
+    let http_config = config.requests.http_config();
+    let auth_http = http_config.auth_client()?;
+    let http_auth = HttpAuth::new(config.http_server.http_auth_config(), auth_http.clone())?;
+    let auth_options = config.auth;
 
-    if args.server {
-        log::info!("Starting web server on port {}", args.port);
-        http::run_server(mcp_server, args.port).await?;
-    } else {
-        log::info!("Starting stdio server");
-        let service = mcp_server.serve(stdio()).await?;
-        service.waiting().await?;
+    if let Some(organization) = config.store_credentials {
+        let token = if auth_options.pat.is_empty() {
+            eprint!("Personal access tokens (comma separated): ");
+            let mut token = String::new();
+            std::io::stdin().read_line(&mut token)?;
+            token.trim().to_string()
+        } else {
+            auth_options.pat.join(",")
+        };
+        if token.is_empty() {
+            return Err("No personal access token given".into());
+        }
+        auth::PatCredential::save(organization.as_deref(), &token)?;
+        eprintln!("Personal access token stored in the OS keyring");
+        return Ok(());
     }
 
+    if let Some(organization) = config.login {
+        let store =
+            auth::RefreshTokenStore::open(auth_options.credential_store, organization.as_deref())
+                .ok_or("Unable to locate the configuration directory")?;
+        auth::device_code::login(
+            &auth_http,
+            auth_options.cloud,
+            auth_options
+                .tenant_id
+                .as_deref()
+                .unwrap_or(auth::device_code::DEFAULT_TENANT),
+            auth_options
+                .client_id
+                .as_deref()
+                .unwrap_or(auth::device_code::DEFAULT_CLIENT_ID),
+            AZURE_DEVOPS_SCOPE,
+            &store,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let profiles = match auth_options.profiles.as_deref() {
+        Some(path) => auth::load_profiles(path)?,
+        None => Vec::new(),
+    };
+    let credentials = auth::build_credential_pool(
+        &AuthConfig {
+            methods: auth_options.methods,
+            tenant_id: auth_options.tenant_id,
+            client_id: auth_options.client_id,
+            client_secret: auth_options.client_secret,
+            client_certificate_path: auth_options.client_certificate,
+            pats: auth_options.pat,
+            credential_store: auth_options.credential_store,
+            organization: None,
+            cloud: auth_options.cloud,
+            http: auth_http,
+        },
+        &profiles,
+    )?;
+    for organization in credentials.organizations() {
+        tracing::info!(
+            "Loaded authentication profile for organization {}",
+            organization
+        );
+    }
+    if let Command::Doctor {
+        organization,
+        project,
+    } = &command
+    {
+        let client = AzureDevOpsClient::with_credentials(credentials)
+            .with_http_config(&http_config)?
+            .with_cloud(auth_options.cloud)
+            .with_api_versions(config.requests.api_version_config())
+            .with_retry_config(config.requests.retry_config());
+        let tools = &config.tools;
+        let organizations = match (organization.is_empty(), &tools.default_organization) {
+            (true, Some(default)) => vec![default.clone()],
+            _ => organization.clone(),
+        };
+        let project = project.as_ref().or(tools.default_project.as_ref());
+        let report = context::scope(
+            client.context(),
+            doctor::run(&client, organizations, project.map(String::as_str)),
+        )
+        .await;
+        print!("{}", report);
+        if !report.is_healthy() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    let requests = &config.requests;
+    let mut client = AzureDevOpsClient::with_credentials(credentials)
+        .with_http_config(&http_config)?
+        .with_cloud(auth_options.cloud)
+        .with_api_versions(requests.api_version_config())
+        .with_cache_config(config.cache.cache_config())
+        .with_retry_config(requests.retry_config())
+        .with_rate_limit(requests.rate_limit_config())
+        .with_circuit_breaker(requests.circuit_breaker_config())
+        .with_named_queries(config.tools.named_queries())
+        .with_write_policy(config.tools.write_policy());
+    if let Some(dir) = &requests.mock {
+        client = client.with_transport(Arc::new(Fixtures::new(dir)));
+        tracing::info!(
+            "Serving the requests from the fixtures in {}",
+            dir.display()
+        );
+    }
+    if let Some(path) = &requests.record {
+        client = client.with_transport(Arc::new(Recorder::create(path)?));
+        tracing::info!("Recording the requests to {}", path.display());
+    }
+    if let Some(path) = &requests.replay {
+        client = client.with_transport(Arc::new(Replay::open(path)?));
+        tracing::info!("Replaying the requests recorded in {}", path.display());
+    }
+    if let Some(store_config) = config.cache.store_config() {
+        client = client.with_work_item_store(Arc::new(WorkItemStore::open(store_config)?));
+    }
+    let server_options = &config.http_server;
+    if let Some(receiver) = server_options.service_hook_receiver() {
+        client = client.with_service_hook_receiver(receiver);
+    }
+    let mut mcp_server = AzureMcpServer::new(client)
+        .with_tool_timeouts(config.tools.tool_timeouts())
+        .with_tool_defaults(config.tools.tool_defaults())
+        .with_result_budget(config.tools.result_budget())
+        .with_date_rendering(config.tools.date_rendering())
+        .with_required_session_credentials(server_options.require_session_credentials)
+        .with_tool_access(config.tools.tool_access())?;
+    let serves_http = command
+        == Command::Serve {
+            transport: Transport::Http,
+        };
+    if let Some(path) = &config.output.audit_log {
+        if !serves_http && path.as_os_str() == "-" {
+            return Err("The audit log can be written to stdout only by the HTTP server".into());
+        }
+        mcp_server = mcp_server.with_audit_log(AuditLog::open(path)?);
+        tracing::info!("Auditing the tool invocations to {}", path.display());
+    }
+    let drain_timeout = Duration::from_secs(server_options.drain_timeout);
+
+    match command {
+        Command::Serve {
+            transport: Transport::Http,
+        } => {
+            tracing::info!("Starting web server on port {}", server_options.port);
+            if !http_auth.is_enabled() {
+                tracing::warn!(
+                    "The HTTP server accepts unauthenticated requests, set --http-tokens or --http-jwt-issuer"
+                );
+            }
+            if server_options.admin_token.is_some() {
+                tracing::info!("Admin endpoint enabled on {}", http::ADMIN_TOOLS_PATH);
+            }
+            if server_options.service_hook_secret.is_some() {
+                tracing::info!("Receiving service hook events on {}", SERVICE_HOOK_PATH);
+            }
+            http::run_server(
+                mcp_server,
+                HttpServerConfig {
+                    port: server_options.port,
+                    admin_token: server_options.admin_token.clone(),
+                    auth: http_auth,
+                    tls: server_options.tls_config(),
+                    cors: server_options.cors_config(),
+                    drain_timeout,
+                    client_limits: server_options.client_limit_config(),
+                    service_hook_secret: server_options.service_hook_secret.clone(),
+                },
+                shutdown_signal(),
+            )
+            .await?;
+        }
+        Command::Serve {
+            transport: Transport::Stdio,
+        } => {
+            tracing::info!("Starting stdio server");
+            let service = mcp_server.clone().serve(stdio()).await?;
+            tokio::select! {
+                result = service.waiting() => {
+                    result?;
+                }
+                () = shutdown_signal() => {
+                    tracing::info!(
+                        "Shutting down, waiting for {} tool calls to finish",
+                        mcp_server.tool_calls_in_flight()
+                    );
+                    if tokio::time::timeout(drain_timeout, mcp_server.tool_calls_finished())
+                        .await
+                        .is_err()
+                    {
+                        tracing::warn!("Stopping with tool calls still running after the drain timeout");
+                    }
+                }
+            }
+        }
+        Command::Tools {
+            command: ToolsCommand::List,
+        } => {
+            println!("{}", serde_json::to_string_pretty(&mcp_server.tools())?);
+        }
+        Command::Call { tool, args } => {
+            let arguments = match serde_json::from_str(&args)? {
+                serde_json::Value::Object(arguments) => arguments,
+                _ => return Err("The tool arguments must be a JSON object".into()),
+            };
+            if !call_tool(mcp_server, tool, arguments).await? {
+                std::process::exit(1);
+            }
+        }
+        Command::Doctor { .. } => unreachable!("handled before building the MCP server"),
+    }
+
+    // The telemetry is flushed when dropped, on return
     Ok(())
 }
+
+/// Invoke a tool through an in-process MCP session, printing its result to stdout,
+/// returning whether it succeeded
+async fn call_tool(
+    server: AzureMcpServer,
+    tool: String,
+    arguments: serde_json::Map<String, serde_json::Value>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    let (service, client) = tokio::join!(
+        server.serve(tokio::io::split(server_io)),
+        ().serve(tokio::io::split(client_io))
+    );
+    let (service, client) = (service?, client?);
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: tool.into(),
+            arguments: Some(arguments),
+        })
+        .await;
+    client.cancel().await?;
+    service.cancel().await?;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(ServiceError::McpError(e)) => {
+            eprintln!("{}", e.message);
+            return Ok(false);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    match &result.structured_content {
+        Some(structured) => println!("{}", serde_json::to_string_pretty(structured)?),
+        None => {
+            for content in &result.content {
+                match &content.raw {
+                    RawContent::Text(text) => println!("{}", text.text),
+                    other => println!("{}", serde_json::to_string_pretty(other)?),
+                }
+            }
+        }
+    }
+    Ok(result.is_error != Some(true))
+}
+
+/// Resolve on SIGINT (Ctrl+C) or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Unable to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Unable to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        () = interrupt => {}
+        () = terminate => {}
+    }
+}
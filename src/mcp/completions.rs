@@ -0,0 +1,141 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::{boards, classification_nodes, iterations, organizations, projects};
+use rmcp::model::{CompletionContext, CompletionInfo};
+
+/// Candidates of an argument, by name, given the arguments already resolved. Metadata
+/// requests go through the client cache, so completing as the user types is cheap.
+async fn candidates(
+    client: &dyn AzureBoardsApi,
+    argument: &str,
+    context: &CompletionContext,
+) -> Result<Vec<String>, AzureError> {
+    let organization = context.get_argument("organization").map(String::as_str);
+    let project = context.get_argument("project").map(String::as_str);
+    let team = context
+        .get_argument("team_id")
+        .or(context.get_argument("team"))
+        .map(String::as_str);
+
+    match (argument, organization, project) {
+        ("organization", _, _) => {
+            let profile = organizations::get_profile(client).await?;
+            let organizations = organizations::list_organizations(client, &profile.id).await?;
+            Ok(organizations.into_iter().map(|o| o.account_name).collect())
+        }
+        ("project", Some(organization), _) => {
+            let projects = projects::list_projects(client, organization).await?;
+            Ok(projects.into_iter().map(|p| p.name).collect())
+        }
+        ("team_id" | "team", Some(organization), Some(project)) => {
+            let teams = boards::list_teams(client, organization, project).await?;
+            Ok(teams.into_iter().map(|t| t.name).collect())
+        }
+        ("iteration" | "iteration_path", Some(organization), Some(project)) => match team {
+            Some(team) => {
+                let iterations =
+                    iterations::get_team_iterations(client, organization, project, team, None)
+                        .await?;
+                Ok(iterations.into_iter().map(|i| i.path).collect())
+            }
+            None => {
+                let root = classification_nodes::list_iteration_paths(
+                    client,
+                    organization,
+                    project,
+                    None,
+                    10,
+                )
+                .await?;
+                let mut paths = Vec::new();
+                root.collect_paths(&mut paths);
+                Ok(paths)
+            }
+        },
+        ("area_path", Some(organization), Some(project)) => {
+            let root =
+                classification_nodes::list_area_paths(client, organization, project, None, 10)
+                    .await?;
+            let mut paths = Vec::new();
+            root.collect_paths(&mut paths);
+            Ok(paths)
+        }
+        ("work_item_type", Some(organization), Some(project)) => {
+            let types = boards::list_work_item_types(client, organization, project).await?;
+            Ok(types.into_iter().map(|t| t.name).collect())
+        }
+        // Assignees are the members of the team, the project has no cheap member list
+        ("assigned_to", Some(organization), Some(project)) => match team {
+            Some(team) => {
+                let members = client
+                    .list_team_members(organization, project, team)
+                    .await?;
+                Ok(members
+                    .into_iter()
+                    .map(|m| m.identity.display_name)
+                    .collect())
+            }
+            None => Ok(vec![]),
+        },
+        _ => Ok(vec![]),
+    }
+}
+
+/// Keep the candidates containing the typed value, case insensitively, those starting
+/// with it first, then alphabetically
+fn matching(candidates: Vec<String>, value: &str) -> CompletionInfo {
+    let value = value.to_lowercase();
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|c| c.to_lowercase().contains(&value))
+        .collect();
+    matches.sort_by_cached_key(|c| (!c.to_lowercase().starts_with(&value), c.clone()));
+    matches.dedup();
+
+    let total = matches.len();
+    matches.truncate(CompletionInfo::MAX_VALUES);
+    CompletionInfo {
+        has_more: Some(total > matches.len()),
+        total: Some(total as u32),
+        values: matches,
+    }
+}
+
+/// Complete an argument of a prompt or resource template. Failures to get the
+/// candidates return no completion rather than an error, as the user is still typing.
+pub async fn complete(
+    client: &dyn AzureBoardsApi,
+    argument: &str,
+    value: &str,
+    context: Option<&CompletionContext>,
+) -> CompletionInfo {
+    let context = context.cloned().unwrap_or_default();
+    match candidates(client, argument, &context).await {
+        Ok(candidates) => matching(candidates, value),
+        Err(e) => {
+            tracing::debug!("Failed to complete argument {}: {}", argument, e);
+            CompletionInfo::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        let candidates = vec![
+            "Contoso\\Web".to_string(),
+            "Contoso\\Mobile\\Web".to_string(),
+            "Web".to_string(),
+        ];
+        let completion = matching(candidates, "web");
+        assert_eq!(
+            completion.values,
+            vec!["Web", "Contoso\\Mobile\\Web", "Contoso\\Web"]
+        );
+        assert_eq!(completion.total, Some(3));
+        assert_eq!(completion.has_more, Some(false));
+    }
+}
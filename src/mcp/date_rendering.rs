@@ -0,0 +1,132 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rmcp::model::{CallToolResult, RawContent};
+use tz::TimeZone;
+
+/// Timestamps of the Azure DevOps responses, e.g. `2025-01-10T14:03:27.513Z`
+static RE_TIMESTAMP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})").unwrap()
+});
+
+/// Rendering of the timestamps of the text results, which the agents otherwise compare
+/// poorly with the current date when asked what changed recently
+#[derive(Debug, Clone, Default)]
+pub struct DateRendering {
+    /// Time zone the timestamps are written in, to the minute, as given when absent
+    pub timezone: Option<TimeZone>,
+    /// Append how long ago (or in how long) the timestamps are, e.g. `(3 days ago)`
+    pub relative: bool,
+}
+
+impl DateRendering {
+    pub fn is_enabled(&self) -> bool {
+        self.timezone.is_some() || self.relative
+    }
+
+    /// Rewrite the timestamps of the text contents of a result. The structured content
+    /// is left as is, for the clients parsing it.
+    pub fn render(&self, result: &mut CallToolResult) {
+        if !self.is_enabled() {
+            return;
+        }
+        let now = Utc::now();
+        for content in &mut result.content {
+            if let RawContent::Text(text) = &mut content.raw {
+                text.text = self.render_text(&text.text, now);
+            }
+        }
+    }
+
+    fn render_text(&self, text: &str, now: DateTime<Utc>) -> String {
+        RE_TIMESTAMP
+            .replace_all(text, |captures: &Captures| {
+                let Ok(timestamp) = DateTime::parse_from_rfc3339(&captures[0]) else {
+                    return captures[0].to_string();
+                };
+                let mut rendered = match self.offset_at(timestamp) {
+                    Some(offset) => format_in(timestamp, offset),
+                    None => captures[0].to_string(),
+                };
+                if self.relative {
+                    rendered.push_str(&format!(" ({})", relative(timestamp.to_utc(), now)));
+                }
+                rendered
+            })
+            .into_owned()
+    }
+
+    /// Offset of the time zone at a timestamp
+    fn offset_at(&self, timestamp: DateTime<FixedOffset>) -> Option<FixedOffset> {
+        let time_type = self
+            .timezone
+            .as_ref()?
+            .find_local_time_type(timestamp.timestamp())
+            .ok()?;
+        FixedOffset::east_opt(time_type.ut_offset())
+    }
+}
+
+/// Timestamp to the minute with its offset, `Z` for UTC
+fn format_in(timestamp: DateTime<FixedOffset>, offset: FixedOffset) -> String {
+    let local = timestamp.with_timezone(&offset);
+    if offset.local_minus_utc() == 0 {
+        local.format("%Y-%m-%d %H:%MZ").to_string()
+    } else {
+        local.format("%Y-%m-%d %H:%M%:z").to_string()
+    }
+}
+
+/// Distance of a timestamp from now, in its largest whole unit
+fn relative(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - timestamp).num_seconds();
+    let (amount, unit) = match seconds.unsigned_abs() {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86400 => (s / 3600, "hour"),
+        s if s < 30 * 86400 => (s / 86400, "day"),
+        s if s < 365 * 86400 => (s / (30 * 86400), "month"),
+        s => (s / (365 * 86400), "year"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    if seconds > 0 {
+        format!("{} {}{} ago", amount, unit, plural)
+    } else {
+        format!("in {} {}{}", amount, unit, plural)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_text() {
+        let now = DateTime::parse_from_rfc3339("2025-01-13T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let text = "id,ChangedDate,TargetDate\n\
+                    1,2025-01-10T14:03:27.513Z,2025-07-01T00:00:00Z\n\
+                    2,2025-01-13T11:58:00+00:00,";
+
+        let rendering = DateRendering {
+            timezone: Some(TimeZone::from_posix_tz("CET-1CEST,M3.5.0,M10.5.0/3").unwrap()),
+            relative: true,
+        };
+        assert_eq!(
+            rendering.render_text(text, now),
+            "id,ChangedDate,TargetDate\n\
+             1,2025-01-10 15:03+01:00 (2 days ago),2025-07-01 02:00+02:00 (in 5 months)\n\
+             2,2025-01-13 12:58+01:00 (2 minutes ago),"
+        );
+
+        let rendering = DateRendering {
+            timezone: Some(TimeZone::utc()),
+            relative: false,
+        };
+        assert_eq!(
+            rendering.render_text("2025-01-10T14:03:27+02:00", now),
+            "2025-01-10 12:03Z"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use crate::azure::client::AzureError;
+use rmcp::{ErrorData as McpError, model::ErrorCode};
+use serde_json::{Value, json};
+
+/// Map the Azure DevOps failures to MCP errors whose code and `kind` tell the agent
+/// why the call failed (e.g. a rule violation on a field), with the details in `data`
+impl From<AzureError> for McpError {
+    fn from(e: AzureError) -> Self {
+        let message = e.to_string();
+        let (code, kind) = match &e {
+            AzureError::AuthError(_) | AzureError::Unauthorized(_) => (-32001, "unauthorized"),
+            AzureError::Forbidden(_) => (-32003, "forbidden"),
+            AzureError::NotFound(_) => (-32004, "not_found"),
+            AzureError::Conflict(_) => (-32009, "conflict"),
+            AzureError::RuleViolation { .. } => (-32022, "rule_violation"),
+            AzureError::Rejected(_) => (-32022, "invalid_request"),
+            AzureError::Throttled { .. } => (-32029, "throttled"),
+            AzureError::Unavailable(_) => (-32053, "unavailable"),
+            AzureError::ServerError(_) => (-32050, "server_error"),
+            AzureError::PolicyViolation { .. } => (-32023, "policy_violation"),
+            AzureError::UnresolvedPath { .. } => (-32602, "unresolved_path"),
+            AzureError::UnresolvedIdentity { .. } => (-32602, "unresolved_identity"),
+            AzureError::HttpError(_) | AzureError::SerdeJson(_) | AzureError::ApiError(_) => {
+                (-32000, "error")
+            }
+        };
+
+        let mut data = json!({ "kind": kind });
+        if let Some(failure) = e.failure() {
+            data["status"] = failure.status.into();
+            if let Some(type_key) = &failure.type_key {
+                data["type_key"] = type_key.as_str().into();
+            }
+        }
+        match e {
+            AzureError::RuleViolation {
+                field: Some(field), ..
+            } => data["field"] = Value::String(field),
+            AzureError::PolicyViolation { rule, .. } => data["rule"] = Value::String(rule),
+            AzureError::UnresolvedPath { candidates, .. }
+            | AzureError::UnresolvedIdentity { candidates, .. } => {
+                data["candidates"] = candidates.into()
+            }
+            AzureError::Throttled {
+                retry_after: Some(retry_after),
+                ..
+            }
+            | AzureError::Unavailable(retry_after) => {
+                data["retry_after_secs"] = retry_after.as_secs().max(1).into()
+            }
+            _ => {}
+        }
+
+        McpError {
+            code: ErrorCode(code),
+            message: message.into(),
+            data: Some(data),
+        }
+    }
+}
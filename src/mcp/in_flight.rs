@@ -0,0 +1,64 @@
+use tokio::sync::watch;
+
+/// Count of the tool calls running across the sessions, so that shutting down waits
+/// for them to finish
+pub struct InFlightCalls {
+    count: watch::Sender<usize>,
+}
+
+impl Default for InFlightCalls {
+    fn default() -> Self {
+        Self {
+            count: watch::Sender::new(0),
+        }
+    }
+}
+
+impl InFlightCalls {
+    /// Count a tool call until the returned guard is dropped
+    pub fn start(&self) -> InFlightCall<'_> {
+        self.count.send_modify(|count| *count += 1);
+        InFlightCall { calls: self }
+    }
+
+    pub fn count(&self) -> usize {
+        *self.count.borrow()
+    }
+
+    /// Wait until no tool call is running
+    pub async fn finished(&self) {
+        let _ = self.count.subscribe().wait_for(|count| *count == 0).await;
+    }
+}
+
+/// Tool call counted as running
+pub struct InFlightCall<'a> {
+    calls: &'a InFlightCalls,
+}
+
+impl Drop for InFlightCall<'_> {
+    fn drop(&mut self) {
+        self.calls.count.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_finished() {
+        let calls = InFlightCalls::default();
+        calls.finished().await;
+
+        let call = calls.start();
+        assert_eq!(calls.count(), 1);
+        let waiting = tokio::time::timeout(Duration::from_millis(10), calls.finished());
+        assert!(waiting.await.is_err());
+
+        drop(call);
+        assert_eq!(calls.count(), 0);
+        calls.finished().await;
+    }
+}
@@ -1,2 +1,14 @@
+pub mod completions;
+pub mod date_rendering;
+pub mod error;
+pub mod in_flight;
+pub mod prompts;
+pub mod resources;
+pub mod result_budget;
+pub mod sampling;
 pub mod server;
+pub mod subscriptions;
+pub mod tool_access;
+pub mod tool_defaults;
 pub mod tools;
+pub mod watches;
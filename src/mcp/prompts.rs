@@ -0,0 +1,184 @@
+use rmcp::{
+    ErrorData as McpError,
+    model::{
+        GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+    },
+};
+
+struct PromptDefinition {
+    name: &'static str,
+    description: &'static str,
+    /// Name, description and whether the argument is required
+    arguments: &'static [(&'static str, &'static str, bool)],
+    /// Instructions with `{argument}` placeholders
+    template: &'static str,
+}
+
+const PROMPTS: &[PromptDefinition] = &[
+    PromptDefinition {
+        name: "triage_bug",
+        description: "Triage a bug: assess severity, find duplicates and suggest area and owner",
+        arguments: &[
+            ("organization", "AzDO org name", true),
+            ("project", "AzDO project name", true),
+            ("id", "Bug work item ID", true),
+        ],
+        template: "Triage bug {id} of project {project} in organization {organization}.
+
+1. Read the bug with azdo_get_work_item (include_latest_n_comments: 10) and the code \
+linked to it with azdo_get_work_item_development_links.
+2. Look for duplicates with azdo_search_work_items, searching the key terms of the title \
+in work_item_types [\"Bug\"], and read the closest matches.
+3. Pick the area path from azdo_list_area_paths and look at who handled similar bugs.
+
+Reply with:
+- Summary: one or two sentences on the problem and its impact
+- Severity: 1 (critical) to 4 (low), with the reason
+- Duplicates: IDs and titles of the likely duplicates, or none
+- Suggested area path and assignee, with the reason
+- Missing information to ask the reporter
+
+Do not change the bug unless asked to. When asked, apply the changes with \
+azdo_update_work_item and record the reasoning with azdo_add_comment.",
+    },
+    PromptDefinition {
+        name: "plan_sprint",
+        description: "Plan the next sprint of a team from its velocity, capacity and backlog",
+        arguments: &[
+            ("organization", "AzDO org name", true),
+            ("project", "AzDO project name", true),
+            ("team_id", "Team ID or name", true),
+            (
+                "iteration",
+                "Iteration to plan, name or path (defaults to the next one)",
+                false,
+            ),
+        ],
+        template: "Plan the sprint {iteration} of team {team_id} in project {project} of \
+organization {organization}.
+
+1. Find the iteration with azdo_list_iteration_paths and azdo_get_team_current_iteration \
+(the one after the current iteration when none is given).
+2. Get the velocity of the last iterations with azdo_get_velocity and the days off in the \
+sprint with azdo_get_team_days_off.
+3. List the candidate work with azdo_query_work_items: the team area path, states not \
+done, not in a past iteration, including the unfinished items of the current iteration \
+from azdo_get_iteration_work_items.
+
+Reply with:
+- Capacity: expected velocity adjusted for the days off, with the reasoning
+- Proposed scope: a table of ID, type, title, estimate and why it is in, by priority, \
+up to the capacity
+- Stretch items and what was left out, with the reason
+- Risks: dependencies, unestimated or oversized items
+
+Do not move work items into the sprint unless asked to. When asked, set their iteration \
+path with azdo_update_work_item.",
+    },
+    PromptDefinition {
+        name: "summarize_iteration",
+        description: "Summarize the progress or outcome of an iteration of a team",
+        arguments: &[
+            ("organization", "AzDO org name", true),
+            ("project", "AzDO project name", true),
+            ("team_id", "Team ID or name", true),
+            (
+                "iteration",
+                "Iteration ID, name or path (defaults to the current one)",
+                false,
+            ),
+        ],
+        template: "Summarize the iteration {iteration} of team {team_id} in project {project} of \
+organization {organization}.
+
+1. Get its work items with azdo_get_iteration_work_items and its burndown with \
+azdo_get_sprint_burndown (both default to the current iteration when none is given).
+2. Get the cycle and lead times of the work completed in its dates with \
+azdo_get_flow_metrics.
+
+Reply with:
+- Headline: on track, at risk or done, in one sentence
+- Completed: the work items done, grouped by type
+- In progress and not started, with owners
+- Burndown: how the remaining work evolved and the projected outcome
+- Blockers and risks, from the states, tags and latest comments
+- Flow: cycle and lead times compared with the previous iterations when available",
+    },
+];
+
+/// Prompts listed by `prompts/list`
+pub fn list() -> Vec<Prompt> {
+    PROMPTS
+        .iter()
+        .map(|prompt| {
+            Prompt::new(
+                prompt.name,
+                Some(prompt.description),
+                Some(
+                    prompt
+                        .arguments
+                        .iter()
+                        .map(|(name, description, required)| PromptArgument {
+                            name: name.to_string(),
+                            title: None,
+                            description: Some(description.to_string()),
+                            required: Some(*required),
+                        })
+                        .collect(),
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Render a prompt with the given arguments
+pub fn get(name: &str, arguments: Option<&JsonObject>) -> Result<GetPromptResult, McpError> {
+    let prompt = PROMPTS
+        .iter()
+        .find(|prompt| prompt.name == name)
+        .ok_or_else(|| McpError::invalid_params(format!("Unknown prompt: {}", name), None))?;
+
+    let mut text = prompt.template.to_string();
+    for (argument, _, required) in prompt.arguments {
+        let value = match arguments.and_then(|a| a.get(*argument)) {
+            Some(serde_json::Value::String(value)) if !value.trim().is_empty() => {
+                value.trim().to_string()
+            }
+            Some(serde_json::Value::Number(value)) => value.to_string(),
+            _ if *required => {
+                return Err(McpError::invalid_params(
+                    format!("Missing argument {} of prompt {}", argument, name),
+                    None,
+                ));
+            }
+            _ => "(not given)".to_string(),
+        };
+        text = text.replace(&format!("{{{}}}", argument), &value);
+    }
+
+    Ok(GetPromptResult {
+        description: Some(prompt.description.to_string()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_get() {
+        let arguments = json!({"organization": "contoso", "project": "Web", "id": 42});
+        let result = get("triage_bug", arguments.as_object()).unwrap();
+        let rmcp::model::PromptMessageContent::Text { text } = &result.messages[0].content else {
+            panic!("expected a text message");
+        };
+        assert!(text.starts_with("Triage bug 42 of project Web in organization contoso."));
+        assert!(!text.contains('{'));
+
+        let arguments = json!({"organization": "contoso", "project": "Web"});
+        assert!(get("triage_bug", arguments.as_object()).is_err());
+        assert!(get("unknown", None).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::azure::{api::AzureBoardsApi, work_items};
+use crate::mcp::tools::support::{simplify_work_item_json, work_items_to_csv};
+use rmcp::{
+    ErrorData as McpError,
+    model::{
+        AnnotateAble, RawResourceTemplate, ReadResourceResult, ResourceContents, ResourceTemplate,
+    },
+};
+
+const SCHEME: &str = "azdo://";
+
+/// URI templates of the resources, listed by `resources/templates/list`
+pub fn templates() -> Vec<ResourceTemplate> {
+    [
+        (
+            "azdo://{organization}/{project}/workitems/{id}",
+            "work_item",
+            "Work item by ID, as CSV",
+        ),
+        (
+            "azdo://{organization}/{project}/queries/{queryId}",
+            "query_results",
+            "Work items returned by a saved query, as CSV",
+        ),
+    ]
+    .into_iter()
+    .map(|(uri_template, name, description)| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some("text/csv".to_string()),
+        }
+        .no_annotation()
+    })
+    .collect()
+}
+
+/// Resource identified by an `azdo://` URI
+#[derive(Debug, PartialEq)]
+enum ResourceUri {
+    WorkItem {
+        organization: String,
+        project: String,
+        id: u32,
+    },
+    Query {
+        organization: String,
+        project: String,
+        query_id: String,
+    },
+}
+
+fn parse_uri(uri: &str) -> Option<ResourceUri> {
+    let segments: Vec<String> = uri
+        .strip_prefix(SCHEME)?
+        .split('/')
+        .map(|segment| urlencoding::decode(segment).map(|s| s.into_owned()))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match segments.as_slice() {
+        [organization, project, kind, id] if kind == "workitems" => Some(ResourceUri::WorkItem {
+            organization: organization.clone(),
+            project: project.clone(),
+            id: id.parse().ok()?,
+        }),
+        [organization, project, kind, query_id] if kind == "queries" => Some(ResourceUri::Query {
+            organization: organization.clone(),
+            project: project.clone(),
+            query_id: query_id.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Whether a URI is one of the resources
+pub fn is_resource(uri: &str) -> bool {
+    parse_uri(uri).is_some()
+}
+
+/// Whether a URI is the resource of a work item
+pub fn is_work_item(uri: &str, organization: &str, project: &str, work_item_id: u32) -> bool {
+    matches!(
+        parse_uri(uri),
+        Some(ResourceUri::WorkItem { organization: o, project: p, id })
+            if id == work_item_id
+                && o.eq_ignore_ascii_case(organization)
+                && p.eq_ignore_ascii_case(project)
+    )
+}
+
+/// Read a resource, rendering its work items like the tools do
+pub async fn read(client: &dyn AzureBoardsApi, uri: &str) -> Result<ReadResourceResult, McpError> {
+    tracing::info!("Resource read: {}", uri);
+    let work_items = match parse_uri(uri) {
+        Some(ResourceUri::WorkItem {
+            organization,
+            project,
+            id,
+        }) => {
+            let work_item =
+                work_items::get_work_item(client, &organization, &project, id, None, &[])
+                    .await?
+                    .ok_or_else(|| {
+                        McpError::resource_not_found(format!("Work item not found: {}", uri), None)
+                    })?;
+            vec![work_item]
+        }
+        Some(ResourceUri::Query {
+            organization,
+            project,
+            query_id,
+        }) => work_items::run_saved_query(client, &organization, &project, &query_id, None).await?,
+        None => {
+            return Err(McpError::resource_not_found(
+                format!("Unknown resource URI: {}", uri),
+                None,
+            ));
+        }
+    };
+
+    let mut json_value = serde_json::to_value(&work_items).unwrap();
+    simplify_work_item_json(&mut json_value);
+    let csv_output = work_items_to_csv(&json_value, &[])
+        .map_err(|e| McpError::internal_error(format!("Failed to convert to CSV: {}", e), None))?;
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some("text/csv".to_string()),
+            text: csv_output,
+            meta: None,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uri() {
+        assert_eq!(
+            parse_uri("azdo://contoso/My%20Project/workitems/42"),
+            Some(ResourceUri::WorkItem {
+                organization: "contoso".to_string(),
+                project: "My Project".to_string(),
+                id: 42,
+            })
+        );
+        assert_eq!(
+            parse_uri("azdo://contoso/Web/queries/8a1b0c2d-0000-0000-0000-000000000000"),
+            Some(ResourceUri::Query {
+                organization: "contoso".to_string(),
+                project: "Web".to_string(),
+                query_id: "8a1b0c2d-0000-0000-0000-000000000000".to_string(),
+            })
+        );
+        assert_eq!(parse_uri("azdo://contoso/Web/workitems/abc"), None);
+        assert_eq!(parse_uri("https://contoso/Web/workitems/1"), None);
+        assert!(is_work_item(
+            "azdo://contoso/My%20Project/workitems/42",
+            "Contoso",
+            "my project",
+            42
+        ));
+    }
+}
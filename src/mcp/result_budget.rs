@@ -0,0 +1,349 @@
+use crate::mcp::tools::support::Cursor;
+use rmcp::model::{CallToolResult, JsonObject, RawContent, Tool};
+use serde_json::Value;
+
+/// Characters per token of the estimate, about the average of English text and JSON
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Characters of the budget left to the truncation marker
+const MARKER_RESERVE: usize = 400;
+
+/// Prefix of the last line of the text results of the paged tools
+const NEXT_CURSOR_PREFIX: &str = "\nnext_cursor=";
+
+/// Estimated tokens of a text
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Position of the first result of a call of a paged tool, to resume after the results
+/// kept
+#[derive(Debug)]
+pub struct Paging {
+    cursor: Cursor,
+}
+
+impl Paging {
+    /// Paging of a call, when the tool takes `max_results` and `cursor`
+    pub fn of(tool: &Tool, arguments: Option<&JsonObject>) -> Option<Self> {
+        let properties = tool.input_schema.get("properties")?.as_object()?;
+        if !properties.contains_key("cursor") || !properties.contains_key("max_results") {
+            return None;
+        }
+        let cursor = match arguments
+            .and_then(|arguments| arguments.get("cursor"))
+            .and_then(Value::as_str)
+            .filter(|cursor| !cursor.is_empty())
+        {
+            Some(cursor) => Cursor::decode(cursor).ok()?,
+            None => Cursor::default(),
+        };
+        Some(Self { cursor })
+    }
+
+    /// Cursor of the results following the first `kept` ones. It is only known when the
+    /// next page continues the upstream page the call started in, all the results
+    /// returned coming from it.
+    fn resume(&self, next_cursor: Option<&str>, kept: usize) -> Option<String> {
+        let next = Cursor::decode(next_cursor?).ok()?;
+        (next.token == self.cursor.token).then(|| {
+            Cursor {
+                token: self.cursor.token.clone(),
+                offset: self.cursor.offset + kept,
+            }
+            .encode()
+        })
+    }
+}
+
+/// Limit of the size of the tool results, so that they fit in the context of the
+/// model of the client
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResultBudget {
+    /// Estimated tokens of each text content and of the structured content, 0 disables
+    /// the limit
+    pub max_tokens: usize,
+}
+
+impl ResultBudget {
+    /// Truncate the contents of a result over the budget. The rows of the tables (CSV),
+    /// the items of the largest array of the structured content, and else the text, are
+    /// cut, and the text ends with a marker telling how to get the rest.
+    pub fn fit(&self, result: &mut CallToolResult, paging: Option<&Paging>) {
+        if self.max_tokens == 0 {
+            return;
+        }
+        for content in &mut result.content {
+            if let RawContent::Text(text) = &mut content.raw
+                && estimate_tokens(&text.text) > self.max_tokens
+            {
+                text.text = self.fit_text(&text.text, paging);
+            }
+        }
+        if let Some(structured) = &mut result.structured_content
+            && estimate_tokens(&structured.to_string()) > self.max_tokens
+        {
+            self.fit_structured(structured, paging);
+        }
+    }
+
+    fn limit(&self) -> usize {
+        (self.max_tokens * CHARS_PER_TOKEN).saturating_sub(MARKER_RESERVE)
+    }
+
+    fn fit_text(&self, text: &str, paging: Option<&Paging>) -> String {
+        let (body, next_cursor) = match text.rsplit_once(NEXT_CURSOR_PREFIX) {
+            Some((body, cursor)) if !cursor.contains('\n') => (body, Some(cursor)),
+            _ => (text, None),
+        };
+        let limit = self.limit();
+
+        // Whole rows of a table, with its header
+        if let Some(ends) = row_ends(body) {
+            let mut chars = 0;
+            let mut start = 0;
+            let mut kept = 0;
+            for (row, end) in ends.iter().enumerate() {
+                chars += body[start..*end].chars().count();
+                start = *end;
+                if chars > limit {
+                    break;
+                }
+                kept = row;
+            }
+            let total = ends.len() - 1;
+            // Over the budget by less than the marker, nothing to cut
+            if kept == total {
+                return text.to_string();
+            }
+            if kept > 0 {
+                let mut fitted = body[..ends[kept]]
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string();
+                let cursor = paging.and_then(|paging| paging.resume(next_cursor, kept));
+                let next = match (&cursor, paging) {
+                    (Some(_), _) => {
+                        "Call again with the next_cursor below for the rest".to_string()
+                    }
+                    (None, Some(_)) => format!(
+                        "Call again with max_results of at most {} to page through them",
+                        kept
+                    ),
+                    (None, None) => NARROW.to_string(),
+                };
+                fitted.push_str(&format!(
+                    "\n[Truncated to fit {} tokens: {} of {} rows shown. {}]",
+                    self.max_tokens, kept, total, next
+                ));
+                if let Some(cursor) = cursor {
+                    fitted.push_str(NEXT_CURSOR_PREFIX);
+                    fitted.push_str(&cursor);
+                }
+                return fitted;
+            }
+        }
+
+        let cut = body
+            .char_indices()
+            .nth(limit)
+            .map_or(body.len(), |(i, _)| i);
+        let next = match paging {
+            Some(_) => "Call again with a smaller max_results, or narrow the request",
+            None => NARROW,
+        };
+        format!(
+            "{}\n[Truncated to fit {} tokens: {} of {} characters shown. {}]",
+            &body[..cut],
+            self.max_tokens,
+            body[..cut].chars().count(),
+            body.chars().count(),
+            next
+        )
+    }
+
+    /// Cut the largest array of the structured content, then its long strings, keeping
+    /// it valid against the output schema of the tool
+    fn fit_structured(&self, structured: &mut Value, paging: Option<&Paging>) {
+        let limit = self.limit();
+        let Value::Object(object) = structured else {
+            return;
+        };
+        let largest = object
+            .iter()
+            .filter_map(|(name, value)| Some((name, value.as_array()?.len())))
+            .max_by_key(|(_, len)| *len)
+            .map(|(name, len)| (name.clone(), len));
+        if let Some((name, total)) = largest
+            && total > 1
+        {
+            let items = match object.get_mut(&name) {
+                Some(Value::Array(items)) => std::mem::take(items),
+                _ => return,
+            };
+            let mut chars = Value::Object(object.clone()).to_string().chars().count();
+            let mut kept = 0;
+            for item in &items {
+                // The item and its separator
+                chars += item.to_string().chars().count() + 1;
+                if chars > limit && kept > 0 {
+                    break;
+                }
+                kept += 1;
+            }
+            object.insert(name, Value::Array(items.into_iter().take(kept).collect()));
+            if kept < total {
+                if object.get("count").and_then(Value::as_u64) == Some(total as u64) {
+                    object.insert("count".to_string(), Value::from(kept));
+                }
+                let next_cursor = object.remove("next_cursor");
+                let next_cursor = next_cursor.as_ref().and_then(Value::as_str);
+                if let Some(cursor) = paging.and_then(|paging| paging.resume(next_cursor, kept)) {
+                    object.insert("next_cursor".to_string(), Value::String(cursor));
+                }
+            }
+        }
+
+        if structured.to_string().chars().count() > limit {
+            cut_strings(structured, (limit / 8).max(MARKER_RESERVE));
+        }
+    }
+}
+
+const NARROW: &str = "Narrow the request (e.g. a more selective query) to get the rest";
+
+/// Byte offsets of the ends of the rows of a CSV or markdown table, its header first,
+/// or None when the text is not a table of several rows
+fn row_ends(text: &str) -> Option<Vec<usize>> {
+    if text.starts_with("| ") {
+        return markdown_row_ends(text);
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(text.as_bytes());
+    let mut ends = Vec::new();
+    let mut record = csv::StringRecord::new();
+    while reader.read_record(&mut record).ok()? {
+        ends.push(reader.position().byte() as usize);
+    }
+    (ends.len() > 2).then_some(ends)
+}
+
+/// Byte offsets of the ends of the lines of a markdown table, the header ending with its
+/// delimiter row
+fn markdown_row_ends(text: &str) -> Option<Vec<usize>> {
+    let mut ends = Vec::new();
+    let mut start = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if !line.starts_with('|') || (i == 1 && !line.starts_with("| ---")) {
+            return None;
+        }
+        start += line.len();
+        if i > 0 {
+            ends.push(start);
+        }
+    }
+    (ends.len() > 2).then_some(ends)
+}
+
+/// Cut the strings longer than `max` characters
+fn cut_strings(value: &mut Value, max: usize) {
+    match value {
+        Value::String(text) => {
+            if let Some((cut, _)) = text.char_indices().nth(max) {
+                text.truncate(cut);
+                text.push_str("…[truncated]");
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| cut_strings(item, max)),
+        Value::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| cut_strings(field, max)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn text(result: &CallToolResult) -> &str {
+        &result.content[0].as_text().unwrap().text
+    }
+
+    #[test]
+    fn test_fit_table() {
+        let schema = json!({"properties": {"max_results": {}, "cursor": {}}});
+        let tool = Tool::new(
+            "azdo_list",
+            "",
+            Arc::new(schema.as_object().unwrap().clone()),
+        );
+        let cursor = |offset| Cursor {
+            token: None,
+            offset,
+        };
+        let arguments = json!({"cursor": cursor(10).encode()});
+        let paging = Paging::of(&tool, arguments.as_object()).unwrap();
+
+        let rows = (10..110)
+            .map(|id| format!("{},\"Title, {}\"\n", id, id))
+            .collect::<String>();
+        let mut result = CallToolResult::success(vec![Content::text(format!(
+            "id,Title\n{}next_cursor={}",
+            rows,
+            cursor(110).encode()
+        ))]);
+        result.structured_content = Some(json!({
+            "count": 100,
+            "work_items": (10..110).map(|id| json!({"id": id})).collect::<Vec<_>>(),
+            "next_cursor": cursor(110).encode(),
+        }));
+        let budget = ResultBudget { max_tokens: 150 };
+        budget.fit(&mut result, Some(&paging));
+
+        let fitted = text(&result);
+        assert!(fitted.starts_with("id,Title\n10,\"Title, 10\"\n"));
+        assert!(fitted.contains("[Truncated to fit 150 tokens: 12 of 100 rows shown."));
+        assert!(fitted.ends_with(&format!("\nnext_cursor={}", cursor(22).encode())));
+        let structured = result.structured_content.unwrap();
+        let kept = structured["count"].as_u64().unwrap() as usize;
+        assert_eq!(structured["work_items"].as_array().unwrap().len(), kept);
+        assert_eq!(structured["next_cursor"], cursor(10 + kept).encode());
+    }
+
+    #[test]
+    fn test_fit_markdown_table() {
+        let rows = (0..100)
+            .map(|id| format!("\n| {} | Title {} |", id, id))
+            .collect::<String>();
+        let mut result = CallToolResult::success(vec![Content::text(format!(
+            "| id | Title |\n| --- | --- |{}",
+            rows
+        ))]);
+        ResultBudget { max_tokens: 150 }.fit(&mut result, None);
+        let fitted = text(&result);
+        assert!(fitted.starts_with("| id | Title |\n| --- | --- |\n| 0 | Title 0 |\n"));
+        assert!(
+            fitted
+                .contains("\n| 9 | Title 9 |\n[Truncated to fit 150 tokens: 10 of 100 rows shown.")
+        );
+    }
+
+    #[test]
+    fn test_fit_text() {
+        let mut result = CallToolResult::success(vec![Content::text("é".repeat(1000))]);
+        ResultBudget { max_tokens: 200 }.fit(&mut result, None);
+        assert!(text(&result).starts_with(&"é".repeat(400)));
+        assert!(text(&result).ends_with(
+            "\n[Truncated to fit 200 tokens: 400 of 1000 characters shown. \
+             Narrow the request (e.g. a more selective query) to get the rest]"
+        ));
+
+        let mut result = CallToolResult::success(vec![Content::text("x".repeat(1000))]);
+        ResultBudget { max_tokens: 0 }.fit(&mut result, None);
+        assert_eq!(text(&result).len(), 1000);
+    }
+}
@@ -0,0 +1,68 @@
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    model::{
+        Content, ContextInclusion, CreateMessageRequestParam, ErrorCode, Role, SamplingMessage,
+    },
+};
+use std::future::Future;
+
+tokio::task_local! {
+    static PEER: Peer<RoleServer>;
+}
+
+/// Run a tool invocation able to ask the client's model through [`create_message`]
+pub async fn scope<F: Future>(peer: Peer<RoleServer>, future: F) -> F::Output {
+    PEER.scope(peer, future).await
+}
+
+fn sampling_error(message: String) -> McpError {
+    McpError {
+        code: ErrorCode(-32000),
+        message: message.into(),
+        data: None,
+    }
+}
+
+/// Ask the model of the MCP client to answer a prompt (`sampling/createMessage`),
+/// returning its text answer
+pub async fn create_message(
+    system_prompt: &str,
+    prompt: String,
+    max_tokens: u32,
+) -> Result<String, McpError> {
+    let peer = PEER
+        .try_with(Peer::clone)
+        .map_err(|_| sampling_error("Sampling is only available to tools".to_string()))?;
+    if peer
+        .peer_info()
+        .is_none_or(|info| info.capabilities.sampling.is_none())
+    {
+        return Err(sampling_error(
+            "The MCP client does not support sampling".to_string(),
+        ));
+    }
+
+    let result = peer
+        .create_message(CreateMessageRequestParam {
+            messages: vec![SamplingMessage {
+                role: Role::User,
+                content: Content::text(prompt),
+            }],
+            model_preferences: None,
+            system_prompt: Some(system_prompt.to_string()),
+            include_context: Some(ContextInclusion::None),
+            temperature: None,
+            max_tokens,
+            stop_sequences: None,
+            metadata: None,
+        })
+        .await
+        .map_err(|e| sampling_error(format!("Sampling failed: {}", e)))?;
+
+    result
+        .message
+        .content
+        .as_text()
+        .map(|text| text.text.clone())
+        .ok_or_else(|| sampling_error("The client's model did not answer with text".to_string()))
+}
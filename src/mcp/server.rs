@@ -1,15 +1,413 @@
+use crate::audit::{self, AuditLog, AuditRecord};
 use crate::azure::client::AzureDevOpsClient;
+use crate::azure::{context, write_policy};
+use crate::journal::{self, SessionJournal};
+use crate::mcp::date_rendering::DateRendering;
+use crate::mcp::in_flight::InFlightCalls;
+use crate::mcp::result_budget::{Paging, ResultBudget};
+use crate::mcp::subscriptions::{ResourceSubscriptions, SessionSubscriptions};
+use crate::mcp::tool_access::{ToolAccess, ToolAccessControl, matches_glob};
+use crate::mcp::tool_defaults::ToolDefaults;
+use crate::mcp::watches::{self, SessionWatches, WatchSession};
+use crate::mcp::{completions, prompts, resources, sampling};
+use crate::progress;
+use crate::server::auth::Caller;
+use azure_core::auth::TokenCredential;
 use rmcp::{
-    handler::server::router::tool::ToolRouter,
-    model::{Implementation, ServerCapabilities, ServerInfo},
-    tool_handler,
+    RoleServer,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext},
+    model::{
+        CallToolRequestParam, CompleteRequestParam, CompleteResult, GetPromptRequestParam,
+        GetPromptResult, Implementation, InitializeRequestParam, InitializeResult,
+        ListPromptsResult, ListResourceTemplatesResult, ListToolsResult, PaginatedRequestParam,
+        ProgressNotificationParam, ProgressToken, ReadResourceRequestParam, ReadResourceResult,
+        ServerCapabilities, ServerInfo, SetLevelRequestParam, SubscribeRequestParam,
+        UnsubscribeRequestParam,
+    },
+    service::{NotificationContext, RequestContext},
 };
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// Time limits of the tool invocations, after which the tool fails and its in-flight
+/// requests are dropped
+#[derive(Debug, Clone, Default)]
+pub struct ToolTimeouts {
+    /// Limit of the tools without an override, `None` lets them run indefinitely
+    pub default: Option<Duration>,
+    /// Limits by tool name, `None` lets the tool run indefinitely
+    pub overrides: HashMap<String, Option<Duration>>,
+}
+
+impl ToolTimeouts {
+    fn of(&self, tool: &str) -> Option<Duration> {
+        self.overrides.get(tool).copied().unwrap_or(self.default)
+    }
+}
+
+/// Tools returning complete data for the client to save or paste, whose results are
+/// neither truncated nor have their dates rewritten
+const EXPORT_TOOLS: &[&str] = &["azdo_export_work_items", "azdo_export_dependency_graph"];
 
 #[derive(Clone)]
 pub struct AzureMcpServer {
     client: Arc<AzureDevOpsClient>,
     tool_router: ToolRouter<Self>,
+    tool_timeouts: Arc<ToolTimeouts>,
+    tool_access: Arc<ToolAccessControl>,
+    tool_defaults: Arc<ToolDefaults>,
+    result_budget: ResultBudget,
+    date_rendering: Arc<DateRendering>,
+    /// Client acting as the user of the HTTP session, when they gave their credential
+    session_client: Arc<OnceLock<Arc<AzureDevOpsClient>>>,
+    /// Tool calls running, shared by the sessions
+    in_flight: Arc<InFlightCalls>,
+    /// Clients subscribed to resources, shared by the sessions
+    resource_subscriptions: Arc<ResourceSubscriptions>,
+    /// Resources the session subscribed to
+    subscribed: SessionSubscriptions,
+    /// Work items the session watches, polled in the background
+    watches: SessionWatches,
+    /// Work item changes of the session, which its tools can undo
+    journal: SessionJournal,
+    audit_log: Option<Arc<AuditLog>>,
+    require_session_credentials: bool,
+}
+
+impl AzureMcpServer {
+    /// Limit how long the tools can run
+    pub fn with_tool_timeouts(mut self, timeouts: ToolTimeouts) -> Self {
+        self.tool_timeouts = Arc::new(timeouts);
+        self
+    }
+
+    /// Fill in the organization, project and team the tool arguments omit
+    pub fn with_tool_defaults(mut self, defaults: ToolDefaults) -> Self {
+        self.tool_defaults = Arc::new(defaults);
+        self
+    }
+
+    /// Truncate the tool results over a budget of tokens
+    pub fn with_result_budget(mut self, budget: ResultBudget) -> Self {
+        self.result_budget = budget;
+        self
+    }
+
+    /// Write the timestamps of the text results in a time zone, or with how long ago
+    /// they are
+    pub fn with_date_rendering(mut self, rendering: DateRendering) -> Self {
+        self.date_rendering = Arc::new(rendering);
+        self
+    }
+
+    /// Record the tool invocations, their caller and the Azure DevOps mutations they
+    /// made in an audit log
+    pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+        self.audit_log = Some(Arc::new(log));
+        self
+    }
+
+    /// Refuse the HTTP sessions whose user does not give their own Azure DevOps
+    /// credential, rather than acting with the credential of the server
+    pub fn with_required_session_credentials(mut self, required: bool) -> Self {
+        self.require_session_credentials = required;
+        self
+    }
+
+    /// Server of a new HTTP session, sharing everything but the session credential
+    pub fn for_session(&self) -> Self {
+        Self {
+            session_client: Default::default(),
+            subscribed: Default::default(),
+            watches: Default::default(),
+            journal: Default::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Act as the user of the session with their credential, failing without one when
+    /// session credentials are required
+    pub fn bind_session(&self, credential: Option<Arc<dyn TokenCredential>>) -> Result<(), String> {
+        match credential {
+            Some(credential) => {
+                let _ = self
+                    .session_client
+                    .set(Arc::new(self.client.with_other_credential(credential)));
+                Ok(())
+            }
+            None if self.require_session_credentials => Err(
+                "This server requires the Azure DevOps credential of the user, in the X-AzDO-PAT or X-AzDO-Token header"
+                    .to_string(),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Client of the requests, acting as the user of the session when they gave their
+    /// credential
+    fn client(&self) -> &AzureDevOpsClient {
+        self.session_client.get().unwrap_or(&self.client)
+    }
+
+    /// Client of the requests, for the tasks outliving the tool invocations
+    fn shared_client(&self) -> Arc<AzureDevOpsClient> {
+        Arc::clone(self.session_client.get().unwrap_or(&self.client))
+    }
+
+    /// Limit the tools offered, failing on unknown tool groups and on tool globs matching
+    /// no tool
+    pub fn with_tool_access(mut self, access: ToolAccess) -> Result<Self, String> {
+        check_tool_access(&access)?;
+        self.tool_access = Arc::new(ToolAccessControl::new(access));
+        Ok(self)
+    }
+
+    /// Names of the tool groups, the directories of src/mcp/tools
+    pub fn tool_groups() -> BTreeSet<&'static str> {
+        TOOL_GROUPS.iter().map(|(_, group)| *group).collect()
+    }
+
+    pub fn tool_access(&self) -> ToolAccess {
+        self.tool_access.get()
+    }
+
+    /// Change the tools offered to all the sessions, notifying their clients, failing on
+    /// unknown tool groups and on tool globs matching no tool
+    pub async fn set_tool_access(&self, access: ToolAccess) -> Result<(), String> {
+        check_tool_access(&access)?;
+        self.tool_access.set(access).await;
+        Ok(())
+    }
+
+    /// Number of tool calls running across the sessions
+    pub fn tool_calls_in_flight(&self) -> usize {
+        self.in_flight.count()
+    }
+
+    /// Notify the sessions subscribed to a work item that it changed, returning the
+    /// number of notifications sent
+    pub async fn notify_work_item_changed(
+        &self,
+        organization: &str,
+        project: &str,
+        work_item_id: u32,
+    ) -> usize {
+        self.resource_subscriptions
+            .notify_updated(|uri| resources::is_work_item(uri, organization, project, work_item_id))
+            .await
+    }
+
+    /// Wait until no tool call is running, across the sessions
+    pub async fn tool_calls_finished(&self) {
+        self.in_flight.finished().await
+    }
+
+    /// Tools offered to the clients
+    pub fn tools(&self) -> Vec<rmcp::model::Tool> {
+        let access = self.tool_access.get();
+        self.tool_router
+            .list_all()
+            .into_iter()
+            .filter(|tool| self.tool_allowed(&access, tool))
+            .map(|tool| self.tool_defaults.advertise(tool))
+            .collect()
+    }
+
+    fn tool_allowed(&self, access: &ToolAccess, tool: &rmcp::model::Tool) -> bool {
+        let group = TOOL_GROUPS
+            .iter()
+            .find(|(name, _)| *name == tool.name)
+            .map_or("", |(_, group)| *group);
+        access.allows(group, tool)
+    }
+
+    /// Call a tool, writing the invocation to the audit log
+    async fn call_audited(
+        &self,
+        log: &AuditLog,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let parts = context
+            .extensions
+            .get::<hyper::http::request::Parts>()
+            .cloned();
+        let client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+        let tool = request.name.clone();
+        let arguments = request
+            .arguments
+            .clone()
+            .map_or(serde_json::Value::Null, serde_json::Value::Object);
+        let started = Instant::now();
+        let (result, mutations) = audit::scope(
+            self.tool_router
+                .call(ToolCallContext::new(self, request, context)),
+        )
+        .await;
+        let error = match &result {
+            Ok(result) if result.is_error == Some(true) => Some("Tool returned an error"),
+            Ok(_) => None,
+            Err(e) => Some(e.message.as_ref()),
+        };
+        log.write(&AuditRecord {
+            time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            caller: parts
+                .as_ref()
+                .and_then(|parts| parts.extensions.get::<Caller>())
+                .map(|caller| caller.0.as_str()),
+            session: parts
+                .as_ref()
+                .and_then(|parts| parts.headers.get("mcp-session-id"))
+                .and_then(|value| value.to_str().ok()),
+            client: client.as_deref(),
+            credential: if self.session_client.get().is_some() {
+                "session"
+            } else {
+                "server"
+            },
+            tool: &tool,
+            arguments: audit::redact(&arguments),
+            success: error.is_none(),
+            error,
+            duration_ms: started.elapsed().as_millis() as u64,
+            mutations,
+        });
+        result
+    }
+
+    /// Run a tool invocation within a span recording its name, latency and outcome.
+    /// The invocation is dropped, stopping its in-flight requests, when the client
+    /// cancels the request or the tool runs past its timeout. The progress it reports
+    /// is sent to the client when the request has a progress token, it can ask the
+    /// client's model through sampling and start watches reporting to the session.
+    async fn invoke(
+        &self,
+        tool: &'static str,
+        context: RequestContext<RoleServer>,
+        invocation: impl Future<Output = Result<rmcp::model::CallToolResult, rmcp::ErrorData>>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let span = tracing::info_span!(
+            "tool",
+            tool,
+            latency_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let _call = self.in_flight.start();
+        let started = Instant::now();
+        let timeout = self.tool_timeouts.of(tool);
+        let invocation = async {
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, invocation)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(tool_error(format!(
+                            "Tool {} timed out after {}s",
+                            tool,
+                            timeout.as_secs()
+                        )))
+                    }),
+                None => invocation.await,
+            }
+        };
+        let invocation = sampling::scope(context.peer.clone(), async {
+            match context.meta.get_progress_token() {
+                Some(token) => with_progress(&context.peer, token, invocation).await,
+                None => invocation.await,
+            }
+        });
+        let session = WatchSession {
+            client: self.shared_client(),
+            peer: context.peer.clone(),
+            watches: self.watches.clone(),
+        };
+        let invocation = watches::scope(session, invocation);
+        let invocation = journal::scope(self.journal.clone(), tool, invocation);
+        let invocation = write_policy::scope(invocation);
+        let invocation = context::scope(self.client().context(), invocation);
+        let result = tokio::select! {
+            result = invocation.instrument(span.clone()) => result,
+            _ = context.ct.cancelled() => Err(tool_error(format!("Tool {} cancelled", tool))),
+        };
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+        if let Err(e) = &result {
+            span.record("error", e.message.as_ref());
+        }
+        result
+    }
+}
+
+/// Run an invocation, sending the progress it reports as progress notifications. The
+/// notifications whose progress does not increase (a later phase of the tool starting
+/// over) are skipped, as the progress of a request must increase.
+async fn with_progress<T>(
+    peer: &rmcp::Peer<RoleServer>,
+    token: ProgressToken,
+    invocation: impl Future<Output = T>,
+) -> T {
+    let (sender, mut updates) = tokio::sync::mpsc::unbounded_channel();
+    let invocation = progress::scope(sender, invocation);
+    tokio::pin!(invocation);
+    let mut last = 0;
+    loop {
+        tokio::select! {
+            biased;
+            result = &mut invocation => return result,
+            Some(update) = updates.recv() => {
+                if update.progress <= last {
+                    continue;
+                }
+                last = update.progress;
+                let notification = ProgressNotificationParam {
+                    progress_token: token.clone(),
+                    progress: update.progress as f64,
+                    total: update.total.map(|total| total as f64),
+                    message: Some(update.message),
+                };
+                if let Err(e) = peer.notify_progress(notification).await {
+                    tracing::debug!("Failed to send the progress notification: {}", e);
+                }
+            }
+        }
+    }
+}
+
+fn check_tool_access(access: &ToolAccess) -> Result<(), String> {
+    let groups = AzureMcpServer::tool_groups();
+    if let Some(group) = access
+        .disabled_groups
+        .iter()
+        .find(|group| !groups.contains(group.as_str()))
+    {
+        return Err(format!(
+            "Unknown tool group {}, expected one of {}",
+            group,
+            groups.into_iter().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    // A mistyped glob would silently offer every tool, or none
+    match access
+        .allowed_tools
+        .iter()
+        .chain(&access.denied_tools)
+        .find(|glob| !TOOL_GROUPS.iter().any(|(tool, _)| matches_glob(glob, tool)))
+    {
+        Some(glob) => Err(format!("Tool glob {} matches no tool", glob)),
+        None => Ok(()),
+    }
+}
+
+fn tool_error(message: String) -> rmcp::ErrorData {
+    rmcp::ErrorData {
+        code: rmcp::model::ErrorCode(-32000),
+        message: message.into(),
+        data: None,
+    }
 }
 
 // Tool router implementation is auto-generated by build.rs
@@ -17,7 +415,6 @@ pub struct AzureMcpServer {
 // and generates the impl block in target/debug/build/.../out/generated_tools.rs
 include!(concat!(env!("OUT_DIR"), "/generated_tools.rs"));
 
-#[tool_handler]
 impl rmcp::ServerHandler for AzureMcpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
@@ -31,8 +428,243 @@ impl rmcp::ServerHandler for AzureMcpServer {
             instructions: Some(
                 "Use this tool to interact with Azure DevOps Boards and Work Items".into(),
             ),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_prompts()
+                .enable_completions()
+                .enable_logging()
+                .build(),
             ..Default::default()
         }
     }
+
+    /// Bind the streamable HTTP sessions to the credential of their user, given in the
+    /// headers of the initialize request
+    async fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, rmcp::ErrorData> {
+        if let Some(parts) = context.extensions.get::<hyper::http::request::Parts>() {
+            self.bind_session(crate::server::auth::session_credential(&parts.headers))
+                .map_err(|e| rmcp::ErrorData::invalid_request(e, None))?;
+        }
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        Ok(self.get_info())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.resource_subscriptions
+            .register(context.peer.clone(), self.subscribed.clone());
+        self.tool_access.register(context.peer);
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        Ok(ListToolsResult::with_all_items(self.tools()))
+    }
+
+    async fn call_tool(
+        &self,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
+        let access = self.tool_access.get();
+        if let Some(route) = self.tool_router.map.get(request.name.as_ref()) {
+            // Disabled tools are refused like unknown ones, clients may still have them
+            // listed
+            if !self.tool_allowed(&access, &route.attr) {
+                return Err(rmcp::ErrorData::invalid_params(
+                    format!("Tool {} is disabled", request.name),
+                    None,
+                ));
+            }
+            if !self.tool_defaults.is_empty() {
+                self.tool_defaults
+                    .fill(&route.attr, request.arguments.get_or_insert_default());
+            }
+        }
+        let export = EXPORT_TOOLS.contains(&request.name.as_ref());
+        let paging = self
+            .tool_router
+            .map
+            .get(request.name.as_ref())
+            .and_then(|route| Paging::of(&route.attr, request.arguments.as_ref()));
+        let mut result = match &self.audit_log {
+            Some(log) => self.call_audited(log, request, context).await?,
+            None => {
+                self.tool_router
+                    .call(ToolCallContext::new(self, request, context))
+                    .await?
+            }
+        };
+        if !export {
+            self.date_rendering.render(&mut result);
+            self.result_budget.fit(&mut result, paging.as_ref());
+        }
+        Ok(result)
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, rmcp::ErrorData> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        context::scope(
+            self.client().context(),
+            resources::read(self.client(), &request.uri),
+        )
+        .await
+    }
+
+    /// The watch notifications are the only log messages, they are sent whatever the
+    /// level
+    async fn set_level(
+        &self,
+        _request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        Ok(())
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        if !resources::is_resource(&request.uri) {
+            return Err(rmcp::ErrorData::resource_not_found(
+                format!("Unknown resource URI: {}", request.uri),
+                None,
+            ));
+        }
+        self.subscribed.lock().unwrap().insert(request.uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        self.subscribed.lock().unwrap().remove(&request.uri);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, rmcp::ErrorData> {
+        Ok(ListPromptsResult::with_all_items(prompts::list()))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, rmcp::ErrorData> {
+        prompts::get(&request.name, request.arguments.as_ref())
+    }
+
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, rmcp::ErrorData> {
+        Ok(CompleteResult {
+            completion: context::scope(
+                self.client().context(),
+                completions::complete(
+                    self.client(),
+                    &request.argument.name,
+                    &request.argument.value,
+                    request.context.as_ref(),
+                ),
+            )
+            .await,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::fixtures::Fixtures;
+    use rmcp::ServiceExt;
+    use rmcp::model::CallToolResult;
+    use serde_json::{Value, json};
+
+    /// Call a tool through an in-process session, like the `call` command
+    async fn call(server: AzureMcpServer, tool: &'static str, arguments: Value) -> CallToolResult {
+        let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+        let (service, client) = tokio::join!(
+            server.serve(tokio::io::split(server_io)),
+            ().serve(tokio::io::split(client_io))
+        );
+        let (service, client) = (service.unwrap(), client.unwrap());
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: tool.into(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await
+            .unwrap();
+        client.cancel().await.unwrap();
+        service.cancel().await.unwrap();
+        result
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        serde_json::to_string(&result.structured_content).unwrap()
+            + &serde_json::to_string(&result.content).unwrap()
+    }
+
+    // On worker threads with the default stack, which the tool futures used to overflow
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_demo_fixtures() {
+        let demo = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/demo");
+        let server = || {
+            AzureMcpServer::new(
+                AzureDevOpsClient::new().with_transport(Arc::new(Fixtures::new(demo))),
+            )
+        };
+        let project = json!({"organization": "contoso", "project": "Fabrikam"});
+
+        let mut arguments = project.clone();
+        arguments["query"] = json!("SELECT [System.Id] FROM WorkItems");
+        let result = call(server(), "azdo_query_work_items", arguments).await;
+        assert_ne!(result.is_error, Some(true));
+        assert!(text(&result).contains("Order confirmation email is sent twice"));
+
+        let mut arguments = project.clone();
+        arguments["id"] = json!(1);
+        let result = call(server(), "azdo_get_work_item", arguments).await;
+        assert_ne!(result.is_error, Some(true));
+        assert!(text(&result).contains("Checkout supports saved cards"));
+
+        let mut arguments = project;
+        arguments["id"] = json!(1);
+        arguments["state"] = json!("Resolved");
+        let result = call(server(), "azdo_update_work_item", arguments).await;
+        assert_ne!(result.is_error, Some(true));
+    }
 }
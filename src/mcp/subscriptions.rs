@@ -0,0 +1,55 @@
+use rmcp::{Peer, RoleServer, model::ResourceUpdatedNotificationParam};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+/// Resource URIs a session subscribed to
+pub type SessionSubscriptions = Arc<Mutex<BTreeSet<String>>>;
+
+/// Clients subscribed to resources, shared by the sessions, notified when their
+/// resources change
+#[derive(Default)]
+pub struct ResourceSubscriptions {
+    sessions: Mutex<Vec<(Peer<RoleServer>, SessionSubscriptions)>>,
+}
+
+impl ResourceSubscriptions {
+    /// Notify a client of the changes to the resources its session subscribes to, the
+    /// disconnected ones being forgotten
+    pub fn register(&self, peer: Peer<RoleServer>, subscriptions: SessionSubscriptions) {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|(peer, _)| !peer.is_transport_closed());
+        sessions.push((peer, subscriptions));
+    }
+
+    /// Send `notifications/resources/updated` for the subscribed resources matching,
+    /// returning the number of notifications sent
+    pub async fn notify_updated(&self, matches: impl Fn(&str) -> bool) -> usize {
+        let notifications: Vec<(Peer<RoleServer>, String)> = {
+            let mut sessions = self.sessions.lock().unwrap();
+            sessions.retain(|(peer, _)| !peer.is_transport_closed());
+            sessions
+                .iter()
+                .flat_map(|(peer, subscriptions)| {
+                    subscriptions
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .filter(|uri| matches(uri))
+                        .map(|uri| (peer.clone(), uri.clone()))
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        };
+        let mut sent = 0;
+        for (peer, uri) in notifications {
+            match peer
+                .notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                .await
+            {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::debug!("Failed to send the resource notification: {}", e),
+            }
+        }
+        sent
+    }
+}
@@ -0,0 +1,179 @@
+use rmcp::{Peer, RoleServer, model::Tool};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, RwLock};
+
+/// Tools offered to the clients
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolAccess {
+    /// Offer only the read-only tools, e.g. during a release freeze
+    #[serde(default)]
+    pub read_only: bool,
+    /// Groups of tools not offered, named after their directory in `src/mcp/tools`
+    /// (e.g. `service_hooks`)
+    #[serde(default)]
+    pub disabled_groups: BTreeSet<String>,
+    /// Tools offered, as name globs where `*` matches any text and `?` any character
+    /// (e.g. `azdo_query_*`), all of them when empty
+    #[serde(default)]
+    pub allowed_tools: BTreeSet<String>,
+    /// Tools not offered, as name globs, even when allowed
+    #[serde(default)]
+    pub denied_tools: BTreeSet<String>,
+}
+
+impl ToolAccess {
+    /// Whether a tool of a group is offered
+    pub fn allows(&self, group: &str, tool: &Tool) -> bool {
+        let read_only = tool
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.read_only_hint)
+            .unwrap_or(false);
+        let name = tool.name.as_ref();
+        (read_only || !self.read_only)
+            && !self.disabled_groups.contains(group)
+            && (self.allowed_tools.is_empty()
+                || self
+                    .allowed_tools
+                    .iter()
+                    .any(|glob| matches_glob(glob, name)))
+            && !self
+                .denied_tools
+                .iter()
+                .any(|glob| matches_glob(glob, name))
+    }
+}
+
+/// Whether a name matches a glob, where `*` matches any text and `?` any character
+pub fn matches_glob(glob: &str, name: &str) -> bool {
+    let glob = glob.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut g, mut n) = (0, 0);
+    // Position of the last `*` and of the name when reaching it, to backtrack to
+    let mut star = None;
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, n));
+                g += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_g, star_n)) => {
+                    g = star_g + 1;
+                    n = star_n + 1;
+                    star = Some((star_g, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|c| *c == '*')
+}
+
+/// Tool access shared by the sessions of the server and changed at runtime, with the
+/// clients to notify when the tools offered change
+#[derive(Default)]
+pub struct ToolAccessControl {
+    access: RwLock<ToolAccess>,
+    peers: Mutex<Vec<Peer<RoleServer>>>,
+}
+
+impl ToolAccessControl {
+    pub fn new(access: ToolAccess) -> Self {
+        Self {
+            access: RwLock::new(access),
+            peers: Mutex::default(),
+        }
+    }
+
+    pub fn get(&self) -> ToolAccess {
+        self.access.read().unwrap().clone()
+    }
+
+    /// Notify a client of the changes to come, the disconnected ones being forgotten
+    pub fn register(&self, peer: Peer<RoleServer>) {
+        let mut peers = self.peers.lock().unwrap();
+        peers.retain(|peer| !peer.is_transport_closed());
+        peers.push(peer);
+    }
+
+    /// Change the tools offered, sending `notifications/tools/list_changed` to the
+    /// clients when they differ
+    pub async fn set(&self, access: ToolAccess) {
+        {
+            let mut current = self.access.write().unwrap();
+            if *current == access {
+                return;
+            }
+            *current = access;
+        }
+        tracing::info!("Tool access changed to {:?}", self.get());
+
+        let peers = {
+            let mut peers = self.peers.lock().unwrap();
+            peers.retain(|peer| !peer.is_transport_closed());
+            peers.clone()
+        };
+        for peer in peers {
+            if let Err(e) = peer.notify_tool_list_changed().await {
+                tracing::debug!("Failed to send the tool list change: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::ToolAnnotations;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_allows() {
+        let schema = Arc::new(serde_json::Map::new());
+        let read = Tool::new("azdo_list_teams", "", schema.clone())
+            .annotate(ToolAnnotations::new().read_only(true));
+        let write = Tool::new("azdo_create_team", "", schema)
+            .annotate(ToolAnnotations::new().read_only(false));
+
+        let access = ToolAccess {
+            read_only: true,
+            ..Default::default()
+        };
+        assert!(access.allows("teams", &read));
+        assert!(!access.allows("teams", &write));
+
+        let access = ToolAccess {
+            read_only: false,
+            disabled_groups: BTreeSet::from(["teams".to_string()]),
+            ..Default::default()
+        };
+        assert!(!access.allows("teams", &read));
+        assert!(access.allows("work_items", &write));
+
+        let access = ToolAccess {
+            allowed_tools: BTreeSet::from(["azdo_*_team?".to_string()]),
+            denied_tools: BTreeSet::from(["azdo_create_*".to_string()]),
+            ..Default::default()
+        };
+        assert!(access.allows("teams", &read));
+        assert!(!access.allows("teams", &write));
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("azdo_query_*", "azdo_query_work_items"));
+        assert!(matches_glob("*", ""));
+        assert!(matches_glob("*_work_*s", "azdo_get_work_items"));
+        assert!(matches_glob("azdo_get_?eam", "azdo_get_team"));
+        assert!(!matches_glob("azdo_get_?eam", "azdo_get_teams"));
+        assert!(!matches_glob("*_work_item", "azdo_get_work_items"));
+        assert!(!matches_glob("azdo_query", "azdo_query_work_items"));
+    }
+}
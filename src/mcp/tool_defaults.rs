@@ -0,0 +1,136 @@
+use rmcp::model::{JsonObject, Tool};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Organization, project and team the tools act on when their arguments omit them,
+/// saving the agents from asking for them in single-organization setups
+#[derive(Debug, Clone, Default)]
+pub struct ToolDefaults {
+    pub organization: Option<String>,
+    pub project: Option<String>,
+    /// Team of the `team_id` arguments, not given to the destructive tools so that
+    /// deleting or overwriting a team always names it
+    pub team: Option<String>,
+}
+
+impl ToolDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.organization.is_none() && self.project.is_none() && self.team.is_none()
+    }
+
+    /// Arguments with a default for a tool, and their default
+    fn of<'a>(&'a self, tool: &Tool) -> impl Iterator<Item = (&'static str, &'a str)> {
+        let destructive = tool
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.destructive_hint)
+            .unwrap_or(false);
+        let properties = tool
+            .input_schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        [
+            ("organization", self.organization.as_deref()),
+            ("project", self.project.as_deref()),
+            ("team_id", self.team.as_deref().filter(|_| !destructive)),
+        ]
+        .into_iter()
+        .filter_map(move |(name, value)| Some((name, value?)))
+        .filter(move |(name, _)| properties.contains_key(*name))
+    }
+
+    /// Tool with the arguments having a default made optional, their description
+    /// naming the default
+    pub fn advertise(&self, mut tool: Tool) -> Tool {
+        let defaults = self.of(&tool).collect::<Vec<_>>();
+        if defaults.is_empty() {
+            return tool;
+        }
+        let mut schema = JsonObject::clone(&tool.input_schema);
+        for (name, value) in defaults {
+            if let Some(Value::Array(required)) = schema.get_mut("required") {
+                required.retain(|required| required != name);
+            }
+            if let Some(Value::Object(property)) = schema
+                .get_mut("properties")
+                .and_then(|properties| properties.get_mut(name))
+            {
+                let description = match property.get("description").and_then(Value::as_str) {
+                    Some(description) => format!("{} (default: {})", description, value),
+                    None => format!("Default: {}", value),
+                };
+                property.insert("description".to_string(), Value::String(description));
+            }
+        }
+        tool.input_schema = Arc::new(schema);
+        tool
+    }
+
+    /// Fill in the arguments having a default that are missing, null or blank
+    pub fn fill(&self, tool: &Tool, arguments: &mut JsonObject) {
+        for (name, value) in self.of(tool) {
+            let omitted = match arguments.get(name) {
+                None | Some(Value::Null) => true,
+                Some(Value::String(given)) => given.trim().is_empty(),
+                Some(_) => false,
+            };
+            if omitted {
+                arguments.insert(name.to_string(), Value::String(value.to_string()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::ToolAnnotations;
+    use serde_json::json;
+
+    #[test]
+    fn test_defaults() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "organization": {"type": "string", "description": "AzDO org name"},
+                "project": {"type": "string"},
+                "team_id": {"type": "string"},
+            },
+            "required": ["organization", "project", "team_id"],
+        });
+        let schema = Arc::new(schema.as_object().unwrap().clone());
+        let tool = Tool::new("azdo_get_team", "", schema.clone());
+        let destructive = Tool::new("azdo_delete_team", "", schema)
+            .annotate(ToolAnnotations::new().destructive(true));
+        let defaults = ToolDefaults {
+            organization: Some("contoso".to_string()),
+            project: None,
+            team: Some("Platform".to_string()),
+        };
+
+        let advertised = defaults.advertise(tool.clone());
+        assert_eq!(advertised.input_schema["required"], json!(["project"]));
+        assert_eq!(
+            advertised.input_schema["properties"]["organization"]["description"],
+            "AzDO org name (default: contoso)"
+        );
+        assert_eq!(
+            advertised.input_schema["properties"]["team_id"]["description"],
+            "Default: Platform"
+        );
+
+        let mut arguments = json!({"organization": " ", "project": "Fabrikam"})
+            .as_object()
+            .unwrap()
+            .clone();
+        defaults.fill(&destructive, &mut arguments);
+        assert_eq!(
+            Value::Object(arguments.clone()),
+            json!({"organization": "contoso", "project": "Fabrikam"})
+        );
+        defaults.fill(&tool, &mut arguments);
+        assert_eq!(arguments["team_id"], "Platform");
+    }
+}
@@ -0,0 +1,201 @@
+use crate::analytics::{burndown, forecast};
+use crate::azure::{analytics, api::AzureBoardsApi};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use chrono::{Days, Utc};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Confidence levels of the forecasts
+const CONFIDENCES: [f64; 4] = [0.5, 0.7, 0.85, 0.95];
+
+/// Trials of the Monte Carlo simulations
+const TRIALS: usize = 10_000;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ForecastWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name (optional)
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Area path, child paths included (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Work item types to include (optional, default: every type but Task)
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// Weeks of completed work the throughput is sampled from (optional, default 12)
+    #[serde(default)]
+    pub history_weeks: Option<u32>,
+    /// Work items to complete, to forecast when they will be done (optional)
+    #[serde(default)]
+    pub items: Option<u32>,
+    /// Date (YYYY-MM-DD), to forecast how many work items will be done by then (optional)
+    #[serde(default)]
+    pub target_date: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_forecast_work_items",
+    description = "Monte Carlo forecast from the weekly throughput of completed work items: when N work items will be done, how many will be done by a date, or the probability of N by a date",
+    read_only = true
+)]
+pub async fn forecast_work_items(
+    client: &dyn AzureBoardsApi,
+    args: ForecastWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_forecast_work_items(team_id={:?}, area_path={:?}, items={:?}, target_date={:?})",
+        args.team_id,
+        args.area_path,
+        args.items,
+        args.target_date
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    let today = Utc::now().date_naive();
+    let target_date = match args.target_date.as_deref() {
+        Some(date) => {
+            let target = burndown::parse_date(date).ok_or_else(|| {
+                invalid_params(format!("Invalid date '{}', expected YYYY-MM-DD", date))
+            })?;
+            if target <= today {
+                return Err(invalid_params(format!(
+                    "The target date {} is not in the future",
+                    target
+                )));
+            }
+            Some(target)
+        }
+        None => None,
+    };
+    if args.items.is_none() && target_date.is_none() {
+        return Err(invalid_params(
+            "Give items, target_date or both to forecast".to_string(),
+        ));
+    }
+    let history_weeks = args.history_weeks.unwrap_or(12).max(1);
+
+    let from = today
+        .checked_sub_days(Days::new(u64::from(history_weeks) * 7 - 1))
+        .unwrap_or(today);
+    let mut filters = vec![
+        "StateCategory eq 'Completed'".to_string(),
+        format!("CompletedDateSK ge {}", from.format("%Y%m%d")),
+    ];
+    if args.work_item_types.is_empty() {
+        filters.push("WorkItemType ne 'Task'".to_string());
+    } else {
+        filters.push(format!(
+            "({})",
+            args.work_item_types
+                .iter()
+                .map(|t| format!("WorkItemType eq {}", analytics::odata_string(t)))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ));
+    }
+    if let Some(team) = &args.team_id {
+        filters.push(analytics::team_filter(team));
+    }
+    if let Some(area) = &args.area_path {
+        filters.push(format!(
+            "(Area/AreaPath eq {} or startswith(Area/AreaPath, {}))",
+            analytics::odata_string(area),
+            analytics::odata_string(&format!("{}\\", area))
+        ));
+    }
+    let query = analytics::ODataQuery {
+        filter: Some(filters.join(" and ")),
+        select: Some("WorkItemId,CompletedDate".to_string()),
+        ..Default::default()
+    };
+    let completed =
+        analytics::query_work_items(client, &args.organization, Some(&args.project), &query)
+            .await
+            .map_err(McpError::from)?;
+    let dates: Vec<_> = completed
+        .iter()
+        .filter_map(|item| burndown::parse_date(item.completed_date.as_deref()?))
+        .collect();
+    let throughput = forecast::weekly_throughput(&dates, today, history_weeks);
+
+    let mean = throughput.iter().sum::<u32>() as f64 / throughput.len() as f64;
+    let mut lines = vec![format!(
+        "Weekly throughput of the last {} weeks (oldest first): {} (mean {:.1})",
+        history_weeks,
+        throughput
+            .iter()
+            .map(|count| count.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        mean
+    )];
+    if mean == 0.0 {
+        lines.push("No work items completed in the history, nothing to forecast".to_string());
+        return Ok(CallToolResult::success(vec![Content::text(
+            lines.join("\n"),
+        )]));
+    }
+
+    let mut rng = fastrand::Rng::new();
+    let weeks_needed = args
+        .items
+        .map(|items| forecast::simulate_weeks(&throughput, items, TRIALS, &mut rng));
+    if let (Some(items), Some(weeks_needed)) = (args.items, &weeks_needed) {
+        lines.push(String::new());
+        lines.push(format!("When {} work items will be done", items));
+        lines.push("confidence,weeks,date".to_string());
+        for confidence in CONFIDENCES {
+            let Some(weeks) = forecast::at_confidence(weeks_needed, confidence, false) else {
+                continue;
+            };
+            let date = today
+                .checked_add_days(Days::new(u64::from(weeks) * 7))
+                .unwrap_or(today);
+            lines.push(format!("{:.0}%,{},{}", confidence * 100.0, weeks, date));
+        }
+    }
+
+    if let Some(target_date) = target_date {
+        // The forecasts are in whole weeks, the nearest to the date
+        let weeks = ((target_date - today).num_days() as f64 / 7.0).round() as u32;
+        let done = forecast::simulate_items(&throughput, weeks, TRIALS, &mut rng);
+        lines.push(String::new());
+        lines.push(format!(
+            "How many work items will be done by {} ({} weeks)",
+            target_date, weeks
+        ));
+        lines.push("confidence,items".to_string());
+        for confidence in CONFIDENCES {
+            if let Some(items) = forecast::at_confidence(&done, confidence, true) {
+                lines.push(format!("{:.0}%,{}", confidence * 100.0, items));
+            }
+        }
+        if let (Some(items), Some(weeks_needed)) = (args.items, &weeks_needed) {
+            lines.push(String::new());
+            lines.push(format!(
+                "Probability of {} work items done by {}: {:.0}%",
+                items,
+                target_date,
+                forecast::probability_within(weeks_needed, weeks) * 100.0
+            ));
+        }
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(
+        lines.join("\n"),
+    )]))
+}
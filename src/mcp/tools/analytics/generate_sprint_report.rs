@@ -0,0 +1,253 @@
+use crate::analytics::burndown::{self, MemberCapacity, RevisionSnapshot, SprintCalendar};
+use crate::analytics::sprint::{self, SprintItem};
+use crate::azure::{api::AzureBoardsApi, iterations, teams, work_items};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use chrono::{Days, Utc};
+use futures::{StreamExt, stream};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Revision histories fetched at once
+const CONCURRENT_REQUESTS: usize = 8;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GenerateSprintReportArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Iteration ID, name or path (optional, defaults to the current iteration)
+    #[serde(default)]
+    pub iteration: Option<String>,
+    /// States considered done (default: ["Closed", "Done"])
+    #[serde(default)]
+    pub done_states: Vec<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_generate_sprint_report",
+    description = "Sprint retrospective of a team iteration: committed vs completed work items, scope added and removed during the sprint, carry-over and completion per person with their capacity",
+    read_only = true
+)]
+pub async fn generate_sprint_report(
+    client: &dyn AzureBoardsApi,
+    args: GenerateSprintReportArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_generate_sprint_report(team_id={}, iteration={:?})",
+        args.team_id,
+        args.iteration
+    );
+    let done_states = if args.done_states.is_empty() {
+        vec!["Closed".to_string(), "Done".to_string()]
+    } else {
+        args.done_states
+    };
+
+    let map_err = McpError::from;
+    let (organization, project) = (&args.organization, &args.project);
+
+    let iteration = iterations::find_team_iteration(
+        client,
+        organization,
+        project,
+        &args.team_id,
+        args.iteration.as_deref(),
+    )
+    .await
+    .map_err(map_err)?;
+
+    let (Some(start), Some(finish)) = (
+        iteration
+            .attributes
+            .start_date
+            .as_deref()
+            .and_then(burndown::parse_date),
+        iteration
+            .attributes
+            .finish_date
+            .as_deref()
+            .and_then(burndown::parse_date),
+    ) else {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "Iteration {} has no start/finish dates",
+            iteration.name
+        ))]));
+    };
+    let now = Utc::now();
+    if start > now.date_naive() {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "Iteration {} has not started yet",
+            iteration.name
+        ))]));
+    }
+    let start_instant = start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let end_instant = finish
+        .checked_add_days(Days::new(1))
+        .unwrap_or(finish)
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .min(now);
+
+    // Capacity of each team member in the sprint, in hours
+    let settings = teams::get_team_settings(client, organization, project, &args.team_id)
+        .await
+        .map_err(map_err)?;
+    let days_off =
+        iterations::get_team_days_off(client, organization, project, &args.team_id, &iteration.id)
+            .await
+            .map_err(map_err)?;
+    let capacity =
+        iterations::get_team_capacity(client, organization, project, &args.team_id, &iteration.id)
+            .await
+            .map_err(map_err)?;
+    let to_ranges = |ranges: &[iterations::DateRange]| {
+        ranges
+            .iter()
+            .filter_map(|r| {
+                Some((
+                    burndown::parse_date(&r.start)?,
+                    burndown::parse_date(&r.end)?,
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+    let calendar = SprintCalendar {
+        start,
+        finish,
+        working_days: burndown::parse_working_days(&settings.working_days),
+        team_days_off: to_ranges(&days_off.days_off),
+    };
+    let member_hours: BTreeMap<String, f64> = capacity
+        .team_members
+        .iter()
+        .filter_map(|m| {
+            let name = m.team_member["displayName"].as_str()?.to_string();
+            let member = MemberCapacity {
+                capacity_per_day: m.activities.iter().map(|a| a.capacity_per_day).sum(),
+                days_off: to_ranges(&m.days_off),
+            };
+            Some((name, calendar.member_hours(&member)))
+        })
+        .collect();
+
+    // The work items in the sprint when it started and when it ended, so that the ones
+    // moved to the next sprint after its end are counted too
+    let field_values = teams::get_team_field_values(client, organization, project, &args.team_id)
+        .await
+        .map_err(map_err)?;
+    let query = sprint_wiql(&iteration.path, &field_values.values);
+    let mut ids = BTreeSet::new();
+    // None queries the work items of now, for the sprints in progress
+    for instant in [
+        Some(start_instant),
+        (end_instant < now).then_some(end_instant),
+    ] {
+        let query = match instant {
+            Some(instant) => format!("{} ASOF '{}'", query, instant.to_rfc3339()),
+            None => query.clone(),
+        };
+        ids.extend(
+            work_items::query_work_item_ids(client, organization, project, &query)
+                .await
+                .map_err(map_err)?,
+        );
+    }
+
+    let total = ids.len() as u64;
+    let mut done = 0;
+    let requests: Vec<_> = ids
+        .iter()
+        .map(|id| async move {
+            work_items::get_work_item_revisions(client, organization, project, *id).await
+        })
+        .collect();
+    let histories: Vec<_> = stream::iter(requests)
+        .buffered(CONCURRENT_REQUESTS)
+        .inspect(|_| {
+            done += 1;
+            crate::progress::report(done, Some(total), || {
+                format!("Fetched the history of {} of {} work items", done, total)
+            });
+        })
+        .collect()
+        .await;
+
+    let mut items = Vec::new();
+    for (id, revisions) in ids.iter().zip(histories) {
+        let revisions = revisions.map_err(map_err)?;
+        let latest = revisions.last().map(|r| &r.fields);
+        let text = |field: &str| {
+            latest
+                .and_then(|fields| fields.get(field))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        items.push(SprintItem {
+            id: *id,
+            title: text("System.Title"),
+            work_item_type: text("System.WorkItemType"),
+            revisions: revisions
+                .iter()
+                .filter_map(|r| RevisionSnapshot::from_fields(&r.fields))
+                .collect(),
+        });
+    }
+
+    let report = sprint::compute_sprint_report(
+        &items,
+        &iteration.path,
+        start_instant,
+        end_instant,
+        &done_states,
+        &member_hours,
+    );
+    let status = if end_instant < now {
+        "ended"
+    } else {
+        "in progress"
+    };
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Iteration {} ({} to {}, {})\n{}",
+        iteration.path,
+        start,
+        finish,
+        status,
+        compact_llm::to_compact_string(&report).unwrap()
+    ))]))
+}
+
+/// WIQL query of the work items of an iteration owned by the team, by its area paths
+fn sprint_wiql(iteration_path: &str, areas: &[teams::TeamFieldValue]) -> String {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let mut query = format!(
+        "SELECT [System.Id] FROM WorkItems WHERE [System.TeamProject] = @project \
+         AND [System.IterationPath] UNDER {}",
+        quote(iteration_path)
+    );
+    if !areas.is_empty() {
+        let areas = areas
+            .iter()
+            .map(|area| {
+                let operator = if area.include_children { "UNDER" } else { "=" };
+                format!("[System.AreaPath] {} {}", operator, quote(&area.value))
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        query.push_str(&format!(" AND ({})", areas));
+    }
+    query
+}
@@ -0,0 +1,214 @@
+use crate::analytics::aging;
+use crate::analytics::burndown::RevisionSnapshot;
+use crate::azure::{analytics, api::AzureBoardsApi, work_items};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use chrono::Utc;
+use futures::{StreamExt, stream};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Work items in progress the report can cover, each needing its revision history
+const MAX_ITEMS: usize = 500;
+
+/// Revision histories fetched at once
+const CONCURRENT_REQUESTS: usize = 8;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetAgingWipArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name (optional)
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Area path, child paths included (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Work item types to include (optional, default: every type but Task)
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// Days in a column after which a work item is flagged as stale (optional, default 14)
+    #[serde(default)]
+    pub threshold_days: Option<f64>,
+}
+
+#[mcp_tool(
+    name = "azdo_get_aging_wip",
+    description = "Aging work in progress: how long each in-progress work item has been in its state and board column, grouped by assignee and column, flagging the ones over a threshold",
+    read_only = true
+)]
+pub async fn get_aging_wip(
+    client: &dyn AzureBoardsApi,
+    args: GetAgingWipArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_aging_wip(team_id={:?}, area_path={:?}, threshold_days={:?})",
+        args.team_id,
+        args.area_path,
+        args.threshold_days
+    );
+    let threshold_days = args.threshold_days.unwrap_or(14.0);
+    let map_err = McpError::from;
+
+    let mut filters =
+        vec!["(StateCategory eq 'InProgress' or StateCategory eq 'Resolved')".to_string()];
+    if args.work_item_types.is_empty() {
+        filters.push("WorkItemType ne 'Task'".to_string());
+    } else {
+        filters.push(format!(
+            "({})",
+            args.work_item_types
+                .iter()
+                .map(|t| format!("WorkItemType eq {}", analytics::odata_string(t)))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ));
+    }
+    if let Some(team) = &args.team_id {
+        filters.push(analytics::team_filter(team));
+    }
+    if let Some(area) = &args.area_path {
+        filters.push(format!(
+            "(Area/AreaPath eq {} or startswith(Area/AreaPath, {}))",
+            analytics::odata_string(area),
+            analytics::odata_string(&format!("{}\\", area))
+        ));
+    }
+    let query = analytics::ODataQuery {
+        filter: Some(filters.join(" and ")),
+        select: Some("WorkItemId,Title,WorkItemType".to_string()),
+        ..Default::default()
+    };
+    let in_progress =
+        analytics::query_work_items(client, &args.organization, Some(&args.project), &query)
+            .await
+            .map_err(map_err)?;
+    if in_progress.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No work items in progress",
+        )]));
+    }
+    if in_progress.len() > MAX_ITEMS {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "{} work items are in progress, at most {} can be reported, filter them by \
+                 team_id, area_path or work_item_types",
+                in_progress.len(),
+                MAX_ITEMS
+            )
+            .into(),
+            data: None,
+        });
+    }
+
+    let (organization, project) = (&args.organization, &args.project);
+    let total = in_progress.len() as u64;
+    let mut done = 0;
+    let requests: Vec<_> = in_progress
+        .iter()
+        .map(|item| async move {
+            work_items::get_work_item_revisions(client, organization, project, item.work_item_id)
+                .await
+        })
+        .collect();
+    let histories: Vec<_> = stream::iter(requests)
+        .buffered(CONCURRENT_REQUESTS)
+        .inspect(|_| {
+            done += 1;
+            crate::progress::report(done, Some(total), || {
+                format!("Fetched the history of {} of {} work items", done, total)
+            });
+        })
+        .collect()
+        .await;
+
+    let now = Utc::now();
+    let mut items = Vec::new();
+    for (item, revisions) in in_progress.iter().zip(histories) {
+        let revisions: Vec<RevisionSnapshot> = revisions
+            .map_err(map_err)?
+            .iter()
+            .filter_map(|r| RevisionSnapshot::from_fields(&r.fields))
+            .collect();
+        items.extend(aging::aging_item(
+            item.work_item_id,
+            item.title.as_deref().unwrap_or_default(),
+            item.work_item_type.as_deref().unwrap_or_default(),
+            &revisions,
+            now,
+            threshold_days,
+        ));
+    }
+    let groups = aging::group_aging(&mut items);
+
+    let write_err = |e: csv::Error| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    };
+    let mut groups_csv = csv::Writer::from_writer(vec![]);
+    groups_csv
+        .write_record(["assigned_to", "column", "items", "stale", "oldest_days"])
+        .map_err(write_err)?;
+    for group in &groups {
+        groups_csv
+            .write_record([
+                group.assigned_to.clone(),
+                group.column.clone(),
+                group.count.to_string(),
+                group.stale.to_string(),
+                format!("{:.1}", group.oldest_days),
+            ])
+            .map_err(write_err)?;
+    }
+    let mut items_csv = csv::Writer::from_writer(vec![]);
+    items_csv
+        .write_record([
+            "assigned_to",
+            "column",
+            "id",
+            "type",
+            "title",
+            "state",
+            "days_in_state",
+            "days_in_column",
+            "stale",
+        ])
+        .map_err(write_err)?;
+    for item in &items {
+        items_csv
+            .write_record([
+                item.assigned_to.clone(),
+                item.column.clone(),
+                item.id.to_string(),
+                item.work_item_type.clone(),
+                item.title.clone(),
+                item.state.clone(),
+                format!("{:.1}", item.days_in_state),
+                format!("{:.1}", item.days_in_column),
+                item.stale.to_string(),
+            ])
+            .map_err(write_err)?;
+    }
+
+    let stale = items.iter().filter(|item| item.stale).count();
+    let csv_text =
+        |wtr: csv::Writer<Vec<u8>>| String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{} work items in progress, {} in their column for more than {} days\n\n{}\n{}",
+        items.len(),
+        stale,
+        threshold_days,
+        csv_text(groups_csv),
+        csv_text(items_csv)
+    ))]))
+}
@@ -0,0 +1,190 @@
+use crate::analytics::flow;
+use crate::azure::{analytics, api::AzureBoardsApi};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use chrono::{Days, Utc};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetFlowMetricsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name (optional)
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Area path, child paths included (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Completed from (YYYY-MM-DD, optional, default 90 days ago)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Completed to (YYYY-MM-DD, optional, default today)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Work item types to include (optional, default: every type but Task)
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// Break the statistics down per work item type (optional, default false)
+    #[serde(default)]
+    pub group_by_type: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_get_flow_metrics",
+    description = "Cycle time (activated->closed) and lead time (created->closed) percentiles for completed work",
+    read_only = true
+)]
+pub async fn get_flow_metrics(
+    client: &dyn AzureBoardsApi,
+    args: GetFlowMetricsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_flow_metrics(team_id={:?}, area_path={:?}, from={:?}, to={:?})",
+        args.team_id,
+        args.area_path,
+        args.from,
+        args.to
+    );
+    let today = Utc::now().date_naive();
+    let to = args.to.unwrap_or_else(|| today.to_string());
+    let from = args.from.unwrap_or_else(|| {
+        today
+            .checked_sub_days(Days::new(90))
+            .unwrap_or(today)
+            .to_string()
+    });
+    for date in [&from, &to] {
+        if crate::analytics::burndown::parse_date(date).is_none() {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: format!("Invalid date '{}', expected YYYY-MM-DD", date).into(),
+                data: None,
+            });
+        }
+    }
+
+    let mut filters = vec![
+        "StateCategory eq 'Completed'".to_string(),
+        format!("CompletedDateSK ge {}", from.replace('-', "")),
+        format!("CompletedDateSK le {}", to.replace('-', "")),
+    ];
+    if args.work_item_types.is_empty() {
+        filters.push("WorkItemType ne 'Task'".to_string());
+    } else {
+        filters.push(format!(
+            "({})",
+            args.work_item_types
+                .iter()
+                .map(|t| format!("WorkItemType eq {}", analytics::odata_string(t)))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ));
+    }
+    if let Some(team) = &args.team_id {
+        filters.push(analytics::team_filter(team));
+    }
+    if let Some(area) = &args.area_path {
+        filters.push(format!(
+            "(Area/AreaPath eq {} or startswith(Area/AreaPath, {}))",
+            analytics::odata_string(area),
+            analytics::odata_string(&format!("{}\\", area))
+        ));
+    }
+
+    let query = analytics::ODataQuery {
+        filter: Some(filters.join(" and ")),
+        select: Some(
+            "WorkItemId,WorkItemType,CreatedDate,ActivatedDate,CompletedDate,CycleTimeDays,LeadTimeDays"
+                .to_string(),
+        ),
+        ..Default::default()
+    };
+    let items =
+        analytics::query_work_items(client, &args.organization, Some(&args.project), &query)
+            .await
+            .map_err(McpError::from)?;
+
+    // (cycle times, lead times) per group
+    let mut groups: BTreeMap<String, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+    for item in &items {
+        let cycle = item.cycle_time_days.or_else(|| {
+            flow::days_between(
+                item.activated_date.as_deref(),
+                item.completed_date.as_deref(),
+            )
+        });
+        let lead = item.lead_time_days.or_else(|| {
+            flow::days_between(item.created_date.as_deref(), item.completed_date.as_deref())
+        });
+        let mut keys = vec!["all".to_string()];
+        if args.group_by_type {
+            keys.push(item.work_item_type.clone().unwrap_or_default());
+        }
+        for key in keys {
+            let entry = groups.entry(key).or_default();
+            entry.0.extend(cycle);
+            entry.1.extend(lead);
+        }
+    }
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "scope", "metric", "count", "mean", "min", "p50", "p85", "p95", "max",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for (scope, (cycle, lead)) in &groups {
+        for (metric, values) in [("cycle_time", cycle), ("lead_time", lead)] {
+            let Some(stats) = flow::duration_stats(values) else {
+                continue;
+            };
+            wtr.write_record([
+                scope.clone(),
+                metric.to_string(),
+                stats.count.to_string(),
+                format!("{:.1}", stats.mean),
+                format!("{:.1}", stats.min),
+                format!("{:.1}", stats.p50),
+                format!("{:.1}", stats.p85),
+                format!("{:.1}", stats.p95),
+                format!("{:.1}", stats.max),
+            ])
+            .map_err(|e| McpError {
+                code: ErrorCode(-32000),
+                message: format!("Failed to write CSV: {}", e).into(),
+                data: None,
+            })?;
+        }
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Completed {} to {}, durations in days\n{}",
+        from, to, data
+    ))]))
+}
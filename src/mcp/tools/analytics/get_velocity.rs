@@ -0,0 +1,173 @@
+use crate::azure::{analytics, api::AzureBoardsApi, iterations};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetVelocityArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Number of past iterations to include (optional, default 6)
+    #[serde(default)]
+    pub iterations: Option<usize>,
+    /// Work item types to count (optional, default: every type but Task)
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_get_velocity",
+    description = "Get team velocity: completed story points/effort per iteration for the last N sprints",
+    read_only = true
+)]
+pub async fn get_velocity(
+    client: &dyn AzureBoardsApi,
+    args: GetVelocityArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_velocity(team_id={}, iterations={:?})",
+        args.team_id,
+        args.iterations
+    );
+    let map_err = McpError::from;
+
+    let mut past = iterations::get_team_iterations(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        Some("past"),
+    )
+    .await
+    .map_err(map_err)?;
+    past.sort_by(|a, b| a.attributes.finish_date.cmp(&b.attributes.finish_date));
+    let skip = past.len().saturating_sub(args.iterations.unwrap_or(6));
+    let past = &past[skip..];
+
+    if past.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No past iterations found",
+        )]));
+    }
+
+    let iteration_filter = past
+        .iter()
+        .map(|i| {
+            format!(
+                "Iteration/IterationPath eq {}",
+                analytics::odata_string(&i.path)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let type_filter = if args.work_item_types.is_empty() {
+        "WorkItemType ne 'Task'".to_string()
+    } else {
+        args.work_item_types
+            .iter()
+            .map(|t| format!("WorkItemType eq {}", analytics::odata_string(t)))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    };
+    let apply = format!(
+        "filter(({}) and ({}) and StateCategory eq 'Completed' and {})\
+         /groupby((Iteration/IterationPath), aggregate($count as Count, StoryPoints with sum as StoryPoints, Effort with sum as Effort))",
+        iteration_filter,
+        type_filter,
+        analytics::team_filter(&args.team_id)
+    );
+    let rows = analytics::aggregate(
+        client,
+        &args.organization,
+        Some(&args.project),
+        "WorkItems",
+        &apply,
+    )
+    .await
+    .map_err(map_err)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "iteration",
+        "start",
+        "finish",
+        "completed_items",
+        "story_points",
+        "effort",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    let mut total_points = 0.0;
+    let mut total_effort = 0.0;
+    for iteration in past {
+        let row = rows.iter().find(|r| {
+            r.pointer("/Iteration/IterationPath")
+                .and_then(|p| p.as_str())
+                == Some(iteration.path.as_str())
+        });
+        let number = |name: &str| row.and_then(|r| r.get(name)).and_then(|v| v.as_f64());
+        let points = number("StoryPoints").unwrap_or(0.0);
+        let effort = number("Effort").unwrap_or(0.0);
+        total_points += points;
+        total_effort += effort;
+
+        wtr.write_record([
+            iteration.name.clone(),
+            iteration
+                .attributes
+                .start_date
+                .as_deref()
+                .map(|d| d.chars().take(10).collect())
+                .unwrap_or_default(),
+            iteration
+                .attributes
+                .finish_date
+                .as_deref()
+                .map(|d| d.chars().take(10).collect())
+                .unwrap_or_default(),
+            number("Count").unwrap_or(0.0).to_string(),
+            points.to_string(),
+            effort.to_string(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    let count = past.len() as f64;
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{}avg_story_points={:.1},avg_effort={:.1}",
+        data,
+        total_points / count,
+        total_effort / count
+    ))]))
+}
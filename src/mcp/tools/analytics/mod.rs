@@ -0,0 +1,15 @@
+// Analytics module
+pub mod forecast_work_items;
+pub mod generate_sprint_report;
+pub mod get_aging_wip;
+pub mod get_flow_metrics;
+pub mod get_velocity;
+pub mod query_analytics;
+
+// Re-export the public items
+pub use forecast_work_items::{ForecastWorkItemsArgs, forecast_work_items};
+pub use generate_sprint_report::{GenerateSprintReportArgs, generate_sprint_report};
+pub use get_aging_wip::{GetAgingWipArgs, get_aging_wip};
+pub use get_flow_metrics::{GetFlowMetricsArgs, get_flow_metrics};
+pub use get_velocity::{GetVelocityArgs, get_velocity};
+pub use query_analytics::{QueryAnalyticsArgs, query_analytics};
@@ -0,0 +1,85 @@
+use crate::azure::{analytics, api::AzureBoardsApi};
+use crate::compact_llm;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct QueryAnalyticsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name (optional, queries the whole organization when omitted)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Entity set (e.g., "WorkItems", "WorkItemSnapshot", "Iterations", "Areas")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub entity_set: String,
+    /// OData $filter (e.g., "WorkItemType eq 'Bug' and State eq 'Active'")
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// OData $select (e.g., "WorkItemId,Title,State")
+    #[serde(default)]
+    pub select: Option<String>,
+    /// OData $apply (e.g., "groupby((State), aggregate($count as Count))")
+    #[serde(default)]
+    pub apply: Option<String>,
+    /// OData $orderby
+    #[serde(default)]
+    pub orderby: Option<String>,
+    /// OData $top, rows queried in total (optional, default 200)
+    #[serde(default)]
+    pub top: Option<u32>,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_query_analytics",
+    description = "Run a raw Analytics OData query; cheapest way to answer aggregate/reporting questions",
+    read_only = true
+)]
+pub async fn query_analytics(
+    client: &dyn AzureBoardsApi,
+    args: QueryAnalyticsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_query_analytics(entity_set={})",
+        args.entity_set
+    );
+    let query = analytics::ODataQuery {
+        filter: args.filter,
+        select: args.select,
+        // Aggregations return one row per group, $top would truncate them
+        top: if args.apply.is_some() {
+            args.top
+        } else {
+            Some(args.top.unwrap_or(200))
+        },
+        apply: args.apply,
+        orderby: args.orderby,
+    };
+    let first_page = analytics::query_path(&args.entity_set, &query);
+    let page = args
+        .paging
+        .fetch_page(|token| {
+            let path = token.unwrap_or_else(|| first_page.clone());
+            let organization = &args.organization;
+            let project = args.project.as_deref();
+            async move {
+                analytics::query_page::<serde_json::Value>(client, organization, project, &path)
+                    .await
+            }
+        })
+        .await?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(compact_llm::to_compact_string(&page.items).unwrap()),
+    )]))
+}
@@ -0,0 +1,5 @@
+// Audit module
+pub mod query_audit_log;
+
+// Re-export the public items
+pub use query_audit_log::{QueryAuditLogArgs, query_audit_log};
@@ -0,0 +1,164 @@
+use crate::azure::{api::AzureBoardsApi, audit};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use chrono::{Duration, SecondsFormat, Utc};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct QueryAuditLogArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Start time, RFC 3339 or YYYY-MM-DD (optional, default 7 days ago)
+    #[serde(default)]
+    pub start_time: Option<String>,
+    /// End time, RFC 3339 or YYYY-MM-DD (optional, default now)
+    #[serde(default)]
+    pub end_time: Option<String>,
+    /// Only entries whose actor name or UPN contains this text (optional)
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Only entries whose area or action contains this text (optional, e.g. "Security", "CSS")
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+/// Normalize a date or datetime argument to RFC 3339
+fn to_rfc3339(value: &str) -> Option<String> {
+    if let Some(datetime) = crate::analytics::burndown::parse_datetime(value) {
+        return Some(datetime.to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+    crate::analytics::burndown::parse_date(value).map(|d| format!("{}T00:00:00Z", d))
+}
+
+#[mcp_tool(
+    name = "azdo_query_audit_log",
+    description = "Query the organization audit log by time range, actor and action",
+    read_only = true
+)]
+pub async fn query_audit_log(
+    client: &dyn AzureBoardsApi,
+    args: QueryAuditLogArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_query_audit_log(start_time={:?}, end_time={:?}, actor={:?}, action={:?})",
+        args.start_time,
+        args.end_time,
+        args.actor,
+        args.action
+    );
+    let now = Utc::now();
+    let start_time = match args.start_time.as_deref() {
+        Some(value) => to_rfc3339(value),
+        None => Some((now - Duration::days(7)).to_rfc3339_opts(SecondsFormat::Secs, true)),
+    };
+    let end_time = match args.end_time.as_deref() {
+        Some(value) => to_rfc3339(value),
+        None => Some(now.to_rfc3339_opts(SecondsFormat::Secs, true)),
+    };
+    let (Some(start_time), Some(end_time)) = (start_time, end_time) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "Invalid time, expected RFC 3339 or YYYY-MM-DD".into(),
+            data: None,
+        });
+    };
+
+    let actor = args.actor.map(|a| a.to_lowercase());
+    let action = args.action.map(|a| a.to_lowercase());
+    let contains = |value: &Option<String>, needle: &str| {
+        value
+            .as_deref()
+            .is_some_and(|v| v.to_lowercase().contains(needle))
+    };
+    let keep = |entry: &audit::AuditLogEntry| {
+        actor
+            .as_deref()
+            .is_none_or(|a| contains(&entry.actor_display_name, a) || contains(&entry.actor_upn, a))
+            && action
+                .as_deref()
+                .is_none_or(|a| contains(&entry.area, a) || contains(&entry.action_id, a))
+    };
+    let page = args
+        .paging
+        .fetch_page(|token| {
+            let (organization, start_time, end_time) = (&args.organization, &start_time, &end_time);
+            async move {
+                let (entries, next) = audit::query_audit_log(
+                    client,
+                    organization,
+                    start_time,
+                    end_time,
+                    token.as_deref(),
+                )
+                .await?;
+                Ok((entries.into_iter().filter(|e| keep(e)).collect(), next))
+            }
+        })
+        .await?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "timestamp",
+        "actor",
+        "action",
+        "category",
+        "project",
+        "ip_address",
+        "details",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for entry in &page.items {
+        wtr.write_record([
+            entry.timestamp.as_str(),
+            entry
+                .actor_upn
+                .as_deref()
+                .or(entry.actor_display_name.as_deref())
+                .unwrap_or_default(),
+            entry.action_id.as_deref().unwrap_or_default(),
+            entry.category_display_name.as_deref().unwrap_or_default(),
+            entry
+                .project_name
+                .as_deref()
+                .or(entry.scope_display_name.as_deref())
+                .unwrap_or_default(),
+            entry.ip_address.as_deref().unwrap_or_default(),
+            entry.details.as_deref().unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
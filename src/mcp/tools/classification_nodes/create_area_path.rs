@@ -0,0 +1,56 @@
+use crate::azure::{api::AzureBoardsApi, classification_nodes};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateAreaPathArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Name of the new area node
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// Parent area path (e.g., "MyProject\\Team1"), defaults to the project root
+    #[serde(default)]
+    pub parent_path: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_create_area_path",
+    description = "Create an area path under a parent area path",
+    read_only = false
+)]
+pub async fn create_area_path(
+    client: &dyn AzureBoardsApi,
+    args: CreateAreaPathArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_create_area_path(name={}, parent_path={:?})",
+        args.name,
+        args.parent_path
+    );
+
+    let node = classification_nodes::create_area_node(
+        client,
+        &args.organization,
+        &args.project,
+        args.parent_path.as_deref(),
+        &args.name,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        node.id, node.path
+    ))]))
+}
@@ -0,0 +1,99 @@
+use crate::azure::{api::AzureBoardsApi, classification_nodes, iterations};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateIterationArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Name of the new iteration (e.g., "Sprint 42")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// Parent iteration path (e.g., "MyProject\\2025"), defaults to the project root
+    #[serde(default)]
+    pub parent_path: Option<String>,
+    /// Start date (YYYY-MM-DD)
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// Finish date (YYYY-MM-DD)
+    #[serde(default)]
+    pub finish_date: Option<String>,
+    /// Team ID or name to assign the new iteration to (optional)
+    #[serde(default)]
+    pub team_id: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_create_iteration",
+    description = "Create an iteration with dates, optionally assigning it to a team",
+    read_only = false
+)]
+pub async fn create_iteration(
+    client: &dyn AzureBoardsApi,
+    args: CreateIterationArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_create_iteration(name={}, parent_path={:?}, start_date={:?}, finish_date={:?}, team_id={:?})",
+        args.name,
+        args.parent_path,
+        args.start_date,
+        args.finish_date,
+        args.team_id
+    );
+
+    // Azure DevOps requires both dates or none
+    if args.start_date.is_some() != args.finish_date.is_some() {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "start_date and finish_date must be provided together".into(),
+            data: None,
+        });
+    }
+
+    let node = classification_nodes::create_iteration_node(
+        client,
+        &args.organization,
+        &args.project,
+        args.parent_path.as_deref(),
+        &args.name,
+        args.start_date.as_deref(),
+        args.finish_date.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    if let Some(team_id) = &args.team_id {
+        iterations::add_team_iteration(
+            client,
+            &args.organization,
+            &args.project,
+            team_id,
+            &node.identifier,
+        )
+        .await
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!(
+                "Iteration {} created but not assigned to team {}: {}",
+                node.path, team_id, e
+            )
+            .into(),
+            data: None,
+        })?;
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        node.identifier, node.path
+    ))]))
+}
@@ -0,0 +1,92 @@
+use crate::azure::{api::AzureBoardsApi, classification_nodes};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DeleteClassificationNodeArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Node kind: "area" or "iteration"
+    pub kind: String,
+    /// Full path of the node to delete (e.g., "MyProject\\Sprint 1")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub path: String,
+    /// Full path of the node that will receive the work items of the deleted one
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub reclassify_path: String,
+}
+
+#[mcp_tool(
+    name = "azdo_delete_classification_node",
+    description = "Delete an area/iteration path, moving its work items to another path",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn delete_classification_node(
+    client: &dyn AzureBoardsApi,
+    args: DeleteClassificationNodeArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_delete_classification_node(kind={}, path={}, reclassify_path={})",
+        args.kind,
+        args.path,
+        args.reclassify_path
+    );
+
+    let Some(structure_group) = classification_nodes::structure_group(&args.kind) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid kind '{}'. Valid values are: 'area', 'iteration'",
+                args.kind
+            )
+            .into(),
+            data: None,
+        });
+    };
+
+    let reclassify_node = classification_nodes::get_node(
+        client,
+        &args.organization,
+        &args.project,
+        structure_group,
+        &args.reclassify_path,
+    )
+    .await
+    .map_err(|e| McpError {
+        code: ErrorCode(-32602),
+        message: format!(
+            "Reclassification path '{}' not found: {}",
+            args.reclassify_path, e
+        )
+        .into(),
+        data: None,
+    })?;
+
+    classification_nodes::delete_node(
+        client,
+        &args.organization,
+        &args.project,
+        structure_group,
+        &args.path,
+        reclassify_node.id,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Deleted {}, work items moved to {}",
+        args.path, args.reclassify_path
+    ))]))
+}
@@ -1,9 +1,9 @@
-use crate::azure::{classification_nodes, client::AzureDevOpsClient};
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::azure::{api::AzureBoardsApi, cache, classification_nodes};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,31 +19,37 @@ pub struct ListAreaPathsArgs {
     /// Optional parent path to traverse the tree (e.g., "Area1\\SubArea1")
     #[serde(default)]
     pub parent_path: Option<String>,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_list_area_paths",
-    description = "List area paths for a project"
+    description = "List area paths for a project",
+    read_only = true
 )]
 pub async fn list_area_paths(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListAreaPathsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_area_paths");
+    tracing::info!("Tool invoked: azdo_list_area_paths");
 
-    let root_node = classification_nodes::list_area_paths(
-        client,
-        &args.organization,
-        &args.project,
-        args.parent_path.as_deref(),
-        10, // depth
+    let root_node = cache::bypass(
+        args.no_cache,
+        classification_nodes::list_area_paths(
+            client,
+            &args.organization,
+            &args.project,
+            args.parent_path.as_deref(),
+            10, // depth
+        ),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     // Flatten the tree into a list of paths
     fn collect_paths(node: &classification_nodes::ClassificationNode, paths: &mut Vec<String>) {
@@ -59,7 +65,9 @@ pub async fn list_area_paths(
     collect_paths(&root_node, &mut paths);
 
     // Return as comma-separated list
+    let page = args.paging.page(paths)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        paths.join(","),
+        page.append_next_cursor(page.items.join(",")),
     )]))
 }
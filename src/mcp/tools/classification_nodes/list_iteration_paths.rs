@@ -1,5 +1,5 @@
-use crate::azure::{classification_nodes, client::AzureDevOpsClient, iterations};
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::azure::{api::AzureBoardsApi, cache, classification_nodes, iterations};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -22,31 +22,38 @@ pub struct ListIterationPathsArgs {
     /// Optional timeframe filter: "current", "past", or "future" (only applies when team_id is provided)
     #[serde(default)]
     pub timeframe: Option<String>,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_list_iteration_paths",
-    description = "List iteration paths for a project or team"
+    description = "List iteration paths for a project or team",
+    read_only = true
 )]
 pub async fn list_iteration_paths(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListIterationPathsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_iteration_paths");
+    tracing::info!("Tool invoked: azdo_list_iteration_paths");
 
     // Validate timeframe if provided
-    if let Some(ref timeframe) = args.timeframe {
-        if !matches!(timeframe.as_str(), "current" | "past" | "future") {
-            return Err(McpError {
-                code: ErrorCode(-32602),
-                message: format!(
-                    "Invalid timeframe '{}'. Valid values are: 'current', 'past', 'future'",
-                    timeframe
-                )
-                .into(),
-                data: None,
-            });
-        }
+    if let Some(ref timeframe) = args.timeframe
+        && !matches!(timeframe.as_str(), "current" | "past" | "future")
+    {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid timeframe '{}'. Valid values are: 'current', 'past', 'future'",
+                timeframe
+            )
+            .into(),
+            data: None,
+        });
     }
 
     // If team_id is provided, use team-specific iterations
@@ -59,11 +66,7 @@ pub async fn list_iteration_paths(
             None, // Get all iterations first
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
         // Filter by timeframe if provided (post-acquisition filtering)
         if let Some(ref timeframe) = args.timeframe {
@@ -104,25 +107,25 @@ pub async fn list_iteration_paths(
                 "No iterations found",
             )]))
         } else {
+            let page = args.paging.page(csv_lines)?;
             Ok(CallToolResult::success(vec![Content::text(
-                csv_lines.join(","),
+                page.append_next_cursor(page.items.join(",")),
             )]))
         }
     } else {
         // Use project-level classification nodes
-        let root_node = classification_nodes::list_iteration_paths(
-            client,
-            &args.organization,
-            &args.project,
-            None,
-            10, // depth
+        let root_node = cache::bypass(
+            args.no_cache,
+            classification_nodes::list_iteration_paths(
+                client,
+                &args.organization,
+                &args.project,
+                None,
+                10, // depth
+            ),
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
         // Flatten the tree into a list of paths and return as CSV
         let mut paths = Vec::new();
@@ -134,8 +137,9 @@ pub async fn list_iteration_paths(
                 "No iterations found",
             )]))
         } else {
+            let page = args.paging.page(paths)?;
             Ok(CallToolResult::success(vec![Content::text(
-                paths.join(","),
+                page.append_next_cursor(page.items.join(",")),
             )]))
         }
     }
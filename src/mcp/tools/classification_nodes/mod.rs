@@ -1,7 +1,17 @@
 // Classification Nodes module
+pub mod create_area_path;
+pub mod create_iteration;
+pub mod delete_classification_node;
 pub mod list_area_paths;
 pub mod list_iteration_paths;
+pub mod move_classification_node;
+pub mod update_classification_node;
 
 // Re-export the public items
+pub use create_area_path::{CreateAreaPathArgs, create_area_path};
+pub use create_iteration::{CreateIterationArgs, create_iteration};
+pub use delete_classification_node::{DeleteClassificationNodeArgs, delete_classification_node};
 pub use list_area_paths::{ListAreaPathsArgs, list_area_paths};
 pub use list_iteration_paths::{ListIterationPathsArgs, list_iteration_paths};
+pub use move_classification_node::{MoveClassificationNodeArgs, move_classification_node};
+pub use update_classification_node::{UpdateClassificationNodeArgs, update_classification_node};
@@ -0,0 +1,84 @@
+use crate::azure::{api::AzureBoardsApi, classification_nodes};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct MoveClassificationNodeArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Node kind: "area" or "iteration"
+    pub kind: String,
+    /// Full path of the node to move (e.g., "MyProject\\Team1\\Backend")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub path: String,
+    /// Full path of the new parent (e.g., "MyProject\\Team2")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub new_parent_path: String,
+}
+
+#[mcp_tool(
+    name = "azdo_move_classification_node",
+    description = "Move an area/iteration path under a new parent",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn move_classification_node(
+    client: &dyn AzureBoardsApi,
+    args: MoveClassificationNodeArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_move_classification_node(kind={}, path={}, new_parent_path={})",
+        args.kind,
+        args.path,
+        args.new_parent_path
+    );
+
+    let Some(structure_group) = classification_nodes::structure_group(&args.kind) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid kind '{}'. Valid values are: 'area', 'iteration'",
+                args.kind
+            )
+            .into(),
+            data: None,
+        });
+    };
+
+    let node = classification_nodes::get_node(
+        client,
+        &args.organization,
+        &args.project,
+        structure_group,
+        &args.path,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let moved = classification_nodes::move_node(
+        client,
+        &args.organization,
+        &args.project,
+        structure_group,
+        node.id,
+        &args.new_parent_path,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        moved.id, moved.path
+    ))]))
+}
@@ -0,0 +1,108 @@
+use crate::azure::{api::AzureBoardsApi, classification_nodes};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateClassificationNodeArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Node kind: "area" or "iteration"
+    pub kind: String,
+    /// Full path of the node (e.g., "MyProject\\Sprint 1")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub path: String,
+    /// New name of the node
+    #[serde(default)]
+    pub new_name: Option<String>,
+    /// New start date (YYYY-MM-DD), iterations only
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// New finish date (YYYY-MM-DD), iterations only
+    #[serde(default)]
+    pub finish_date: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_update_classification_node",
+    description = "Rename an area/iteration path or change iteration dates",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn update_classification_node(
+    client: &dyn AzureBoardsApi,
+    args: UpdateClassificationNodeArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_update_classification_node(kind={}, path={}, new_name={:?})",
+        args.kind,
+        args.path,
+        args.new_name
+    );
+
+    let Some(structure_group) = classification_nodes::structure_group(&args.kind) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid kind '{}'. Valid values are: 'area', 'iteration'",
+                args.kind
+            )
+            .into(),
+            data: None,
+        });
+    };
+
+    let mut body = serde_json::Map::new();
+    if let Some(new_name) = &args.new_name {
+        body.insert("name".to_string(), serde_json::json!(new_name));
+    }
+    if args.start_date.is_some() || args.finish_date.is_some() {
+        if structure_group != "iterations" {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "Dates can only be set on iterations".into(),
+                data: None,
+            });
+        }
+        body.insert(
+            "attributes".to_string(),
+            serde_json::json!({
+                "startDate": args.start_date,
+                "finishDate": args.finish_date,
+            }),
+        );
+    }
+    if body.is_empty() {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "Either new_name or dates must be provided".into(),
+            data: None,
+        });
+    }
+
+    let node = classification_nodes::update_node(
+        client,
+        &args.organization,
+        &args.project,
+        structure_group,
+        &args.path,
+        &serde_json::Value::Object(body),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        node.id, node.path
+    ))]))
+}
@@ -0,0 +1,109 @@
+use crate::azure::{api::AzureBoardsApi, dashboards};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetDashboardWidgetsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Dashboard ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub dashboard_id: String,
+    /// Include the raw widget settings (optional, default false)
+    #[serde(default)]
+    pub include_settings: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_get_dashboard_widgets",
+    description = "List the widgets of a dashboard with the saved queries they reference",
+    read_only = true
+)]
+pub async fn get_dashboard_widgets(
+    client: &dyn AzureBoardsApi,
+    args: GetDashboardWidgetsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_dashboard_widgets(dashboard_id={})",
+        args.dashboard_id
+    );
+    let dashboard = dashboards::get_dashboard(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &args.dashboard_id,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let mut header = vec!["id", "name", "type", "position", "size", "query_ids"];
+    if args.include_settings {
+        header.push("settings");
+    }
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(&header).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for widget in &dashboard.widgets {
+        let position = widget
+            .position
+            .as_ref()
+            .map(|p| format!("{},{}", p.row, p.column))
+            .unwrap_or_default();
+        let size = widget
+            .size
+            .as_ref()
+            .map(|s| format!("{}x{}", s.column_span, s.row_span))
+            .unwrap_or_default();
+        let mut record = vec![
+            widget.id.clone(),
+            widget.name.clone(),
+            widget.widget_type().to_string(),
+            position,
+            size,
+            widget.query_ids().join(";"),
+        ];
+        if args.include_settings {
+            record.push(widget.settings.clone().unwrap_or_default());
+        }
+        wtr.write_record(&record).map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Dashboard: {}\n{}",
+        dashboard.name, data
+    ))]))
+}
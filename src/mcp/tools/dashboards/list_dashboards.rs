@@ -0,0 +1,82 @@
+use crate::azure::{api::AzureBoardsApi, dashboards};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListDashboardsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_list_dashboards",
+    description = "List team dashboards",
+    read_only = true
+)]
+pub async fn list_dashboards(
+    client: &dyn AzureBoardsApi,
+    args: ListDashboardsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_list_dashboards(team_id={})",
+        args.team_id
+    );
+    let dashboards =
+        dashboards::list_dashboards(client, &args.organization, &args.project, &args.team_id)
+            .await
+            .map_err(McpError::from)?;
+
+    let page = args.paging.page(dashboards)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["id", "name", "description"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for dashboard in &page.items {
+        wtr.write_record([
+            dashboard.id.as_str(),
+            dashboard.name.as_str(),
+            dashboard.description.as_deref().unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
@@ -0,0 +1,7 @@
+// Dashboards module
+pub mod get_dashboard_widgets;
+pub mod list_dashboards;
+
+// Re-export the public items
+pub use get_dashboard_widgets::{GetDashboardWidgetsArgs, get_dashboard_widgets};
+pub use list_dashboards::{ListDashboardsArgs, list_dashboards};
@@ -0,0 +1,7 @@
+// Identities module
+pub mod resolve_identities;
+pub mod search_identities;
+
+// Re-export the public items
+pub use resolve_identities::{ResolveIdentitiesArgs, resolve_identities};
+pub use search_identities::{SearchIdentitiesArgs, search_identities};
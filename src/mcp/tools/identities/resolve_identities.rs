@@ -0,0 +1,84 @@
+use crate::azure::{api::AzureBoardsApi, users};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ResolveIdentitiesArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Identity GUIDs, identity descriptors or subject descriptors
+    pub identities: Vec<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_resolve_identities",
+    description = "Resolve identity GUIDs or descriptors to display names and emails",
+    read_only = true
+)]
+pub async fn resolve_identities(
+    client: &dyn AzureBoardsApi,
+    args: ResolveIdentitiesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_resolve_identities(count={})",
+        args.identities.len()
+    );
+    if args.identities.is_empty() {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "At least one identity is required".into(),
+            data: None,
+        });
+    }
+
+    let profiles = users::resolve_users(client, &args.organization, &args.identities)
+        .await
+        .map_err(McpError::from)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["identity", "display_name", "email", "unique_name", "kind"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for identity in &args.identities {
+        let profile = profiles.get(identity);
+        wtr.write_record([
+            identity.as_str(),
+            profile.map(|p| p.display_name.as_str()).unwrap_or_default(),
+            profile.and_then(|p| p.email.as_deref()).unwrap_or_default(),
+            profile
+                .and_then(|p| p.unique_name.as_deref())
+                .unwrap_or_default(),
+            profile.map(|p| p.kind.as_str()).unwrap_or("unresolved"),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(data)]))
+}
@@ -0,0 +1,101 @@
+use crate::azure::{api::AzureBoardsApi, identities};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SearchIdentitiesArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Name, account name or email to search for
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub query: String,
+    /// Include inactive identities (optional, default false)
+    #[serde(default)]
+    pub include_inactive: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_search_identities",
+    description = "Search users and groups by name or email; returns unique names, IDs and descriptors",
+    read_only = true
+)]
+pub async fn search_identities(
+    client: &dyn AzureBoardsApi,
+    args: SearchIdentitiesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_search_identities(query={})", args.query);
+    let identities = identities::search_identities(client, &args.organization, &args.query)
+        .await
+        .map_err(McpError::from)?;
+
+    let identities: Vec<_> = identities
+        .into_iter()
+        .filter(|i| args.include_inactive || i.is_active)
+        .collect();
+    let page = args.paging.page(identities)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "display_name",
+        "unique_name",
+        "email",
+        "kind",
+        "id",
+        "descriptor",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for identity in &page.items {
+        wtr.write_record([
+            identity.display_name(),
+            identity.property("Account").unwrap_or_default(),
+            identity.property("Mail").unwrap_or_default(),
+            if identity.is_container {
+                "group"
+            } else {
+                "user"
+            },
+            identity.id.as_str(),
+            identity
+                .subject_descriptor
+                .as_deref()
+                .or(identity.descriptor.as_deref())
+                .unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
@@ -1,7 +1,15 @@
 // Tools module
+pub mod analytics;
+pub mod audit;
 pub mod classification_nodes;
+pub mod dashboards;
+pub mod identities;
+pub mod notifications;
 pub mod organizations;
+pub mod plans;
+pub mod processes;
 pub mod projects;
+pub mod service_hooks;
 pub mod support;
 pub mod tags;
 pub mod teams;
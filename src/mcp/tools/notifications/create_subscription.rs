@@ -0,0 +1,103 @@
+use crate::azure::{api::AzureBoardsApi, boards, notifications, projects};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateSubscriptionArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Subscription description
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub description: String,
+    /// Notify on work item changes under this area path (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Notify on work item changes of this type (optional)
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+    /// Notify only when the work item is assigned to this user (optional, "@Me" for the caller)
+    #[serde(default)]
+    pub assigned_to: Option<String>,
+    /// Team ID or name to subscribe (optional, defaults to the caller)
+    #[serde(default)]
+    pub team_id: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_create_subscription",
+    description = "Create an email notification subscription for work item changes",
+    read_only = false
+)]
+pub async fn create_subscription(
+    client: &dyn AzureBoardsApi,
+    args: CreateSubscriptionArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_create_subscription(description={})",
+        args.description
+    );
+    let map_err = McpError::from;
+
+    let mut clauses = Vec::new();
+    if let Some(area_path) = args.area_path {
+        clauses.push(notifications::SubscriptionClause {
+            field_name: "Area path".to_string(),
+            operator: "Under".to_string(),
+            value: area_path,
+        });
+    }
+    if let Some(work_item_type) = args.work_item_type {
+        clauses.push(notifications::SubscriptionClause {
+            field_name: "Work item type".to_string(),
+            operator: "=".to_string(),
+            value: work_item_type,
+        });
+    }
+    if let Some(assigned_to) = args.assigned_to {
+        clauses.push(notifications::SubscriptionClause {
+            field_name: "Assigned to".to_string(),
+            operator: "=".to_string(),
+            value: assigned_to,
+        });
+    }
+
+    let project = projects::get_project(client, &args.organization, &args.project)
+        .await
+        .map_err(map_err)?;
+    let subscriber_id = match &args.team_id {
+        Some(team_id) => Some(
+            boards::get_team(client, &args.organization, &args.project, team_id)
+                .await
+                .map_err(map_err)?
+                .id,
+        ),
+        None => None,
+    };
+
+    let subscription = notifications::create_subscription(
+        client,
+        &args.organization,
+        &args.description,
+        notifications::WORK_ITEM_CHANGED_EVENT,
+        &clauses,
+        Some(&project.id),
+        subscriber_id.as_deref(),
+    )
+    .await
+    .map_err(map_err)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Created subscription {}",
+        subscription.id
+    ))]))
+}
@@ -0,0 +1,44 @@
+use crate::azure::{api::AzureBoardsApi, notifications};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DeleteSubscriptionArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Subscription ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub subscription_id: String,
+}
+
+#[mcp_tool(
+    name = "azdo_delete_subscription",
+    description = "Delete a notification subscription",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn delete_subscription(
+    client: &dyn AzureBoardsApi,
+    args: DeleteSubscriptionArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_delete_subscription(subscription_id={})",
+        args.subscription_id
+    );
+    notifications::delete_subscription(client, &args.organization, &args.subscription_id)
+        .await
+        .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Deleted subscription {}",
+        args.subscription_id
+    ))]))
+}
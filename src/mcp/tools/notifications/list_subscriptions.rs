@@ -0,0 +1,109 @@
+use crate::azure::{api::AzureBoardsApi, boards, notifications};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListSubscriptionsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name, required with team_id
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Team ID or name (optional, defaults to the caller's own subscriptions)
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_list_subscriptions",
+    description = "List personal or team notification subscriptions",
+    read_only = true
+)]
+pub async fn list_subscriptions(
+    client: &dyn AzureBoardsApi,
+    args: ListSubscriptionsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_list_subscriptions(team_id={:?})",
+        args.team_id
+    );
+    let map_err = McpError::from;
+
+    let target_id = match (&args.project, &args.team_id) {
+        (Some(project), Some(team_id)) => Some(
+            boards::get_team(client, &args.organization, project, team_id)
+                .await
+                .map_err(map_err)?
+                .id,
+        ),
+        (None, Some(_)) => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "project is required when team_id is set".into(),
+                data: None,
+            });
+        }
+        _ => None,
+    };
+
+    let subscriptions =
+        notifications::list_subscriptions(client, &args.organization, target_id.as_deref())
+            .await
+            .map_err(map_err)?;
+
+    let page = args.paging.page(subscriptions)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["id", "description", "event_type", "subscriber", "status"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for subscription in &page.items {
+        wtr.write_record([
+            subscription.id.as_str(),
+            subscription.description.as_deref().unwrap_or_default(),
+            subscription.event_type(),
+            subscription
+                .subscriber
+                .as_ref()
+                .and_then(|s| s.get("displayName"))
+                .and_then(|n| n.as_str())
+                .unwrap_or_default(),
+            subscription.status.as_deref().unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
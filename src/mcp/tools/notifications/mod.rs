@@ -0,0 +1,9 @@
+// Notifications module
+pub mod create_subscription;
+pub mod delete_subscription;
+pub mod list_subscriptions;
+
+// Re-export the public items
+pub use create_subscription::{CreateSubscriptionArgs, create_subscription};
+pub use delete_subscription::{DeleteSubscriptionArgs, delete_subscription};
+pub use list_subscriptions::{ListSubscriptionsArgs, list_subscriptions};
@@ -0,0 +1,42 @@
+use crate::azure::{api::AzureBoardsApi, credentials};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CheckCredentialsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name, enables the work item write check
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_check_credentials",
+    description = "Diagnose the credentials: org validity, user, granted scopes (probed), expiry, api-version support and how to fix missing access",
+    read_only = true
+)]
+pub async fn check_credentials(
+    client: &dyn AzureBoardsApi,
+    args: CheckCredentialsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_check_credentials(organization={})",
+        args.organization
+    );
+    let check = credentials::check_credentials(client, &args.organization, args.project.as_deref())
+        .await
+        .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&check).unwrap(),
+    )]))
+}
@@ -0,0 +1,57 @@
+use crate::azure::{api::AzureBoardsApi, security};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CheckPermissionsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Area path, e.g. "Project\\Team", defaults to the project root
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Iteration path, e.g. "Project\\Sprint 1"
+    #[serde(default)]
+    pub iteration_path: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_check_permissions",
+    description = "Evaluate the effective permissions of the current identity on a project, area path and iteration path: what it can and cannot do (view/edit/delete work items, bypass rules, manage nodes)",
+    read_only = true
+)]
+pub async fn check_permissions(
+    client: &dyn AzureBoardsApi,
+    args: CheckPermissionsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_check_permissions(organization={}, project={}, area_path={:?}, iteration_path={:?})",
+        args.organization,
+        args.project,
+        args.area_path,
+        args.iteration_path
+    );
+    let report = security::check_permissions(
+        client,
+        &args.organization,
+        &args.project,
+        args.area_path.as_deref(),
+        args.iteration_path.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&report).unwrap(),
+    )]))
+}
@@ -1,4 +1,4 @@
-use crate::azure::{client::AzureDevOpsClient, organizations};
+use crate::azure::{api::AzureBoardsApi, organizations};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -12,20 +12,17 @@ pub struct GetCurrentUserArgs {}
 
 #[mcp_tool(
     name = "azdo_get_current_user",
-    description = "Get current user profile"
+    description = "Get current user profile",
+    read_only = true
 )]
 pub async fn get_current_user(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     _args: GetCurrentUserArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_get_current_user");
+    tracing::info!("Tool invoked: azdo_get_current_user");
     let profile = organizations::get_profile(client)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
     let mut wtr = csv::WriterBuilder::new()
         .has_headers(false)
@@ -1,4 +1,5 @@
-use crate::azure::{client::AzureDevOpsClient, organizations};
+use crate::azure::{api::AzureBoardsApi, organizations};
+use crate::mcp::tools::support::PagingArgs;
 
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -9,14 +10,22 @@ use rmcp::{
 };
 
 #[derive(Deserialize, JsonSchema)]
-pub struct ListOrganizationsArgs {}
+pub struct ListOrganizationsArgs {
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
 
-#[mcp_tool(name = "azdo_list_organizations", description = "List organizations")]
+#[mcp_tool(
+    name = "azdo_list_organizations",
+    description = "List organizations",
+    read_only = true
+)]
 pub async fn list_organizations(
-    client: &AzureDevOpsClient,
-    _args: ListOrganizationsArgs,
+    client: &dyn AzureBoardsApi,
+    args: ListOrganizationsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_organizations");
+    tracing::info!("Tool invoked: azdo_list_organizations");
 
     // First, get the user's profile to obtain their member ID
     let profile = organizations::get_profile(client)
@@ -30,16 +39,14 @@ pub async fn list_organizations(
     // Then, list all organizations for this member ID
     let orgs = organizations::list_organizations(client, &profile.id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
     // Extract just the organization names for compact response
     let org_names: Vec<String> = orgs.into_iter().map(|org| org.account_name).collect();
 
+    let page = args.paging.page(org_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        org_names.join(","),
+        page.append_next_cursor(page.items.join(",")),
     )]))
 }
@@ -1,7 +1,11 @@
 // Organizations module
+pub mod check_credentials;
+pub mod check_permissions;
 pub mod get_current_user;
 pub mod list_organizations;
 
 // Re-export the public items
+pub use check_credentials::{CheckCredentialsArgs, check_credentials};
+pub use check_permissions::{CheckPermissionsArgs, check_permissions};
 pub use get_current_user::{GetCurrentUserArgs, get_current_user};
 pub use list_organizations::{ListOrganizationsArgs, list_organizations};
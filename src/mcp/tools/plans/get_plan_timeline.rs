@@ -0,0 +1,172 @@
+use crate::azure::{
+    api::AzureBoardsApi,
+    plans::{self, PlanMarker},
+};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use chrono::{Days, Utc};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetPlanTimelineArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Plan ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub plan_id: String,
+    /// Timeline start (YYYY-MM-DD, optional, default today)
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// Timeline end (YYYY-MM-DD, optional, default start + 90 days)
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_get_plan_timeline",
+    description = "Get a Delivery Plan timeline: teams, work items per iteration and markers",
+    read_only = true
+)]
+pub async fn get_plan_timeline(
+    client: &dyn AzureBoardsApi,
+    args: GetPlanTimelineArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_plan_timeline(plan_id={})",
+        args.plan_id
+    );
+    let map_err = McpError::from;
+
+    let start = match args.start_date.as_deref() {
+        Some(d) => crate::analytics::burndown::parse_date(d),
+        None => Some(Utc::now().date_naive()),
+    };
+    let end = match args.end_date.as_deref() {
+        Some(d) => crate::analytics::burndown::parse_date(d),
+        None => start.and_then(|s| s.checked_add_days(Days::new(90))),
+    };
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "Invalid date, expected YYYY-MM-DD".into(),
+            data: None,
+        });
+    };
+
+    let plan = plans::get_plan(client, &args.organization, &args.project, &args.plan_id)
+        .await
+        .map_err(map_err)?;
+    let timeline = plans::get_delivery_timeline(
+        client,
+        &args.organization,
+        &args.project,
+        &args.plan_id,
+        &start.to_string(),
+        &end.to_string(),
+    )
+    .await
+    .map_err(map_err)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "team",
+        "iteration",
+        "start",
+        "finish",
+        "id",
+        "type",
+        "title",
+        "state",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for team in &timeline.teams {
+        let column = |name: &str| team.field_reference_names.iter().position(|f| f == name);
+        let columns = [
+            column("System.Id"),
+            column("System.WorkItemType"),
+            column("System.Title"),
+            column("System.State"),
+        ];
+        for iteration in &team.iterations {
+            for work_item in &iteration.work_items {
+                let value = |index: Option<usize>| match index.and_then(|i| work_item.get(i)) {
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(serde_json::Value::Null) | None => String::new(),
+                    Some(v) => v.to_string(),
+                };
+                wtr.write_record([
+                    team.name.clone().unwrap_or_default(),
+                    iteration.name.clone(),
+                    iteration
+                        .start_date
+                        .as_deref()
+                        .map(|d| d.chars().take(10).collect())
+                        .unwrap_or_default(),
+                    iteration
+                        .finish_date
+                        .as_deref()
+                        .map(|d| d.chars().take(10).collect())
+                        .unwrap_or_default(),
+                    value(columns[0]),
+                    value(columns[1]),
+                    value(columns[2]),
+                    value(columns[3]),
+                ])
+                .map_err(|e| McpError {
+                    code: ErrorCode(-32000),
+                    message: format!("Failed to write CSV: {}", e).into(),
+                    data: None,
+                })?;
+            }
+        }
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    let markers: Vec<PlanMarker> = plan
+        .properties
+        .as_ref()
+        .and_then(|p| p.get("markers"))
+        .and_then(|m| serde_json::from_value(m.clone()).ok())
+        .unwrap_or_default();
+    let markers = markers
+        .iter()
+        .map(|m| {
+            format!(
+                "{} {}",
+                m.date.chars().take(10).collect::<String>(),
+                m.label
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Plan: {} ({} to {})\nMarkers: {}\n{}",
+        plan.name, timeline.start_date, timeline.end_date, markers, data
+    ))]))
+}
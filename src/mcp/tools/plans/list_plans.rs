@@ -0,0 +1,77 @@
+use crate::azure::{api::AzureBoardsApi, plans};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListPlansArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_list_plans",
+    description = "List Delivery Plans of a project",
+    read_only = true
+)]
+pub async fn list_plans(
+    client: &dyn AzureBoardsApi,
+    args: ListPlansArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_list_plans(project={})", args.project);
+    let plans = plans::list_plans(client, &args.organization, &args.project)
+        .await
+        .map_err(McpError::from)?;
+
+    let page = args.paging.page(plans)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["id", "name", "type", "description"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for plan in &page.items {
+        wtr.write_record([
+            plan.id.as_str(),
+            plan.name.as_str(),
+            plan.plan_type.as_str(),
+            plan.description.as_deref().unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
@@ -0,0 +1,7 @@
+// Plans module
+pub mod get_plan_timeline;
+pub mod list_plans;
+
+// Re-export the public items
+pub use get_plan_timeline::{GetPlanTimelineArgs, get_plan_timeline};
+pub use list_plans::{ListPlansArgs, list_plans};
@@ -0,0 +1,81 @@
+use crate::azure::{api::AzureBoardsApi, processes};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetProcessArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Process name or ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub process: String,
+}
+
+#[mcp_tool(
+    name = "azdo_get_process",
+    description = "Get process details: parent process, derived processes and projects using it",
+    read_only = true
+)]
+pub async fn get_process(
+    client: &dyn AzureBoardsApi,
+    args: GetProcessArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_get_process(process={})", args.process);
+    let processes = processes::list_processes(client, &args.organization, true)
+        .await
+        .map_err(McpError::from)?;
+
+    let process = processes
+        .iter()
+        .find(|p| {
+            p.type_id.eq_ignore_ascii_case(&args.process)
+                || p.name.eq_ignore_ascii_case(&args.process)
+        })
+        .ok_or_else(|| McpError {
+            code: ErrorCode(-32602),
+            message: format!("Process '{}' not found", args.process).into(),
+            data: None,
+        })?;
+
+    let parent = process
+        .parent_process_type_id
+        .as_deref()
+        .and_then(|id| processes.iter().find(|p| p.type_id == id))
+        .map(|p| p.name.clone());
+    let derived: Vec<&str> = processes
+        .iter()
+        .filter(|p| p.parent_process_type_id.as_deref() == Some(process.type_id.as_str()))
+        .map(|p| p.name.as_str())
+        .collect();
+    let projects: Vec<&str> = process
+        .projects
+        .iter()
+        .flatten()
+        .map(|p| p.name.as_str())
+        .collect();
+
+    let output = serde_json::json!({
+        "id": process.type_id,
+        "name": process.name,
+        "description": process.description,
+        "referenceName": process.reference_name,
+        "type": process.customization_type,
+        "parent": parent,
+        "derived": derived,
+        "default": process.is_default,
+        "enabled": process.is_enabled,
+        "projects": projects,
+    });
+
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&output).unwrap(),
+    )]))
+}
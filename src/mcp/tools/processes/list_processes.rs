@@ -0,0 +1,103 @@
+use crate::azure::{api::AzureBoardsApi, processes};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListProcessesArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_list_processes",
+    description = "List processes with their parent process and the projects using them",
+    read_only = true
+)]
+pub async fn list_processes(
+    client: &dyn AzureBoardsApi,
+    args: ListProcessesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_list_processes");
+    let processes = processes::list_processes(client, &args.organization, true)
+        .await
+        .map_err(McpError::from)?;
+
+    let page = args.paging.page(processes.iter().collect())?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "id", "name", "type", "parent", "default", "enabled", "projects",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for process in &page.items {
+        // Inherited processes reference their parent by id, resolve it to a name
+        let parent = process
+            .parent_process_type_id
+            .as_deref()
+            .filter(|id| *id != "00000000-0000-0000-0000-000000000000")
+            .map(|id| {
+                processes
+                    .iter()
+                    .find(|p| p.type_id == id)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| id.to_string())
+            })
+            .unwrap_or_default();
+        let projects = process
+            .projects
+            .as_ref()
+            .map(|p| {
+                p.iter()
+                    .map(|x| x.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .unwrap_or_default();
+
+        wtr.write_record([
+            process.type_id.as_str(),
+            process.name.as_str(),
+            process.customization_type.as_deref().unwrap_or_default(),
+            &parent,
+            &process.is_default.to_string(),
+            &process.is_enabled.unwrap_or(true).to_string(),
+            &projects,
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
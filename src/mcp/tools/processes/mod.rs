@@ -0,0 +1,7 @@
+// Processes module
+pub mod get_process;
+pub mod list_processes;
+
+// Re-export the public items
+pub use get_process::{GetProcessArgs, get_process};
+pub use list_processes::{ListProcessesArgs, list_processes};
@@ -0,0 +1,126 @@
+use crate::azure::{api::AzureBoardsApi, processes, projects};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_POLL_ATTEMPTS: u32 = 60;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateProjectArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// Project description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Process template name or ID (optional, defaults to the org default process)
+    #[serde(default)]
+    pub process: Option<String>,
+    /// "private" or "public" (optional, default "private")
+    #[serde(default)]
+    pub visibility: Option<String>,
+    /// "Git" or "Tfvc" (optional, default "Git")
+    #[serde(default)]
+    pub version_control: Option<String>,
+    /// Poll the operation until the project is created (optional, default false)
+    #[serde(default)]
+    pub wait_for_completion: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_create_project",
+    description = "Create a project; optionally wait for the creation to complete",
+    read_only = false
+)]
+pub async fn create_project(
+    client: &dyn AzureBoardsApi,
+    args: CreateProjectArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_create_project(name={})", args.name);
+
+    let visibility = args.visibility.as_deref().unwrap_or("private");
+    if !matches!(visibility, "private" | "public") {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!("Invalid visibility '{}'", visibility).into(),
+            data: None,
+        });
+    }
+    let version_control = match args.version_control.as_deref() {
+        None => "Git",
+        Some(v) if v.eq_ignore_ascii_case("git") => "Git",
+        Some(v) if v.eq_ignore_ascii_case("tfvc") => "Tfvc",
+        Some(v) => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: format!("Invalid version control type '{}'", v).into(),
+                data: None,
+            });
+        }
+    };
+
+    let available = processes::list_processes(client, &args.organization, false)
+        .await
+        .map_err(McpError::from)?;
+    let process = match args.process.as_deref() {
+        Some(p) => available
+            .iter()
+            .find(|x| x.type_id.eq_ignore_ascii_case(p) || x.name.eq_ignore_ascii_case(p)),
+        None => available.iter().find(|x| x.is_default),
+    }
+    .ok_or_else(|| McpError {
+        code: ErrorCode(-32602),
+        message: format!(
+            "Process '{}' not found",
+            args.process.as_deref().unwrap_or("default")
+        )
+        .into(),
+        data: None,
+    })?;
+
+    let mut operation = projects::create_project(
+        client,
+        &args.organization,
+        &args.name,
+        args.description.as_deref(),
+        visibility,
+        &process.type_id,
+        version_control,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    if args.wait_for_completion {
+        let mut attempts = 0;
+        while !operation.is_completed() && attempts < MAX_POLL_ATTEMPTS {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            attempts += 1;
+            operation = projects::get_operation(client, &args.organization, &operation.id)
+                .await
+                .map_err(McpError::from)?;
+            crate::progress::report(attempts as u64, Some(MAX_POLL_ATTEMPTS as u64), || {
+                format!("Project creation {}", operation.status)
+            });
+        }
+    }
+
+    let mut output = format!(
+        "operation={},status={},process={}",
+        operation.id, operation.status, process.name
+    );
+    if let Some(message) = &operation.result_message {
+        output.push_str(&format!(",message={}", message));
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(output)]))
+}
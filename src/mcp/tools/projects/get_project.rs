@@ -0,0 +1,77 @@
+use crate::azure::{api::AzureBoardsApi, cache, projects};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetProjectArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name or ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Include the project properties (optional, default false)
+    #[serde(default)]
+    pub include_properties: bool,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_get_project",
+    description = "Get project details (process, visibility, default team, properties)",
+    read_only = true
+)]
+pub async fn get_project(
+    client: &dyn AzureBoardsApi,
+    args: GetProjectArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_get_project(project={})", args.project);
+    let project = cache::bypass(
+        args.no_cache,
+        projects::get_project(client, &args.organization, &args.project),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let capabilities = project.capabilities.as_ref();
+    let mut output = serde_json::json!({
+        "id": project.id,
+        "name": project.name,
+        "description": project.description,
+        "state": project.state,
+        "visibility": project.visibility,
+        "process": capabilities
+            .and_then(|c| c.pointer("/processTemplate/templateName"))
+            .cloned(),
+        "versionControl": capabilities
+            .and_then(|c| c.pointer("/versioncontrol/sourceControlType"))
+            .cloned(),
+        "defaultTeam": project.default_team.as_ref().map(|t| t.name.clone()),
+        "lastUpdateTime": project.last_update_time,
+    });
+
+    if args.include_properties {
+        let properties = cache::bypass(
+            args.no_cache,
+            projects::get_project_properties(client, &args.organization, &project.id),
+        )
+        .await
+        .map_err(McpError::from)?;
+        let properties: serde_json::Map<String, serde_json::Value> =
+            properties.into_iter().map(|p| (p.name, p.value)).collect();
+        output["properties"] = serde_json::Value::Object(properties);
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&output).unwrap(),
+    )]))
+}
@@ -1,10 +1,10 @@
-use crate::azure::{client::AzureDevOpsClient, projects};
+use crate::azure::{api::AzureBoardsApi, cache, projects};
 
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -14,29 +14,37 @@ pub struct ListProjectsArgs {
     /// AzDO org name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub organization: String,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_list_projects",
-    description = "List projects in an organization"
+    description = "List projects in an organization",
+    read_only = true
 )]
 pub async fn list_projects(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListProjectsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_projects");
-    let projects = projects::list_projects(client, &args.organization)
-        .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+    tracing::info!("Tool invoked: azdo_list_projects");
+    let projects = cache::bypass(
+        args.no_cache,
+        projects::list_projects(client, &args.organization),
+    )
+    .await
+    .map_err(McpError::from)?;
 
     // Extract just the project names for compact response
     let project_names: Vec<String> = projects.into_iter().map(|project| project.name).collect();
 
+    let page = args.paging.page(project_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        project_names.join(","),
+        page.append_next_cursor(page.items.join(",")),
     )]))
 }
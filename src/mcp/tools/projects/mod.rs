@@ -1,5 +1,9 @@
 // Projects module
+pub mod create_project;
+pub mod get_project;
 pub mod list_projects;
 
 // Re-export the public items
+pub use create_project::{CreateProjectArgs, create_project};
+pub use get_project::{GetProjectArgs, get_project};
 pub use list_projects::{ListProjectsArgs, list_projects};
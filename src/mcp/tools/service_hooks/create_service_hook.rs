@@ -0,0 +1,110 @@
+use crate::azure::{api::AzureBoardsApi, projects, service_hooks};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+const EVENT_TYPES: [&str; 5] = [
+    "workitem.created",
+    "workitem.updated",
+    "workitem.deleted",
+    "workitem.restored",
+    "workitem.commented",
+];
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateServiceHookArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Event: workitem.created, workitem.updated, workitem.deleted, workitem.restored, workitem.commented
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub event_type: String,
+    /// Webhook URL receiving the events
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub url: String,
+    /// Only events for work items under this area path (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Only events for this work item type (optional)
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+    /// Only when this field changes, workitem.updated only (optional, e.g. "System.State")
+    #[serde(default)]
+    pub changed_field: Option<String>,
+    /// Extra HTTP headers, one "Name:Value" per line (optional)
+    #[serde(default)]
+    pub http_headers: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_create_service_hook",
+    description = "Create a webhook service hook for work item events",
+    read_only = false
+)]
+pub async fn create_service_hook(
+    client: &dyn AzureBoardsApi,
+    args: CreateServiceHookArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_create_service_hook(event_type={})",
+        args.event_type
+    );
+    if !EVENT_TYPES.contains(&args.event_type.as_str()) {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid event_type '{}'. Valid values are: {}",
+                args.event_type,
+                EVENT_TYPES.join(", ")
+            )
+            .into(),
+            data: None,
+        });
+    }
+    if args.changed_field.is_some() && args.event_type != "workitem.updated" {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "changed_field is only supported with workitem.updated".into(),
+            data: None,
+        });
+    }
+
+    let map_err = McpError::from;
+    let project = projects::get_project(client, &args.organization, &args.project)
+        .await
+        .map_err(map_err)?;
+
+    let publisher_inputs = service_hooks::PublisherInputs {
+        project_id: project.id,
+        area_path: args.area_path,
+        work_item_type: args.work_item_type,
+        changed_fields: args.changed_field,
+    };
+    let consumer_inputs = service_hooks::ConsumerInputs {
+        url: args.url,
+        http_headers: args.http_headers,
+        resource_details_to_send: None,
+    };
+    let subscription = service_hooks::create_webhook_subscription(
+        client,
+        &args.organization,
+        &args.event_type,
+        &publisher_inputs,
+        &consumer_inputs,
+    )
+    .await
+    .map_err(map_err)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Created service hook {}",
+        subscription.id
+    ))]))
+}
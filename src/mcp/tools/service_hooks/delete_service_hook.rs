@@ -0,0 +1,44 @@
+use crate::azure::{api::AzureBoardsApi, service_hooks};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DeleteServiceHookArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Service hook subscription ID
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub subscription_id: String,
+}
+
+#[mcp_tool(
+    name = "azdo_delete_service_hook",
+    description = "Delete a service hook subscription",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn delete_service_hook(
+    client: &dyn AzureBoardsApi,
+    args: DeleteServiceHookArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_delete_service_hook(subscription_id={})",
+        args.subscription_id
+    );
+    service_hooks::delete_subscription(client, &args.organization, &args.subscription_id)
+        .await
+        .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Deleted service hook {}",
+        args.subscription_id
+    ))]))
+}
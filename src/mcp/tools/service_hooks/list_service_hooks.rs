@@ -0,0 +1,81 @@
+use crate::azure::{api::AzureBoardsApi, service_hooks};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListServiceHooksArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_list_service_hooks",
+    description = "List service hook subscriptions",
+    read_only = true
+)]
+pub async fn list_service_hooks(
+    client: &dyn AzureBoardsApi,
+    args: ListServiceHooksArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_list_service_hooks");
+    let subscriptions = service_hooks::list_subscriptions(client, &args.organization)
+        .await
+        .map_err(McpError::from)?;
+
+    let page = args.paging.page(subscriptions)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["id", "event_type", "consumer", "action", "status", "target"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for subscription in &page.items {
+        wtr.write_record([
+            subscription.id.as_str(),
+            subscription.event_type.as_str(),
+            subscription.consumer_id.as_str(),
+            subscription.consumer_action_id.as_str(),
+            subscription.status.as_deref().unwrap_or_default(),
+            subscription
+                .consumer_inputs
+                .as_ref()
+                .and_then(|i| i.get("url"))
+                .and_then(|u| u.as_str())
+                .unwrap_or_default(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
+}
@@ -0,0 +1,13 @@
+// Service hooks module
+pub mod create_service_hook;
+pub mod delete_service_hook;
+pub mod list_service_hooks;
+pub mod subscribe_work_item_notifications;
+
+// Re-export the public items
+pub use create_service_hook::{CreateServiceHookArgs, create_service_hook};
+pub use delete_service_hook::{DeleteServiceHookArgs, delete_service_hook};
+pub use list_service_hooks::{ListServiceHooksArgs, list_service_hooks};
+pub use subscribe_work_item_notifications::{
+    SubscribeWorkItemNotificationsArgs, subscribe_work_item_notifications,
+};
@@ -0,0 +1,111 @@
+use crate::azure::context;
+use crate::azure::{api::AzureBoardsApi, projects, service_hooks};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Events the server forwards to the sessions subscribed to the work items
+const EVENT_TYPES: [&str; 3] = ["workitem.created", "workitem.updated", "workitem.commented"];
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SubscribeWorkItemNotificationsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Only events for work items under this area path (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Only events for this work item type (optional)
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_subscribe_work_item_notifications",
+    description = "Create the service hooks posting the work item events of a project to this server, which notifies the sessions subscribed to the work item resources",
+    read_only = false
+)]
+pub async fn subscribe_work_item_notifications(
+    client: &dyn AzureBoardsApi,
+    args: SubscribeWorkItemNotificationsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_subscribe_work_item_notifications(project={})",
+        args.project
+    );
+    let Some(receiver) = context::current().service_hook_receiver.clone() else {
+        return Err(McpError {
+            code: ErrorCode(-32600),
+            message: "The server does not receive service hooks, start it with `serve http`, \
+                      --service-hook-secret and --public-url"
+                .into(),
+            data: None,
+        });
+    };
+
+    let map_err = McpError::from;
+    let project = projects::get_project(client, &args.organization, &args.project)
+        .await
+        .map_err(map_err)?;
+    // The hooks already posting to the server are kept, so that the tool can be run again
+    let existing = service_hooks::list_subscriptions(client, &args.organization)
+        .await
+        .map_err(map_err)?;
+
+    let mut lines = Vec::new();
+    for event_type in EVENT_TYPES {
+        let found = existing.iter().find(|subscription| {
+            subscription.event_type == event_type
+                && subscription
+                    .consumer_inputs
+                    .as_ref()
+                    .is_some_and(|inputs| inputs["url"] == receiver.url.as_str())
+                && subscription
+                    .publisher_inputs
+                    .as_ref()
+                    .is_some_and(|inputs| inputs["projectId"] == project.id.as_str())
+        });
+        if let Some(subscription) = found {
+            lines.push(format!(
+                "{}: existing service hook {}",
+                event_type, subscription.id
+            ));
+            continue;
+        }
+        let publisher_inputs = service_hooks::PublisherInputs {
+            project_id: project.id.clone(),
+            area_path: args.area_path.clone(),
+            work_item_type: args.work_item_type.clone(),
+            changed_fields: None,
+        };
+        let subscription = service_hooks::create_webhook_subscription(
+            client,
+            &args.organization,
+            event_type,
+            &publisher_inputs,
+            &receiver.consumer_inputs(),
+        )
+        .await
+        .map_err(map_err)?;
+        lines.push(format!(
+            "{}: created service hook {}",
+            event_type, subscription.id
+        ));
+    }
+
+    lines.push(format!(
+        "Subscribe to azdo://{}/{}/workitems/{{id}} to be notified of the changes",
+        args.organization, args.project
+    ));
+    Ok(CallToolResult::success(vec![Content::text(
+        lines.join("\n"),
+    )]))
+}
@@ -0,0 +1,161 @@
+use super::reference_names;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Fields giving the type of the work item of a row rather than a field value
+const TYPE_FIELDS: [&str; 2] = ["System.WorkItemType", "Type"];
+
+/// Row of an imported CSV, with the fields of the work item to create
+#[derive(Debug, PartialEq)]
+pub struct ImportRow {
+    /// Line of the row in the CSV, the header being line 1
+    pub line: usize,
+    pub work_item_type: String,
+    /// Reference names and values of the fields, without the empty cells
+    pub fields: Vec<(String, Value)>,
+    /// Why the work item cannot be created, when invalid
+    pub errors: Vec<String>,
+}
+
+impl ImportRow {
+    pub fn title(&self) -> &str {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == "System.Title")
+            .and_then(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    }
+}
+
+/// Parse the rows of a CSV whose columns are mapped to fields by header, by simplified
+/// (e.g. `AssignedTo`) or reference name (e.g. `Custom.Severity`), each header being
+/// taken as the field name without a mapping. The rows without a type column value
+/// get the default type. Fails on a malformed CSV or a mapping of a missing column,
+/// the invalid rows carrying their errors.
+pub fn parse_import_rows(
+    csv: &str,
+    mapping: &BTreeMap<String, String>,
+    default_type: Option<&str>,
+) -> Result<Vec<ImportRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(false)
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .clone();
+    if let Some(column) = mapping.keys().find(|column| {
+        !headers
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(column))
+    }) {
+        return Err(format!("The CSV has no column {}", column));
+    }
+
+    // Field of each column, the unmapped columns being ignored when there is a mapping
+    let columns: Vec<Option<String>> = headers
+        .iter()
+        .map(|header| {
+            let field = if mapping.is_empty() {
+                Some(header.to_string())
+            } else {
+                mapping
+                    .iter()
+                    .find(|(column, _)| column.eq_ignore_ascii_case(header))
+                    .map(|(_, field)| field.clone())
+            };
+            field.filter(|field| !field.is_empty())
+        })
+        .collect();
+    let is_type = |field: &str| {
+        TYPE_FIELDS
+            .iter()
+            .any(|type_field| type_field.eq_ignore_ascii_case(field))
+    };
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 2;
+        let record = record.map_err(|e| format!("Invalid CSV at line {}: {}", line, e))?;
+        let mut row = ImportRow {
+            line,
+            work_item_type: default_type.unwrap_or_default().to_string(),
+            fields: Vec::new(),
+            errors: Vec::new(),
+        };
+        for (value, field) in record.iter().zip(&columns) {
+            let Some(field) = field else {
+                continue;
+            };
+            if value.is_empty() {
+                continue;
+            }
+            if is_type(field) {
+                row.work_item_type = value.to_string();
+                continue;
+            }
+            let reference = reference_names(std::slice::from_ref(field)).remove(0);
+            if row.fields.iter().any(|(known, _)| *known == reference) {
+                row.errors.push(format!("{} is given twice", reference));
+                continue;
+            }
+            row.fields
+                .push((reference, Value::String(value.to_string())));
+        }
+        if row.work_item_type.is_empty() {
+            row.errors
+                .push("No work item type, set a type column or work_item_type".to_string());
+        }
+        if row.title().is_empty() {
+            row.errors.push("No title".to_string());
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_import_rows() {
+        let csv = "\
+Summary,Kind,Owner,Notes
+Fix login,Bug,jane@contoso.com,ignored
+Add export,,,
+,Task,,";
+        let mapping = BTreeMap::from([
+            ("summary".to_string(), "Title".to_string()),
+            ("Kind".to_string(), "Type".to_string()),
+            ("Owner".to_string(), "AssignedTo".to_string()),
+        ]);
+        let rows = parse_import_rows(csv, &mapping, Some("User Story")).unwrap();
+        assert_eq!(
+            rows[0],
+            ImportRow {
+                line: 2,
+                work_item_type: "Bug".to_string(),
+                fields: vec![
+                    ("System.Title".to_string(), "Fix login".into()),
+                    ("System.AssignedTo".to_string(), "jane@contoso.com".into()),
+                ],
+                errors: Vec::new(),
+            }
+        );
+        assert_eq!(rows[1].work_item_type, "User Story");
+        assert_eq!(rows[2].errors, ["No title"]);
+
+        let mapping = BTreeMap::from([("Estimate".to_string(), "Effort".to_string())]);
+        assert_eq!(
+            parse_import_rows(csv, &mapping, None).unwrap_err(),
+            "The CSV has no column Estimate"
+        );
+        let rows = parse_import_rows("Title\nFix login", &BTreeMap::new(), None).unwrap();
+        assert_eq!(
+            rows[0].errors,
+            ["No work item type, set a type column or work_item_type"]
+        );
+    }
+}
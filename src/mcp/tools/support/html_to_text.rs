@@ -0,0 +1,67 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// Static regex patterns for text cleaning (compiled once, reused many times)
+static RE_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r" +").unwrap());
+static RE_NEWLINES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n+").unwrap());
+static RE_LEADING_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n[ ]+").unwrap());
+static RE_TRAILING_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ ]+\n").unwrap());
+static RE_DASHES: Lazy<Regex> = Lazy::new(|| Regex::new(r"-{3,}\n").unwrap());
+static RE_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\[image\]").unwrap());
+
+/// Converts the HTML of the rich-text fields and of the comments to compact plain text,
+/// keeping the lists and the links but dropping the markup, the styles and the images. The HTML is
+/// returned as is when it can't be parsed.
+pub fn html_to_text(html: &str) -> String {
+    // Convert HTML to plain text, width doesn't matter as we don't need wrapping
+    let Ok(mut plain_text) = html2text::from_read(html.as_bytes(), usize::MAX) else {
+        return html.to_string();
+    };
+
+    // Normalize newlines: replace \r with \n
+    plain_text = plain_text.replace('\r', "\n");
+
+    // Normalize tabulations: replace \t with 1 space
+    plain_text = plain_text.replace('\t', " ");
+
+    // Normalize emdashes: replace ─ with -
+    plain_text = plain_text.replace('─', "-");
+
+    // Remove multiple consecutive spaces
+    plain_text = RE_SPACES.replace_all(&plain_text, " ").to_string();
+
+    // Collapse multiple consecutive newlines into single newlines
+    plain_text = RE_NEWLINES.replace_all(&plain_text, "\n").to_string();
+
+    // Remove leading whitespace before newlines (spaces, tabs, etc.)
+    plain_text = RE_LEADING_WS.replace_all(&plain_text, "\n").to_string();
+
+    // Remove trailing whitespace before newlines (spaces, tabs, etc.)
+    plain_text = RE_TRAILING_WS.replace_all(&plain_text, "\n").to_string();
+
+    // Collapse 3+ dashes followed by newline to just 3 dashes + newline
+    plain_text = RE_DASHES.replace_all(&plain_text, "---\n").to_string();
+
+    // Remove [Image] strings (case insensitive)
+    plain_text = RE_IMAGE.replace_all(&plain_text, "").to_string();
+
+    plain_text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text(
+                "<div><span style=\"color:red\">Steps:</span></div>\
+                 <ol><li>Open  the <b>login</b> page</li><li>Submit</li></ol>\
+                 <div><img src=\"x.png\"></div>"
+            ),
+            "Steps:\n1. Open the login page\n2. Submit"
+        );
+        assert_eq!(html_to_text("plain"), "plain");
+    }
+}
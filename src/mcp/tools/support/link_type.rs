@@ -0,0 +1,12 @@
+/// Azure DevOps link type of a friendly name ("parent", "child", "related", "duplicate",
+/// "dependency"), other names being used as they are
+pub fn link_type_reference(link_type: &str) -> &str {
+    match link_type.to_lowercase().as_str() {
+        "parent" => "System.LinkTypes.Hierarchy-Forward",
+        "child" => "System.LinkTypes.Hierarchy-Reverse",
+        "related" => "System.LinkTypes.Related",
+        "duplicate" => "System.LinkTypes.Duplicate-Forward",
+        "dependency" => "System.LinkTypes.Dependency-Forward",
+        _ => link_type,
+    }
+}
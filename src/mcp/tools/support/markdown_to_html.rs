@@ -0,0 +1,250 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+// Static regex patterns of the blocks and of the inline markup
+static RE_HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(#{1,6})\s+(.*?)\s*#*$").unwrap());
+static RE_RULE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:-{3,}|\*{3,}|_{3,})$").unwrap());
+static RE_LIST_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^( *)(?:([-*+])|(\d+)[.)])\s+(.*)$").unwrap());
+static RE_LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)(?:\s+&quot;[^)]*&quot;)?\)").unwrap());
+static RE_AUTOLINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"&lt;(https?://[^\s&]+)&gt;").unwrap());
+static RE_BOLD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\*\*(\S(?:.*?\S)?)\*\*|__(\S(?:.*?\S)?)__").unwrap());
+static RE_ITALIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*(\S(?:[^*]*?\S)?)\*").unwrap());
+static RE_UNDERSCORE_ITALIC: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(^|[^\w])_(\S(?:[^_]*?\S)?)_($|[^\w])").unwrap());
+static RE_STRIKE: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~(\S(?:.*?\S)?)~~").unwrap());
+static RE_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new("\u{0}(\\d+)\u{0}").unwrap());
+
+/// Converts markdown to the HTML of the rich-text fields and of the comments: headings,
+/// paragraphs, bold, italic, strikethrough, inline code, fenced code blocks, links,
+/// nested bulleted and numbered lists, block quotes and rules. Any HTML in the markdown
+/// is escaped, so that it shows as written.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut quote: Vec<&str> = Vec::new();
+    // Indentation and tag of the lists open, the innermost last
+    let mut lists: Vec<(usize, &str)> = Vec::new();
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        // Fenced code block, written as is up to its closing fence
+        if let Some(fence) = ["```", "~~~"]
+            .into_iter()
+            .find(|fence| trimmed.starts_with(fence))
+        {
+            flush(&mut html, &mut paragraph, &mut quote, &mut lists);
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with(fence))
+                .collect();
+            html.push_str("<pre><code>");
+            html.push_str(&escape(&code.join("\n")));
+            html.push_str("</code></pre>");
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph, &mut quote, &mut lists);
+            continue;
+        }
+
+        if let Some(quoted) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_lists(&mut html, &mut lists);
+            quote.push(quoted.trim());
+            continue;
+        }
+        flush_quote(&mut html, &mut quote);
+
+        if let Some(captures) = RE_HEADING.captures(trimmed) {
+            flush(&mut html, &mut paragraph, &mut quote, &mut lists);
+            let level = captures[1].len();
+            html.push_str(&format!(
+                "<h{}>{}</h{}>",
+                level,
+                inline(&captures[2]),
+                level
+            ));
+            continue;
+        }
+
+        if RE_RULE.is_match(trimmed) {
+            flush(&mut html, &mut paragraph, &mut quote, &mut lists);
+            html.push_str("<hr>");
+            continue;
+        }
+
+        if let Some(captures) = RE_LIST_ITEM.captures(line) {
+            flush_paragraph(&mut html, &mut paragraph);
+            let indent = captures[1].len();
+            let tag = if captures.get(2).is_some() {
+                "ul"
+            } else {
+                "ol"
+            };
+            // Close the deeper lists, and the list at this level if of another kind
+            while let Some((open_indent, open_tag)) = lists.last().copied() {
+                if open_indent > indent || (open_indent == indent && open_tag != tag) {
+                    html.push_str(&format!("</li></{}>", open_tag));
+                    lists.pop();
+                } else {
+                    break;
+                }
+            }
+            match lists.last() {
+                Some((open_indent, _)) if *open_indent == indent => html.push_str("</li><li>"),
+                _ => {
+                    html.push_str(&format!("<{}><li>", tag));
+                    lists.push((indent, tag));
+                }
+            }
+            html.push_str(&inline(&captures[4]));
+            continue;
+        }
+
+        // Continuation of a list item, or a line of a paragraph
+        if !lists.is_empty() && paragraph.is_empty() {
+            html.push(' ');
+            html.push_str(&inline(trimmed));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    flush(&mut html, &mut paragraph, &mut quote, &mut lists);
+    html
+}
+
+fn flush(
+    html: &mut String,
+    paragraph: &mut Vec<&str>,
+    quote: &mut Vec<&str>,
+    lists: &mut Vec<(usize, &str)>,
+) {
+    flush_paragraph(html, paragraph);
+    flush_quote(html, quote);
+    close_lists(html, lists);
+}
+
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        // The line breaks within a paragraph are kept, as the Boards UI users expect
+        let lines: Vec<String> = paragraph.drain(..).map(inline).collect();
+        html.push_str(&format!("<p>{}</p>", lines.join("<br>")));
+    }
+}
+
+fn flush_quote(html: &mut String, quote: &mut Vec<&str>) {
+    if !quote.is_empty() {
+        let lines: Vec<String> = quote.drain(..).map(inline).collect();
+        html.push_str(&format!("<blockquote>{}</blockquote>", lines.join("<br>")));
+    }
+}
+
+fn close_lists(html: &mut String, lists: &mut Vec<(usize, &str)>) {
+    while let Some((_, tag)) = lists.pop() {
+        html.push_str(&format!("</li></{}>", tag));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Converts the inline markup of a line. The code spans and the link targets are set
+/// aside first, so that their characters are not taken as emphasis.
+fn inline(text: &str) -> String {
+    let mut protected: Vec<String> = Vec::new();
+    let mut protect = |html: String| {
+        protected.push(html);
+        format!("\u{0}{}\u{0}", protected.len() - 1)
+    };
+
+    // Code spans, between backticks
+    let mut line = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let Some(length) = rest[start + 1..].find('`') else {
+            break;
+        };
+        line.push_str(&escape(&rest[..start]));
+        let code = &rest[start + 1..start + 1 + length];
+        line.push_str(&protect(format!("<code>{}</code>", escape(code))));
+        rest = &rest[start + 2 + length..];
+    }
+    line.push_str(&escape(rest));
+
+    let line = RE_LINK.replace_all(&line, |captures: &Captures| {
+        // No scripts in the links
+        if captures[2].to_ascii_lowercase().starts_with("javascript:") {
+            return captures[1].to_string();
+        }
+        format!(
+            "<a href=\"{}\">{}</a>",
+            protect(captures[2].to_string()),
+            &captures[1]
+        )
+    });
+    let line = RE_AUTOLINK.replace_all(&line, |captures: &Captures| {
+        let url = protect(captures[1].to_string());
+        format!("<a href=\"{}\">{}</a>", url, url)
+    });
+    let line = RE_BOLD.replace_all(&line, |captures: &Captures| {
+        let bold = captures.get(1).or(captures.get(2)).unwrap().as_str();
+        format!("<strong>{}</strong>", bold)
+    });
+    let line = RE_ITALIC.replace_all(&line, "<em>$1</em>");
+    let line = RE_UNDERSCORE_ITALIC.replace_all(&line, "$1<em>$2</em>$3");
+    let line = RE_STRIKE.replace_all(&line, "<del>$1</del>");
+
+    RE_PLACEHOLDER
+        .replace_all(&line, |captures: &Captures| {
+            protected[captures[1].parse::<usize>().unwrap()].clone()
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html() {
+        let markdown = "\
+## Steps <1>
+
+Open the **login** page, _then_ submit
+with `a_b*c` and [the docs](https://x.test/a_b?q=1&r=*2*).
+
+- First
+  - Nested ~~item~~
+- Second
+1. One
+2. Two
+
+```rust
+let x = a < b;
+```
+> Quoted *text*
+---";
+        assert_eq!(
+            markdown_to_html(markdown),
+            "<h2>Steps &lt;1&gt;</h2>\
+             <p>Open the <strong>login</strong> page, <em>then</em> submit<br>\
+             with <code>a_b*c</code> and \
+             <a href=\"https://x.test/a_b?q=1&amp;r=*2*\">the docs</a>.</p>\
+             <ul><li>First<ul><li>Nested <del>item</del></li></ul></li><li>Second</li></ul>\
+             <ol><li>One</li><li>Two</li></ol>\
+             <pre><code>let x = a &lt; b;</code></pre>\
+             <blockquote>Quoted <em>text</em></blockquote>\
+             <hr>"
+        );
+    }
+}
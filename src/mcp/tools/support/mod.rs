@@ -1,10 +1,37 @@
 // Support module for shared utility functions
 mod board_columns_to_csv;
+mod csv_import;
 mod deserialize_non_empty_string;
+mod html_to_text;
+mod link_type;
+mod markdown_to_html;
+mod outline;
+mod output_format;
+mod paging;
 mod simplify_work_item_json;
+mod text_format;
+mod text_similarity;
+mod work_item_fields;
+mod work_item_graph;
+mod work_items_result;
 mod work_items_to_csv;
 
 pub use board_columns_to_csv::board_columns_to_csv;
+pub use csv_import::{ImportRow, parse_import_rows};
 pub use deserialize_non_empty_string::deserialize_non_empty_string;
-pub use simplify_work_item_json::simplify_work_item_json;
-pub use work_items_to_csv::work_items_to_csv;
+pub use html_to_text::html_to_text;
+pub use link_type::link_type_reference;
+pub use markdown_to_html::markdown_to_html;
+pub use outline::{OutlineItem, parse_outline};
+pub use output_format::OutputFormat;
+pub use paging::{Cursor, Page, PagingArgs};
+pub use simplify_work_item_json::{simplify_work_item_json, simplify_work_item_json_with};
+pub use text_format::TextFormat;
+pub use text_similarity::text_similarity;
+pub use work_item_fields::{project_work_item_fields, reference_names};
+pub use work_item_graph::{GraphEdge, GraphNode, WorkItemGraph, graph_edge};
+pub use work_items_result::{
+    WorkItemOutput, WorkItemsOutput, work_item_result, work_items_result,
+    work_items_result_with_columns,
+};
+pub use work_items_to_csv::{WORK_ITEM_COLUMNS, work_item_columns, work_items_to_csv};
@@ -0,0 +1,226 @@
+use super::reference_names;
+use serde_json::Value;
+
+/// Columns of a tab in the indentation of an outline
+const TAB_WIDTH: usize = 4;
+
+/// Line of an outline, with the work item to create under its parent line
+#[derive(Debug, PartialEq)]
+pub struct OutlineItem {
+    /// Line in the outline, from 1
+    pub line: usize,
+    /// Index of the parent line in the items, None for the top level ones
+    pub parent: Option<usize>,
+    /// Level in the hierarchy, from 0 for the top level
+    pub depth: usize,
+    pub work_item_type: String,
+    pub title: String,
+    /// Reference names and values of the annotated fields
+    pub fields: Vec<(String, Value)>,
+    /// Why the work item cannot be created, when invalid
+    pub errors: Vec<String>,
+}
+
+/// Parse an outline of work items, a line each, nested by Markdown heading level or by
+/// indentation, the list bullets being optional. A line is an optional `Type:` prefix,
+/// the title and optional field annotations, e.g. `- Feature: Checkout {Priority=1;
+/// Tags=web}`. The lines without a type get the type of their level, the known types
+/// being the ones of the process (any when empty).
+pub fn parse_outline(
+    outline: &str,
+    level_types: &[String],
+    known_types: &[String],
+) -> Vec<OutlineItem> {
+    let known = |name: &str| {
+        known_types
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(name))
+            .cloned()
+    };
+    let mut items: Vec<OutlineItem> = Vec::new();
+    // Rank and index of the lines the next ones can be nested in, outermost first
+    let mut open: Vec<(usize, usize)> = Vec::new();
+
+    for (index, text) in outline.lines().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let indent: usize = text
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum();
+        let text = text.trim();
+        // The headings rank above any list item, by level
+        let (rank, text) = match text.find(|c| c != '#') {
+            Some(level @ 1..=6) if text[level..].starts_with(' ') => (level, &text[level..]),
+            _ => (10 + indent, strip_bullet(text)),
+        };
+        while open.last().is_some_and(|(open_rank, _)| *open_rank >= rank) {
+            open.pop();
+        }
+
+        let mut item = OutlineItem {
+            line: index + 1,
+            parent: open.last().map(|(_, parent)| *parent),
+            depth: open.len(),
+            work_item_type: String::new(),
+            title: String::new(),
+            fields: Vec::new(),
+            errors: Vec::new(),
+        };
+        let (text, annotations) = match text.trim_end().strip_suffix('}') {
+            Some(rest) if rest.contains('{') => {
+                let (text, annotations) = rest.rsplit_once('{').unwrap();
+                (text, Some(annotations))
+            }
+            _ => (text, None),
+        };
+        let mut title = text.trim();
+        match title
+            .split_once(':')
+            .and_then(|(prefix, rest)| Some((known(prefix.trim())?, rest)))
+        {
+            Some((work_item_type, rest)) => {
+                item.work_item_type = work_item_type;
+                title = rest.trim();
+            }
+            None => match level_types.get(item.depth) {
+                Some(work_item_type) => item.work_item_type = work_item_type.clone(),
+                None => item.errors.push(format!(
+                    "No work item type for level {}, prefix the title with `Type:`",
+                    item.depth + 1
+                )),
+            },
+        }
+        if !known_types.is_empty()
+            && !item.work_item_type.is_empty()
+            && known(&item.work_item_type).is_none()
+        {
+            item.errors
+                .push(format!("Unknown work item type {}", item.work_item_type));
+        }
+        item.title = title.to_string();
+        if item.title.is_empty() {
+            item.errors.push("No title".to_string());
+        }
+        for annotation in annotations
+            .into_iter()
+            .flat_map(|annotations| annotations.split(';'))
+            .map(str::trim)
+            .filter(|annotation| !annotation.is_empty())
+        {
+            match annotation.split_once('=') {
+                Some((field, value)) if !field.trim().is_empty() => {
+                    let field = reference_names(&[field.trim().to_string()]).remove(0);
+                    item.fields
+                        .push((field, Value::String(value.trim().to_string())));
+                }
+                _ => item.errors.push(format!(
+                    "Invalid field annotation `{}`, expected Field=value",
+                    annotation
+                )),
+            }
+        }
+
+        open.push((rank, items.len()));
+        items.push(item);
+    }
+    items
+}
+
+/// Text of a list item without its bullet (`-`, `*`, `+`, `1.` or `1)`) and checkbox
+fn strip_bullet(text: &str) -> &str {
+    let text = match text.split_once(' ') {
+        Some((bullet, rest))
+            if matches!(bullet, "-" | "*" | "+")
+                || (bullet.len() > 1
+                    && bullet[..bullet.len() - 1]
+                        .chars()
+                        .all(|c| c.is_ascii_digit())
+                    && (bullet.ends_with('.') || bullet.ends_with(')'))) =>
+        {
+            rest.trim_start()
+        }
+        _ => text,
+    };
+    ["[ ] ", "[x] ", "[X] "]
+        .iter()
+        .find_map(|checkbox| text.strip_prefix(checkbox))
+        .unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_outline() {
+        let outline = "\
+# Checkout revamp {Priority=1; Tags=web; Custom.Risk=High}
+## Payment methods
+- Add Apple Pay {AssignedTo=jane@contoso.com}
+\t- [ ] Integrate the SDK
+    - Bug: Wallet fails on iOS 17
+1. Task: Write the docs
+- Spike: Compare providers
+
+## {Priority=2}
+- Task: Deploy {Effort}";
+        let items = parse_outline(
+            outline,
+            &strings(&["Epic", "Feature", "User Story", "Task"]),
+            &strings(&["Epic", "Feature", "User Story", "Task", "Bug"]),
+        );
+        let summary: Vec<_> = items
+            .iter()
+            .map(|item| {
+                (
+                    item.line,
+                    item.parent,
+                    item.work_item_type.as_str(),
+                    item.title.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, None, "Epic", "Checkout revamp"),
+                (2, Some(0), "Feature", "Payment methods"),
+                (3, Some(1), "User Story", "Add Apple Pay"),
+                (4, Some(2), "Task", "Integrate the SDK"),
+                (5, Some(2), "Bug", "Wallet fails on iOS 17"),
+                (6, Some(1), "Task", "Write the docs"),
+                (7, Some(1), "User Story", "Spike: Compare providers"),
+                (9, Some(0), "Feature", ""),
+                (10, Some(7), "Task", "Deploy"),
+            ]
+        );
+        assert_eq!(
+            items[0].fields,
+            [
+                ("Microsoft.VSTS.Common.Priority".to_string(), "1".into()),
+                ("System.Tags".to_string(), "web".into()),
+                ("Custom.Risk".to_string(), "High".into()),
+            ]
+        );
+        assert_eq!(items[7].errors, ["No title"]);
+        assert_eq!(
+            items[8].errors,
+            ["Invalid field annotation `Effort`, expected Field=value"]
+        );
+
+        let items = parse_outline("- A\n  - B\n    - C", &strings(&["Epic", "Feature"]), &[]);
+        assert_eq!(
+            items[2].errors,
+            ["No work item type for level 3, prefix the title with `Type:`"]
+        );
+        let items = parse_outline("Story: Login", &strings(&["Epic"]), &strings(&["Epic"]));
+        assert_eq!(items[0].title, "Story: Login");
+    }
+}
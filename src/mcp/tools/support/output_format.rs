@@ -0,0 +1,13 @@
+use rmcp::schemars::{self, JsonSchema};
+use serde::Deserialize;
+
+/// Format of the text output of the tools returning lists of work items
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// CSV, the most compact
+    #[default]
+    Csv,
+    /// Markdown table, easier to read and to show to the user
+    Markdown,
+}
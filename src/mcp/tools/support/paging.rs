@@ -0,0 +1,239 @@
+use crate::azure::client::AzureError;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use rmcp::{
+    ErrorData as McpError,
+    model::ErrorCode,
+    schemars::{self, JsonSchema},
+};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// Results returned when `max_results` is not given
+pub const DEFAULT_MAX_RESULTS: usize = 100;
+
+/// Upper bound of `max_results`
+pub const MAX_RESULTS: usize = 1000;
+
+/// Upstream pages fetched in one call before returning what was collected so far
+const MAX_FETCHES: usize = 20;
+
+// Paging arguments of the list and query tools, flattened into their arguments (not
+// a doc comment, it would become the description of their schemas)
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct PagingArgs {
+    /// Maximum number of results (optional, default 100, at most 1000)
+    #[serde(default)]
+    pub max_results: Option<usize>,
+    /// Cursor returned as `next_cursor` by the previous call, to get the next page (optional)
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Position in the results: the continuation token of the upstream page, for the APIs
+/// paging their results, and the results of that page already returned
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(default, rename = "t", skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(default, rename = "o")]
+    pub offset: usize,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, McpError> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| McpError {
+                code: ErrorCode(-32602),
+                message: "Invalid cursor, pass the next_cursor of the previous call".into(),
+                data: None,
+            })
+    }
+}
+
+/// A page of results with the cursor of the next one, if any
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Append the cursor of the next page, if any, to the text output of a tool
+    pub fn append_next_cursor(&self, mut text: String) -> String {
+        if let Some(cursor) = &self.next_cursor {
+            text.push_str(&format!("\nnext_cursor={}", cursor));
+        }
+        text
+    }
+}
+
+impl PagingArgs {
+    pub fn max_results(&self) -> usize {
+        self.max_results
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS)
+    }
+
+    pub fn cursor(&self) -> Result<Cursor, McpError> {
+        match self.cursor.as_deref() {
+            Some(cursor) if !cursor.is_empty() => Cursor::decode(cursor),
+            _ => Ok(Cursor::default()),
+        }
+    }
+
+    /// Page of results fetched in full
+    pub fn page<T>(&self, items: Vec<T>) -> Result<Page<T>, McpError> {
+        let offset = self.cursor()?.offset;
+        let end = offset.saturating_add(self.max_results());
+        let next_cursor = (end < items.len()).then(|| {
+            Cursor {
+                token: None,
+                offset: end,
+            }
+            .encode()
+        });
+        Ok(Page {
+            items: items.into_iter().skip(offset).take(end - offset).collect(),
+            next_cursor,
+        })
+    }
+
+    /// Cursor of the next page of an API skipping the results itself, the offset of the
+    /// cursor being the results to skip, after `returned` results of `total`
+    pub fn next_offset_cursor(
+        &self,
+        returned: usize,
+        total: usize,
+    ) -> Result<Option<String>, McpError> {
+        let offset = self.cursor()?.offset + returned;
+        Ok((returned > 0 && offset < total).then(|| {
+            Cursor {
+                token: None,
+                offset,
+            }
+            .encode()
+        }))
+    }
+
+    /// Page of results of an API paging them itself: `fetch` gets the upstream page of
+    /// a continuation token (none for the first one) and returns its results with the
+    /// continuation token of the next page
+    pub async fn fetch_page<T, F, Fut>(&self, mut fetch: F) -> Result<Page<T>, McpError>
+    where
+        F: FnMut(Option<String>) -> Fut,
+        Fut: Future<Output = Result<(Vec<T>, Option<String>), AzureError>>,
+    {
+        let max_results = self.max_results();
+        let Cursor {
+            mut token,
+            mut offset,
+        } = self.cursor()?;
+        let mut items = Vec::new();
+
+        for fetches in 1..=MAX_FETCHES {
+            let (results, next_token) = fetch(token.clone()).await.map_err(McpError::from)?;
+            let fetched = results.len();
+            let taken = fetched
+                .saturating_sub(offset)
+                .min(max_results - items.len());
+            items.extend(results.into_iter().skip(offset).take(taken));
+            crate::progress::report(fetches as u64, None, || {
+                format!("Collected {} results ({} pages)", items.len(), fetches)
+            });
+
+            // The rest of this upstream page is returned by the next call
+            if offset + taken < fetched {
+                return Ok(Page {
+                    items,
+                    next_cursor: Some(
+                        Cursor {
+                            token,
+                            offset: offset + taken,
+                        }
+                        .encode(),
+                    ),
+                });
+            }
+
+            let Some(next_token) = next_token else {
+                return Ok(Page {
+                    items,
+                    next_cursor: None,
+                });
+            };
+            token = Some(next_token);
+            offset = 0;
+            if items.len() == max_results {
+                break;
+            }
+        }
+
+        // Either full or too many upstream pages were fetched (e.g. filtered out)
+        Ok(Page {
+            items,
+            next_cursor: Some(Cursor { token, offset }.encode()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page() {
+        let paging = PagingArgs {
+            max_results: Some(2),
+            cursor: None,
+        };
+        let page = paging.page(vec![1, 2, 3]).unwrap();
+        assert_eq!(page.items, vec![1, 2]);
+
+        let paging = PagingArgs {
+            max_results: Some(2),
+            cursor: page.next_cursor,
+        };
+        let page = paging.page(vec![1, 2, 3]).unwrap();
+        assert_eq!(page.items, vec![3]);
+        assert_eq!(page.next_cursor, None);
+
+        let paging = PagingArgs {
+            max_results: None,
+            cursor: Some("not a cursor".to_string()),
+        };
+        assert!(paging.page(vec![1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page() {
+        // Upstream pages of 3 results, tokens are the page numbers
+        let fetch = |token: Option<String>| async move {
+            let page: usize = token.map_or(0, |t| t.parse().unwrap());
+            let results = (page * 3..page * 3 + 3).collect::<Vec<_>>();
+            Ok((results, (page < 2).then(|| (page + 1).to_string())))
+        };
+
+        let mut cursor = None;
+        let mut all = Vec::new();
+        loop {
+            let paging = PagingArgs {
+                max_results: Some(4),
+                cursor,
+            };
+            let page = paging.fetch_page(fetch).await.unwrap();
+            all.extend(page.items);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(all, (0..9).collect::<Vec<_>>());
+    }
+}
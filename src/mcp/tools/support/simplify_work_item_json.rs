@@ -1,19 +1,27 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
+use super::html_to_text;
 use serde_json::Value;
 
-// Static regex patterns for text cleaning (compiled once, reused many times)
-static RE_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r" +").unwrap());
-static RE_NEWLINES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n+").unwrap());
-static RE_LEADING_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n[ ]+").unwrap());
-static RE_TRAILING_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ ]+\n").unwrap());
-static RE_DASHES: Lazy<Regex> = Lazy::new(|| Regex::new(r"-{3,}\n").unwrap());
-static RE_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\[image\]").unwrap());
+/// Simplified names of the fields holding HTML
+const RICH_TEXT_FIELDS: &[&str] = &[
+    "Acceptance",
+    "Description",
+    "Justification",
+    "ReproSteps",
+    "SystemInfo",
+    "History",
+];
 
 /// Recursively simplifies the JSON output to reduce token usage for LLMs.
 /// It removes "_links", "url", "descriptor", "imageUrl", "avatar" and simplifies field names.
-/// It also flattens the "fields" object to the root level and removes redundant properties.
+/// It also flattens the "fields" object to the root level and removes redundant properties,
+/// and converts the HTML of the rich-text fields and of the comments to text.
 pub fn simplify_work_item_json(value: &mut Value) {
+    simplify_work_item_json_with(value, false);
+}
+
+/// [`simplify_work_item_json`], keeping the HTML of the rich-text fields and of the
+/// comments when `raw_html` is set
+pub fn simplify_work_item_json_with(value: &mut Value, raw_html: bool) {
     match value {
         Value::Object(map) => {
             // Remove unnecessary fields at the top level and in nested objects
@@ -60,6 +68,8 @@ pub fn simplify_work_item_json(value: &mut Value) {
                             key.strip_prefix("Microsoft.VSTS.CMMI.")
                                 .unwrap()
                                 .to_string()
+                        } else if key.starts_with("Microsoft.VSTS.TCM.") {
+                            key.strip_prefix("Microsoft.VSTS.TCM.").unwrap().to_string()
                         } else if key.contains("_Kanban.Column") {
                             // Handle dynamic WEF_..._Kanban.Column -> Column
                             "Column".to_string()
@@ -99,67 +109,27 @@ pub fn simplify_work_item_json(value: &mut Value) {
                             _ => new_key,
                         };
 
-                        // Convert HTML to text for specific fields
-                        if matches!(
-                            final_key.as_str(),
-                            "Acceptance" | "Description" | "Justification"
-                        ) {
-                            if let Value::String(html_content) = &val {
-                                // Convert HTML to plain text, width doesn't matter as we don't need wrapping
-                                if let Ok(mut plain_text) =
-                                    html2text::from_read(html_content.as_bytes(), usize::MAX)
-                                {
-                                    // Normalize newlines: replace \r with \n
-                                    plain_text = plain_text.replace('\r', "\n");
-
-                                    // Normalize tabulations: replace \t with 1 space
-                                    plain_text = plain_text.replace('\t', " ");
-
-                                    // Normalize emdashes: replace ─ with -
-                                    plain_text = plain_text.replace('─', "-");
-
-                                    // Remove multiple consecutive spaces
-                                    plain_text =
-                                        RE_SPACES.replace_all(&plain_text, " ").to_string();
-
-                                    // Collapse multiple consecutive newlines into single newlines
-                                    plain_text =
-                                        RE_NEWLINES.replace_all(&plain_text, "\n").to_string();
-
-                                    // Remove leading whitespace before newlines (spaces, tabs, etc.)
-                                    plain_text =
-                                        RE_LEADING_WS.replace_all(&plain_text, "\n").to_string();
-
-                                    // Remove trailing whitespace before newlines (spaces, tabs, etc.)
-                                    plain_text =
-                                        RE_TRAILING_WS.replace_all(&plain_text, "\n").to_string();
-
-                                    // Collapse 3+ dashes followed by newline to just 3 dashes + newline
-                                    plain_text =
-                                        RE_DASHES.replace_all(&plain_text, "---\n").to_string();
-
-                                    // Remove [Image] strings (case insensitive)
-                                    plain_text = RE_IMAGE.replace_all(&plain_text, "").to_string();
-
-                                    val = Value::String(plain_text.trim().to_string());
-                                }
-                            }
+                        // Convert HTML to text for the rich-text fields
+                        if !raw_html
+                            && RICH_TEXT_FIELDS.contains(&final_key.as_str())
+                            && let Value::String(html_content) = &val
+                        {
+                            val = Value::String(html_to_text(html_content));
                         }
 
                         // Optimize Tags field by removing spaces after semicolons
-                        if final_key == "Tags" {
-                            if let Value::String(tags) = &val {
-                                val = Value::String(tags.replace("; ", ";"));
-                            }
+                        if final_key == "Tags"
+                            && let Value::String(tags) = &val
+                        {
+                            val = Value::String(tags.replace("; ", ";"));
                         }
 
                         // Abbreviate Type field to just first letter
-                        if final_key == "Type" {
-                            if let Value::String(type_val) = &val {
-                                if let Some(first_char) = type_val.chars().next() {
-                                    val = Value::String(first_char.to_string());
-                                }
-                            }
+                        if final_key == "Type"
+                            && let Value::String(type_val) = &val
+                            && let Some(first_char) = type_val.chars().next()
+                        {
+                            val = Value::String(first_char.to_string());
                         }
 
                         // Only insert if not already present (prefer existing values)
@@ -175,15 +145,24 @@ pub fn simplify_work_item_json(value: &mut Value) {
                 }
             }
 
+            // Convert the HTML of the comments to text
+            if !raw_html && let Some(Value::Array(comments)) = map.get_mut("comments") {
+                for comment in comments {
+                    if let Some(Value::String(text)) = comment.get_mut("text") {
+                        *text = html_to_text(text);
+                    }
+                }
+            }
+
             // Recursively process all remaining values
             for (_, v) in map.iter_mut() {
-                simplify_work_item_json(v);
+                simplify_work_item_json_with(v, raw_html);
             }
         }
         Value::Array(arr) => {
             // Recursively process all array elements
             for item in arr.iter_mut() {
-                simplify_work_item_json(item);
+                simplify_work_item_json_with(item, raw_html);
             }
         }
         _ => {}
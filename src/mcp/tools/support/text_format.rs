@@ -0,0 +1,24 @@
+use super::markdown_to_html;
+use rmcp::schemars::{self, JsonSchema};
+use serde::Deserialize;
+
+/// Format of the rich text given to the tools writing descriptions and comments
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TextFormat {
+    /// HTML, stored as is
+    #[default]
+    Html,
+    /// Markdown, converted to HTML
+    Markdown,
+}
+
+impl TextFormat {
+    /// HTML Azure DevOps expects of a text in this format
+    pub fn to_html(self, text: &str) -> String {
+        match self {
+            TextFormat::Html => text.to_string(),
+            TextFormat::Markdown => markdown_to_html(text),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+/// Trigrams of the words of a text, lowercased and padded so that the short words and
+/// the word boundaries count too
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let mut trigrams = HashSet::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let chars: Vec<char> = format!("  {} ", word.to_lowercase()).chars().collect();
+        trigrams.extend(chars.windows(3).map(|w| [w[0], w[1], w[2]]));
+    }
+    trigrams
+}
+
+/// Similarity of two texts from 0 to 1, the Dice coefficient of their trigrams,
+/// tolerating typos, plurals and reordered words
+pub fn text_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(&b).count();
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_similarity() {
+        assert_eq!(text_similarity("Login fails", "login FAILS!"), 1.0);
+        let typo = text_similarity(
+            "Login page crashes on Safari",
+            "Login page crashs in Safari",
+        );
+        let other = text_similarity("Login page crashes on Safari", "Export the invoices as PDF");
+        assert!(typo > 0.6, "{}", typo);
+        assert!(other < 0.2, "{}", other);
+        assert_eq!(text_similarity("", "Login"), 0.0);
+    }
+}
@@ -0,0 +1,142 @@
+use serde_json::Value;
+
+/// Reference names of the simplified field names of the work item outputs
+const REFERENCE_NAMES: &[(&str, &str)] = &[
+    ("Parent", "System.Parent"),
+    ("Type", "System.WorkItemType"),
+    ("Title", "System.Title"),
+    ("Description", "System.Description"),
+    ("Acceptance", "Microsoft.VSTS.Common.AcceptanceCriteria"),
+    ("ReproSteps", "Microsoft.VSTS.TCM.ReproSteps"),
+    ("SystemInfo", "Microsoft.VSTS.TCM.SystemInfo"),
+    ("Column", "System.BoardColumn"),
+    ("Lane", "System.BoardLane"),
+    ("Priority", "Microsoft.VSTS.Common.Priority"),
+    ("AssignedTo", "System.AssignedTo"),
+    ("CreatedBy", "System.CreatedBy"),
+    ("CreatedDate", "System.CreatedDate"),
+    ("ChangedBy", "System.ChangedBy"),
+    ("ChangedDate", "System.ChangedDate"),
+    ("AreaPath", "System.AreaPath"),
+    ("Iteration", "System.IterationPath"),
+    ("Project", "System.TeamProject"),
+    ("Tags", "System.Tags"),
+    ("StartDate", "Microsoft.VSTS.Scheduling.StartDate"),
+    ("TargetDate", "Microsoft.VSTS.Scheduling.TargetDate"),
+    ("Effort", "Microsoft.VSTS.Scheduling.Effort"),
+    ("Risk", "Microsoft.VSTS.Common.Risk"),
+    ("Justification", "Microsoft.VSTS.CMMI.Justification"),
+    ("ValueArea", "Microsoft.VSTS.Common.ValueArea"),
+    ("StackRank", "Microsoft.VSTS.Common.StackRank"),
+    ("StateChangeDate", "Microsoft.VSTS.Common.StateChangeDate"),
+    ("History", "System.History"),
+];
+
+/// Prefixes the outputs strip from the reference names
+const STRIPPED_PREFIXES: &[&str] = &[
+    "System.",
+    "Microsoft.VSTS.Common.",
+    "Microsoft.VSTS.Scheduling.",
+    "Microsoft.VSTS.CMMI.",
+    "Microsoft.VSTS.TCM.",
+];
+
+/// Reference names to request of the fields given by simplified (e.g. `AssignedTo`)
+/// or reference name (e.g. `Custom.Severity`), the other simple names being taken as
+/// `System.` fields
+pub fn reference_names(fields: &[String]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains('.') {
+                return field.clone();
+            }
+            REFERENCE_NAMES
+                .iter()
+                .find(|(simplified, _)| simplified.eq_ignore_ascii_case(field))
+                .map_or_else(
+                    || format!("System.{}", field),
+                    |(_, reference)| reference.to_string(),
+                )
+        })
+        .collect()
+}
+
+/// Name of a field in the outputs simplified by `simplify_work_item_json`
+fn simplified_name(field: &str) -> String {
+    if let Some((simplified, _)) = REFERENCE_NAMES.iter().find(|(simplified, reference)| {
+        simplified.eq_ignore_ascii_case(field) || reference.eq_ignore_ascii_case(field)
+    }) {
+        return simplified.to_string();
+    }
+    STRIPPED_PREFIXES
+        .iter()
+        .find_map(|prefix| field.strip_prefix(prefix))
+        .unwrap_or(field)
+        .to_string()
+}
+
+/// Keep only the given fields, the ID and the comments, of simplified work items,
+/// returning the names of the fields kept
+pub fn project_work_item_fields(value: &mut Value, fields: &[String]) -> Vec<String> {
+    if fields.is_empty() {
+        return Vec::new();
+    }
+    let names: Vec<String> = fields.iter().map(|field| simplified_name(field)).collect();
+    let project = |item: &mut Value| {
+        if let Value::Object(map) = item {
+            map.retain(|key, _| {
+                key == "id"
+                    || key == "comments"
+                    || names.iter().any(|name| name.eq_ignore_ascii_case(key))
+            });
+        }
+    };
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(project),
+        item => project(item),
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fields() {
+        let fields = ["title", "Column", "Custom.Severity", "Reason"].map(String::from);
+        assert_eq!(
+            reference_names(&fields),
+            [
+                "System.Title",
+                "System.BoardColumn",
+                "Custom.Severity",
+                "System.Reason"
+            ]
+        );
+
+        let mut items = json!([{
+            "id": 1,
+            "rev": 3,
+            "Title": "Fix login",
+            "Column": "Doing",
+            "AssignedTo": "Jane",
+            "Custom.Severity": "1 - Critical",
+        }]);
+        assert_eq!(
+            project_work_item_fields(&mut items, &fields),
+            ["Title", "Column", "Custom.Severity", "Reason"]
+        );
+        assert_eq!(
+            items,
+            json!([{
+                "id": 1,
+                "Title": "Fix login",
+                "Column": "Doing",
+                "Custom.Severity": "1 - Critical",
+            }])
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::azure::models::WorkItemRelation;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Link of a parent work item to its child
+pub const CHILD_LINK: &str = "System.LinkTypes.Hierarchy-Forward";
+
+/// Link of a work item to its parent
+pub const PARENT_LINK: &str = "System.LinkTypes.Hierarchy-Reverse";
+
+/// Link of a predecessor work item to its successor
+pub const SUCCESSOR_LINK: &str = "System.LinkTypes.Dependency-Forward";
+
+/// Link of a successor work item to its predecessor
+pub const PREDECESSOR_LINK: &str = "System.LinkTypes.Dependency-Reverse";
+
+/// Work item of a graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub title: String,
+    pub work_item_type: String,
+    pub state: String,
+}
+
+/// Edge of a graph, from the parent to the child or from the predecessor to the successor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphEdge {
+    Child(u32, u32),
+    Successor(u32, u32),
+}
+
+/// Work items and the hierarchy and dependency links between them
+#[derive(Debug, Default)]
+pub struct WorkItemGraph {
+    pub nodes: BTreeMap<u32, GraphNode>,
+    pub edges: BTreeSet<GraphEdge>,
+}
+
+/// ID of the work item a relation links to, None for the links to other artifacts
+pub fn related_work_item_id(relation: &WorkItemRelation) -> Option<u32> {
+    let (collection, id) = relation.url.rsplit_once('/')?;
+    collection
+        .to_ascii_lowercase()
+        .ends_with("/workitems")
+        .then(|| id.parse().ok())
+        .flatten()
+}
+
+/// Edge of the hierarchy or dependency link of a work item, None for the other links
+pub fn graph_edge(id: u32, relation: &WorkItemRelation) -> Option<GraphEdge> {
+    let related = related_work_item_id(relation)?;
+    match relation.rel.as_str() {
+        CHILD_LINK => Some(GraphEdge::Child(id, related)),
+        PARENT_LINK => Some(GraphEdge::Child(related, id)),
+        SUCCESSOR_LINK => Some(GraphEdge::Successor(id, related)),
+        PREDECESSOR_LINK => Some(GraphEdge::Successor(related, id)),
+        _ => None,
+    }
+}
+
+/// Group of the states by which the nodes are colored
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StateGroup {
+    Proposed,
+    InProgress,
+    Resolved,
+    Completed,
+    Removed,
+}
+
+impl StateGroup {
+    /// Group of the states of the default processes, in progress for the custom ones
+    fn of(state: &str) -> Self {
+        match state.to_ascii_lowercase().as_str() {
+            "new" | "proposed" | "to do" | "approved" | "design" => Self::Proposed,
+            "resolved" => Self::Resolved,
+            "closed" | "done" | "completed" => Self::Completed,
+            "removed" | "cut" => Self::Removed,
+            _ => Self::InProgress,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Proposed => "proposed",
+            Self::InProgress => "in_progress",
+            Self::Resolved => "resolved",
+            Self::Completed => "completed",
+            Self::Removed => "removed",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Self::Proposed => "#e0e0e0",
+            Self::InProgress => "#9fc5e8",
+            Self::Resolved => "#ffe599",
+            Self::Completed => "#b6d7a8",
+            Self::Removed => "#f4cccc",
+        }
+    }
+
+    const ALL: [Self; 5] = [
+        Self::Proposed,
+        Self::InProgress,
+        Self::Resolved,
+        Self::Completed,
+        Self::Removed,
+    ];
+}
+
+impl WorkItemGraph {
+    /// Edges between the work items of the graph
+    fn inner_edges(&self) -> impl Iterator<Item = &GraphEdge> {
+        self.edges.iter().filter(|edge| {
+            let (GraphEdge::Child(from, to) | GraphEdge::Successor(from, to)) = edge;
+            self.nodes.contains_key(from) && self.nodes.contains_key(to)
+        })
+    }
+
+    /// Mermaid flowchart, the dependencies as solid arrows and the hierarchy as dotted
+    /// ones, the nodes colored by state
+    pub fn to_mermaid(&self) -> String {
+        let escape = |text: &str| text.replace('"', "#quot;");
+        let mut lines = vec!["flowchart LR".to_string()];
+        for (id, node) in &self.nodes {
+            lines.push(format!(
+                "    wi{}[\"#{} {}: {}<br/>{}\"]:::{}",
+                id,
+                id,
+                escape(&node.work_item_type),
+                escape(&node.title),
+                escape(&node.state),
+                StateGroup::of(&node.state).name()
+            ));
+        }
+        for edge in self.inner_edges() {
+            lines.push(match edge {
+                GraphEdge::Child(parent, child) => format!("    wi{} -.-> wi{}", parent, child),
+                GraphEdge::Successor(from, to) => format!("    wi{} --> wi{}", from, to),
+            });
+        }
+        for group in StateGroup::ALL {
+            lines.push(format!(
+                "    classDef {} fill:{},stroke:#666666",
+                group.name(),
+                group.color()
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Graphviz DOT digraph, the dependencies as solid arrows and the hierarchy as dashed
+    /// ones, the nodes colored by state
+    pub fn to_dot(&self) -> String {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut lines = vec![
+            "digraph work_items {".to_string(),
+            "    rankdir=LR;".to_string(),
+            "    node [shape=box, style=\"rounded,filled\"];".to_string(),
+        ];
+        for (id, node) in &self.nodes {
+            lines.push(format!(
+                "    {} [label=\"#{} {}\\n{}\\n{}\", fillcolor=\"{}\"];",
+                id,
+                id,
+                escape(&node.work_item_type),
+                escape(&node.title),
+                escape(&node.state),
+                StateGroup::of(&node.state).color()
+            ));
+        }
+        for edge in self.inner_edges() {
+            lines.push(match edge {
+                GraphEdge::Child(parent, child) => {
+                    format!("    {} -> {} [style=dashed];", parent, child)
+                }
+                GraphEdge::Successor(from, to) => format!("    {} -> {};", from, to),
+            });
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation(rel: &str, url: &str) -> WorkItemRelation {
+        WorkItemRelation {
+            rel: rel.to_string(),
+            url: url.to_string(),
+            attributes: None,
+        }
+    }
+
+    fn graph() -> WorkItemGraph {
+        let node = |title: &str, state: &str| GraphNode {
+            title: title.to_string(),
+            work_item_type: "User Story".to_string(),
+            state: state.to_string(),
+        };
+        WorkItemGraph {
+            nodes: BTreeMap::from([
+                (1, node("Login \"SSO\"", "Active")),
+                (2, node("Logout", "Done")),
+            ]),
+            edges: BTreeSet::from([
+                GraphEdge::Successor(1, 2),
+                GraphEdge::Child(1, 2),
+                GraphEdge::Child(1, 3),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_graph_edge() {
+        let url = "https://dev.azure.com/contoso/_apis/wit/workItems/42";
+        assert_eq!(
+            graph_edge(7, &relation(PARENT_LINK, url)),
+            Some(GraphEdge::Child(42, 7))
+        );
+        assert_eq!(
+            graph_edge(7, &relation(SUCCESSOR_LINK, url)),
+            Some(GraphEdge::Successor(7, 42))
+        );
+        assert_eq!(
+            graph_edge(7, &relation("System.LinkTypes.Related", url)),
+            None
+        );
+        let artifact = "vstfs:///Git/Commit/1%2F2%2F3";
+        assert_eq!(graph_edge(7, &relation("ArtifactLink", artifact)), None);
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        assert_eq!(
+            graph().to_mermaid().lines().take(5).collect::<Vec<_>>(),
+            [
+                "flowchart LR",
+                "    wi1[\"#1 User Story: Login #quot;SSO#quot;<br/>Active\"]:::in_progress",
+                "    wi2[\"#2 User Story: Logout<br/>Done\"]:::completed",
+                "    wi1 -.-> wi2",
+                "    wi1 --> wi2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = graph().to_dot();
+        assert!(dot.contains(
+            "    1 [label=\"#1 User Story\\nLogin \\\"SSO\\\"\\nActive\", fillcolor=\"#9fc5e8\"];"
+        ));
+        assert!(dot.contains("    1 -> 2 [style=dashed];\n    1 -> 2;\n}"));
+        assert!(!dot.contains("-> 3"));
+    }
+}
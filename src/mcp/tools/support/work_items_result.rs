@@ -0,0 +1,153 @@
+use super::{OutputFormat, WORK_ITEM_COLUMNS, work_item_columns, work_items_to_csv};
+use crate::compact_llm::{self, CompactOptions};
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Structured output of the tools returning a list of work items
+#[derive(Serialize, JsonSchema)]
+pub struct WorkItemsOutput {
+    /// Number of work items returned
+    pub count: usize,
+    /// Work items with the simplified field names of the CSV columns (e.g. `Title`,
+    /// `AssignedTo`, `AreaPath`)
+    pub work_items: Vec<Map<String, Value>>,
+    /// Cursor of the next page of the list and query tools, when there are more results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Structured output of the tools returning a single work item
+#[derive(Serialize, JsonSchema)]
+pub struct WorkItemOutput {
+    /// Work item with simplified field names (e.g. `Title`, `AssignedTo`, `AreaPath`)
+    pub work_item: Map<String, Value>,
+}
+
+/// Result with simplified work items as CSV text and as structured content, or the
+/// given message when there are none, with the cursor of the next page if any
+pub fn work_items_result(
+    json_value: Value,
+    empty_message: &str,
+    next_cursor: Option<String>,
+) -> Result<CallToolResult, McpError> {
+    work_items_result_with_columns(
+        json_value,
+        empty_message,
+        next_cursor,
+        &[],
+        OutputFormat::Csv,
+    )
+}
+
+/// [`work_items_result`] with columns besides the known fields, e.g. the custom fields
+/// requested, and the text in the given format
+pub fn work_items_result_with_columns(
+    json_value: Value,
+    empty_message: &str,
+    next_cursor: Option<String>,
+    extra_columns: &[String],
+    format: OutputFormat,
+) -> Result<CallToolResult, McpError> {
+    let work_items: Vec<Map<String, Value>> = match json_value {
+        Value::Array(items) => items
+            .into_iter()
+            .filter_map(|item| match item {
+                Value::Object(map) => Some(map),
+                _ => None,
+            })
+            .collect(),
+        Value::Object(map) => vec![map],
+        _ => vec![],
+    };
+
+    let mut text = if work_items.is_empty() {
+        empty_message.to_string()
+    } else {
+        let items: Vec<Value> = work_items.iter().cloned().map(Value::Object).collect();
+        match format {
+            OutputFormat::Csv => {
+                work_items_to_csv(&Value::Array(items), extra_columns).map_err(|e| McpError {
+                    code: ErrorCode(-32000),
+                    message: format!("Failed to convert to CSV: {}", e).into(),
+                    data: None,
+                })?
+            }
+            OutputFormat::Markdown => {
+                compact_llm::to_markdown_table(&items, &work_item_columns(&items, extra_columns))
+            }
+        }
+    };
+    if let Some(cursor) = &next_cursor {
+        text.push_str(&format!("\nnext_cursor={}", cursor));
+    }
+    let output = WorkItemsOutput {
+        count: work_items.len(),
+        work_items,
+        next_cursor,
+    };
+
+    Ok(CallToolResult {
+        content: vec![Content::text(text)],
+        structured_content: Some(serde_json::to_value(output).unwrap()),
+        is_error: Some(false),
+        meta: None,
+    })
+}
+
+/// Result with a simplified work item as compact text, without its empty fields and the
+/// important ones first, and as structured content
+pub fn work_item_result(json_value: Value) -> CallToolResult {
+    let options = CompactOptions {
+        omit_empty: true,
+        key_order: WORK_ITEM_COLUMNS,
+    };
+    let text = compact_llm::to_compact_string_with(&json_value, &options).unwrap();
+    let output = WorkItemOutput {
+        work_item: match json_value {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        },
+    };
+
+    CallToolResult {
+        content: vec![Content::text(text)],
+        structured_content: Some(serde_json::to_value(output).unwrap()),
+        is_error: Some(false),
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_work_items_result() {
+        let result = work_items_result(
+            json!([{"id": 1, "Title": "Fix login"}]),
+            "No work items found",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            result.structured_content,
+            Some(json!({"count": 1, "work_items": [{"id": 1, "Title": "Fix login"}]}))
+        );
+
+        let result = work_items_result(json!([]), "No work items found", None).unwrap();
+        assert_eq!(
+            result.structured_content,
+            Some(json!({"count": 0, "work_items": []}))
+        );
+        assert_eq!(
+            result.content[0].as_text().map(|t| t.text.as_str()),
+            Some("No work items found")
+        );
+    }
+}
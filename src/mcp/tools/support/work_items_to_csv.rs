@@ -1,40 +1,67 @@
 use crate::compact_llm;
 use serde_json::Value;
 
-/// Converts work items JSON to CSV format with dynamic column detection.
-/// Only includes columns that have at least one non-null value across all items.
-pub fn work_items_to_csv(json_value: &Value) -> Result<String, String> {
-    // Define all possible fields in preferred order
-    let all_fields = vec![
-        "id",
-        "Type",
-        "Title",
-        "Description",
-        "Acceptance",
-        "Column",
-        "Lane",
-        "Priority",
-        "AssignedTo",
-        "CreatedBy",
-        "CreatedDate",
-        "ChangedBy",
-        "ChangedDate",
-        "AreaPath",
-        "Iteration",
-        "Project",
-        "Tags",
-        "StartDate",
-        "TargetDate",
-        "Effort",
-        "Risk",
-        "Justification",
-        "ValueArea",
-        "StackRank",
-        "StateChangeDate",
-        "History",
-        "comments",
-    ];
+/// Known fields of the simplified work items, in the preferred order
+pub const WORK_ITEM_COLUMNS: &[&str] = &[
+    "id",
+    "Parent",
+    "Type",
+    "Title",
+    "Description",
+    "Acceptance",
+    "ReproSteps",
+    "SystemInfo",
+    "Column",
+    "Lane",
+    "Priority",
+    "AssignedTo",
+    "CreatedBy",
+    "CreatedDate",
+    "ChangedBy",
+    "ChangedDate",
+    "AreaPath",
+    "Iteration",
+    "Project",
+    "Tags",
+    "StartDate",
+    "TargetDate",
+    "Effort",
+    "Risk",
+    "Justification",
+    "ValueArea",
+    "StackRank",
+    "StateChangeDate",
+    "History",
+    "comments",
+];
+
+/// Columns of a table of work items: the fields having at least one non-null value
+/// across all items, in the preferred order, the `extra_columns` (e.g. custom fields)
+/// following the known ones
+pub fn work_item_columns<'a>(items: &[Value], extra_columns: &'a [String]) -> Vec<&'a str> {
+    let mut all_fields = WORK_ITEM_COLUMNS.to_vec();
+    for column in extra_columns {
+        if !all_fields.contains(&column.as_str()) {
+            all_fields.push(column);
+        }
+    }
+
+    // Detect which fields actually have values
+    all_fields
+        .into_iter()
+        .filter(|field| {
+            items.iter().any(|item| {
+                item.get(field)
+                    .map(|v| !v.is_null() && v.as_str().is_none_or(|s| !s.is_empty()))
+                    .unwrap_or(false)
+            })
+        })
+        .collect()
+}
 
+/// Converts work items JSON to CSV format with dynamic column detection.
+/// Only includes the [`work_item_columns`].
+pub fn work_items_to_csv(json_value: &Value, extra_columns: &[String]) -> Result<String, String> {
     // Normalize input to array
     let items = match json_value {
         Value::Array(arr) => arr.as_slice(),
@@ -46,18 +73,7 @@ pub fn work_items_to_csv(json_value: &Value) -> Result<String, String> {
         return Ok(String::new());
     }
 
-    // Detect which fields actually have values
-    let mut active_fields = Vec::new();
-    for field in &all_fields {
-        let has_value = items.iter().any(|item| {
-            item.get(field)
-                .map(|v| !v.is_null() && v.as_str().map_or(true, |s| !s.is_empty()))
-                .unwrap_or(false)
-        });
-        if has_value {
-            active_fields.push(*field);
-        }
-    }
+    let active_fields = work_item_columns(items, extra_columns);
 
     // Build CSV
     let mut wtr = csv::Writer::from_writer(vec![]);
@@ -1,10 +1,10 @@
-use crate::azure::{client::AzureDevOpsClient, tags};
+use crate::azure::{api::AzureBoardsApi, tags};
 
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -17,26 +17,27 @@ pub struct ListTagsArgs {
     /// AzDO project name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub project: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
-#[mcp_tool(name = "azdo_list_tags", description = "List tags")]
+#[mcp_tool(name = "azdo_list_tags", description = "List tags", read_only = true)]
 pub async fn list_tags(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListTagsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_tags");
+    tracing::info!("Tool invoked: azdo_list_tags");
     let tags = tags::list_tags(client, &args.organization, &args.project)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
     // Extract just the tag names for compact response
     let tag_names: Vec<String> = tags.into_iter().map(|tag| tag.name).collect();
 
+    let page = args.paging.page(tag_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        tag_names.join(","),
+        page.append_next_cursor(page.items.join(",")),
     )]))
 }
@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards};
 use crate::compact_llm;
 use crate::mcp::tools::support::deserialize_non_empty_string;
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -23,12 +23,16 @@ pub struct GetBoardArgs {
     pub board_id: String,
 }
 
-#[mcp_tool(name = "azdo_get_team_board", description = "Get board details")]
+#[mcp_tool(
+    name = "azdo_get_team_board",
+    description = "Get board details",
+    read_only = true
+)]
 pub async fn get_team_board(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: GetBoardArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_get_team_board(team_id={}, board_id={})",
         args.team_id,
         args.board_id
@@ -41,11 +45,7 @@ pub async fn get_team_board(
         &args.board_id,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     Ok(CallToolResult::success(vec![Content::text(
         compact_llm::to_compact_string(&board).unwrap(),
@@ -1,5 +1,5 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
-use crate::mcp::tools::support::{board_columns_to_csv, deserialize_non_empty_string};
+use crate::azure::{api::AzureBoardsApi, boards};
+use crate::mcp::tools::support::{PagingArgs, board_columns_to_csv, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -20,14 +20,21 @@ pub struct ListBoardColumnsArgs {
     pub team_id: String,
     /// Board ID or name
     pub board_id: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
-#[mcp_tool(name = "azdo_list_board_columns", description = "List board columns")]
+#[mcp_tool(
+    name = "azdo_list_board_columns",
+    description = "List board columns",
+    read_only = true
+)]
 pub async fn list_board_columns(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListBoardColumnsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_list_board_columns(team_id={}, board_id={})",
         args.team_id,
         args.board_id
@@ -40,17 +47,16 @@ pub async fn list_board_columns(
         &args.board_id,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
-    let csv_data = board_columns_to_csv(&columns).map_err(|e| McpError {
+    let page = args.paging.page(columns)?;
+    let csv_data = board_columns_to_csv(&page.items).map_err(|e| McpError {
         code: ErrorCode(-32000),
         message: e.into(),
         data: None,
     })?;
 
-    Ok(CallToolResult::success(vec![Content::text(csv_data)]))
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(csv_data),
+    )]))
 }
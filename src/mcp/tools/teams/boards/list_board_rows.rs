@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards};
 use crate::compact_llm;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -21,17 +21,21 @@ pub struct ListBoardRowsArgs {
     pub team_id: String,
     /// Board ID or name
     pub board_id: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_list_board_rows",
-    description = "List board rows (swimlanes)"
+    description = "List board rows (swimlanes)",
+    read_only = true
 )]
 pub async fn list_board_rows(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListBoardRowsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_list_board_rows(team_id={}, board_id={})",
         args.team_id,
         args.board_id
@@ -44,11 +48,7 @@ pub async fn list_board_rows(
         &args.board_id,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     // Extract row names into an array
     let row_names: Vec<String> = rows
@@ -56,7 +56,9 @@ pub async fn list_board_rows(
         .map(|row| row.name.unwrap_or_default())
         .collect();
 
+    let page = args.paging.page(row_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        compact_llm::to_compact_string(&row_names).unwrap(),
+        page.append_next_cursor(compact_llm::to_compact_string(&page.items).unwrap()),
     )]))
 }
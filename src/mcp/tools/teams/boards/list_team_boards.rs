@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards};
 use crate::compact_llm;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,29 +19,34 @@ pub struct ListBoardsArgs {
     pub project: String,
     /// Team ID or name
     pub team_id: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
-#[mcp_tool(name = "azdo_list_team_boards", description = "List boards")]
+#[mcp_tool(
+    name = "azdo_list_team_boards",
+    description = "List boards",
+    read_only = true
+)]
 pub async fn list_team_boards(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListBoardsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_list_team_boards(team_id={})",
         args.team_id
     );
     let boards = boards::list_boards(client, &args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
 
     // Extract just the board names for compact response
     let board_names: Vec<String> = boards.into_iter().map(|board| board.name).collect();
 
+    let page = args.paging.page(board_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        compact_llm::to_compact_string(&board_names).unwrap(),
+        page.append_next_cursor(compact_llm::to_compact_string(&page.items).unwrap()),
     )]))
 }
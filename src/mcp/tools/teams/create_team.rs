@@ -0,0 +1,51 @@
+use crate::azure::{api::AzureBoardsApi, boards};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateTeamArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// Team description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_create_team",
+    description = "Create a team",
+    read_only = false
+)]
+pub async fn create_team(
+    client: &dyn AzureBoardsApi,
+    args: CreateTeamArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_create_team(name={})", args.name);
+    let team = boards::create_team(
+        client,
+        &args.organization,
+        &args.project,
+        &args.name,
+        args.description.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        team.id, team.name
+    ))]))
+}
@@ -0,0 +1,43 @@
+use crate::azure::{api::AzureBoardsApi, boards};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DeleteTeamArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+}
+
+#[mcp_tool(
+    name = "azdo_delete_team",
+    description = "Delete a team",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn delete_team(
+    client: &dyn AzureBoardsApi,
+    args: DeleteTeamArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_delete_team(team_id={})", args.team_id);
+    boards::delete_team(client, &args.organization, &args.project, &args.team_id)
+        .await
+        .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Deleted team {}",
+        args.team_id
+    ))]))
+}
@@ -0,0 +1,129 @@
+use crate::azure::{api::AzureBoardsApi, iterations, work_items};
+use crate::mcp::tools::support::{
+    OutputFormat, PagingArgs, deserialize_non_empty_string, simplify_work_item_json,
+    work_items_result, work_items_result_with_columns,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::CallToolResult,
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::HashMap;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetIterationWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Iteration ID, name or path (optional, defaults to the current iteration)
+    #[serde(default)]
+    pub iteration: Option<String>,
+    /// Format of the text output (optional): `csv` (default) or `markdown` (table)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_get_iteration_work_items",
+    description = "Get work items committed to a team iteration, with parent IDs",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
+)]
+pub async fn get_iteration_work_items(
+    client: &dyn AzureBoardsApi,
+    args: GetIterationWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_iteration_work_items(team_id={}, iteration={:?})",
+        args.team_id,
+        args.iteration
+    );
+
+    let iteration = iterations::find_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let iteration_items = iterations::get_iteration_work_items(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    // Collect the IDs in the order returned and the parent of each child
+    let mut ids = Vec::new();
+    let mut parents = HashMap::new();
+    for link in &iteration_items.work_item_relations {
+        if !ids.contains(&link.target.id) {
+            ids.push(link.target.id);
+        }
+        if let Some(source) = &link.source
+            && link.rel.as_deref() == Some("System.LinkTypes.Hierarchy-Forward")
+        {
+            parents.insert(link.target.id, source.id);
+        }
+    }
+
+    if ids.is_empty() {
+        return work_items_result(
+            serde_json::json!([]),
+            &format!("No work items found in {}", iteration.name),
+            None,
+        );
+    }
+
+    let page = args.paging.page(ids)?;
+    let items = work_items::get_work_items(
+        client,
+        &args.organization,
+        &args.project,
+        &page.items,
+        None,
+        &[],
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    // Convert to JSON value, simplify, attach the parent ID, then return as CSV and
+    // structured content
+    let mut json_value = serde_json::to_value(&items).unwrap();
+    simplify_work_item_json(&mut json_value);
+    if let Some(array) = json_value.as_array_mut() {
+        for item in array {
+            let parent = item
+                .get("id")
+                .and_then(|id| id.as_u64())
+                .and_then(|id| parents.get(&(id as u32)));
+            if let (Some(obj), Some(parent)) = (item.as_object_mut(), parent) {
+                obj.insert("Parent".to_string(), serde_json::json!(parent));
+            }
+        }
+    }
+
+    work_items_result_with_columns(
+        json_value,
+        &format!("No work items found in {}", iteration.name),
+        page.next_cursor,
+        &[],
+        args.output_format,
+    )
+}
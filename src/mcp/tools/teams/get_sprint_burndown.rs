@@ -0,0 +1,233 @@
+use crate::analytics::burndown::{
+    self, BurndownMetric, MemberCapacity, RevisionSnapshot, SprintCalendar,
+};
+use crate::azure::{api::AzureBoardsApi, iterations, teams, work_items};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use futures::{StreamExt, stream};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetSprintBurndownArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Iteration ID, name or path (optional, defaults to the current iteration)
+    #[serde(default)]
+    pub iteration: Option<String>,
+    /// Metric: "remaining_work" (hours, default) or "count" (open work items)
+    #[serde(default)]
+    pub metric: Option<String>,
+    /// States considered done (default: ["Closed", "Done", "Removed"])
+    #[serde(default)]
+    pub done_states: Vec<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_get_sprint_burndown",
+    description = "Compute sprint burndown per working day (remaining, ideal, capacity)",
+    read_only = true
+)]
+pub async fn get_sprint_burndown(
+    client: &dyn AzureBoardsApi,
+    args: GetSprintBurndownArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_sprint_burndown(team_id={}, iteration={:?}, metric={:?})",
+        args.team_id,
+        args.iteration,
+        args.metric
+    );
+
+    let metric = match args.metric.as_deref() {
+        None | Some("remaining_work") => BurndownMetric::RemainingWork,
+        Some("count") => BurndownMetric::Count,
+        Some(other) => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: format!(
+                    "Invalid metric '{}'. Valid values are: 'remaining_work', 'count'",
+                    other
+                )
+                .into(),
+                data: None,
+            });
+        }
+    };
+    let done_states = if args.done_states.is_empty() {
+        vec![
+            "Closed".to_string(),
+            "Done".to_string(),
+            "Removed".to_string(),
+        ]
+    } else {
+        args.done_states
+    };
+
+    let map_err = McpError::from;
+
+    let iteration = iterations::find_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration.as_deref(),
+    )
+    .await
+    .map_err(map_err)?;
+
+    let (Some(start), Some(finish)) = (
+        iteration
+            .attributes
+            .start_date
+            .as_deref()
+            .and_then(burndown::parse_date),
+        iteration
+            .attributes
+            .finish_date
+            .as_deref()
+            .and_then(burndown::parse_date),
+    ) else {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "Iteration {} has no start/finish dates",
+            iteration.name
+        ))]));
+    };
+
+    let settings =
+        teams::get_team_settings(client, &args.organization, &args.project, &args.team_id)
+            .await
+            .map_err(map_err)?;
+    let days_off = iterations::get_team_days_off(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+    )
+    .await
+    .map_err(map_err)?;
+    let capacity = iterations::get_team_capacity(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+    )
+    .await
+    .map_err(map_err)?;
+
+    let to_ranges = |ranges: &[iterations::DateRange]| {
+        ranges
+            .iter()
+            .filter_map(|r| {
+                Some((
+                    burndown::parse_date(&r.start)?,
+                    burndown::parse_date(&r.end)?,
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let calendar = SprintCalendar {
+        start,
+        finish,
+        working_days: burndown::parse_working_days(&settings.working_days),
+        team_days_off: to_ranges(&days_off.days_off),
+    };
+    let members: Vec<MemberCapacity> = capacity
+        .team_members
+        .iter()
+        .map(|m| MemberCapacity {
+            capacity_per_day: m.activities.iter().map(|a| a.capacity_per_day).sum(),
+            days_off: to_ranges(&m.days_off),
+        })
+        .collect();
+
+    // Revision history of every work item committed to the iteration
+    let iteration_items = iterations::get_iteration_work_items(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+    )
+    .await
+    .map_err(map_err)?;
+
+    // Each work item once, like azdo_get_iteration_work_items, as it can be the target of
+    // several relations
+    let mut ids = Vec::new();
+    for link in &iteration_items.work_item_relations {
+        if !ids.contains(&link.target.id) {
+            ids.push(link.target.id);
+        }
+    }
+    let (organization, project) = (&args.organization, &args.project);
+    let requests: Vec<_> = ids
+        .iter()
+        .map(|id| async move {
+            work_items::get_work_item_revisions(client, organization, project, *id).await
+        })
+        .collect();
+    let histories: Vec<_> = stream::iter(requests)
+        .buffered(work_items::MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+    let mut items = Vec::new();
+    for revisions in histories {
+        items.push(
+            revisions
+                .map_err(map_err)?
+                .iter()
+                .filter_map(|r| RevisionSnapshot::from_fields(&r.fields))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let points = burndown::compute_burndown(
+        &calendar,
+        &members,
+        &items,
+        &iteration.path,
+        &done_states,
+        metric,
+        chrono::Utc::now().date_naive(),
+    );
+
+    if points.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "Iteration {} has no working days",
+            iteration.name
+        ))]));
+    }
+
+    // Return CSV format: date,remaining,ideal,capacity
+    let mut csv_lines = vec!["date,remaining,ideal,capacity".to_string()];
+    for point in points {
+        csv_lines.push(format!(
+            "{},{},{:.1},{:.1}",
+            point.date,
+            point
+                .remaining
+                .map(|r| format!("{:.1}", r))
+                .unwrap_or_default(),
+            point.ideal,
+            point.capacity
+        ));
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(
+        csv_lines.join("\n"),
+    )]))
+}
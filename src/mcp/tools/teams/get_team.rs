@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards, cache};
 
 use crate::mcp::tools::support::deserialize_non_empty_string;
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,21 +19,27 @@ pub struct GetTeamArgs {
     pub project: String,
     /// Team ID or name
     pub team_id: String,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
 }
 
-#[mcp_tool(name = "azdo_get_team", description = "Get team details")]
+#[mcp_tool(
+    name = "azdo_get_team",
+    description = "Get team details",
+    read_only = true
+)]
 pub async fn get_team(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: GetTeamArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_get_team(team_id={})", args.team_id);
-    let team = boards::get_team(client, &args.organization, &args.project, &args.team_id)
-        .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+    tracing::info!("Tool invoked: azdo_get_team(team_id={})", args.team_id);
+    let team = cache::bypass(
+        args.no_cache,
+        boards::get_team(client, &args.organization, &args.project, &args.team_id),
+    )
+    .await
+    .map_err(McpError::from)?;
 
     let description = team.description.unwrap_or_default();
     let output = format!("{},{}", team.name, description);
@@ -0,0 +1,75 @@
+use crate::azure::{api::AzureBoardsApi, teams};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetTeamAreaPathsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+}
+
+#[mcp_tool(
+    name = "azdo_get_team_area_paths",
+    description = "Get area paths owned by a team and its default area path",
+    read_only = true
+)]
+pub async fn get_team_area_paths(
+    client: &dyn AzureBoardsApi,
+    args: GetTeamAreaPathsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_team_area_paths(team_id={})",
+        args.team_id
+    );
+    let field_values =
+        teams::get_team_field_values(client, &args.organization, &args.project, &args.team_id)
+            .await
+            .map_err(McpError::from)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["area_path", "include_children", "default"])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+
+    for value in &field_values.values {
+        wtr.write_record([
+            value.value.as_str(),
+            &value.include_children.to_string(),
+            &(value.value == field_values.default_value).to_string(),
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(data)]))
+}
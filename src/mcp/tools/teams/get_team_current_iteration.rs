@@ -1,9 +1,9 @@
-use crate::azure::{client::AzureDevOpsClient, iterations};
+use crate::azure::{api::AzureBoardsApi, iterations};
 use crate::mcp::tools::support::deserialize_non_empty_string;
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -22,13 +22,14 @@ pub struct GetTeamCurrentIterationArgs {
 
 #[mcp_tool(
     name = "azdo_get_team_current_iteration",
-    description = "Get current iteration/sprint for team"
+    description = "Get current iteration/sprint for team",
+    read_only = true
 )]
 pub async fn get_team_current_iteration(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: GetTeamCurrentIterationArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_get_team_current_iteration(team_id={})",
         args.team_id
     );
@@ -40,11 +41,7 @@ pub async fn get_team_current_iteration(
         &args.team_id,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     match iteration {
         Some(iteration) => {
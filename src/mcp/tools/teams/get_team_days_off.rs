@@ -0,0 +1,84 @@
+use crate::azure::{api::AzureBoardsApi, iterations};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetTeamDaysOffArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Iteration ID, name or path (optional, defaults to the current iteration)
+    #[serde(default)]
+    pub iteration: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_get_team_days_off",
+    description = "Get team days off for an iteration",
+    read_only = true
+)]
+pub async fn get_team_days_off(
+    client: &dyn AzureBoardsApi,
+    args: GetTeamDaysOffArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_team_days_off(team_id={}, iteration={:?})",
+        args.team_id,
+        args.iteration
+    );
+
+    let iteration = iterations::find_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let days_off = iterations::get_team_days_off(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    if days_off.days_off.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "No team days off in {}",
+            iteration.name
+        ))]));
+    }
+
+    // Return CSV format: start_date,end_date (one range per line)
+    let csv_lines: Vec<String> = days_off
+        .days_off
+        .iter()
+        .map(|range| {
+            format!(
+                "{},{}",
+                range.start.split('T').next().unwrap_or(&range.start),
+                range.end.split('T').next().unwrap_or(&range.end)
+            )
+        })
+        .collect();
+
+    Ok(CallToolResult::success(vec![Content::text(
+        csv_lines.join("\n"),
+    )]))
+}
@@ -1,5 +1,5 @@
-use crate::azure::client::AzureDevOpsClient;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::azure::api::AzureBoardsApi;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -18,29 +18,34 @@ pub struct ListTeamMembersArgs {
     pub project: String,
     /// Team ID or name
     pub team_id: String,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
-#[mcp_tool(name = "azdo_list_team_members", description = "List team members")]
+#[mcp_tool(
+    name = "azdo_list_team_members",
+    description = "List team members",
+    read_only = true
+)]
 pub async fn list_team_members(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListTeamMembersArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_team_members");
+    tracing::info!("Tool invoked: azdo_list_team_members");
     let members = client
         .list_team_members(&args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(McpError::from)?;
+
+    let page = args.paging.page(members)?;
 
     let mut wtr = csv::WriterBuilder::new()
         .has_headers(false)
         .from_writer(vec![]);
 
-    for member in members {
-        wtr.write_record(&[member.identity.display_name, member.identity.unique_name])
+    for member in &page.items {
+        wtr.write_record([&member.identity.display_name, &member.identity.unique_name])
             .map_err(|e| McpError {
                 code: ErrorCode(-32000),
                 message: format!("Failed to write CSV: {}", e).into(),
@@ -66,5 +71,7 @@ pub async fn list_team_members(
         data: None,
     })?;
 
-    Ok(CallToolResult::success(vec![Content::text(data)]))
+    Ok(CallToolResult::success(vec![Content::text(
+        page.append_next_cursor(data),
+    )]))
 }
@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards, cache};
 
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -17,26 +17,37 @@ pub struct ListTeamsArgs {
     /// AzDO project name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub project: String,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
-#[mcp_tool(name = "azdo_list_teams", description = "List teams in the project")]
+#[mcp_tool(
+    name = "azdo_list_teams",
+    description = "List teams in the project",
+    read_only = true
+)]
 pub async fn list_teams(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListTeamsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_teams");
-    let teams = boards::list_teams(client, &args.organization, &args.project)
-        .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+    tracing::info!("Tool invoked: azdo_list_teams");
+    let teams = cache::bypass(
+        args.no_cache,
+        boards::list_teams(client, &args.organization, &args.project),
+    )
+    .await
+    .map_err(McpError::from)?;
 
     // Extract just the team names for compact response
     let team_names: Vec<String> = teams.into_iter().map(|team| team.name).collect();
 
+    let page = args.paging.page(team_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        team_names.join(","),
+        page.append_next_cursor(page.items.join(",")),
     )]))
 }
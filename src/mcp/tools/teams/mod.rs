@@ -1,12 +1,30 @@
 // Teams module
 pub mod boards;
+pub mod create_team;
+pub mod delete_team;
+pub mod get_iteration_work_items;
+pub mod get_sprint_burndown;
 pub mod get_team;
+pub mod get_team_area_paths;
 pub mod get_team_current_iteration;
+pub mod get_team_days_off;
 pub mod list_team_members;
 pub mod list_teams;
+pub mod update_team;
+pub mod update_team_area_paths;
+pub mod update_team_days_off;
 
 // Re-export the public items
+pub use create_team::{CreateTeamArgs, create_team};
+pub use delete_team::{DeleteTeamArgs, delete_team};
+pub use get_iteration_work_items::{GetIterationWorkItemsArgs, get_iteration_work_items};
+pub use get_sprint_burndown::{GetSprintBurndownArgs, get_sprint_burndown};
 pub use get_team::{GetTeamArgs, get_team};
+pub use get_team_area_paths::{GetTeamAreaPathsArgs, get_team_area_paths};
 pub use get_team_current_iteration::{GetTeamCurrentIterationArgs, get_team_current_iteration};
+pub use get_team_days_off::{GetTeamDaysOffArgs, get_team_days_off};
 pub use list_team_members::{ListTeamMembersArgs, list_team_members};
 pub use list_teams::{ListTeamsArgs, list_teams};
+pub use update_team::{UpdateTeamArgs, update_team};
+pub use update_team_area_paths::{UpdateTeamAreaPathsArgs, update_team_area_paths};
+pub use update_team_days_off::{UpdateTeamDaysOffArgs, update_team_days_off};
@@ -0,0 +1,70 @@
+use crate::azure::{api::AzureBoardsApi, boards};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateTeamArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// New team name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// New team description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_update_team",
+    description = "Rename a team or change its description",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn update_team(
+    client: &dyn AzureBoardsApi,
+    args: UpdateTeamArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_update_team(team_id={}, name={:?})",
+        args.team_id,
+        args.name
+    );
+
+    if args.name.is_none() && args.description.is_none() {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "Either name or description must be provided".into(),
+            data: None,
+        });
+    }
+
+    let team = boards::update_team(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.name.as_deref(),
+        args.description.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let description = team.description.unwrap_or_default();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{},{}",
+        team.name, description
+    ))]))
+}
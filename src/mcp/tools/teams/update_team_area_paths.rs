@@ -0,0 +1,170 @@
+use crate::azure::{
+    api::AzureBoardsApi,
+    classification_nodes,
+    client::AzureError,
+    teams::{self, TeamFieldValue},
+};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct TeamAreaPath {
+    /// Area path (e.g., "MyProject\\Team1")
+    pub path: String,
+    /// Whether the team also owns the sub-areas
+    #[serde(default)]
+    pub include_children: bool,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateTeamAreaPathsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Default area path for new work items created by the team
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub default_area_path: String,
+    /// Full list of area paths owned by the team, replaces the existing list
+    pub area_paths: Vec<TeamAreaPath>,
+}
+
+#[mcp_tool(
+    name = "azdo_update_team_area_paths",
+    description = "Set area paths owned by a team and its default area path (replaces existing)",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn update_team_area_paths(
+    client: &dyn AzureBoardsApi,
+    args: UpdateTeamAreaPathsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_update_team_area_paths(team_id={}, default_area_path={}, area_paths={})",
+        args.team_id,
+        args.default_area_path,
+        args.area_paths.len()
+    );
+
+    let mut values: Vec<TeamFieldValue> = args
+        .area_paths
+        .into_iter()
+        .map(|a| TeamFieldValue {
+            value: a.path,
+            include_children: a.include_children,
+        })
+        .collect();
+
+    // The default area path must be one of the team's area paths
+    if !values
+        .iter()
+        .any(|v| v.value.eq_ignore_ascii_case(&args.default_area_path))
+    {
+        values.push(TeamFieldValue {
+            value: args.default_area_path.clone(),
+            include_children: false,
+        });
+    }
+
+    // Validate that all the area paths exist before touching the team
+    for value in &values {
+        match classification_nodes::get_area_node(
+            client,
+            &args.organization,
+            &args.project,
+            &value.value,
+        )
+        .await
+        {
+            Ok(_) => {}
+            Err(AzureError::NotFound(_)) => {
+                return Err(McpError {
+                    code: ErrorCode(-32602),
+                    message: format!(
+                        "Area path '{}' does not exist, use azdo_list_area_paths to get the valid ones",
+                        value.value
+                    )
+                    .into(),
+                    data: None,
+                });
+            }
+            Err(e) => return Err(McpError::from(e)),
+        }
+    }
+
+    let updated = teams::update_team_field_values(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &args.default_area_path,
+        &values,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Updated team area paths: {} area path(s), default {}",
+        updated.values.len(),
+        updated.default_value
+    ))]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::client::ApiFailure;
+    use crate::azure::cloud::Cloud;
+
+    #[tokio::test]
+    async fn test_update_team_area_paths_with_unreadable_area() {
+        let args = || UpdateTeamAreaPathsArgs {
+            organization: "contoso".to_string(),
+            project: "Web".to_string(),
+            team_id: "Web Team".to_string(),
+            default_area_path: "Web\\Payments".to_string(),
+            area_paths: Vec::new(),
+        };
+        let failing = |status: u16| {
+            let mut api = MockAzureBoardsApi::new();
+            api.expect_cloud().return_const(Cloud::default());
+            api.expect_send_json()
+                .times(1)
+                .returning(move |_, _, _, _, _| {
+                    let failure = ApiFailure {
+                        status,
+                        type_key: None,
+                        message: "Failed".to_string(),
+                    };
+                    Err(match status {
+                        404 => AzureError::NotFound(failure),
+                        _ => AzureError::Forbidden(failure),
+                    })
+                });
+            api
+        };
+
+        let e = update_team_area_paths(&failing(404), args())
+            .await
+            .unwrap_err();
+        assert_eq!(e.code, ErrorCode(-32602));
+        assert!(e.message.contains("does not exist"));
+
+        let e = update_team_area_paths(&failing(403), args())
+            .await
+            .unwrap_err();
+        assert_eq!(e.code, ErrorCode(-32003));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::analytics::burndown::parse_date;
+use crate::azure::{
+    api::AzureBoardsApi,
+    iterations::{self, DateRange, TeamDaysOff},
+};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DaysOffRange {
+    /// First day off (YYYY-MM-DD)
+    pub start: String,
+    /// Last day off, inclusive (YYYY-MM-DD)
+    pub end: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateTeamDaysOffArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    pub team_id: String,
+    /// Iteration ID, name or path (optional, defaults to the current iteration)
+    #[serde(default)]
+    pub iteration: Option<String>,
+    /// Full list of team days off, replaces the existing list (empty list clears it)
+    pub days_off: Vec<DaysOffRange>,
+}
+
+#[mcp_tool(
+    name = "azdo_update_team_days_off",
+    description = "Set team days off for an iteration (replaces existing)",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
+pub async fn update_team_days_off(
+    client: &dyn AzureBoardsApi,
+    args: UpdateTeamDaysOffArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_update_team_days_off(team_id={}, iteration={:?}, ranges={})",
+        args.team_id,
+        args.iteration,
+        args.days_off.len()
+    );
+
+    for range in &args.days_off {
+        let (start, end) = (parse_date(&range.start), parse_date(&range.end));
+        let message = match (start, end) {
+            (None, _) => format!("Invalid start date '{}', expected YYYY-MM-DD", range.start),
+            (_, None) => format!("Invalid end date '{}', expected YYYY-MM-DD", range.end),
+            (Some(start), Some(end)) if end < start => format!(
+                "The days off from {} to {} end before they start",
+                range.start, range.end
+            ),
+            _ => continue,
+        };
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: message.into(),
+            data: None,
+        });
+    }
+
+    let iteration = iterations::find_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration.as_deref(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let days_off = TeamDaysOff {
+        days_off: args
+            .days_off
+            .into_iter()
+            .map(|range| DateRange {
+                start: range.start,
+                end: range.end,
+            })
+            .collect(),
+    };
+
+    let updated = iterations::update_team_days_off(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        &iteration.id,
+        &days_off,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Updated team days off in {}: {} range(s)",
+        iteration.name,
+        updated.days_off.len()
+    ))]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+
+    #[tokio::test]
+    async fn test_update_team_days_off_with_invalid_dates() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_send_json().never();
+        let args = |start: &str, end: &str| UpdateTeamDaysOffArgs {
+            organization: "contoso".to_string(),
+            project: "Web".to_string(),
+            team_id: "Web Team".to_string(),
+            iteration: None,
+            days_off: vec![DaysOffRange {
+                start: start.to_string(),
+                end: end.to_string(),
+            }],
+        };
+
+        for (start, end) in [
+            ("2025-13-01", "2025-13-02"),
+            ("2025-03-10", "next friday"),
+            ("2025-03-14", "2025-03-10"),
+        ] {
+            let e = update_team_days_off(&api, args(start, end))
+                .await
+                .unwrap_err();
+            assert_eq!(e.code, ErrorCode(-32602));
+        }
+    }
+}
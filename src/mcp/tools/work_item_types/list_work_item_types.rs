@@ -1,10 +1,10 @@
-use crate::azure::{boards, client::AzureDevOpsClient};
+use crate::azure::{api::AzureBoardsApi, boards, cache};
 use crate::compact_llm;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -17,29 +17,37 @@ pub struct ListWorkItemTypesArgs {
     /// AzDO project name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub project: String,
+    /// Bypass the metadata cache and fetch fresh data (optional, default false)
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_list_work_item_types",
-    description = "List work item types"
+    description = "List work item types",
+    read_only = true
 )]
 pub async fn list_work_item_types(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: ListWorkItemTypesArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_list_work_item_types");
-    let types = boards::list_work_item_types(client, &args.organization, &args.project)
-        .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+    tracing::info!("Tool invoked: azdo_list_work_item_types");
+    let types = cache::bypass(
+        args.no_cache,
+        boards::list_work_item_types(client, &args.organization, &args.project),
+    )
+    .await
+    .map_err(McpError::from)?;
 
     // Extract just the work item type names for compact response
     let type_names: Vec<String> = types.into_iter().map(|wit| wit.name).collect();
 
+    let page = args.paging.page(type_names)?;
+
     Ok(CallToolResult::success(vec![Content::text(
-        compact_llm::to_compact_string(&type_names).unwrap(),
+        page.append_next_cursor(compact_llm::to_compact_string(&page.items).unwrap()),
     )]))
 }
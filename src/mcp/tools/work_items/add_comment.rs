@@ -1,10 +1,10 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
+use crate::azure::{api::AzureBoardsApi, work_items};
 use crate::compact_llm;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{TextFormat, deserialize_non_empty_string};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,19 +19,23 @@ pub struct AddCommentArgs {
     pub project: String,
     /// Work item ID to add comment to
     pub work_item_id: u32,
-    /// Comment text (supports markdown)
+    /// Comment text (HTML, or markdown with `format`)
     pub text: String,
+    /// Format of the text: `html` (default) or `markdown`, converted to HTML
+    #[serde(default)]
+    pub format: TextFormat,
 }
 
 #[mcp_tool(
     name = "azdo_add_comment",
-    description = "Add a comment to a work item"
+    description = "Add a comment to a work item",
+    read_only = false
 )]
 pub async fn add_comment(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: AddCommentArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_add_comment(work_item_id={}, text_length={})",
         args.work_item_id,
         args.text.len()
@@ -42,14 +46,10 @@ pub async fn add_comment(
         &args.organization,
         &args.project,
         args.work_item_id,
-        &args.text,
+        &args.format.to_html(&args.text),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     Ok(CallToolResult::success(vec![Content::text(
         compact_llm::to_compact_string(&result).unwrap(),
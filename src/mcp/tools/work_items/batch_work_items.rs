@@ -0,0 +1,186 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::batch::{self, BatchOperation};
+use crate::azure::{assignees, path_resolver};
+use crate::mcp::tools::support::{
+    deserialize_non_empty_string, link_type_reference, reference_names,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BatchOperationArgs {
+    /// "create", "update" or "link"
+    pub op: String,
+    /// Type of the work item to create, e.g. "Task"
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+    /// ID of the work item to update
+    #[serde(default)]
+    pub id: Option<u32>,
+    /// Fields to create or update the work item with, by simplified or reference name,
+    /// e.g. {"Title": "Login page", "Microsoft.VSTS.Common.Priority": 1}
+    #[serde(default)]
+    pub fields: BTreeMap<String, Value>,
+    /// Source and target work item IDs of the link, negative for the work items created
+    /// by the batch: -1 for the first create operation, -2 for the second...
+    #[serde(default)]
+    pub source_id: Option<i64>,
+    #[serde(default)]
+    pub target_id: Option<i64>,
+    /// Link type: "Parent", "Child", "Related", "Duplicate", "Dependency" or a reference
+    /// name
+    #[serde(default)]
+    pub link_type: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BatchWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Operations, in order
+    pub operations: Vec<BatchOperationArgs>,
+}
+
+fn operation(index: usize, args: BatchOperationArgs) -> Result<BatchOperation, String> {
+    let fields = |fields: BTreeMap<String, Value>| -> Vec<(String, Value)> {
+        let names: Vec<String> = fields.keys().cloned().collect();
+        reference_names(&names)
+            .into_iter()
+            .zip(fields.into_values())
+            .collect()
+    };
+    let missing = |name: &str| format!("Operation {}: {} is required", index, name);
+    match args.op.to_lowercase().as_str() {
+        "create" => Ok(BatchOperation::Create {
+            work_item_type: args
+                .work_item_type
+                .ok_or_else(|| missing("work_item_type"))?,
+            fields: fields(args.fields),
+        }),
+        "update" if args.fields.is_empty() => Err(missing("fields")),
+        "update" => Ok(BatchOperation::Update {
+            id: args.id.ok_or_else(|| missing("id"))?,
+            fields: fields(args.fields),
+        }),
+        "link" => Ok(BatchOperation::Link {
+            source_id: args.source_id.ok_or_else(|| missing("source_id"))?,
+            target_id: args.target_id.ok_or_else(|| missing("target_id"))?,
+            link_type: link_type_reference(
+                args.link_type
+                    .as_deref()
+                    .ok_or_else(|| missing("link_type"))?,
+            )
+            .to_string(),
+        }),
+        op => Err(format!(
+            "Operation {}: unknown op {}, expected create, update or link",
+            index, op
+        )),
+    }
+}
+
+#[mcp_tool(
+    name = "azdo_batch_work_items",
+    description = "Create, update and link work items in a single round trip, the links able to refer to the work items created by the batch (-1 the first one), returning the result of each operation. The operations are not a transaction: each one succeeds or fails on its own.",
+    read_only = false,
+    destructive = true
+)]
+pub async fn batch_work_items(
+    client: &dyn AzureBoardsApi,
+    args: BatchWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_batch_work_items(project={}, operations={})",
+        args.project,
+        args.operations.len()
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    if args.operations.is_empty() {
+        return Err(invalid_params("No operations".to_string()));
+    }
+    let mut operations = args
+        .operations
+        .into_iter()
+        .enumerate()
+        .map(|(index, args)| operation(index, args))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid_params)?;
+    for operation in &mut operations {
+        if let BatchOperation::Create { fields, .. } | BatchOperation::Update { fields, .. } =
+            operation
+        {
+            path_resolver::resolve_field_paths(client, &args.organization, &args.project, fields)
+                .await
+                .map_err(McpError::from)?;
+            assignees::resolve_field_assignee(client, &args.organization, fields)
+                .await
+                .map_err(McpError::from)?;
+        }
+    }
+    let plan = batch::plan(&operations).map_err(invalid_params)?;
+
+    let results = batch::run_batch(
+        client,
+        &args.organization,
+        &args.project,
+        &operations,
+        &plan,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    let summary = format!(
+        "{} operations, {} succeeded, {} failed",
+        results.len(),
+        results.len() - failed,
+        failed
+    );
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err =
+        |e: csv::Error| McpError::internal_error(format!("Failed to write the CSV: {}", e), None);
+    wtr.write_record(["index", "op", "status", "work_item_id", "rev", "error"])
+        .map_err(write_err)?;
+    for result in results {
+        wtr.write_record([
+            result.index.to_string().as_str(),
+            result.operation,
+            result.status.to_string().as_str(),
+            result
+                .work_item_id
+                .map(|id| id.to_string())
+                .unwrap_or_default()
+                .as_str(),
+            result
+                .rev
+                .map(|rev| rev.to_string())
+                .unwrap_or_default()
+                .as_str(),
+            result.error.as_deref().unwrap_or_default(),
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{}\n{}",
+        summary, csv
+    ))]))
+}
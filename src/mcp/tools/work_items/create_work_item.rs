@@ -1,10 +1,11 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
-use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, simplify_work_item_json};
+use crate::azure::{api::AzureBoardsApi, assignees, path_resolver, work_items};
+use crate::mcp::tools::support::{
+    TextFormat, deserialize_non_empty_string, simplify_work_item_json, work_item_result,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, ErrorCode},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -26,7 +27,7 @@ pub struct CreateWorkItemArgs {
     pub title: String,
 
     // Core optional fields
-    /// Work item description (Basic HTML supported)
+    /// Work item description (Basic HTML supported, or markdown with `format`)
     #[serde(default)]
     pub description: Option<String>,
 
@@ -112,14 +113,24 @@ pub struct CreateWorkItemArgs {
     /// Optional extra fields as JSON string (for custom fields)
     #[serde(default)]
     pub fields: Option<String>,
+
+    /// Format of the description, acceptance criteria and reproduction steps: `html`
+    /// (default) or `markdown`, converted to HTML
+    #[serde(default)]
+    pub format: TextFormat,
 }
 
-#[mcp_tool(name = "azdo_create_work_item", description = "Create work item")]
+#[mcp_tool(
+    name = "azdo_create_work_item",
+    description = "Create work item",
+    output = "crate::mcp::tools::support::WorkItemOutput",
+    read_only = false
+)]
 pub async fn create_work_item(
-    client: &AzureDevOpsClient,
-    args: CreateWorkItemArgs,
+    client: &dyn AzureBoardsApi,
+    mut args: CreateWorkItemArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_create_work_item(work_item_type={}, title={}, area_path={:?}, iteration_path={:?})",
         args.work_item_type,
         args.title,
@@ -135,14 +146,29 @@ pub async fn create_work_item(
 
     // Core optional fields
     if let Some(desc) = &args.description {
-        field_map.insert("System.Description".to_string(), serde_json::json!(desc));
+        field_map.insert(
+            "System.Description".to_string(),
+            serde_json::json!(args.format.to_html(desc)),
+        );
     }
     if let Some(assigned_to) = &args.assigned_to {
+        let assigned_to = assignees::resolve_assignee(client, &args.organization, assigned_to)
+            .await
+            .map_err(McpError::from)?;
         field_map.insert(
             "System.AssignedTo".to_string(),
             serde_json::json!(assigned_to),
         );
     }
+    path_resolver::resolve_area_and_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &mut args.area_path,
+        &mut args.iteration_path,
+    )
+    .await
+    .map_err(McpError::from)?;
     if let Some(area_path) = &args.area_path {
         field_map.insert("System.AreaPath".to_string(), serde_json::json!(area_path));
     }
@@ -230,13 +256,13 @@ pub async fn create_work_item(
     if let Some(acceptance_criteria) = &args.acceptance_criteria {
         field_map.insert(
             "Microsoft.VSTS.Common.AcceptanceCriteria".to_string(),
-            serde_json::json!(acceptance_criteria),
+            serde_json::json!(args.format.to_html(acceptance_criteria)),
         );
     }
     if let Some(repro_steps) = &args.repro_steps {
         field_map.insert(
             "Microsoft.VSTS.TCM.ReproSteps".to_string(),
-            serde_json::json!(repro_steps),
+            serde_json::json!(args.format.to_html(repro_steps)),
         );
     }
 
@@ -272,15 +298,11 @@ pub async fn create_work_item(
         &fields_vec,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     // If parent_id is provided, create parent-child link
     if let Some(parent_id) = args.parent_id {
-        log::info!(
+        tracing::info!(
             "Creating parent-child link: child={}, parent={}",
             work_item.id,
             parent_id
@@ -301,11 +323,46 @@ pub async fn create_work_item(
         })?;
     }
 
-    // Convert to JSON value, simplify, then serialize
+    // Convert to JSON value, simplify, then return as text and structured content
     let mut json_value = serde_json::to_value(&work_item).unwrap();
     simplify_work_item_json(&mut json_value);
 
-    Ok(CallToolResult::success(vec![Content::text(
-        compact_llm::to_compact_string(&json_value).unwrap(),
-    )]))
+    Ok(work_item_result(json_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use crate::azure::context::{self, ClientContext};
+    use crate::azure::write_policy::WritePolicy;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_create_work_item_against_policy() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        // Refused before any request
+        api.expect_send_json().never();
+        let context = ClientContext {
+            write_policy: Arc::new(WritePolicy {
+                allowed_area_paths: vec!["Web\\Payments".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let args: CreateWorkItemArgs = serde_json::from_value(serde_json::json!({
+            "organization": "contoso",
+            "project": "Web",
+            "work_item_type": "Bug",
+            "title": "Checkout fails",
+        }))
+        .unwrap();
+        let error = context::scope(Arc::new(context), create_work_item(&api, args))
+            .await
+            .unwrap_err();
+        assert!(error.message.contains("only the ones under Web\\Payments"));
+    }
 }
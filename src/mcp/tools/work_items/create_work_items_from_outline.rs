@@ -0,0 +1,294 @@
+use crate::azure::context;
+use crate::azure::{api::AzureBoardsApi, assignees, boards, path_resolver, work_items};
+use crate::mcp::tools::support::{OutlineItem, deserialize_non_empty_string, parse_outline};
+use futures::{StreamExt, stream};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+
+/// Lines an outline can have
+const MAX_ITEMS: usize = 500;
+
+/// Work items created at once, the ones of a level of the hierarchy
+const BATCH_SIZE: usize = 10;
+
+/// Result of the creation of the work item of an outline line
+enum Outcome {
+    Pending,
+    Created(u32),
+    Failed(String),
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateWorkItemsFromOutlineArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Outline of the work items, a line each, nested by Markdown heading level or
+    /// indentation, e.g. "# Epic: Checkout\n## Payments {Priority=1}\n- Add Apple Pay
+    /// {AssignedTo=jane@contoso.com; Tags=web}\n  - Task: Integrate the SDK". A line is an
+    /// optional `Type:` prefix, the title and optional `{Field=value; ...}` annotations,
+    /// by simplified or reference name.
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub outline: String,
+    /// Type of the lines without a `Type:` prefix, by level (optional, default ["Epic",
+    /// "Feature", "User Story", "Task"])
+    #[serde(default)]
+    pub level_types: Vec<String>,
+    /// ID of an existing work item the top level lines are created under (optional)
+    #[serde(default)]
+    pub parent_id: Option<u32>,
+    /// Area path of the work items without an AreaPath annotation (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Iteration path of the work items without an Iteration annotation (optional)
+    #[serde(default)]
+    pub iteration_path: Option<String>,
+    /// Only validate the outline, without creating the work items (optional)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_create_work_items_from_outline",
+    description = "Create a hierarchy of work items (e.g. Epic > Features > Stories > Tasks) from a Markdown or indented outline, parents first, returning the ID created for each line. Nothing is created when a line is invalid.",
+    read_only = false
+)]
+pub async fn create_work_items_from_outline(
+    client: &dyn AzureBoardsApi,
+    mut args: CreateWorkItemsFromOutlineArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_create_work_items_from_outline(project={}, parent_id={:?}, dry_run={})",
+        args.project,
+        args.parent_id,
+        args.dry_run
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    let (organization, project) = (&args.organization, &args.project);
+    let level_types = if args.level_types.is_empty() {
+        ["Epic", "Feature", "User Story", "Task"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect()
+    } else {
+        args.level_types
+    };
+    let known_types: Vec<String> = boards::list_work_item_types(client, organization, project)
+        .await
+        .map_err(McpError::from)?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+
+    let mut items = parse_outline(&args.outline, &level_types, &known_types);
+    if items.is_empty() {
+        return Err(invalid_params("The outline has no lines".to_string()));
+    }
+    if items.len() > MAX_ITEMS {
+        return Err(invalid_params(format!(
+            "The outline has {} lines, at most {} can be created at once",
+            items.len(),
+            MAX_ITEMS
+        )));
+    }
+    context::current()
+        .write_policy
+        .check_bulk(items.len())
+        .map_err(McpError::from)?;
+    path_resolver::resolve_area_and_iteration(
+        client,
+        organization,
+        project,
+        &mut args.area_path,
+        &mut args.iteration_path,
+    )
+    .await
+    .map_err(McpError::from)?;
+    for item in &mut items {
+        if let Err(e) =
+            path_resolver::resolve_field_paths(client, organization, project, &mut item.fields)
+                .await
+        {
+            item.errors.push(e.to_string());
+        }
+        if let Err(e) =
+            assignees::resolve_field_assignee(client, organization, &mut item.fields).await
+        {
+            item.errors.push(e.to_string());
+        }
+        let defaults = [
+            ("System.AreaPath", &args.area_path),
+            ("System.IterationPath", &args.iteration_path),
+        ];
+        for (field, value) in defaults {
+            if let Some(value) = value
+                && !item.fields.iter().any(|(known, _)| known == field)
+            {
+                item.fields
+                    .push((field.to_string(), Value::String(value.clone())));
+            }
+        }
+    }
+
+    let invalid = items.iter().filter(|item| !item.errors.is_empty()).count();
+    let mut outcomes: Vec<Outcome> = items
+        .iter()
+        .map(|item| {
+            if item.errors.is_empty() {
+                Outcome::Pending
+            } else {
+                Outcome::Failed(item.errors.join("; "))
+            }
+        })
+        .collect();
+    if invalid > 0 || args.dry_run {
+        let summary = if invalid > 0 {
+            format!(
+                "No work items created, {} of {} lines are invalid",
+                invalid,
+                items.len()
+            )
+        } else {
+            format!("All {} lines are valid, no work items created", items.len())
+        };
+        return report(&items, &outcomes, args.parent_id, summary, "valid");
+    }
+
+    // A level at a time, so that the parents exist when their children are created
+    let total = items.len() as u64;
+    let mut done = 0;
+    let depth = items
+        .iter()
+        .map(|item| item.depth)
+        .max()
+        .unwrap_or_default();
+    for level in 0..=depth {
+        let mut indices = Vec::new();
+        let mut requests = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            if item.depth != level {
+                continue;
+            }
+            let parent_id = match item.parent.map(|parent| &outcomes[parent]) {
+                None => args.parent_id,
+                Some(Outcome::Created(id)) => Some(*id),
+                Some(_) => {
+                    outcomes[index] = Outcome::Failed("The parent was not created".to_string());
+                    continue;
+                }
+            };
+            let mut fields: Vec<(&str, Value)> = vec![("System.Title", item.title.clone().into())];
+            fields.extend(
+                item.fields
+                    .iter()
+                    .map(|(field, value)| (field.as_str(), value.clone())),
+            );
+            indices.push(index);
+            requests.push(async move {
+                work_items::create_work_item_under(
+                    client,
+                    organization,
+                    project,
+                    &item.work_item_type,
+                    &fields,
+                    parent_id,
+                )
+                .await
+            });
+        }
+        let created: Vec<_> = stream::iter(requests)
+            .buffered(BATCH_SIZE)
+            .inspect(|_| {
+                done += 1;
+                crate::progress::report(done, Some(total), || {
+                    format!("Created {} of {} work items", done, total)
+                });
+            })
+            .collect()
+            .await;
+        for (index, result) in indices.into_iter().zip(created) {
+            outcomes[index] = match result {
+                Ok(work_item) => Outcome::Created(work_item.id),
+                Err(e) => Outcome::Failed(e.to_string()),
+            };
+        }
+    }
+
+    let created = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome, Outcome::Created(_)))
+        .count();
+    let summary = format!(
+        "Created {} of {} work items, {} failed",
+        created,
+        items.len(),
+        items.len() - created
+    );
+    report(&items, &outcomes, args.parent_id, summary, "created")
+}
+
+/// Result of each line as CSV, after the summary
+fn report(
+    items: &[OutlineItem],
+    outcomes: &[Outcome],
+    parent_id: Option<u32>,
+    summary: String,
+    success: &str,
+) -> Result<CallToolResult, McpError> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err =
+        |e: csv::Error| McpError::internal_error(format!("Failed to write the CSV: {}", e), None);
+    wtr.write_record([
+        "line",
+        "status",
+        "id",
+        "parent_id",
+        "type",
+        "title",
+        "error",
+    ])
+    .map_err(write_err)?;
+    let id_of = |index: usize| match outcomes[index] {
+        Outcome::Created(id) => id.to_string(),
+        _ => String::new(),
+    };
+    for (index, (item, outcome)) in items.iter().zip(outcomes).enumerate() {
+        let (status, error) = match outcome {
+            Outcome::Pending | Outcome::Created(_) => (success, ""),
+            Outcome::Failed(error) if item.errors.is_empty() => ("failed", error.as_str()),
+            Outcome::Failed(error) => ("invalid", error.as_str()),
+        };
+        let parent = match item.parent {
+            Some(parent) => id_of(parent),
+            None => parent_id.map(|id| id.to_string()).unwrap_or_default(),
+        };
+        wtr.write_record([
+            item.line.to_string().as_str(),
+            status,
+            id_of(index).as_str(),
+            parent.as_str(),
+            item.work_item_type.as_str(),
+            item.title.as_str(),
+            error,
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{}\n{}",
+        summary, csv
+    ))]))
+}
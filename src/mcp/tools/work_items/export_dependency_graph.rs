@@ -0,0 +1,168 @@
+use crate::azure::{api::AzureBoardsApi, work_items};
+use crate::mcp::tools::support::{
+    GraphEdge, GraphNode, WorkItemGraph, deserialize_non_empty_string, graph_edge,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::BTreeSet;
+
+/// Work items a graph can have
+const MAX_NODES: usize = 200;
+
+/// Format of a dependency graph
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Mermaid flowchart, rendered by the wikis and Markdown viewers
+    #[default]
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+/// Links a dependency graph walks
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphLinks {
+    /// Parent/child links, walked down to the children
+    Hierarchy,
+    /// Predecessor/successor links, walked both ways
+    Dependency,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ExportDependencyGraphArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// IDs of the work items the graph is walked from
+    pub ids: Vec<u32>,
+    /// Links walked (optional, default both: ["hierarchy", "dependency"])
+    #[serde(default)]
+    pub links: Vec<GraphLinks>,
+    /// Links walked from the root work items (optional, default 3)
+    #[serde(default)]
+    pub depth: Option<u32>,
+    /// Format of the graph (optional): `mermaid` (default) or `dot`
+    #[serde(default)]
+    pub format: GraphFormat,
+}
+
+#[mcp_tool(
+    name = "azdo_export_dependency_graph",
+    description = "Walk the predecessor/successor and parent/child links from root work items and return the graph as Mermaid or Graphviz DOT, the work items colored by state",
+    read_only = true
+)]
+pub async fn export_dependency_graph(
+    client: &dyn AzureBoardsApi,
+    args: ExportDependencyGraphArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_export_dependency_graph(ids={:?}, links={:?}, depth={:?}, format={:?})",
+        args.ids,
+        args.links,
+        args.depth,
+        args.format
+    );
+    if args.ids.is_empty() {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: "Give the IDs of the work items to walk the graph from".into(),
+            data: None,
+        });
+    }
+    let links = if args.links.is_empty() {
+        vec![GraphLinks::Hierarchy, GraphLinks::Dependency]
+    } else {
+        args.links
+    };
+    let depth = args.depth.unwrap_or(3);
+
+    let mut graph = WorkItemGraph::default();
+    let mut visited: BTreeSet<u32> = args.ids.iter().copied().collect();
+    let mut frontier: Vec<u32> = visited.iter().copied().collect();
+    let mut truncated = false;
+    for level in 0..=depth {
+        if frontier.is_empty() {
+            break;
+        }
+        let fetched = work_items::get_work_items_with_relations(
+            client,
+            &args.organization,
+            &args.project,
+            &frontier,
+        )
+        .await
+        .map_err(McpError::from)?;
+        let mut next = Vec::new();
+        for work_item in &fetched {
+            let text = |field: &str| {
+                work_item
+                    .fields
+                    .get(field)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            graph.nodes.insert(
+                work_item.id,
+                GraphNode {
+                    title: text("System.Title"),
+                    work_item_type: text("System.WorkItemType"),
+                    state: text("System.State"),
+                },
+            );
+            for relation in work_item.relations.iter().flatten() {
+                let Some(edge) = graph_edge(work_item.id, relation) else {
+                    continue;
+                };
+                let followed = match edge {
+                    GraphEdge::Child(parent, child) if links.contains(&GraphLinks::Hierarchy) => {
+                        (parent == work_item.id).then_some(child)
+                    }
+                    GraphEdge::Successor(from, to) if links.contains(&GraphLinks::Dependency) => {
+                        Some(if from == work_item.id { to } else { from })
+                    }
+                    _ => continue,
+                };
+                graph.edges.insert(edge);
+                let Some(related) = followed else {
+                    continue;
+                };
+                if level == depth || visited.contains(&related) {
+                    continue;
+                }
+                if visited.len() >= MAX_NODES {
+                    truncated = true;
+                    continue;
+                }
+                visited.insert(related);
+                next.push(related);
+            }
+        }
+        frontier = next;
+    }
+
+    // The truncation is told in a comment, keeping the graph valid to paste
+    let (graph, comment) = match args.format {
+        GraphFormat::Mermaid => (graph.to_mermaid(), "%%"),
+        GraphFormat::Dot => (graph.to_dot(), "//"),
+    };
+    let text = if truncated {
+        format!(
+            "{} Graph truncated to {} work items, lower the depth\n{}",
+            comment, MAX_NODES, graph
+        )
+    } else {
+        graph
+    };
+    Ok(CallToolResult::success(vec![Content::text(text)]))
+}
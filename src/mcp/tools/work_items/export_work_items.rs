@@ -0,0 +1,176 @@
+use crate::azure::{api::AzureBoardsApi, work_items};
+use crate::mcp::tools::support::{
+    deserialize_non_empty_string, project_work_item_fields, reference_names,
+    simplify_work_item_json_with, work_items_to_csv,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+
+/// Format of an export
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// CSV with a header row
+    #[default]
+    Csv,
+    /// Newline-delimited JSON, a work item per line
+    Ndjson,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ExportWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// WIQL query of the work items (either this, query_id or the filters)
+    #[serde(default)]
+    pub query: Option<String>,
+    /// ID of the saved query of the work items (either this, query or the filters)
+    #[serde(default)]
+    pub query_id: Option<String>,
+    /// Area path the work items are under (filter)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Iteration path the work items are under (filter)
+    #[serde(default)]
+    pub iteration_path: Option<String>,
+    /// Work item types to include (filter, e.g. ["Bug", "User Story"])
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// States to include (filter, e.g. ["Active", "Resolved"])
+    #[serde(default)]
+    pub states: Vec<String>,
+    /// Fields to export (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being exported
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Keep the HTML of the rich-text fields (optional, converted to text by default)
+    #[serde(default)]
+    pub raw_html: bool,
+    /// Format of the export (optional): `csv` (default) or `ndjson`
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[mcp_tool(
+    name = "azdo_export_work_items",
+    description = "Export all the work items of a WIQL query, saved query or filters as CSV or NDJSON, never truncated, for the client to save to a file rather than for the model to read",
+    read_only = true
+)]
+pub async fn export_work_items(
+    client: &dyn AzureBoardsApi,
+    args: ExportWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_export_work_items(project={}, query={:?}, query_id={:?}, format={:?})",
+        args.project,
+        args.query,
+        args.query_id,
+        args.format
+    );
+    let has_filters = args.area_path.is_some()
+        || args.iteration_path.is_some()
+        || !args.work_item_types.is_empty()
+        || !args.states.is_empty();
+    let items = match (&args.query, &args.query_id, has_filters) {
+        (None, Some(query_id), false) => {
+            work_items::run_saved_query_all(
+                client,
+                &args.organization,
+                &args.project,
+                query_id,
+                &reference_names(&args.fields),
+            )
+            .await
+        }
+        (Some(_), None, false) | (None, None, _) => {
+            let query = args.query.clone().unwrap_or_else(|| filters_wiql(&args));
+            let ids = work_items::query_all_work_item_ids(
+                client,
+                &args.organization,
+                &args.project,
+                &query,
+            )
+            .await
+            .map_err(McpError::from)?;
+            work_items::get_all_work_items(
+                client,
+                &args.organization,
+                &args.project,
+                &ids,
+                &reference_names(&args.fields),
+            )
+            .await
+        }
+        _ => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "Give either query, query_id or filters".into(),
+                data: None,
+            });
+        }
+    }
+    .map_err(McpError::from)?;
+
+    let mut json_value = serde_json::to_value(&items).unwrap();
+    simplify_work_item_json_with(&mut json_value, args.raw_html);
+    let columns = project_work_item_fields(&mut json_value, &args.fields);
+    let export = match args.format {
+        ExportFormat::Csv => work_items_to_csv(&json_value, &columns).map_err(|e| {
+            McpError::internal_error(format!("Failed to convert to CSV: {}", e), None)
+        })?,
+        ExportFormat::Ndjson => match &json_value {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| format!("{}\n", item))
+                .collect::<String>(),
+            _ => String::new(),
+        },
+    };
+    Ok(CallToolResult::success(vec![Content::text(export)]))
+}
+
+/// WIQL query of the work items of the project matching the filters
+fn filters_wiql(args: &ExportWorkItemsArgs) -> String {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let list = |values: &[String]| {
+        values
+            .iter()
+            .map(|value| quote(value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut conditions = vec!["[System.TeamProject] = @project".to_string()];
+    if let Some(area_path) = &args.area_path {
+        conditions.push(format!("[System.AreaPath] UNDER {}", quote(area_path)));
+    }
+    if let Some(iteration_path) = &args.iteration_path {
+        conditions.push(format!(
+            "[System.IterationPath] UNDER {}",
+            quote(iteration_path)
+        ));
+    }
+    if !args.work_item_types.is_empty() {
+        conditions.push(format!(
+            "[System.WorkItemType] IN ({})",
+            list(&args.work_item_types)
+        ));
+    }
+    if !args.states.is_empty() {
+        conditions.push(format!("[System.State] IN ({})", list(&args.states)));
+    }
+    format!(
+        "SELECT [System.Id] FROM WorkItems WHERE {} ORDER BY [System.Id]",
+        conditions.join(" AND ")
+    )
+}
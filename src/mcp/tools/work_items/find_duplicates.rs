@@ -0,0 +1,236 @@
+use crate::azure::{api::AzureBoardsApi, work_items};
+use crate::mcp::tools::support::{deserialize_non_empty_string, html_to_text, text_similarity};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Recently changed work items compared, the most recent first
+const MAX_CANDIDATES: usize = 1000;
+
+/// Weight of the title in the score, the description making up the rest when both
+/// work items have one
+const TITLE_WEIGHT: f64 = 0.7;
+
+/// Fields of the compared work items
+const FIELDS: [&str; 5] = [
+    "System.Title",
+    "System.Description",
+    "System.WorkItemType",
+    "System.State",
+    "System.AreaPath",
+];
+
+#[derive(Deserialize, JsonSchema)]
+pub struct FindDuplicatesArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// ID of an existing work item to find the duplicates of (either this or title)
+    #[serde(default)]
+    pub id: Option<u32>,
+    /// Title of the work item about to be filed (either this or id)
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Description of the work item about to be filed (optional)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Area path the candidates are under, child paths included (optional, defaults to
+    /// the area path of the work item of id, else the whole project)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Work item types of the candidates (optional, defaults to the type of the work item
+    /// of id, else every type)
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// Only the candidates changed in the last days (optional, default 180)
+    #[serde(default)]
+    pub days: Option<u32>,
+    /// Minimum similarity score from 0 to 1 (optional, default 0.4)
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    /// Maximum number of duplicates returned (optional, default 10)
+    #[serde(default)]
+    pub max_results: Option<usize>,
+}
+
+#[mcp_tool(
+    name = "azdo_find_duplicates",
+    description = "Find the likely duplicates of a work item, or of a title and description about to be filed, among the recent work items of the same area, ranked by fuzzy similarity. Run it before filing a bug.",
+    read_only = true
+)]
+pub async fn find_duplicates(
+    client: &dyn AzureBoardsApi,
+    args: FindDuplicatesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_find_duplicates(project={}, id={:?}, title={:?}, area_path={:?})",
+        args.project,
+        args.id,
+        args.title,
+        args.area_path
+    );
+    let map_err = McpError::from;
+    let fields: Vec<String> = FIELDS.iter().map(|field| field.to_string()).collect();
+    let text = |fields: &HashMap<String, Value>, field: &str| {
+        let value = fields
+            .get(field)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if field == "System.Description" {
+            html_to_text(value)
+        } else {
+            value.to_string()
+        }
+    };
+
+    // What the candidates are compared with: the work item of id or the given texts
+    let (title, description, area_path, work_item_types) = match (args.id, &args.title) {
+        (Some(id), None) => {
+            let work_item = work_items::get_work_item(
+                client,
+                &args.organization,
+                &args.project,
+                id,
+                None,
+                &fields,
+            )
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| McpError {
+                code: ErrorCode(-32602),
+                message: format!("Work item {} not found", id).into(),
+                data: None,
+            })?;
+            let work_item_types = if args.work_item_types.is_empty() {
+                vec![text(&work_item.fields, "System.WorkItemType")]
+            } else {
+                args.work_item_types
+            };
+            (
+                text(&work_item.fields, "System.Title"),
+                text(&work_item.fields, "System.Description"),
+                args.area_path
+                    .or_else(|| Some(text(&work_item.fields, "System.AreaPath"))),
+                work_item_types,
+            )
+        }
+        (None, Some(title)) => (
+            title.clone(),
+            args.description.clone().unwrap_or_default(),
+            args.area_path,
+            args.work_item_types,
+        ),
+        _ => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "Give either id or title".into(),
+                data: None,
+            });
+        }
+    };
+
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let mut conditions = vec![
+        "[System.TeamProject] = @project".to_string(),
+        format!(
+            "[System.ChangedDate] >= @today - {}",
+            args.days.unwrap_or(180)
+        ),
+    ];
+    if let Some(id) = args.id {
+        conditions.push(format!("[System.Id] <> {}", id));
+    }
+    if let Some(area_path) = area_path.filter(|path| !path.is_empty()) {
+        conditions.push(format!("[System.AreaPath] UNDER {}", quote(&area_path)));
+    }
+    if !work_item_types.is_empty() {
+        conditions.push(format!(
+            "[System.WorkItemType] IN ({})",
+            work_item_types
+                .iter()
+                .map(|t| quote(t))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    let query = format!(
+        "SELECT [System.Id] FROM WorkItems WHERE {} ORDER BY [System.ChangedDate] DESC",
+        conditions.join(" AND ")
+    );
+    let mut ids =
+        work_items::query_work_item_ids(client, &args.organization, &args.project, &query)
+            .await
+            .map_err(map_err)?;
+    ids.truncate(MAX_CANDIDATES);
+    let candidates = work_items::get_work_items(
+        client,
+        &args.organization,
+        &args.project,
+        &ids,
+        None,
+        &fields,
+    )
+    .await
+    .map_err(map_err)?;
+
+    let min_score = args.min_score.unwrap_or(0.4);
+    let mut scored: Vec<_> = candidates
+        .iter()
+        .map(|candidate| {
+            let title_score = text_similarity(&title, &text(&candidate.fields, "System.Title"));
+            let candidate_description = text(&candidate.fields, "System.Description");
+            let score = if description.trim().is_empty() || candidate_description.trim().is_empty()
+            {
+                title_score
+            } else {
+                TITLE_WEIGHT * title_score
+                    + (1.0 - TITLE_WEIGHT) * text_similarity(&description, &candidate_description)
+            };
+            (score, candidate)
+        })
+        .filter(|(score, _)| *score >= min_score)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(args.max_results.unwrap_or(10));
+
+    if scored.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "No likely duplicates among {} work items",
+            candidates.len()
+        ))]));
+    }
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err = |e: csv::Error| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    };
+    wtr.write_record(["id", "score", "type", "state", "title"])
+        .map_err(write_err)?;
+    for (score, candidate) in &scored {
+        wtr.write_record([
+            candidate.id.to_string(),
+            format!("{:.2}", score),
+            text(&candidate.fields, "System.WorkItemType"),
+            text(&candidate.fields, "System.State"),
+            text(&candidate.fields, "System.Title"),
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{} likely duplicates among {} work items\n{}",
+        scored.len(),
+        candidates.len(),
+        csv
+    ))]))
+}
@@ -1,11 +1,12 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
+use crate::azure::{api::AzureBoardsApi, work_items};
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, work_items_to_csv,
+    OutputFormat, deserialize_non_empty_string, project_work_item_fields, reference_names,
+    simplify_work_item_json_with, work_items_result_with_columns,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -23,43 +24,99 @@ pub struct GetWorkItemArgs {
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
     pub include_latest_n_comments: Option<i32>,
+    /// Fields to return (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being returned
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Keep the HTML of the rich-text fields (e.g. `Description`) and of the comments
+    /// (optional, converted to text by default)
+    #[serde(default)]
+    pub raw_html: bool,
 }
 
-#[mcp_tool(name = "azdo_get_work_item", description = "Get work item by ID")]
+#[mcp_tool(
+    name = "azdo_get_work_item",
+    description = "Get work item by ID",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
+)]
 pub async fn get_work_item(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: GetWorkItemArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_get_work_item(id={})", args.id);
+    tracing::info!("Tool invoked: azdo_get_work_item(id={})", args.id);
     let work_item = work_items::get_work_item(
         client,
         &args.organization,
         &args.project,
         args.id as u32,
         args.include_latest_n_comments,
+        &reference_names(&args.fields),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
+
+    // Convert to JSON value, simplify, then return as CSV and structured content
+    let mut json_value = serde_json::to_value(&work_item).unwrap();
+    simplify_work_item_json_with(&mut json_value, args.raw_html);
+    let columns = project_work_item_fields(&mut json_value, &args.fields);
+    work_items_result_with_columns(
+        json_value,
+        "Work item not found",
+        None,
+        &columns,
+        OutputFormat::Csv,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use mockall::predicate::{always, eq};
+    use serde_json::json;
 
-    match work_item {
-        Some(work_item) => {
-            // Convert to JSON value, simplify, then convert to CSV
-            let mut json_value = serde_json::to_value(&work_item).unwrap();
-            simplify_work_item_json(&mut json_value);
-            let csv_output = work_items_to_csv(&json_value).map_err(|e| McpError {
-                code: ErrorCode(-32000),
-                message: format!("Failed to convert to CSV: {}", e).into(),
-                data: None,
-            })?;
+    #[tokio::test]
+    async fn test_get_work_item() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json()
+            .with(
+                always(),
+                eq(reqwest::Method::GET),
+                eq("https://dev.azure.com/contoso/Web/_apis/wit/workitems?ids=42&api-version=7.1"),
+                always(),
+                always(),
+            )
+            .times(1)
+            .returning(|_, _, _, _, _| {
+                let work_item = json!({
+                    "id": 42,
+                    "fields": {
+                        "System.Title": "Checkout fails",
+                        "System.WorkItemType": "Bug",
+                        "System.AssignedTo": {
+                            "displayName": "Jane Doe",
+                            "uniqueName": "jane@contoso.com",
+                        },
+                    },
+                });
+                Ok(json!({"count": 1, "value": [work_item]}).to_string())
+            });
 
-            Ok(CallToolResult::success(vec![Content::text(csv_output)]))
-        }
-        None => Ok(CallToolResult::success(vec![Content::text(
-            "Work item not found",
-        )])),
+        let args = GetWorkItemArgs {
+            organization: "contoso".to_string(),
+            project: "Web".to_string(),
+            id: 42,
+            include_latest_n_comments: None,
+            fields: Vec::new(),
+            raw_html: false,
+        };
+        let result = get_work_item(&api, args).await.unwrap();
+        let work_item = &result.structured_content.unwrap()["work_items"][0];
+        assert_eq!(work_item["Title"], "Checkout fails");
+        assert_eq!(work_item["AssignedTo"], "Jane Doe <jane@contoso.com>");
     }
 }
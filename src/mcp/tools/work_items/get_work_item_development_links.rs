@@ -0,0 +1,116 @@
+use crate::azure::{api::AzureBoardsApi, artifact_links, work_items};
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetWorkItemDevelopmentLinksArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID
+    pub id: i64,
+}
+
+#[mcp_tool(
+    name = "azdo_get_work_item_development_links",
+    description = "Get pull requests, branches, commits and builds linked to a work item",
+    read_only = true
+)]
+pub async fn get_work_item_development_links(
+    client: &dyn AzureBoardsApi,
+    args: GetWorkItemDevelopmentLinksArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_get_work_item_development_links(id={})",
+        args.id
+    );
+    let relations = work_items::get_work_item_relations(
+        client,
+        &args.organization,
+        &args.project,
+        args.id as u32,
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "type",
+        "id",
+        "title",
+        "status",
+        "repository",
+        "author",
+        "date",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for relation in relations.iter().filter(|r| r.rel == "ArtifactLink") {
+        let Some(artifact) = artifact_links::parse_artifact_url(&relation.url) else {
+            continue;
+        };
+        // Artifacts can be deleted or inaccessible, keep the raw URL rather than failing
+        let record = match artifact_links::resolve_artifact(
+            client,
+            &args.organization,
+            &args.project,
+            &artifact,
+        )
+        .await
+        {
+            Ok(resolved) => [
+                resolved.kind.to_string(),
+                resolved.id,
+                resolved.title,
+                resolved.status,
+                resolved.repository,
+                resolved.author,
+                resolved.date,
+            ],
+            Err(e) => {
+                tracing::warn!("Failed to resolve artifact {}: {}", relation.url, e);
+                [
+                    "unresolved".to_string(),
+                    relation.url.clone(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ]
+            }
+        };
+        wtr.write_record(&record).map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(CallToolResult::success(vec![Content::text(data)]))
+}
@@ -1,11 +1,12 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
+use crate::azure::{api::AzureBoardsApi, work_items};
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, work_items_to_csv,
+    OutputFormat, deserialize_non_empty_string, project_work_item_fields, reference_names,
+    simplify_work_item_json_with, work_items_result, work_items_result_with_columns,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -23,22 +24,34 @@ pub struct GetWorkItemsArgs {
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
     pub include_latest_n_comments: Option<i32>,
+    /// Fields to return (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being returned
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Keep the HTML of the rich-text fields (e.g. `Description`) and of the comments
+    /// (optional, converted to text by default)
+    #[serde(default)]
+    pub raw_html: bool,
+    /// Format of the text output (optional): `csv` (default) or `markdown` (table)
+    #[serde(default)]
+    pub output_format: OutputFormat,
 }
 
 #[mcp_tool(
     name = "azdo_get_work_items",
-    description = "Get multiple work items by IDs"
+    description = "Get multiple work items by IDs",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
 )]
 pub async fn get_work_items(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: GetWorkItemsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!("Tool invoked: azdo_get_work_items(ids={:?})", args.ids);
+    tracing::info!("Tool invoked: azdo_get_work_items(ids={:?})", args.ids);
 
     if args.ids.is_empty() {
-        return Ok(CallToolResult::success(vec![Content::text(
-            "No work items found",
-        )]));
+        return work_items_result(serde_json::json!([]), "No work items found", None);
     }
 
     let ids: Vec<u32> = args.ids.iter().map(|&id| id as u32).collect();
@@ -48,28 +61,20 @@ pub async fn get_work_items(
         &args.project,
         &ids,
         args.include_latest_n_comments,
+        &reference_names(&args.fields),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
-    if work_items.is_empty() {
-        return Ok(CallToolResult::success(vec![Content::text(
-            "No work items found",
-        )]));
-    }
-
-    // Convert to JSON value, simplify, then convert to CSV
+    // Convert to JSON value, simplify, then return as CSV and structured content
     let mut json_value = serde_json::to_value(&work_items).unwrap();
-    simplify_work_item_json(&mut json_value);
-    let csv_output = work_items_to_csv(&json_value).map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: format!("Failed to convert to CSV: {}", e).into(),
-        data: None,
-    })?;
-
-    Ok(CallToolResult::success(vec![Content::text(csv_output)]))
+    simplify_work_item_json_with(&mut json_value, args.raw_html);
+    let columns = project_work_item_fields(&mut json_value, &args.fields);
+    work_items_result_with_columns(
+        json_value,
+        "No work items found",
+        None,
+        &columns,
+        args.output_format,
+    )
 }
@@ -0,0 +1,236 @@
+use crate::azure::context;
+use crate::azure::{api::AzureBoardsApi, assignees, path_resolver, work_items};
+use crate::mcp::tools::support::{ImportRow, deserialize_non_empty_string, parse_import_rows};
+use futures::{StreamExt, stream};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Rows an import can have
+const MAX_ROWS: usize = 1000;
+
+/// Work items created at once
+const BATCH_SIZE: usize = 10;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ImportWorkItemsCsvArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// CSV content with a header row, e.g. the text of an attached file
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub csv: String,
+    /// Field of each column by header (optional, e.g. {"Summary": "Title", "Owner":
+    /// "AssignedTo", "Kind": "Type"}), by simplified or reference name, the other columns
+    /// being ignored. Without it, the headers are taken as the field names.
+    #[serde(default)]
+    pub mapping: BTreeMap<String, String>,
+    /// Type of the work items of the rows without a type column value (optional)
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+    /// Only validate the rows, without creating the work items (optional)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_import_work_items_csv",
+    description = "Create work items from the rows of a CSV, with columns mapped to fields, returning the result of each row. Nothing is created when a row is invalid.",
+    read_only = false
+)]
+pub async fn import_work_items_csv(
+    client: &dyn AzureBoardsApi,
+    args: ImportWorkItemsCsvArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_import_work_items_csv(project={}, dry_run={})",
+        args.project,
+        args.dry_run
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    let mut rows = parse_import_rows(&args.csv, &args.mapping, args.work_item_type.as_deref())
+        .map_err(invalid_params)?;
+    if rows.is_empty() {
+        return Err(invalid_params("The CSV has no rows".to_string()));
+    }
+    if rows.len() > MAX_ROWS {
+        return Err(invalid_params(format!(
+            "The CSV has {} rows, at most {} can be imported at once",
+            rows.len(),
+            MAX_ROWS
+        )));
+    }
+    let policy = context::current().write_policy.clone();
+    policy.check_bulk(rows.len()).map_err(McpError::from)?;
+
+    let (organization, project) = (&args.organization, &args.project);
+    for row in &mut rows {
+        if let Err(e) =
+            path_resolver::resolve_field_paths(client, organization, project, &mut row.fields).await
+        {
+            row.errors.push(e.to_string());
+        }
+        if let Err(e) =
+            assignees::resolve_field_assignee(client, organization, &mut row.fields).await
+        {
+            row.errors.push(e.to_string());
+        }
+        // Checked again on creation, but up front so that no row is created when one
+        // breaks the policy
+        let fields: HashMap<String, serde_json::Value> = row.fields.iter().cloned().collect();
+        if let Err(e) = policy.check_fields(project, &fields) {
+            row.errors.push(e.to_string());
+        }
+    }
+
+    // Like the import of the Boards web UI, the invalid rows stop the whole import
+    let invalid = rows.iter().filter(|row| !row.errors.is_empty()).count();
+    if invalid > 0 || args.dry_run {
+        let results = rows.iter().map(|row| {
+            let status = if row.errors.is_empty() {
+                "valid"
+            } else {
+                "invalid"
+            };
+            (row, status, None, row.errors.join("; "))
+        });
+        let summary = if invalid > 0 {
+            format!(
+                "No work items created, {} of {} rows are invalid",
+                invalid,
+                rows.len()
+            )
+        } else {
+            format!("All {} rows are valid, no work items created", rows.len())
+        };
+        return report(results, summary);
+    }
+
+    let total = rows.len() as u64;
+    let mut done = 0;
+    let requests: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<(&str, serde_json::Value)> = row
+                .fields
+                .iter()
+                .map(|(field, value)| (field.as_str(), value.clone()))
+                .collect();
+            async move {
+                work_items::create_work_item(
+                    client,
+                    organization,
+                    project,
+                    &row.work_item_type,
+                    &fields,
+                )
+                .await
+            }
+        })
+        .collect();
+    let created: Vec<_> = stream::iter(requests)
+        .buffered(BATCH_SIZE)
+        .inspect(|_| {
+            done += 1;
+            crate::progress::report(done, Some(total), || {
+                format!("Imported {} of {} rows", done, total)
+            });
+        })
+        .collect()
+        .await;
+
+    let failed = created.iter().filter(|result| result.is_err()).count();
+    let summary = format!(
+        "Created {} of {} work items, {} failed",
+        rows.len() - failed,
+        rows.len(),
+        failed
+    );
+    let results = rows.iter().zip(&created).map(|(row, result)| match result {
+        Ok(work_item) => (row, "created", Some(work_item.id), String::new()),
+        Err(e) => (row, "failed", None, e.to_string()),
+    });
+    report(results, summary)
+}
+
+/// Result of each row as CSV, after the summary
+fn report<'a>(
+    results: impl Iterator<Item = (&'a ImportRow, &'static str, Option<u32>, String)>,
+    summary: String,
+) -> Result<CallToolResult, McpError> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err =
+        |e: csv::Error| McpError::internal_error(format!("Failed to write the CSV: {}", e), None);
+    wtr.write_record(["line", "status", "id", "title", "error"])
+        .map_err(write_err)?;
+    for (row, status, id, error) in results {
+        wtr.write_record([
+            row.line.to_string().as_str(),
+            status,
+            id.map(|id| id.to_string()).unwrap_or_default().as_str(),
+            row.title(),
+            error.as_str(),
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{}\n{}",
+        summary, csv
+    ))]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use crate::azure::context::ClientContext;
+    use crate::azure::write_policy::WritePolicy;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_import_work_items_csv_against_policy() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        // Reported as invalid before any request
+        api.expect_send_json().never();
+        let context = ClientContext {
+            write_policy: Arc::new(WritePolicy {
+                allowed_area_paths: vec!["Web\\Payments".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let args: ImportWorkItemsCsvArgs = serde_json::from_value(serde_json::json!({
+            "organization": "contoso",
+            "project": "Web",
+            "csv": "Title\nCheckout fails\n",
+            "work_item_type": "Bug",
+            "dry_run": true,
+        }))
+        .unwrap();
+        let result = context::scope(Arc::new(context), import_work_items_csv(&api, args))
+            .await
+            .unwrap();
+        let text = result.content[0]
+            .as_text()
+            .map(|t| t.text.as_str())
+            .unwrap();
+        assert!(text.starts_with("No work items created, 1 of 1 rows are invalid"));
+        assert!(text.contains("only the ones under Web\\Payments"));
+    }
+}
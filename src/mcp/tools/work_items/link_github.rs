@@ -0,0 +1,80 @@
+use crate::azure::{api::AzureBoardsApi, artifact_links, work_items};
+use crate::compact_llm;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::json;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LinkGithubArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID
+    pub work_item_id: u32,
+    /// GitHub pull request or issue URL, e.g. "https://github.com/owner/repo/pull/12"
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub github_url: String,
+}
+
+#[mcp_tool(
+    name = "azdo_link_github",
+    description = "Link a GitHub pull request or issue to a work item (repository connected to Azure Boards) and return the AB#<id> token to put in its description",
+    read_only = false
+)]
+pub async fn link_github(
+    client: &dyn AzureBoardsApi,
+    args: LinkGithubArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_link_github(work_item_id={}, github_url={})",
+        args.work_item_id,
+        args.github_url
+    );
+
+    let Some(artifact) = artifact_links::parse_github_url(&args.github_url) else {
+        return Err(McpError {
+            code: ErrorCode(-32602),
+            message: format!(
+                "Invalid GitHub URL {}, expected https://github.com/<owner>/<repo>/pull/<number> or .../issues/<number>",
+                args.github_url
+            )
+            .into(),
+            data: None,
+        });
+    };
+    let url =
+        artifact_links::github_artifact_url(client, &args.organization, &args.project, &artifact)
+            .await
+            .map_err(McpError::from)?;
+    let linked = work_items::add_artifact_link(
+        client,
+        &args.organization,
+        &args.project,
+        args.work_item_id,
+        &url,
+        artifact.link_name(),
+    )
+    .await
+    .map_err(McpError::from)?;
+
+    let result = json!({
+        "work_item_id": args.work_item_id,
+        "github_url": args.github_url,
+        "artifact_url": url,
+        "already_linked": !linked,
+        // Mentioned in the pull request or issue, links it back from GitHub
+        "token": format!("AB#{}", args.work_item_id),
+    });
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&result).unwrap(),
+    )]))
+}
@@ -1,10 +1,10 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
+use crate::azure::{api::AzureBoardsApi, work_items};
 use crate::compact_llm;
-use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::tools::support::{deserialize_non_empty_string, link_type_reference};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, Content},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -25,42 +25,32 @@ pub struct LinkWorkItemsArgs {
     pub link_type: String,
 }
 
-#[mcp_tool(name = "azdo_link_work_items", description = "Link work items")]
+#[mcp_tool(
+    name = "azdo_link_work_items",
+    description = "Link work items",
+    read_only = false
+)]
 pub async fn link_work_items(
-    client: &AzureDevOpsClient,
+    client: &dyn AzureBoardsApi,
     args: LinkWorkItemsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_link_work_items(source_id={}, target_id={}, link_type={})",
         args.source_id,
         args.target_id,
         args.link_type
     );
 
-    // Map friendly names to Azure DevOps link type names
-    let link_type_ref = match args.link_type.to_lowercase().as_str() {
-        "parent" => "System.LinkTypes.Hierarchy-Forward",
-        "child" => "System.LinkTypes.Hierarchy-Reverse",
-        "related" => "System.LinkTypes.Related",
-        "duplicate" => "System.LinkTypes.Duplicate-Forward",
-        "dependency" => "System.LinkTypes.Dependency-Forward",
-        _ => &args.link_type, // Use as-is if not a known friendly name
-    };
-
     let result = work_items::link_work_items(
         client,
         &args.organization,
         &args.project,
         args.source_id,
         args.target_id,
-        link_type_ref,
+        link_type_reference(&args.link_type),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
     Ok(CallToolResult::success(vec![Content::text(
         compact_llm::to_compact_string(&result).unwrap(),
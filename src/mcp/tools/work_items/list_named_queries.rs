@@ -0,0 +1,45 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::context;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListNamedQueriesArgs {}
+
+#[mcp_tool(
+    name = "azdo_list_named_queries",
+    description = "List the named WIQL queries of the server configuration, with their parameters and defaults, to run with azdo_run_named_query",
+    read_only = true
+)]
+pub async fn list_named_queries(
+    _client: &dyn AzureBoardsApi,
+    _args: ListNamedQueriesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_list_named_queries");
+    let queries = context::current().named_queries.clone();
+    if queries.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No named queries configured",
+        )]));
+    }
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err =
+        |e: csv::Error| McpError::internal_error(format!("Failed to write the CSV: {}", e), None);
+    wtr.write_record(["name", "parameters", "wiql"])
+        .map_err(write_err)?;
+    for query in queries.iter() {
+        wtr.write_record([
+            query.name.as_str(),
+            query.parameter_list().as_str(),
+            query.wiql.as_str(),
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(csv)]))
+}
@@ -0,0 +1,48 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::compact_llm;
+use crate::journal;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListRecentChangesArgs {
+    /// Maximum number of changes returned, the most recent first (optional, default 20)
+    #[serde(default)]
+    pub max_results: Option<usize>,
+}
+
+#[mcp_tool(
+    name = "azdo_list_recent_changes",
+    description = "List the work item changes (creations, field updates, links, comments) made by the tools in this session, most recent first, with the previous field values and whether they were undone",
+    read_only = true
+)]
+pub async fn list_recent_changes(
+    _client: &dyn AzureBoardsApi,
+    args: ListRecentChangesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_list_recent_changes(max_results={:?})",
+        args.max_results
+    );
+    let changes = journal::current()
+        .map(|journal| {
+            journal
+                .lock()
+                .unwrap()
+                .recent(args.max_results.unwrap_or(20))
+        })
+        .unwrap_or_default();
+    if changes.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No work item changes in this session",
+        )]));
+    }
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&changes).unwrap(),
+    )]))
+}
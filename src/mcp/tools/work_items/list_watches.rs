@@ -0,0 +1,41 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::compact_llm;
+use crate::mcp::watches;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListWatchesArgs {}
+
+#[mcp_tool(
+    name = "azdo_list_watches",
+    description = "List the watches of the session started by azdo_watch",
+    read_only = true
+)]
+pub async fn list_watches(
+    _client: &dyn AzureBoardsApi,
+    _args: ListWatchesArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_list_watches");
+    let watches: Vec<serde_json::Value> = watches::list()?
+        .into_iter()
+        .map(|(id, spec)| {
+            let mut watch = serde_json::to_value(spec).unwrap();
+            watch["id"] = id.into();
+            watch
+        })
+        .collect();
+    if watches.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No watches in this session",
+        )]));
+    }
+    Ok(CallToolResult::success(vec![Content::text(
+        compact_llm::to_compact_string(&watches).unwrap(),
+    )]))
+}
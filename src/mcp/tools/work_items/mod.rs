@@ -1,19 +1,59 @@
 // Work Items module
 pub mod add_comment;
+pub mod batch_work_items;
 pub mod create_work_item;
+pub mod create_work_items_from_outline;
+pub mod export_dependency_graph;
+pub mod export_work_items;
+pub mod find_duplicates;
 pub mod get_work_item;
+pub mod get_work_item_development_links;
 pub mod get_work_items;
+pub mod import_work_items_csv;
+pub mod link_github;
 pub mod link_work_items;
+pub mod list_named_queries;
+pub mod list_recent_changes;
+pub mod list_watches;
 pub mod query_work_items;
 pub mod query_work_items_by_wiql;
+pub mod run_named_query;
+pub mod search_local_work_items;
+pub mod search_work_items;
+pub mod summarize_query_results;
+pub mod undo_last_change;
+pub mod unwatch_work_items;
 pub mod update_work_item;
+pub mod watch_work_items;
 
 // Re-export the public items
 pub use add_comment::{AddCommentArgs, add_comment};
+pub use batch_work_items::{BatchOperationArgs, BatchWorkItemsArgs, batch_work_items};
 pub use create_work_item::{CreateWorkItemArgs, create_work_item};
+pub use create_work_items_from_outline::{
+    CreateWorkItemsFromOutlineArgs, create_work_items_from_outline,
+};
+pub use export_dependency_graph::{ExportDependencyGraphArgs, export_dependency_graph};
+pub use export_work_items::{ExportWorkItemsArgs, export_work_items};
+pub use find_duplicates::{FindDuplicatesArgs, find_duplicates};
 pub use get_work_item::{GetWorkItemArgs, get_work_item};
+pub use get_work_item_development_links::{
+    GetWorkItemDevelopmentLinksArgs, get_work_item_development_links,
+};
 pub use get_work_items::{GetWorkItemsArgs, get_work_items};
+pub use import_work_items_csv::{ImportWorkItemsCsvArgs, import_work_items_csv};
+pub use link_github::{LinkGithubArgs, link_github};
 pub use link_work_items::{LinkWorkItemsArgs, link_work_items};
+pub use list_named_queries::{ListNamedQueriesArgs, list_named_queries};
+pub use list_recent_changes::{ListRecentChangesArgs, list_recent_changes};
+pub use list_watches::{ListWatchesArgs, list_watches};
 pub use query_work_items::{QueryWorkItemsArgs, query_work_items};
 pub use query_work_items_by_wiql::{QueryWorkItemsArgsWiql, query_work_items_by_wiql};
+pub use run_named_query::{RunNamedQueryArgs, run_named_query};
+pub use search_local_work_items::{SearchLocalWorkItemsArgs, search_local_work_items};
+pub use search_work_items::{SearchWorkItemsArgs, search_work_items};
+pub use summarize_query_results::{SummarizeQueryResultsArgs, summarize_query_results};
+pub use undo_last_change::{UndoLastChangeArgs, undo_last_change};
+pub use unwatch_work_items::{UnwatchWorkItemsArgs, unwatch_work_items};
 pub use update_work_item::{UpdateWorkItemArgs, update_work_item};
+pub use watch_work_items::{WatchWorkItemsArgs, watch_work_items};
@@ -1,11 +1,12 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
+use crate::azure::{api::AzureBoardsApi, path_resolver, work_items};
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, work_items_to_csv,
+    OutputFormat, PagingArgs, deserialize_non_empty_string, project_work_item_fields,
+    reference_names, simplify_work_item_json_with, work_items_result_with_columns,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -110,17 +111,35 @@ pub struct QueryWorkItemsArgs {
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
     pub include_latest_n_comments: Option<i32>,
+    /// Fields to return (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being returned
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Keep the HTML of the rich-text fields (e.g. `Description`) and of the comments
+    /// (optional, converted to text by default)
+    #[serde(default)]
+    pub raw_html: bool,
+    /// Format of the text output (optional): `csv` (default) or `markdown` (table)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
 }
 
 #[mcp_tool(
     name = "azdo_query_work_items",
-    description = "Query work items by filters"
+    description = "Query work items by filters",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
 )]
 pub async fn query_work_items(
-    client: &AzureDevOpsClient,
-    args: QueryWorkItemsArgs,
+    client: &dyn AzureBoardsApi,
+    mut args: QueryWorkItemsArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_query_work_items(area_path={:?}, iteration_path={:?}, include_board_column={:?}, exclude_state={:?})",
         args.area_path,
         args.iteration_path,
@@ -131,6 +150,16 @@ pub async fn query_work_items(
     // Build WIQL query conditions
     let mut conditions = Vec::new();
 
+    path_resolver::resolve_area_and_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &mut args.area_path,
+        &mut args.iteration_path,
+    )
+    .await
+    .map_err(McpError::from)?;
+
     // Area path filter
     if let Some(area_path) = &args.area_path {
         conditions.push(format!(
@@ -325,37 +354,34 @@ pub async fn query_work_items(
         )
     };
 
-    log::debug!("Executing WIQL query: {}", query);
+    tracing::debug!("Executing WIQL query: {}", query);
 
-    // Execute the query to get work items
-    let work_items = work_items::query_work_items(
+    // Execute the query, then get the work items of the page
+    let ids = work_items::query_work_item_ids(client, &args.organization, &args.project, &query)
+        .await
+        .map_err(McpError::from)?;
+
+    let page = args.paging.page(ids)?;
+    let work_items = work_items::get_work_items(
         client,
         &args.organization,
         &args.project,
-        &query,
+        &page.items,
         args.include_latest_n_comments,
+        &reference_names(&args.fields),
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
-
-    if work_items.is_empty() {
-        return Ok(CallToolResult::success(vec![Content::text(
-            "No work items found",
-        )]));
-    }
+    .map_err(McpError::from)?;
 
-    // Convert to JSON value, simplify, then convert to CSV
+    // Convert to JSON value, simplify, then return as CSV and structured content
     let mut json_value = serde_json::to_value(&work_items).unwrap();
-    simplify_work_item_json(&mut json_value);
-    let csv_output = work_items_to_csv(&json_value).map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: format!("Failed to convert to CSV: {}", e).into(),
-        data: None,
-    })?;
-
-    Ok(CallToolResult::success(vec![Content::text(csv_output)]))
+    simplify_work_item_json_with(&mut json_value, args.raw_html);
+    let columns = project_work_item_fields(&mut json_value, &args.fields);
+    work_items_result_with_columns(
+        json_value,
+        "No work items found",
+        page.next_cursor,
+        &columns,
+        args.output_format,
+    )
 }
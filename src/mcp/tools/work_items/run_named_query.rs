@@ -0,0 +1,163 @@
+use super::{QueryWorkItemsArgsWiql, query_work_items_by_wiql};
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::context;
+use crate::mcp::tools::support::{OutputFormat, PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct RunNamedQueryArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Name of the query, as listed by azdo_list_named_queries
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// Values of the parameters of the query by name (e.g. {"iteration": "Fabrikam\\Sprint
+    /// 12"}), the ones with a default being optional
+    #[serde(default)]
+    pub parameters: BTreeMap<String, Value>,
+    /// Fields to return (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being returned
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Keep the HTML of the rich-text fields (e.g. `Description`) (optional, converted to
+    /// text by default)
+    #[serde(default)]
+    pub raw_html: bool,
+    /// Format of the text output (optional): `csv` (default) or `markdown` (table)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_run_named_query",
+    description = "Run a named WIQL query of the server configuration with the given parameters, returning the work items like azdo_query_work_items_by_wiql",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
+)]
+pub async fn run_named_query(
+    client: &dyn AzureBoardsApi,
+    args: RunNamedQueryArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_run_named_query(name={}, parameters={:?})",
+        args.name,
+        args.parameters
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    let queries = context::current().named_queries.clone();
+    let Some(query) = queries.get(&args.name) else {
+        let known: Vec<&str> = queries.iter().map(|query| query.name.as_str()).collect();
+        return Err(invalid_params(if known.is_empty() {
+            format!("Unknown query {}, no named queries configured", args.name)
+        } else {
+            format!(
+                "Unknown query {}, expected one of: {}",
+                args.name,
+                known.join(", ")
+            )
+        }));
+    };
+    let values: BTreeMap<String, String> = args
+        .parameters
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, value)| match value {
+            Value::String(value) => (name, value),
+            value => (name, value.to_string()),
+        })
+        .collect();
+    let wiql = query.render(&values).map_err(invalid_params)?;
+
+    query_work_items_by_wiql(
+        client,
+        QueryWorkItemsArgsWiql {
+            organization: args.organization,
+            project: args.project,
+            query: wiql,
+            include_latest_n_comments: None,
+            fields: args.fields,
+            raw_html: args.raw_html,
+            output_format: args.output_format,
+            paging: args.paging,
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::api::MockAzureBoardsApi;
+    use crate::azure::cloud::Cloud;
+    use crate::azure::context::{self, ClientContext};
+    use crate::azure::named_queries::{NamedQueries, NamedQuery};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_run_named_query() {
+        let mut api = MockAzureBoardsApi::new();
+        api.expect_cloud().return_const(Cloud::default());
+        api.expect_send_json()
+            .withf(|_, method, url, body, _| {
+                *method == reqwest::Method::POST
+                    && url.contains("/contoso/Web/_apis/wit/wiql?")
+                    && body
+                        .as_deref()
+                        .is_some_and(|body| body.contains("[System.State] = 'Active'"))
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(json!({"workItems": []}).to_string()));
+        let query = NamedQuery::new(
+            "by_state",
+            "SELECT [System.Id] FROM WorkItems WHERE [System.State] = '{state}'",
+        )
+        .unwrap();
+        let context = ClientContext {
+            named_queries: Arc::new(NamedQueries::new([query])),
+            ..Default::default()
+        };
+
+        let args = |name: &str| -> RunNamedQueryArgs {
+            serde_json::from_value(json!({
+                "organization": "contoso",
+                "project": "Web",
+                "name": name,
+                "parameters": {"state": "Active"},
+            }))
+            .unwrap()
+        };
+        let context = Arc::new(context);
+        let result = context::scope(context.clone(), run_named_query(&api, args("by_state")))
+            .await
+            .unwrap();
+        assert_ne!(result.is_error, Some(true));
+        let error = context::scope(context, run_named_query(&api, args("unknown")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode(-32602));
+        // Outside of a context, no query is configured
+        let error = run_named_query(&api, args("by_state")).await.unwrap_err();
+        assert!(error.message.contains("no named queries configured"));
+    }
+}
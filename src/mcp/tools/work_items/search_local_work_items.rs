@@ -0,0 +1,154 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::context;
+use crate::mcp::tools::support::{
+    OutputFormat, PagingArgs, deserialize_non_empty_string, project_work_item_fields,
+    simplify_work_item_json, work_items_result_with_columns,
+};
+use crate::store::{self, StoreFilter};
+use chrono::{DateTime, Utc};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Seconds before the store of a project is synced again
+const SYNC_INTERVAL_SECS: i64 = 60;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SearchLocalWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Text in the title, description or tags (optional, case insensitive)
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Work item types to include (e.g., ["Bug", "User Story"])
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// States to include (e.g., ["Active", "New"])
+    #[serde(default)]
+    pub states: Vec<String>,
+    /// Assignee name or email, or part of it (optional)
+    #[serde(default)]
+    pub assigned_to: Option<String>,
+    /// Area path the work items are in or under (optional)
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Only the work items changed since this date (optional, e.g. "2025-01-10T00:00:00Z")
+    #[serde(default)]
+    pub changed_since: Option<String>,
+    /// Fetch the changes from Azure DevOps first (optional, default true), the local
+    /// work items being searched when it fails
+    #[serde(default)]
+    pub sync: Option<bool>,
+    /// Fields to return (optional, all by default), by simplified name (e.g. `Title`,
+    /// `AssignedTo`, `Column`) or reference name (e.g. `Custom.Severity`), the ID always
+    /// being returned
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Format of the text output (optional): `csv` (default) or `markdown` (table)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+#[mcp_tool(
+    name = "azdo_search_local_work_items",
+    description = "Search the work items mirrored in the local store, fast and available when Azure DevOps is slow or throttling",
+    output = "crate::mcp::tools::support::WorkItemsOutput",
+    read_only = true
+)]
+pub async fn search_local_work_items(
+    client: &dyn AzureBoardsApi,
+    args: SearchLocalWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_search_local_work_items(project={}, text={:?})",
+        args.project,
+        args.text
+    );
+    let Some(work_item_store) = context::current().work_item_store.clone() else {
+        return Err(McpError {
+            code: ErrorCode(-32600),
+            message: "The local work item store is disabled, start the server with \
+                      --work-item-store"
+                .into(),
+            data: None,
+        });
+    };
+
+    // Sync the project, unless done recently, falling back on the local work items
+    let (_, synced_at) = work_item_store
+        .sync_state(&args.organization, &args.project)
+        .await;
+    let stale = synced_at
+        .is_none_or(|synced_at| (Utc::now() - synced_at).num_seconds() >= SYNC_INTERVAL_SECS);
+    let mut note = None;
+    if args.sync.unwrap_or(true)
+        && stale
+        && let Err(e) = store::sync_project(client, &args.organization, &args.project).await
+    {
+        tracing::warn!("Unable to sync the work item store: {}", e);
+        note = Some(match synced_at {
+            Some(synced_at) => format!(
+                "Azure DevOps unavailable ({}), the local work items were last synced at {}",
+                e,
+                synced_at.to_rfc3339()
+            ),
+            None => format!(
+                "Azure DevOps unavailable ({}), the local work items were never synced",
+                e
+            ),
+        });
+    }
+
+    let changed_since = args
+        .changed_since
+        .map(|date| {
+            DateTime::parse_from_rfc3339(&date)
+                .map(|date| date.to_utc())
+                .map_err(|e| McpError {
+                    code: ErrorCode(-32602),
+                    message: format!("Invalid changed_since {}: {}", date, e).into(),
+                    data: None,
+                })
+        })
+        .transpose()?;
+    let filter = StoreFilter {
+        text: args.text,
+        work_item_types: args.work_item_types,
+        states: args.states,
+        assigned_to: args.assigned_to,
+        area_path: args.area_path,
+        changed_since,
+    };
+    let found = work_item_store
+        .search(&args.organization, &args.project, filter)
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+    let page = args.paging.page(found)?;
+
+    // Convert to JSON value, simplify, then return as CSV and structured content
+    let mut json_value = serde_json::to_value(&page.items).unwrap();
+    simplify_work_item_json(&mut json_value);
+    let columns = project_work_item_fields(&mut json_value, &args.fields);
+    let mut result = work_items_result_with_columns(
+        json_value,
+        "No work items found in the local store",
+        page.next_cursor,
+        &columns,
+        args.output_format,
+    )?;
+    if let Some(note) = note {
+        result.content.push(Content::text(note));
+    }
+    Ok(result)
+}
@@ -0,0 +1,149 @@
+use crate::azure::{api::AzureBoardsApi, search};
+use crate::mcp::tools::support::{PagingArgs, deserialize_non_empty_string};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SearchWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// Free text to search for, supports field filters (e.g. "login a:john")
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub search_text: String,
+    /// AzDO project name (optional, searches all projects when omitted)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Work item types to include (e.g., ["Bug", "User Story"])
+    #[serde(default)]
+    pub work_item_types: Vec<String>,
+    /// States to include (e.g., ["Active", "New"])
+    #[serde(default)]
+    pub states: Vec<String>,
+    /// Assignees to include (display name or unique name)
+    #[serde(default)]
+    pub assigned_to: Vec<String>,
+    /// Area paths to include
+    #[serde(default)]
+    pub area_paths: Vec<String>,
+    /// Paging of the results (`max_results` and `cursor`)
+    #[serde(flatten)]
+    pub paging: PagingArgs,
+}
+
+/// Collapse a search highlight into a single line, marking hits with ** **
+fn format_highlight(highlight: &str) -> String {
+    highlight
+        .replace("<highlighthit>", "**")
+        .replace("</highlighthit>", "**")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[mcp_tool(
+    name = "azdo_search_work_items",
+    description = "Relevance ranked full-text work item search with highlighted snippets",
+    read_only = true
+)]
+pub async fn search_work_items(
+    client: &dyn AzureBoardsApi,
+    args: SearchWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_search_work_items(search_text={})",
+        args.search_text
+    );
+    let filters = search::WorkItemSearchFilters {
+        projects: args.project.into_iter().collect(),
+        work_item_types: args.work_item_types,
+        states: args.states,
+        assigned_to: args.assigned_to,
+        area_paths: args.area_paths,
+    };
+    let response = search::search_work_items(
+        client,
+        &args.organization,
+        &args.search_text,
+        &filters,
+        args.paging.max_results() as u32,
+        args.paging.cursor()?.offset as u32,
+    )
+    .await
+    .map_err(McpError::from)?;
+    let next_cursor = args
+        .paging
+        .next_offset_cursor(response.results.len(), response.count as usize)?;
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "id",
+        "project",
+        "type",
+        "state",
+        "title",
+        "assigned_to",
+        "snippet",
+    ])
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to write CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    for result in &response.results {
+        let field = |name: &str| -> String {
+            match result.fields.get(name) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(v) => v.to_string(),
+            }
+        };
+        let snippet = result
+            .hits
+            .iter()
+            .flat_map(|h| h.highlights.iter())
+            .map(|h| format_highlight(h))
+            .collect::<Vec<_>>()
+            .join(" ... ");
+
+        wtr.write_record([
+            field("system.id"),
+            field("system.teamproject"),
+            field("system.workitemtype"),
+            field("system.state"),
+            field("system.title"),
+            field("system.assignedto"),
+            snippet,
+        ])
+        .map_err(|e| McpError {
+            code: ErrorCode(-32000),
+            message: format!("Failed to write CSV: {}", e).into(),
+            data: None,
+        })?;
+    }
+
+    let csv_bytes = wtr.into_inner().map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to get CSV bytes: {}", e).into(),
+        data: None,
+    })?;
+
+    let data = String::from_utf8(csv_bytes).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert CSV to string: {}", e).into(),
+        data: None,
+    })?;
+
+    let mut output = format!("Total: {}\n{}", response.count, data);
+    if let Some(cursor) = next_cursor {
+        output.push_str(&format!("next_cursor={}", cursor));
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(output)]))
+}
@@ -0,0 +1,118 @@
+use crate::azure::{api::AzureBoardsApi, work_items};
+use crate::mcp::sampling;
+use crate::mcp::tools::support::{
+    deserialize_non_empty_string, simplify_work_item_json, work_items_to_csv,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Characters of work item data sent to the client's model, the rest is left out
+const MAX_PROMPT_CHARS: usize = 200_000;
+
+const SYSTEM_PROMPT: &str = "You summarize Azure DevOps work items for a busy reader. \
+Write a short digest: the main themes, the state of the work, notable risks, blockers and \
+outliers, citing work item IDs as #ID. Do not list every work item.";
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SummarizeQueryResultsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// WIQL query string (either this or query_id)
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Saved query ID (either this or query)
+    #[serde(default)]
+    pub query_id: Option<String>,
+    /// What the summary should focus on (optional, e.g. "risks and blockers")
+    #[serde(default)]
+    pub focus: Option<String>,
+    /// Maximum length of the summary in tokens (optional, default 1000)
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+#[mcp_tool(
+    name = "azdo_summarize_query_results",
+    description = "Summarize the work items of a WIQL or saved query with the client's model (MCP sampling); returns only the digest and the IDs",
+    read_only = true
+)]
+pub async fn summarize_query_results(
+    client: &dyn AzureBoardsApi,
+    args: SummarizeQueryResultsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_summarize_query_results(query={:?}, query_id={:?})",
+        args.query,
+        args.query_id
+    );
+
+    let items = match (&args.query, &args.query_id) {
+        (Some(query), None) => {
+            work_items::query_work_items(client, &args.organization, &args.project, query, None)
+                .await
+        }
+        (None, Some(query_id)) => {
+            work_items::run_saved_query(client, &args.organization, &args.project, query_id, None)
+                .await
+        }
+        _ => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "Exactly one of query and query_id is required".into(),
+                data: None,
+            });
+        }
+    }
+    .map_err(McpError::from)?;
+
+    if items.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No work items found",
+        )]));
+    }
+    let ids = items
+        .iter()
+        .map(|item| item.id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut json_value = serde_json::to_value(&items).unwrap();
+    simplify_work_item_json(&mut json_value);
+    let mut csv_output = work_items_to_csv(&json_value, &[]).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+    if csv_output.len() > MAX_PROMPT_CHARS {
+        let end = csv_output[..MAX_PROMPT_CHARS].rfind('\n').unwrap_or(0);
+        csv_output.truncate(end);
+        csv_output.push_str("\n(the remaining work items were left out)");
+    }
+
+    let prompt = format!(
+        "Summarize these {} work items{}.\n\n{}\n",
+        items.len(),
+        args.focus
+            .as_deref()
+            .map(|focus| format!(", focusing on {}", focus))
+            .unwrap_or_default(),
+        csv_output
+    );
+    let summary =
+        sampling::create_message(SYSTEM_PROMPT, prompt, args.max_tokens.unwrap_or(1000)).await?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "{}\n\nids={}",
+        summary.trim(),
+        ids
+    ))]))
+}
@@ -0,0 +1,173 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::work_items;
+use crate::journal::{self, Change, JournalEntry};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UndoLastChangeArgs {
+    /// ID of a change listed by azdo_list_recent_changes to undo alone (optional, by
+    /// default all the changes of the last tool invocation having changed work items)
+    #[serde(default)]
+    pub change_id: Option<u64>,
+    /// Undo even when the work items changed since, overwriting the later changes
+    /// (optional)
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[mcp_tool(
+    name = "azdo_undo_last_change",
+    description = "Undo the work item changes of the last tool invocation of this session, or a single change by ID: created work items are deleted, updated fields restored, links removed and comments deleted. Refuses to overwrite work items changed since unless forced.",
+    read_only = false,
+    destructive = true
+)]
+pub async fn undo_last_change(
+    client: &dyn AzureBoardsApi,
+    args: UndoLastChangeArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_undo_last_change(change_id={:?}, force={})",
+        args.change_id,
+        args.force
+    );
+    let invalid_params = |message: String| McpError {
+        code: ErrorCode(-32602),
+        message: message.into(),
+        data: None,
+    };
+    let Some(journal) = journal::current() else {
+        return Err(invalid_params(
+            "The changes can only be undone by the tools".to_string(),
+        ));
+    };
+    let entries = match args.change_id {
+        Some(id) => match journal.lock().unwrap().get(id) {
+            Some(entry) if entry.undone => {
+                return Err(invalid_params(format!("Change {} was already undone", id)));
+            }
+            Some(entry) => vec![entry],
+            None => return Err(invalid_params(format!("Unknown change {}", id))),
+        },
+        None => journal.lock().unwrap().last_invocation(),
+    };
+    if entries.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(
+            "No work item changes to undo in this session",
+        )]));
+    }
+
+    // The most recent first, so that e.g. the children are deleted before their parent
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let write_err =
+        |e: csv::Error| McpError::internal_error(format!("Failed to write the CSV: {}", e), None);
+    wtr.write_record(["change_id", "work_item_id", "kind", "status", "error"])
+        .map_err(write_err)?;
+    let mut undone = 0;
+    for entry in &entries {
+        let (status, error) = match undo(client, entry, args.force).await {
+            Ok(()) => {
+                journal.lock().unwrap().mark_undone(entry.id);
+                undone += 1;
+                ("undone", String::new())
+            }
+            Err(e) => ("failed", e),
+        };
+        let kind = match entry.change {
+            Change::Created => "created",
+            Change::Updated { .. } => "updated",
+            Change::Linked { .. } => "linked",
+            Change::Commented { .. } => "commented",
+        };
+        wtr.write_record([
+            entry.id.to_string().as_str(),
+            entry.work_item_id.to_string().as_str(),
+            kind,
+            status,
+            error.as_str(),
+        ])
+        .map_err(write_err)?;
+    }
+    let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Undid {} of {} changes made by {}\n{}",
+        undone,
+        entries.len(),
+        entries[0].tool,
+        csv
+    ))]))
+}
+
+/// Revert a change, failing when the work item changed since unless forced
+async fn undo(
+    client: &dyn AzureBoardsApi,
+    entry: &JournalEntry,
+    force: bool,
+) -> Result<(), String> {
+    let (organization, project, id) = (&entry.organization, &entry.project, entry.work_item_id);
+    let changed_since = |rev: Option<i64>| {
+        format!(
+            "Work item {} changed since (revision {} now, {} after the change), pass force to undo anyway",
+            id,
+            rev.map(|rev| rev.to_string()).unwrap_or_default(),
+            entry.rev.map(|rev| rev.to_string()).unwrap_or_default()
+        )
+    };
+    match &entry.change {
+        Change::Created => {
+            if !force && entry.rev.is_some() {
+                let fields = ["System.Rev".to_string()];
+                let current =
+                    work_items::get_work_item(client, organization, project, id, None, &fields)
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .ok_or_else(|| format!("Work item {} no longer exists", id))?;
+                let rev = work_items::rev_of(&current);
+                if rev != entry.rev {
+                    return Err(changed_since(rev));
+                }
+            }
+            work_items::delete_work_item(client, organization, project, id)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+        Change::Updated { previous } => {
+            let rev = if force { None } else { entry.rev };
+            match work_items::restore_work_item_fields(
+                client,
+                organization,
+                project,
+                id,
+                previous,
+                rev,
+            )
+            .await
+            {
+                Ok(_) => Ok(()),
+                Err(AzureError::Conflict(_)) => Err(changed_since(None)),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        Change::Linked { rel, url } => {
+            match work_items::remove_link(client, organization, project, id, rel, url, force).await
+            {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(format!("The link of work item {} no longer exists", id)),
+                Err(AzureError::Conflict(_)) => Err(changed_since(None)),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        Change::Commented { comment_id } => {
+            work_items::delete_comment(client, organization, project, id, *comment_id)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::watches;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UnwatchWorkItemsArgs {
+    /// ID of the watch, as returned by azdo_watch
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub watch_id: String,
+}
+
+#[mcp_tool(
+    name = "azdo_unwatch",
+    description = "Stop a watch started by azdo_watch",
+    read_only = true
+)]
+pub async fn unwatch_work_items(
+    _client: &dyn AzureBoardsApi,
+    args: UnwatchWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!("Tool invoked: azdo_unwatch(watch_id={})", args.watch_id);
+    watches::unwatch(&args.watch_id)?;
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Stopped watch {}",
+        args.watch_id
+    ))]))
+}
@@ -1,10 +1,11 @@
-use crate::azure::{client::AzureDevOpsClient, work_items};
-use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, simplify_work_item_json};
+use crate::azure::{api::AzureBoardsApi, assignees, path_resolver, work_items};
+use crate::mcp::tools::support::{
+    TextFormat, deserialize_non_empty_string, simplify_work_item_json, work_item_result,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, Content, ErrorCode},
+    model::{CallToolResult, ErrorCode},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -24,7 +25,7 @@ pub struct UpdateWorkItemArgs {
     #[serde(default)]
     pub title: Option<String>,
 
-    /// Work item description (Basic HTML supported)
+    /// Work item description (Basic HTML supported, or markdown with `format`)
     #[serde(default)]
     pub description: Option<String>,
 
@@ -99,14 +100,26 @@ pub struct UpdateWorkItemArgs {
     /// Optional extra fields as JSON string (for custom fields)
     #[serde(default)]
     pub fields: Option<String>,
+
+    /// Format of the description, acceptance criteria and reproduction steps: `html`
+    /// (default) or `markdown`, converted to HTML
+    #[serde(default)]
+    pub format: TextFormat,
 }
 
-#[mcp_tool(name = "azdo_update_work_item", description = "Update work item")]
+#[mcp_tool(
+    name = "azdo_update_work_item",
+    description = "Update work item",
+    output = "crate::mcp::tools::support::WorkItemOutput",
+    read_only = false,
+    destructive = true,
+    idempotent = true
+)]
 pub async fn update_work_item(
-    client: &AzureDevOpsClient,
-    args: UpdateWorkItemArgs,
+    client: &dyn AzureBoardsApi,
+    mut args: UpdateWorkItemArgs,
 ) -> Result<CallToolResult, McpError> {
-    log::info!(
+    tracing::info!(
         "Tool invoked: azdo_update_work_item(id={}, title={:?}, state={:?})",
         args.id,
         args.title,
@@ -120,14 +133,29 @@ pub async fn update_work_item(
         field_map.insert("System.Title".to_string(), serde_json::json!(title));
     }
     if let Some(desc) = &args.description {
-        field_map.insert("System.Description".to_string(), serde_json::json!(desc));
+        field_map.insert(
+            "System.Description".to_string(),
+            serde_json::json!(args.format.to_html(desc)),
+        );
     }
     if let Some(assigned_to) = &args.assigned_to {
+        let assigned_to = assignees::resolve_assignee(client, &args.organization, assigned_to)
+            .await
+            .map_err(McpError::from)?;
         field_map.insert(
             "System.AssignedTo".to_string(),
             serde_json::json!(assigned_to),
         );
     }
+    path_resolver::resolve_area_and_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &mut args.area_path,
+        &mut args.iteration_path,
+    )
+    .await
+    .map_err(McpError::from)?;
     if let Some(area_path) = &args.area_path {
         field_map.insert("System.AreaPath".to_string(), serde_json::json!(area_path));
     }
@@ -203,13 +231,13 @@ pub async fn update_work_item(
     if let Some(acceptance_criteria) = &args.acceptance_criteria {
         field_map.insert(
             "Microsoft.VSTS.Common.AcceptanceCriteria".to_string(),
-            serde_json::json!(acceptance_criteria),
+            serde_json::json!(args.format.to_html(acceptance_criteria)),
         );
     }
     if let Some(repro_steps) = &args.repro_steps {
         field_map.insert(
             "Microsoft.VSTS.TCM.ReproSteps".to_string(),
-            serde_json::json!(repro_steps),
+            serde_json::json!(args.format.to_html(repro_steps)),
         );
     }
 
@@ -243,17 +271,11 @@ pub async fn update_work_item(
         &fields_vec,
     )
     .await
-    .map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    .map_err(McpError::from)?;
 
-    // Convert to JSON value, simplify, then serialize
+    // Convert to JSON value, simplify, then return as text and structured content
     let mut json_value = serde_json::to_value(&work_item).unwrap();
     simplify_work_item_json(&mut json_value);
 
-    Ok(CallToolResult::success(vec![Content::text(
-        compact_llm::to_compact_string(&json_value).unwrap(),
-    )]))
+    Ok(work_item_result(json_value))
 }
@@ -0,0 +1,75 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::mcp::tools::support::deserialize_non_empty_string;
+use crate::mcp::watches::{self, WatchSpec, WatchTarget};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, Content, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::time::Duration;
+
+/// Shortest interval between the polls of a watch, sparing the rate limits
+const MIN_INTERVAL_SECS: u64 = 30;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct WatchWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// WIQL query of the work items to watch, the ones entering and leaving its results
+    /// being reported too (either this or ids)
+    #[serde(default)]
+    pub query: Option<String>,
+    /// IDs of the work items to watch (either this or query)
+    #[serde(default)]
+    pub ids: Vec<u32>,
+    /// Seconds between the polls (optional, default 60, at least 30)
+    #[serde(default)]
+    pub interval_seconds: Option<u64>,
+}
+
+#[mcp_tool(
+    name = "azdo_watch",
+    description = "Watch work items, given by WIQL query or IDs, polled in the background: their changes are sent as notifications/message notifications of the azdo_watch logger",
+    read_only = true
+)]
+pub async fn watch_work_items(
+    _client: &dyn AzureBoardsApi,
+    args: WatchWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    tracing::info!(
+        "Tool invoked: azdo_watch(project={}, query={:?}, ids={:?})",
+        args.project,
+        args.query,
+        args.ids
+    );
+    let target = match (args.query, args.ids.is_empty()) {
+        (Some(query), true) => WatchTarget::Query(query),
+        (None, false) => WatchTarget::Ids(args.ids),
+        _ => {
+            return Err(McpError {
+                code: ErrorCode(-32602),
+                message: "Give either query or ids".into(),
+                data: None,
+            });
+        }
+    };
+    let interval = args.interval_seconds.unwrap_or(60).max(MIN_INTERVAL_SECS);
+    let (id, count) = watches::watch(WatchSpec {
+        organization: args.organization,
+        project: args.project,
+        target,
+        interval: Duration::from_secs(interval),
+    })
+    .await?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Started watch {} of {} work items, polled every {}s until azdo_unwatch or the end of the session",
+        id, count, interval
+    ))]))
+}
@@ -0,0 +1,352 @@
+//! Watches of work items polled in the background, for the deployments not receiving
+//! service hooks: a watch of a WIQL query or of work item IDs fetches their revisions
+//! on an interval and sends the changes to the client of its session as
+//! `notifications/message` notifications of the `azdo_watch` logger.
+
+use crate::azure::{api::AzureBoardsApi, client::AzureError, context, work_items};
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    model::{ErrorCode, LoggingLevel, LoggingMessageNotificationParam},
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+
+/// Logger of the notifications of the watches
+const LOGGER: &str = "azdo_watch";
+
+/// Watches a session can have
+const MAX_WATCHES: usize = 20;
+
+/// Work items a watch can follow
+const MAX_WATCHED_ITEMS: usize = 1000;
+
+/// Fields fetched at each poll, the revision telling the changes
+const POLLED_FIELDS: [&str; 4] = [
+    "System.Rev",
+    "System.Title",
+    "System.State",
+    "System.ChangedBy",
+];
+
+/// Watches of a session, by ID, stopped when removed
+pub type SessionWatches = Arc<Mutex<HashMap<String, Watch>>>;
+
+/// Watch running in the background
+pub struct Watch {
+    pub spec: WatchSpec,
+    task: AbortHandle,
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Work items a watch follows
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchTarget {
+    /// Work items returned by a WIQL query, the ones entering and leaving its results
+    /// being reported too
+    Query(String),
+    Ids(Vec<u32>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchSpec {
+    pub organization: String,
+    pub project: String,
+    pub target: WatchTarget,
+    #[serde(serialize_with = "serialize_seconds")]
+    pub interval: Duration,
+}
+
+fn serialize_seconds<S: serde::Serializer>(
+    interval: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(interval.as_secs())
+}
+
+/// Session of a tool invocation, which its watches report to
+#[derive(Clone)]
+pub struct WatchSession {
+    pub client: Arc<dyn AzureBoardsApi>,
+    pub peer: Peer<RoleServer>,
+    pub watches: SessionWatches,
+}
+
+tokio::task_local! {
+    static SESSION: WatchSession;
+}
+
+/// Run a tool invocation able to register watches with [`watch`]
+pub async fn scope<F: Future>(session: WatchSession, future: F) -> F::Output {
+    SESSION.scope(session, future).await
+}
+
+fn session() -> Result<WatchSession, McpError> {
+    SESSION
+        .try_with(WatchSession::clone)
+        .map_err(|_| watch_error("Watches are only available to tools".to_string()))
+}
+
+fn watch_error(message: String) -> McpError {
+    McpError {
+        code: ErrorCode(-32000),
+        message: message.into(),
+        data: None,
+    }
+}
+
+/// Revision of a work item at a poll
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Snapshot {
+    rev: i64,
+    title: String,
+    state: String,
+    changed_by: String,
+}
+
+/// Changes between two polls
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Changes {
+    added: Vec<Value>,
+    changed: Vec<Value>,
+    removed: Vec<u32>,
+}
+
+impl Changes {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Start watching work items for the session of the tool invocation, returning the ID
+/// of the watch and the number of work items it follows
+pub async fn watch(spec: WatchSpec) -> Result<(String, usize), McpError> {
+    let session = session()?;
+    if session.watches.lock().unwrap().len() >= MAX_WATCHES {
+        return Err(watch_error(format!(
+            "A session can have at most {} watches, stop one with azdo_unwatch",
+            MAX_WATCHES
+        )));
+    }
+    // The first poll checks the query and gives the revisions the changes are told from
+    let known = snapshot(session.client.as_ref(), &spec).await?;
+    if known.len() > MAX_WATCHED_ITEMS {
+        return Err(watch_error(format!(
+            "The watch follows {} work items, at most {} can be watched",
+            known.len(),
+            MAX_WATCHED_ITEMS
+        )));
+    }
+    let count = known.len();
+    let id = uuid::Uuid::new_v4().to_string();
+    // The poll outlives the invocation, with its context
+    let task = tokio::spawn(context::scope(
+        context::current(),
+        poll(
+            session.client.clone(),
+            session.peer.clone(),
+            id.clone(),
+            spec.clone(),
+            known,
+        ),
+    ));
+    session.watches.lock().unwrap().insert(
+        id.clone(),
+        Watch {
+            spec,
+            task: task.abort_handle(),
+        },
+    );
+    tracing::info!("Watch {} started", id);
+    Ok((id, count))
+}
+
+/// Stop a watch of the session of the tool invocation
+pub fn unwatch(id: &str) -> Result<WatchSpec, McpError> {
+    let watch = session()?
+        .watches
+        .lock()
+        .unwrap()
+        .remove(id)
+        .ok_or_else(|| watch_error(format!("Unknown watch {}", id)))?;
+    tracing::info!("Watch {} stopped", id);
+    Ok(watch.spec.clone())
+}
+
+/// Watches of the session of the tool invocation
+pub fn list() -> Result<Vec<(String, WatchSpec)>, McpError> {
+    Ok(session()?
+        .watches
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(id, watch)| (id.clone(), watch.spec.clone()))
+        .collect())
+}
+
+/// Poll the work items of a watch on its interval until the client disconnects,
+/// notifying it of the changes
+async fn poll(
+    client: Arc<dyn AzureBoardsApi>,
+    peer: Peer<RoleServer>,
+    id: String,
+    spec: WatchSpec,
+    mut known: BTreeMap<u32, Snapshot>,
+) {
+    let mut interval = tokio::time::interval(spec.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if peer.is_transport_closed() {
+            tracing::info!("Watch {} stopped, its client disconnected", id);
+            return;
+        }
+        let current = match snapshot(client.as_ref(), &spec).await {
+            Ok(current) => current,
+            Err(e) => {
+                tracing::warn!("Watch {} failed to poll: {}", id, e);
+                continue;
+            }
+        };
+        let changes = diff(&known, &current);
+        known = current;
+        if changes.is_empty() {
+            continue;
+        }
+        let mut data = serde_json::json!({
+            "watch_id": id,
+            "organization": spec.organization,
+            "project": spec.project,
+        });
+        if let (Value::Object(data), Ok(Value::Object(changes))) =
+            (&mut data, serde_json::to_value(&changes))
+        {
+            data.extend(changes);
+        }
+        let notification = LoggingMessageNotificationParam {
+            level: LoggingLevel::Notice,
+            logger: Some(LOGGER.to_string()),
+            data,
+        };
+        if let Err(e) = peer.notify_logging_message(notification).await {
+            tracing::debug!("Failed to send the watch notification: {}", e);
+        }
+    }
+}
+
+/// Revisions of the work items of a watch, the first ones when they are too many
+async fn snapshot(
+    client: &dyn AzureBoardsApi,
+    spec: &WatchSpec,
+) -> Result<BTreeMap<u32, Snapshot>, AzureError> {
+    let mut ids = match &spec.target {
+        WatchTarget::Query(query) => {
+            work_items::query_work_item_ids(client, &spec.organization, &spec.project, query)
+                .await?
+        }
+        WatchTarget::Ids(ids) => ids.clone(),
+    };
+    // One more than the limit, telling the watches over it
+    ids.truncate(MAX_WATCHED_ITEMS + 1);
+    let fields: Vec<String> = POLLED_FIELDS
+        .iter()
+        .map(|field| field.to_string())
+        .collect();
+    let fetched = work_items::get_work_items(
+        client,
+        &spec.organization,
+        &spec.project,
+        &ids,
+        None,
+        &fields,
+    )
+    .await?;
+    Ok(fetched
+        .into_iter()
+        .map(|work_item| {
+            let text = |field: &str| match &work_item.fields.get(field) {
+                Some(Value::String(text)) => text.clone(),
+                Some(identity) => identity["displayName"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                None => String::new(),
+            };
+            let snapshot = Snapshot {
+                rev: work_item.fields["System.Rev"].as_i64().unwrap_or_default(),
+                title: text("System.Title"),
+                state: text("System.State"),
+                changed_by: text("System.ChangedBy"),
+            };
+            (work_item.id, snapshot)
+        })
+        .collect())
+}
+
+/// Work items added, changed (a later revision) and removed between two polls
+fn diff(before: &BTreeMap<u32, Snapshot>, after: &BTreeMap<u32, Snapshot>) -> Changes {
+    let entry = |id: &u32, snapshot: &Snapshot| {
+        let mut entry = serde_json::to_value(snapshot).unwrap();
+        entry["id"] = (*id).into();
+        entry
+    };
+    let mut changes = Changes::default();
+    for (id, snapshot) in after {
+        match before.get(id) {
+            None => changes.added.push(entry(id, snapshot)),
+            Some(known) if known.rev != snapshot.rev => changes.changed.push(entry(id, snapshot)),
+            Some(_) => {}
+        }
+    }
+    changes.removed = before
+        .keys()
+        .filter(|id| !after.contains_key(id))
+        .copied()
+        .collect();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(rev: i64, state: &str) -> Snapshot {
+        Snapshot {
+            rev,
+            title: "Fix login".to_string(),
+            state: state.to_string(),
+            changed_by: "Jane".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = BTreeMap::from([(1, snapshot(3, "New")), (2, snapshot(1, "New"))]);
+        let after = BTreeMap::from([(1, snapshot(4, "Active")), (3, snapshot(1, "New"))]);
+        assert_eq!(
+            diff(&before, &after),
+            Changes {
+                added: vec![json!({
+                    "id": 3, "rev": 1, "title": "Fix login", "state": "New", "changed_by": "Jane"
+                })],
+                changed: vec![json!({
+                    "id": 1, "rev": 4, "title": "Fix login", "state": "Active", "changed_by": "Jane"
+                })],
+                removed: vec![2],
+            }
+        );
+        assert!(diff(&after, &after).is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use std::future::Future;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Progress of a long-running operation, e.g. the batches of a work item fetch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    /// Steps done so far
+    pub progress: u64,
+    /// Steps to do, when known
+    pub total: Option<u64>,
+    pub message: String,
+}
+
+tokio::task_local! {
+    static PROGRESS: UnboundedSender<Update>;
+}
+
+/// Run a future, collecting the progress it reports into `sender`
+pub async fn scope<F: Future>(sender: UnboundedSender<Update>, future: F) -> F::Output {
+    PROGRESS.scope(sender, future).await
+}
+
+/// Report the progress of the running operation, does nothing outside of a [`scope`].
+/// The message is only built when the progress is collected.
+pub fn report(progress: u64, total: Option<u64>, message: impl FnOnce() -> String) {
+    let _ = PROGRESS.try_with(|sender| {
+        let _ = sender.send(Update {
+            progress,
+            total,
+            message: message(),
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope() {
+        report(1, None, || unreachable!());
+
+        let (sender, mut updates) = tokio::sync::mpsc::unbounded_channel();
+        scope(sender, async {
+            report(1, Some(2), || "Fetched 1 of 2".to_string());
+        })
+        .await;
+        assert_eq!(
+            updates.recv().await,
+            Some(Update {
+                progress: 1,
+                total: Some(2),
+                message: "Fetched 1 of 2".to_string(),
+            })
+        );
+        assert_eq!(updates.recv().await, None);
+    }
+}
@@ -0,0 +1,417 @@
+use crate::azure::auth::{AccessTokenCredential, PatCredential};
+use azure_core::auth::TokenCredential;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hyper::{HeaderMap, header};
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::signature::Verifier;
+use rsa::{BigUint, RsaPublicKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+
+/// Clock skew tolerated on the validity period of the tokens
+const LEEWAY_SECS: i64 = 60;
+
+/// Minimum interval between refreshes of the signing keys, when a token is signed with
+/// an unknown key
+const KEYS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Header with the personal access token of the user of an HTTP session
+pub const SESSION_PAT_HEADER: &str = "x-azdo-pat";
+
+/// Header with the OAuth access token of the user of an HTTP session
+pub const SESSION_TOKEN_HEADER: &str = "x-azdo-token";
+
+/// Identity of an authenticated MCP client, added to the extensions of its requests:
+/// the user of its JWT, or the fingerprint of its static token
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caller(pub String);
+
+/// Azure DevOps credential of the user opening an HTTP session, if given
+pub fn session_credential(headers: &HeaderMap) -> Option<Arc<dyn TokenCredential>> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    if let Some(pat) = header(SESSION_PAT_HEADER) {
+        Some(Arc::new(PatCredential::new(pat)))
+    } else {
+        header(SESSION_TOKEN_HEADER)
+            .map(|token| Arc::new(AccessTokenCredential::new(token)) as Arc<dyn TokenCredential>)
+    }
+}
+
+/// Credentials the MCP clients of the HTTP server must present as bearer tokens
+#[derive(Debug, Clone, Default)]
+pub struct HttpAuthConfig {
+    /// Static tokens accepted
+    pub tokens: Vec<String>,
+    /// Issuer of the accepted JWTs (e.g. `https://login.microsoftonline.com/{tenant}/v2.0`),
+    /// whose signing keys are found through its OpenID configuration
+    pub jwt_issuer: Option<String>,
+    /// Audience the accepted JWTs are issued for, usually the application ID URI
+    pub jwt_audience: Option<String>,
+}
+
+/// Authenticates the requests of the MCP clients
+pub struct HttpAuth {
+    tokens: Vec<String>,
+    jwt: Option<JwtValidator>,
+}
+
+impl HttpAuth {
+    pub fn new(config: HttpAuthConfig, http: reqwest::Client) -> Result<Self, String> {
+        let jwt = match (config.jwt_issuer, config.jwt_audience) {
+            (Some(issuer), Some(audience)) => Some(JwtValidator {
+                issuer,
+                audience,
+                http,
+                keys: RwLock::default(),
+            }),
+            (None, None) => None,
+            _ => return Err("Both the JWT issuer and audience are required".to_string()),
+        };
+        Ok(Self {
+            tokens: config.tokens,
+            jwt,
+        })
+    }
+
+    /// Whether the requests have to be authenticated
+    pub fn is_enabled(&self) -> bool {
+        !self.tokens.is_empty() || self.jwt.is_some()
+    }
+
+    /// Check the bearer token of a request, returning the identity of the client when
+    /// authentication is enabled
+    pub async fn authorize(&self, headers: &HeaderMap) -> Result<Option<Caller>, String> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or("Missing bearer token")?;
+        // Every token compared, in constant time, not to tell how close a guess is
+        let accepted = self.tokens.iter().fold(false, |accepted, candidate| {
+            accepted | constant_time_eq(candidate.as_bytes(), token.as_bytes())
+        });
+        if accepted {
+            let fingerprint = Sha256::digest(token.as_bytes());
+            return Ok(Some(Caller(format!(
+                "token:{}",
+                fingerprint[..4]
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>()
+            ))));
+        }
+        match &self.jwt {
+            Some(jwt) => jwt.validate(token).await.map(Some),
+            None => Err("Invalid bearer token".to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenIdConfiguration {
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    iss: String,
+    aud: Audience,
+    exp: i64,
+    #[serde(default)]
+    nbf: Option<i64>,
+    #[serde(default)]
+    upn: Option<String>,
+    #[serde(default)]
+    preferred_username: Option<String>,
+    #[serde(default)]
+    oid: Option<String>,
+    #[serde(default)]
+    sub: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::One(one) => one == audience,
+            Audience::Many(many) => many.iter().any(|one| one == audience),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SigningKeys {
+    keys: HashMap<String, VerifyingKey<Sha256>>,
+    refreshed: Option<Instant>,
+}
+
+impl SigningKeys {
+    fn recently_refreshed(&self) -> bool {
+        self.refreshed
+            .is_some_and(|refreshed| refreshed.elapsed() < KEYS_REFRESH_INTERVAL)
+    }
+}
+
+/// Validates RS256 JWTs of an OpenID issuer, like Entra ID
+struct JwtValidator {
+    issuer: String,
+    audience: String,
+    http: reqwest::Client,
+    keys: RwLock<SigningKeys>,
+}
+
+impl JwtValidator {
+    /// Validate a token, returning the user it was issued to
+    async fn validate(&self, token: &str) -> Result<Caller, String> {
+        let invalid = || "Invalid bearer token".to_string();
+        let mut parts = token.split('.');
+        let (Some(header), Some(claims), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let decode = |part: &str| URL_SAFE_NO_PAD.decode(part).map_err(|_| invalid());
+        let jwt_header: JwtHeader =
+            serde_json::from_slice(&decode(header)?).map_err(|_| invalid())?;
+        let jwt_claims: JwtClaims =
+            serde_json::from_slice(&decode(claims)?).map_err(|_| invalid())?;
+        let signature =
+            Signature::try_from(decode(signature)?.as_slice()).map_err(|_| invalid())?;
+        if jwt_header.alg != "RS256" {
+            return Err(format!("Unsupported token algorithm {}", jwt_header.alg));
+        }
+
+        let kid = jwt_header.kid.ok_or_else(invalid)?;
+        let key = self.key(&kid).await?.ok_or("Unknown token signing key")?;
+        key.verify(format!("{}.{}", header, claims).as_bytes(), &signature)
+            .map_err(|_| "Invalid token signature".to_string())?;
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        if jwt_claims.exp + LEEWAY_SECS < now {
+            return Err("Expired token".to_string());
+        }
+        if jwt_claims.nbf.is_some_and(|nbf| nbf - LEEWAY_SECS > now) {
+            return Err("Token not valid yet".to_string());
+        }
+        if jwt_claims.iss != self.issuer {
+            return Err("Token of another issuer".to_string());
+        }
+        if !jwt_claims.aud.contains(&self.audience) {
+            return Err("Token for another audience".to_string());
+        }
+        Ok(Caller(
+            jwt_claims
+                .upn
+                .or(jwt_claims.preferred_username)
+                .or(jwt_claims.oid)
+                .or(jwt_claims.sub)
+                .unwrap_or_else(|| "jwt".to_string()),
+        ))
+    }
+
+    /// Signing key of an ID, refreshing the keys of the issuer when it is unknown (they
+    /// are rotated), at most every [`KEYS_REFRESH_INTERVAL`]
+    async fn key(&self, kid: &str) -> Result<Option<VerifyingKey<Sha256>>, String> {
+        {
+            let keys = self.keys.read().await;
+            if let Some(key) = keys.keys.get(kid) {
+                return Ok(Some(key.clone()));
+            }
+            if keys.recently_refreshed() {
+                return Ok(None);
+            }
+        }
+
+        let mut keys = self.keys.write().await;
+        if !keys.recently_refreshed() {
+            keys.refreshed = Some(Instant::now());
+            keys.keys = self.fetch_keys().await.inspect_err(|e| {
+                tracing::warn!("Failed to get the token signing keys: {}", e);
+            })?;
+        }
+        Ok(keys.keys.get(kid).cloned())
+    }
+
+    async fn fetch_keys(&self) -> Result<HashMap<String, VerifyingKey<Sha256>>, String> {
+        let configuration: OpenIdConfiguration = self
+            .get(&format!(
+                "{}/.well-known/openid-configuration",
+                self.issuer.trim_end_matches('/')
+            ))
+            .await?;
+        let jwks: Jwks = self.get(&configuration.jwks_uri).await?;
+
+        let decode = |value: &str| URL_SAFE_NO_PAD.decode(value).ok();
+        Ok(jwks
+            .keys
+            .into_iter()
+            .filter(|jwk| jwk.kty == "RSA")
+            .filter_map(|jwk| {
+                let n = BigUint::from_bytes_be(&decode(jwk.n.as_deref()?)?);
+                let e = BigUint::from_bytes_be(&decode(jwk.e.as_deref()?)?);
+                let key = RsaPublicKey::new(n, e).ok()?;
+                Some((jwk.kid?, VerifyingKey::new(key)))
+            })
+            .collect())
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        self.http
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Whether two secrets are equal, in a time not depending on where they differ
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    #[tokio::test]
+    async fn test_authorize() {
+        let auth = HttpAuth::new(
+            HttpAuthConfig {
+                tokens: vec!["secret".to_string()],
+                ..Default::default()
+            },
+            reqwest::Client::new(),
+        )
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert!(auth.authorize(&headers).await.is_err());
+        headers.insert(header::AUTHORIZATION, "Bearer other".parse().unwrap());
+        assert!(auth.authorize(&headers).await.is_err());
+        headers.insert(header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        assert_eq!(
+            auth.authorize(&headers).await,
+            Ok(Some(Caller("token:2bb80d53".to_string())))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_credential() {
+        let mut headers = HeaderMap::new();
+        assert!(session_credential(&headers).is_none());
+        headers.insert(SESSION_PAT_HEADER, "abc".parse().unwrap());
+        let token = session_credential(&headers)
+            .unwrap()
+            .get_token("")
+            .await
+            .unwrap();
+        assert_eq!(token.token.secret(), ":abc");
+    }
+
+    #[tokio::test]
+    async fn test_validate_jwt() {
+        use rsa::pkcs1v15::SigningKey;
+        use rsa::signature::{SignatureEncoding, Signer};
+
+        let private_key = rsa::RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 1024).unwrap();
+        let validator = JwtValidator {
+            issuer: "https://issuer".to_string(),
+            audience: "api://mcp".to_string(),
+            http: reqwest::Client::new(),
+            keys: RwLock::new(SigningKeys {
+                keys: HashMap::from([(
+                    "k1".to_string(),
+                    VerifyingKey::new(private_key.to_public_key()),
+                )]),
+                refreshed: Some(Instant::now()),
+            }),
+        };
+        let sign = |claims: serde_json::Value| {
+            let header = serde_json::json!({"alg": "RS256", "kid": "k1"});
+            let message = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let signature = SigningKey::<Sha256>::new(private_key.clone()).sign(message.as_bytes());
+            format!(
+                "{}.{}",
+                message,
+                URL_SAFE_NO_PAD.encode(signature.to_bytes())
+            )
+        };
+
+        let exp = OffsetDateTime::now_utc().unix_timestamp() + 600;
+        let token = sign(serde_json::json!({
+            "iss": "https://issuer", "aud": "api://mcp", "exp": exp, "oid": "user-id"
+        }));
+        assert_eq!(
+            validator.validate(&token).await,
+            Ok(Caller("user-id".to_string()))
+        );
+        assert!(validator.validate(&format!("{}x", token)).await.is_err());
+
+        let token =
+            sign(serde_json::json!({"iss": "https://issuer", "aud": "api://other", "exp": exp}));
+        assert!(validator.validate(&token).await.is_err());
+        let token =
+            sign(serde_json::json!({"iss": "https://issuer", "aud": ["api://mcp"], "exp": 0}));
+        assert!(validator.validate(&token).await.is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use super::http::{HttpResponse, text_response};
+use hyper::{
+    HeaderMap, Method, StatusCode,
+    header::{self, HeaderValue},
+};
+
+/// Headers the browsers may send, besides the configured ones: those of the MCP
+/// transports and of the authentication
+const DEFAULT_ALLOWED_HEADERS: &[&str] = &[
+    "authorization",
+    "content-type",
+    "last-event-id",
+    "mcp-protocol-version",
+    "mcp-session-id",
+    "x-azdo-pat",
+    "x-azdo-token",
+];
+
+/// Headers of the responses the browsers may read
+const EXPOSED_HEADERS: &str = "mcp-session-id";
+
+const ALLOWED_METHODS: &str = "GET, POST, PUT, DELETE, OPTIONS";
+
+/// Browser origins allowed to call the server. Without allowed origins no CORS header
+/// is sent, so browsers refuse the cross-origin requests.
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    /// Origins allowed (e.g. `https://app.example.com`), `*` allowing any
+    pub allowed_origins: Vec<String>,
+    /// Request headers allowed in addition to those of MCP and its authentication
+    pub allowed_headers: Vec<String>,
+    /// Let the browsers send their credentials (cookies, HTTP authentication)
+    pub allow_credentials: bool,
+}
+
+impl CorsConfig {
+    /// `Access-Control-Allow-Origin` of a request origin, if allowed. With credentials,
+    /// the origin is echoed rather than `*`, which browsers refuse then.
+    fn allow_origin(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        let origin_str = origin.to_str().ok()?;
+        if self
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed == origin_str)
+        {
+            Some(origin.clone())
+        } else if self.allowed_origins.iter().any(|allowed| allowed == "*") {
+            Some(if self.allow_credentials {
+                origin.clone()
+            } else {
+                HeaderValue::from_static("*")
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Response to a preflight request, `None` for the other requests
+    pub fn preflight(&self, method: &Method, headers: &HeaderMap) -> Option<HttpResponse> {
+        if method != Method::OPTIONS || !headers.contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+        {
+            return None;
+        }
+        let origin = headers.get(header::ORIGIN)?;
+        let Some(allow_origin) = self.allow_origin(origin) else {
+            return Some(text_response(
+                StatusCode::FORBIDDEN,
+                "Origin not allowed".to_string(),
+            ));
+        };
+
+        let mut response = text_response(StatusCode::NO_CONTENT, String::new());
+        let allowed_headers = DEFAULT_ALLOWED_HEADERS
+            .iter()
+            .map(|header| header.to_string())
+            .chain(self.allowed_headers.iter().map(|h| h.to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let response_headers = response.headers_mut();
+        response_headers.remove(header::CONTENT_TYPE);
+        response_headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        response_headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static(ALLOWED_METHODS),
+        );
+        if let Ok(allowed_headers) = HeaderValue::from_str(&allowed_headers) {
+            response_headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, allowed_headers);
+        }
+        response_headers.insert(
+            header::ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from_static("600"),
+        );
+        self.apply_common(response_headers);
+        Some(response)
+    }
+
+    /// Add the CORS headers to the response of a request from an allowed origin
+    pub fn apply(&self, origin: Option<&HeaderValue>, response: &mut HttpResponse) {
+        let Some(allow_origin) = origin.and_then(|origin| self.allow_origin(origin)) else {
+            return;
+        };
+        let headers = response.headers_mut();
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        headers.insert(
+            header::ACCESS_CONTROL_EXPOSE_HEADERS,
+            HeaderValue::from_static(EXPOSED_HEADERS),
+        );
+        self.apply_common(headers);
+    }
+
+    fn apply_common(&self, headers: &mut HeaderMap) {
+        if self.allow_credentials {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        headers.append(header::VARY, HeaderValue::from_static("Origin"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allow_origin() {
+        let origin = HeaderValue::from_static("https://app.example.com");
+        assert_eq!(CorsConfig::default().allow_origin(&origin), None);
+
+        let config = CorsConfig {
+            allowed_origins: vec!["https://app.example.com".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(config.allow_origin(&origin), Some(origin.clone()));
+        assert_eq!(
+            config.allow_origin(&HeaderValue::from_static("https://other.example.com")),
+            None
+        );
+
+        let config = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allowed_headers: vec![],
+            allow_credentials: true,
+        };
+        assert_eq!(config.allow_origin(&origin), Some(origin));
+    }
+}
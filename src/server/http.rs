@@ -1,36 +1,340 @@
-use crate::mcp::server::AzureMcpServer;
+use super::auth::{HttpAuth, constant_time_eq};
+use super::cors::CorsConfig;
+use super::rate_limit::{ClientKey, ClientLimitConfig, ClientLimiter};
+use super::service_hooks::{self, SERVICE_HOOK_PATH};
+use super::sse::{MESSAGE_PATH, SSE_PATH, SseSessions};
+use super::tls::{TlsAcceptor, TlsConfig};
+use crate::mcp::{server::AzureMcpServer, tool_access::ToolAccess};
+use http_body_util::{BodyExt, Full, Limited, combinators::BoxBody};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header,
+    service::service_fn,
+};
 use hyper_util::{
     rt::{TokioExecutor, TokioIo},
-    server::conn::auto::Builder,
-    service::TowerToHyperService,
+    server::{conn::auto::Builder, graceful::GracefulShutdown},
 };
 use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Path of the admin endpoint reading (GET) and changing (PUT) the tools offered
+pub const ADMIN_TOOLS_PATH: &str = "/admin/tools";
+
+/// Largest body accepted by the admin endpoint
+const ADMIN_BODY_LIMIT: usize = 64 * 1024;
+
+/// Time left to the connections to send the responses of the last tool calls when
+/// shutting down, the event streams keeping theirs open until then
+const RESPONSE_FLUSH: Duration = Duration::from_secs(1);
+
+pub(crate) type HttpResponse = Response<BoxBody<Bytes, Infallible>>;
+
+/// Settings of the HTTP server
+pub struct HttpServerConfig {
+    pub port: u16,
+    /// Bearer token of the admin endpoint, which is not served without it
+    pub admin_token: Option<String>,
+    /// Authentication of the MCP clients
+    pub auth: HttpAuth,
+    /// Certificate to serve HTTPS with, plain HTTP being served without it
+    pub tls: Option<TlsConfig>,
+    /// Browser origins allowed to call the server
+    pub cors: CorsConfig,
+    /// Time the tool calls running when shutting down have to finish
+    pub drain_timeout: Duration,
+    /// Rate and concurrency limits of each client
+    pub client_limits: ClientLimitConfig,
+    /// Secret of the service hooks posting work item events, which are not received
+    /// without it
+    pub service_hook_secret: Option<String>,
+}
+
+/// Routing of the requests to the transports and the admin endpoint, shared by the
+/// connections
+#[derive(Clone)]
+struct Routes {
+    server: AzureMcpServer,
+    mcp: StreamableHttpService<AzureMcpServer>,
+    sse: SseSessions,
+    admin_token: Option<Arc<str>>,
+    service_hook_secret: Option<Arc<str>>,
+    auth: Arc<HttpAuth>,
+    cors: Arc<CorsConfig>,
+    limiter: Arc<ClientLimiter>,
+    /// Set when shutting down, new sessions being refused
+    draining: Arc<AtomicBool>,
+}
+
+impl Routes {
+    async fn handle(self, request: Request<Incoming>, address: IpAddr) -> HttpResponse {
+        if let Some(response) = self.cors.preflight(request.method(), request.headers()) {
+            return response;
+        }
+        let origin = request.headers().get(header::ORIGIN).cloned();
+        let mut response = self.route(request, address).await;
+        self.cors.apply(origin.as_ref(), &mut response);
+        response
+    }
 
-pub async fn run_server(server: AzureMcpServer, port: u16) -> std::io::Result<()> {
-    let service = TowerToHyperService::new(StreamableHttpService::new(
-        move || Ok(server.clone()),
+    async fn route(&self, mut request: Request<Incoming>, address: IpAddr) -> HttpResponse {
+        // The admin endpoint and the service hooks check their own secret
+        let admin = self.admin_token.is_some() && request.uri().path() == ADMIN_TOOLS_PATH;
+        let hook = self.service_hook_secret.is_some() && request.uri().path() == SERVICE_HOOK_PATH;
+        if !admin && !hook {
+            match self.auth.authorize(request.headers()).await {
+                Ok(Some(caller)) => {
+                    request.extensions_mut().insert(caller);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::debug!("Rejected unauthenticated request: {}", e);
+                    return unauthorized(e);
+                }
+            }
+        }
+        if self.draining.load(Ordering::Relaxed) && opens_session(&request) {
+            return text_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "The server is shutting down".to_string(),
+            );
+        }
+        let permit = if admin || hook || !self.limiter.is_enabled() {
+            None
+        } else {
+            match self
+                .limiter
+                .acquire(ClientKey::of(request.headers(), address))
+            {
+                Ok(permit) => Some(permit),
+                Err(retry_after) => return too_many_requests(retry_after),
+            }
+        };
+        if let (true, Some(secret)) = (hook, &self.service_hook_secret) {
+            return service_hooks::receive(&self.server, secret, request).await;
+        }
+        match (request.method(), request.uri().path(), &self.admin_token) {
+            // The event streams last as long as their session, they are not counted
+            (&Method::GET, SSE_PATH, _) => self.sse.connect(&self.server, request.headers()),
+            (&Method::POST, MESSAGE_PATH, _) => self.sse.post(request).await,
+            (_, ADMIN_TOOLS_PATH, Some(token)) => admin_tools(&self.server, token, request).await,
+            (&Method::GET, _, _) => self.mcp.handle(request).await,
+            _ => {
+                // The response of a tool call streams its result, the request stays in
+                // flight until its body is sent
+                let response = self.mcp.handle(request).await;
+                response.map(|body| {
+                    body.map_frame(move |frame| {
+                        let _ = &permit;
+                        frame
+                    })
+                    .boxed()
+                })
+            }
+        }
+    }
+}
+
+/// Serve MCP over streamable HTTP, and over SSE for the older clients, to the clients
+/// passing the authentication. The admin endpoint requests have to carry the admin
+/// token as a bearer token.
+///
+/// When `shutdown` resolves, the server stops accepting connections and sessions and
+/// returns once the tool calls running finished, or at the drain timeout.
+pub async fn run_server(
+    server: AzureMcpServer,
+    config: HttpServerConfig,
+    shutdown: impl Future<Output = ()>,
+) -> std::io::Result<()> {
+    let mcp_server = server.clone();
+    let mcp = StreamableHttpService::new(
+        move || Ok(mcp_server.for_session()),
         LocalSessionManager::default().into(),
         Default::default(),
-    ));
+    );
+    let routes = Routes {
+        server,
+        mcp,
+        sse: SseSessions::default(),
+        admin_token: config.admin_token.map(Arc::<str>::from),
+        service_hook_secret: config.service_hook_secret.map(Arc::<str>::from),
+        auth: Arc::new(config.auth),
+        cors: Arc::new(config.cors),
+        limiter: ClientLimiter::new(config.client_limits),
+        draining: Default::default(),
+    };
+    let tls = config.tls.map(TlsAcceptor::new).transpose()?;
 
-    let addr = format!("0.0.0.0:{}", port);
+    let addr = format!("0.0.0.0:{}", config.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    println!("Listening on http://{}", addr);
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    println!("Listening on {}://{}", scheme, addr);
 
+    let connections = GracefulShutdown::new();
+    tokio::pin!(shutdown);
     loop {
-        let (stream, _) = listener.accept().await?;
-        let io = TokioIo::new(stream);
-        let service = service.clone();
+        let (stream, address) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            () = &mut shutdown => break,
+        };
+        let watcher = connections.watcher();
+        let tls = tls.as_ref().map(|tls| tls.current());
+        let routes = routes.clone();
+        let service = service_fn(move |request: Request<Incoming>| {
+            let routes = routes.clone();
+            async move { Ok::<_, Infallible>(routes.handle(request, address.ip()).await) }
+        });
 
         tokio::spawn(async move {
-            if let Err(err) = Builder::new(TokioExecutor::default())
-                .serve_connection(io, service)
-                .await
-            {
+            let builder = Builder::new(TokioExecutor::default());
+            let result = match tls {
+                Some(tls) => match tls.accept(stream).await {
+                    Ok(stream) => {
+                        watcher
+                            .watch(builder.serve_connection(TokioIo::new(stream), service))
+                            .await
+                    }
+                    Err(err) => {
+                        tracing::debug!("TLS handshake failed: {}", err);
+                        return;
+                    }
+                },
+                None => {
+                    watcher
+                        .watch(builder.serve_connection(TokioIo::new(stream), service))
+                        .await
+                }
+            };
+            if let Err(err) = result {
                 eprintln!("Error serving connection: {:?}", err);
             }
         });
     }
+
+    drop(listener);
+    routes.draining.store(true, Ordering::Relaxed);
+    drain(&routes.server, connections, config.drain_timeout).await;
+    Ok(())
+}
+
+/// Wait for the tool calls running to finish and their responses to be sent, up to the
+/// timeout. The idle connections are closed right away, the others once their
+/// response is sent.
+async fn drain(server: &AzureMcpServer, connections: GracefulShutdown, timeout: Duration) {
+    tracing::info!(
+        "Shutting down, waiting for {} tool calls to finish",
+        server.tool_calls_in_flight()
+    );
+    let mut closed = Box::pin(connections.shutdown());
+    let drained = async {
+        tokio::select! {
+            biased;
+            // Without connections, no response can be sent anymore
+            () = &mut closed => return,
+            () = server.tool_calls_finished() => {}
+        }
+        let _ = tokio::time::timeout(RESPONSE_FLUSH, closed).await;
+    };
+    if tokio::time::timeout(timeout, drained).await.is_err() {
+        tracing::warn!(
+            "Stopping with {} tool calls still running after the drain timeout",
+            server.tool_calls_in_flight()
+        );
+    }
+}
+
+/// Whether a request opens a new MCP session: an SSE stream, or a streamable HTTP
+/// request without session (the initialize request)
+fn opens_session(request: &Request<Incoming>) -> bool {
+    match (request.method(), request.uri().path()) {
+        (&Method::GET, SSE_PATH) => true,
+        (&Method::POST, MESSAGE_PATH | ADMIN_TOOLS_PATH | SERVICE_HOOK_PATH) => false,
+        (&Method::POST, _) => !request.headers().contains_key("mcp-session-id"),
+        _ => false,
+    }
+}
+
+async fn admin_tools(
+    server: &AzureMcpServer,
+    token: &str,
+    request: Request<Incoming>,
+) -> HttpResponse {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()));
+    if !authorized {
+        return text_response(StatusCode::UNAUTHORIZED, "Invalid admin token".to_string());
+    }
+
+    match *request.method() {
+        Method::GET => json_response(&server.tool_access()),
+        Method::PUT => {
+            let body = match Limited::new(request.into_body(), ADMIN_BODY_LIMIT)
+                .collect()
+                .await
+            {
+                Ok(body) => body.to_bytes(),
+                Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+            };
+            let access: ToolAccess = match serde_json::from_slice(&body) {
+                Ok(access) => access,
+                Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+            };
+            match server.set_tool_access(access).await {
+                Ok(()) => json_response(&server.tool_access()),
+                Err(e) => text_response(StatusCode::BAD_REQUEST, e),
+            }
+        }
+        _ => text_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "Expected GET or PUT".to_string(),
+        ),
+    }
+}
+
+fn json_response(access: &ToolAccess) -> HttpResponse {
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(serde_json::to_vec(access).unwrap())).boxed())
+        .unwrap()
+}
+
+fn unauthorized(message: String) -> HttpResponse {
+    let mut response = text_response(StatusCode::UNAUTHORIZED, message);
+    response.headers_mut().insert(
+        header::WWW_AUTHENTICATE,
+        header::HeaderValue::from_static("Bearer"),
+    );
+    response
+}
+
+fn too_many_requests(retry_after: Duration) -> HttpResponse {
+    let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = text_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        format!("Too many requests, retry in {}s", seconds),
+    );
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, header::HeaderValue::from(seconds));
+    response
+}
+
+pub(crate) fn text_response(status: StatusCode, message: String) -> HttpResponse {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Full::new(Bytes::from(message)).boxed())
+        .unwrap()
 }
@@ -1 +1,7 @@
+pub mod auth;
+pub mod cors;
 pub mod http;
+pub mod rate_limit;
+pub mod service_hooks;
+pub mod sse;
+pub mod tls;
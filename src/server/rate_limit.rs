@@ -0,0 +1,183 @@
+use hyper::{HeaderMap, header};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Clients tracked before the idle ones are forgotten
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Wait suggested to the clients over their concurrent request cap
+const CONCURRENCY_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Limits applied to each client of the HTTP server
+#[derive(Debug, Clone, Default)]
+pub struct ClientLimitConfig {
+    /// Sustained requests per second of a client, 0 disables the limit
+    pub requests_per_second: f64,
+    /// Requests a client can send at once after an idle period
+    pub burst: u32,
+    /// Requests of a client processed at once, tool calls lasting until their response
+    /// is sent, 0 disables the cap
+    pub max_concurrent: usize,
+}
+
+/// Client a request is counted against: its bearer token, else its MCP session, else
+/// its IP address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientKey {
+    /// SHA-256 of the token, so that the tokens are not kept around
+    Token([u8; 32]),
+    Session(String),
+    Address(IpAddr),
+}
+
+impl ClientKey {
+    pub fn of(headers: &HeaderMap, address: IpAddr) -> Self {
+        if let Some(token) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+        {
+            return Self::Token(Sha256::digest(token.trim().as_bytes()).into());
+        }
+        match headers
+            .get("mcp-session-id")
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(session) => Self::Session(session.to_string()),
+            None => Self::Address(address),
+        }
+    }
+}
+
+struct ClientState {
+    tokens: f64,
+    refilled_at: Instant,
+    in_flight: usize,
+}
+
+/// Token bucket and concurrent request count of each client
+pub struct ClientLimiter {
+    config: ClientLimitConfig,
+    clients: Mutex<HashMap<ClientKey, ClientState>>,
+}
+
+/// Request counted as in flight for its client until dropped
+pub struct ClientPermit {
+    limiter: Arc<ClientLimiter>,
+    key: ClientKey,
+}
+
+impl Drop for ClientPermit {
+    fn drop(&mut self) {
+        if let Some(state) = self.limiter.clients.lock().unwrap().get_mut(&self.key) {
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+impl ClientLimiter {
+    pub fn new(config: ClientLimitConfig) -> Arc<Self> {
+        Arc::new(Self {
+            config,
+            clients: Mutex::default(),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.requests_per_second > 0.0 || self.config.max_concurrent > 0
+    }
+
+    /// Admit a request of a client, or return how long it should wait before retrying
+    pub fn acquire(self: &Arc<Self>, key: ClientKey) -> Result<ClientPermit, Duration> {
+        self.try_acquire(key, Instant::now())
+    }
+
+    fn try_acquire(
+        self: &Arc<Self>,
+        key: ClientKey,
+        now: Instant,
+    ) -> Result<ClientPermit, Duration> {
+        let burst = self.config.burst.max(1) as f64;
+        let rate = self.config.requests_per_second;
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() >= PRUNE_THRESHOLD {
+            clients.retain(|_, state| {
+                let refilled =
+                    state.tokens + now.duration_since(state.refilled_at).as_secs_f64() * rate;
+                state.in_flight > 0 || (rate > 0.0 && refilled < burst)
+            });
+        }
+        let state = clients.entry(key.clone()).or_insert(ClientState {
+            tokens: burst,
+            refilled_at: now,
+            in_flight: 0,
+        });
+
+        if self.config.max_concurrent > 0 && state.in_flight >= self.config.max_concurrent {
+            return Err(CONCURRENCY_RETRY_AFTER);
+        }
+        if rate > 0.0 {
+            let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * rate).min(burst);
+            state.refilled_at = now;
+            if state.tokens < 1.0 {
+                return Err(Duration::from_secs_f64((1.0 - state.tokens) / rate));
+            }
+            state.tokens -= 1.0;
+        }
+        state.in_flight += 1;
+        Ok(ClientPermit {
+            limiter: self.clone(),
+            key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_try_acquire() {
+        let limiter = ClientLimiter::new(ClientLimitConfig {
+            requests_per_second: 2.0,
+            burst: 2,
+            max_concurrent: 0,
+        });
+        let client = ClientKey::Address(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let other = ClientKey::Session("session".to_string());
+        let now = Instant::now();
+        assert!(limiter.try_acquire(client.clone(), now).is_ok());
+        assert!(limiter.try_acquire(client.clone(), now).is_ok());
+        assert_eq!(
+            limiter.try_acquire(client.clone(), now).err(),
+            Some(Duration::from_millis(500))
+        );
+        assert!(limiter.try_acquire(other, now).is_ok());
+        assert!(
+            limiter
+                .try_acquire(client, now + Duration::from_millis(500))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_max_concurrent() {
+        let limiter = ClientLimiter::new(ClientLimitConfig {
+            max_concurrent: 1,
+            ..Default::default()
+        });
+        let client = ClientKey::Session("session".to_string());
+        let permit = limiter.acquire(client.clone()).unwrap();
+        assert_eq!(
+            limiter.acquire(client.clone()).err(),
+            Some(CONCURRENCY_RETRY_AFTER)
+        );
+        drop(permit);
+        assert!(limiter.acquire(client).is_ok());
+    }
+}
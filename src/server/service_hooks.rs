@@ -0,0 +1,158 @@
+//! Receiver of the Azure DevOps service hooks: the work item events posted to
+//! [`SERVICE_HOOK_PATH`] with the shared secret are forwarded to the MCP sessions
+//! subscribed to the work item as resource-updated notifications.
+
+use super::auth::constant_time_eq;
+use super::http::{HttpResponse, text_response};
+use crate::azure::service_hooks::SECRET_HEADER;
+use crate::mcp::server::AzureMcpServer;
+use http_body_util::{BodyExt, Limited};
+use hyper::{Method, Request, StatusCode, body::Incoming};
+use serde_json::Value;
+
+/// Path the service hooks post their events to
+pub const SERVICE_HOOK_PATH: &str = "/hooks/azdo";
+
+/// Events forwarded to the sessions, the others being acknowledged and ignored
+const FORWARDED_EVENTS: [&str; 3] = ["workitem.created", "workitem.updated", "workitem.commented"];
+
+/// Largest event accepted, the updates carrying the changed fields
+const EVENT_LIMIT: usize = 4 * 1024 * 1024;
+
+/// Work item an event is about
+#[derive(Debug, PartialEq)]
+struct WorkItemEvent {
+    organization: String,
+    project: String,
+    id: u32,
+}
+
+/// Receive an event posted by a service hook, checking its secret
+pub async fn receive(
+    server: &AzureMcpServer,
+    secret: &str,
+    request: Request<Incoming>,
+) -> HttpResponse {
+    if request.method() != Method::POST {
+        return text_response(StatusCode::METHOD_NOT_ALLOWED, "Expected POST".to_string());
+    }
+    let authorized = request
+        .headers()
+        .get(SECRET_HEADER)
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), secret.as_bytes()));
+    if !authorized {
+        return text_response(
+            StatusCode::UNAUTHORIZED,
+            "Invalid service hook secret".to_string(),
+        );
+    }
+
+    let body = match Limited::new(request.into_body(), EVENT_LIMIT)
+        .collect()
+        .await
+    {
+        Ok(body) => body.to_bytes(),
+        Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+    let event: Value = match serde_json::from_slice(&body) {
+        Ok(event) => event,
+        Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+    let event_type = event["eventType"].as_str().unwrap_or_default();
+    if !FORWARDED_EVENTS.contains(&event_type) {
+        tracing::debug!("Ignored service hook event {}", event_type);
+        return text_response(StatusCode::OK, "Ignored".to_string());
+    }
+    let Some(work_item) = work_item_event(&event) else {
+        return text_response(
+            StatusCode::BAD_REQUEST,
+            "The event does not identify a work item".to_string(),
+        );
+    };
+    let sent = server
+        .notify_work_item_changed(&work_item.organization, &work_item.project, work_item.id)
+        .await;
+    tracing::info!(
+        "Service hook event {} for work item {}, notified {} subscriptions",
+        event_type,
+        work_item.id,
+        sent
+    );
+    text_response(StatusCode::OK, "Accepted".to_string())
+}
+
+/// Work item of a `workitem.*` event: the updates carry it as `workItemId` and its
+/// fields in `revision`, the other events carry the work item itself
+fn work_item_event(event: &Value) -> Option<WorkItemEvent> {
+    let resource = &event["resource"];
+    let id = resource["workItemId"]
+        .as_u64()
+        .or_else(|| resource["id"].as_u64())?;
+    let project = resource["revision"]["fields"]["System.TeamProject"]
+        .as_str()
+        .or_else(|| resource["fields"]["System.TeamProject"].as_str())?;
+    let containers = &event["resourceContainers"];
+    let organization = containers["account"]["baseUrl"]
+        .as_str()
+        .or_else(|| containers["collection"]["baseUrl"].as_str())
+        .and_then(organization_of)?;
+    Some(WorkItemEvent {
+        organization,
+        project: project.to_string(),
+        id: u32::try_from(id).ok()?,
+    })
+}
+
+/// Organization of a base URL: its last path segment (`https://dev.azure.com/contoso/`,
+/// `https://server/tfs/contoso/`), or the first label of its host
+/// (`https://contoso.visualstudio.com/`)
+fn organization_of(base_url: &str) -> Option<String> {
+    let (_, rest) = base_url.split_once("://")?;
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    match path.split('/').rfind(|segment| !segment.is_empty()) {
+        Some(segment) => urlencoding::decode(segment).ok().map(|s| s.into_owned()),
+        None => host.split('.').next().map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_work_item_event() {
+        let updated = json!({
+            "eventType": "workitem.updated",
+            "resource": {
+                "id": 7,
+                "workItemId": 42,
+                "revision": {"id": 42, "fields": {"System.TeamProject": "Web"}},
+            },
+            "resourceContainers": {
+                "account": {"baseUrl": "https://dev.azure.com/contoso/"},
+            },
+        });
+        assert_eq!(
+            work_item_event(&updated),
+            Some(WorkItemEvent {
+                organization: "contoso".to_string(),
+                project: "Web".to_string(),
+                id: 42,
+            })
+        );
+
+        let created = json!({
+            "eventType": "workitem.created",
+            "resource": {"id": 5, "fields": {"System.TeamProject": "Web"}},
+            "resourceContainers": {
+                "collection": {"baseUrl": "https://fabrikam.visualstudio.com/"},
+            },
+        });
+        assert_eq!(
+            work_item_event(&created).map(|event| (event.organization, event.id)),
+            Some(("fabrikam".to_string(), 5))
+        );
+        assert_eq!(work_item_event(&json!({"resource": {"id": 5}})), None);
+    }
+}
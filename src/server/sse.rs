@@ -0,0 +1,149 @@
+//! HTTP+SSE transport of the MCP 2024-11-05 revision, for the clients not speaking
+//! streamable HTTP: the client opens an event stream on [`SSE_PATH`], whose first
+//! `endpoint` event tells where to POST its messages, and receives the messages of the
+//! server as `message` events.
+
+use super::auth::session_credential;
+use super::http::{HttpResponse, text_response};
+use crate::mcp::server::AzureMcpServer;
+use futures::{
+    StreamExt,
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+};
+use http_body_util::{BodyExt, Limited, StreamBody};
+use hyper::{
+    HeaderMap, Request, Response, StatusCode,
+    body::{Bytes, Frame, Incoming},
+    header,
+};
+use rmcp::{
+    ServiceExt,
+    model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Path of the event streams
+pub const SSE_PATH: &str = "/sse";
+
+/// Path the clients POST their messages to, with the `sessionId` of their stream
+pub const MESSAGE_PATH: &str = "/message";
+
+/// Interval of the comments sent to keep idle streams open through proxies, also
+/// detecting the disconnected clients
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Largest message accepted from a client
+const MESSAGE_LIMIT: usize = 4 * 1024 * 1024;
+
+/// Sessions of the open event streams, each with its own server session, by ID
+#[derive(Clone, Default)]
+pub struct SseSessions {
+    sessions: Arc<Mutex<HashMap<String, UnboundedSender<ClientJsonRpcMessage>>>>,
+}
+
+impl SseSessions {
+    /// Open an event stream, serving a new MCP session over it until the client
+    /// disconnects, acting as the user of the credential in its headers
+    pub fn connect(&self, server: &AzureMcpServer, headers: &HeaderMap) -> HttpResponse {
+        let server = server.for_session();
+        if let Err(e) = server.bind_session(session_credential(headers)) {
+            return text_response(StatusCode::UNAUTHORIZED, e);
+        }
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let (to_server, from_client) = unbounded::<ClientJsonRpcMessage>();
+        let (to_client, from_server) = unbounded::<ServerJsonRpcMessage>();
+        let (events, body) = unbounded::<Bytes>();
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), to_server);
+        tracing::info!("SSE session {} opened", session_id);
+
+        // The session ends when its client messages stop, once it is forgotten
+        tokio::spawn(async move {
+            match server.serve((to_client, from_client)).await {
+                Ok(service) => {
+                    let _ = service.waiting().await;
+                }
+                Err(e) => tracing::debug!("SSE session failed to initialize: {}", e),
+            }
+        });
+
+        let _ = events.unbounded_send(Bytes::from(format!(
+            "event: endpoint\ndata: {}?sessionId={}\n\n",
+            MESSAGE_PATH, session_id
+        )));
+        tokio::spawn(self.clone().forward(session_id, from_server, events));
+
+        Response::builder()
+            .header(header::CONTENT_TYPE, "text/event-stream")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(BodyExt::boxed(StreamBody::new(
+                body.map(|event| Ok(Frame::data(event))),
+            )))
+            .unwrap()
+    }
+
+    /// Send the messages of the server as events, with keep-alives, until the client
+    /// disconnects or the session ends, then forget the session
+    async fn forward(
+        self,
+        session_id: String,
+        mut messages: UnboundedReceiver<ServerJsonRpcMessage>,
+        events: UnboundedSender<Bytes>,
+    ) {
+        let mut keep_alive = tokio::time::interval(KEEP_ALIVE);
+        keep_alive.tick().await;
+        loop {
+            let event = tokio::select! {
+                message = messages.next() => match message {
+                    Some(message) => format!(
+                        "event: message\ndata: {}\n\n",
+                        serde_json::to_string(&message).unwrap()
+                    ),
+                    None => break,
+                },
+                _ = keep_alive.tick() => ": keep-alive\n\n".to_string(),
+            };
+            if events.unbounded_send(Bytes::from(event)).is_err() {
+                break;
+            }
+        }
+        self.sessions.lock().unwrap().remove(&session_id);
+        tracing::info!("SSE session {} closed", session_id);
+    }
+
+    /// Pass a message POSTed by a client to its session, the response being sent on the
+    /// event stream
+    pub async fn post(&self, request: Request<Incoming>) -> HttpResponse {
+        let session_id = request.uri().query().and_then(|query| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("sessionId="))
+                .map(str::to_string)
+        });
+        let Some(session) = session_id
+            .and_then(|session_id| self.sessions.lock().unwrap().get(&session_id).cloned())
+        else {
+            return text_response(StatusCode::NOT_FOUND, "Unknown session".to_string());
+        };
+
+        let (parts, body) = request.into_parts();
+        let body = match Limited::new(body, MESSAGE_LIMIT).collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+        };
+        let mut message: ClientJsonRpcMessage = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+        };
+        // Like the streamable HTTP transport, the tools see the request of the message
+        message.insert_extension(parts);
+        match session.unbounded_send(message) {
+            Ok(()) => text_response(StatusCode::ACCEPTED, "Accepted".to_string()),
+            Err(_) => text_response(StatusCode::GONE, "Session closed".to_string()),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio_rustls::rustls::ServerConfig;
+
+/// Certificate of the HTTPS server
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// PEM file with the certificate chain
+    pub certificate: PathBuf,
+    /// PEM file with the private key of the certificate (PKCS#8, PKCS#1 or SEC1)
+    pub key: PathBuf,
+    /// Interval at which the files are checked for changes, to reload a renewed
+    /// certificate without restarting, `None` never reloads them
+    pub reload_interval: Option<Duration>,
+}
+
+/// Accepts the TLS connections with the current certificate
+pub struct TlsAcceptor {
+    acceptor: RwLock<tokio_rustls::TlsAcceptor>,
+}
+
+impl TlsAcceptor {
+    pub fn new(config: TlsConfig) -> std::io::Result<Arc<Self>> {
+        let acceptor = Arc::new(Self {
+            acceptor: RwLock::new(load(&config)?),
+        });
+        if let Some(interval) = config.reload_interval {
+            tokio::spawn(reload(Arc::downgrade(&acceptor), config, interval));
+        }
+        Ok(acceptor)
+    }
+
+    pub fn current(&self) -> tokio_rustls::TlsAcceptor {
+        self.acceptor.read().unwrap().clone()
+    }
+}
+
+/// Build the server configuration of the certificate files, with HTTP/2 and HTTP/1.1
+/// offered by ALPN
+fn load(config: &TlsConfig) -> std::io::Result<tokio_rustls::TlsAcceptor> {
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let certificate = std::fs::read(&config.certificate)?;
+    let chain =
+        rustls_pemfile::certs(&mut certificate.as_slice()).collect::<Result<Vec<_>, _>>()?;
+    if chain.is_empty() {
+        return Err(invalid(format!(
+            "No certificate in {}",
+            config.certificate.display()
+        )));
+    }
+    let key = std::fs::read(&config.key)?;
+    let key = rustls_pemfile::private_key(&mut key.as_slice())?
+        .ok_or_else(|| invalid(format!("No private key in {}", config.key.display())))?;
+    let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+    let mut server = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| invalid(e.to_string()))?
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .map_err(|e| invalid(e.to_string()))?;
+    server.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(server).into())
+}
+
+fn modified(config: &TlsConfig) -> Option<(SystemTime, SystemTime)> {
+    let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    Some((modified(&config.certificate)?, modified(&config.key)?))
+}
+
+/// Reload the certificate when its files change, keeping the previous one when the new
+/// one fails to load (e.g. the key is not written yet), until the server stops
+async fn reload(acceptor: std::sync::Weak<TlsAcceptor>, config: TlsConfig, interval: Duration) {
+    let mut loaded = modified(&config);
+    let mut ticks = tokio::time::interval(interval);
+    ticks.tick().await;
+    loop {
+        ticks.tick().await;
+        let Some(acceptor) = acceptor.upgrade() else {
+            return;
+        };
+        let current = modified(&config);
+        if current.is_none() || current == loaded {
+            continue;
+        }
+        match load(&config) {
+            Ok(reloaded) => {
+                *acceptor.acceptor.write().unwrap() = reloaded;
+                loaded = current;
+                tracing::info!("Reloaded the certificate {}", config.certificate.display());
+            }
+            Err(e) => tracing::warn!(
+                "Failed to reload the certificate {}: {}",
+                config.certificate.display(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_load() {
+        let path = |name: &str| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/certificates")
+                .join(name)
+        };
+        // An EC key in SEC1 format
+        let config = TlsConfig {
+            certificate: path("localhost.pem"),
+            key: path("localhost-key.pem"),
+            reload_interval: None,
+        };
+        assert!(load(&config).is_ok());
+
+        let config = TlsConfig {
+            key: path("localhost.pem"),
+            ..config
+        };
+        let e = load(&config).err().unwrap();
+        assert!(e.to_string().starts_with("No private key"));
+    }
+}
@@ -0,0 +1,541 @@
+mod sync;
+
+pub use sync::{SyncReport, sync_project};
+
+use crate::azure::models::WorkItem;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Schema of the store, one migration from each version to the next, the version of a
+/// database being its `user_version`
+const MIGRATIONS: &[&str] = &[
+    // 1: the mirrored projects and their work items
+    "CREATE TABLE projects (
+        key TEXT PRIMARY KEY,
+        watermark TEXT,
+        synced_at TEXT
+    );
+    CREATE TABLE work_items (
+        project TEXT NOT NULL,
+        id INTEGER NOT NULL,
+        rev INTEGER,
+        changed_date TEXT,
+        fields TEXT NOT NULL,
+        stored_at INTEGER NOT NULL,
+        PRIMARY KEY (project, id)
+    );
+    CREATE INDEX work_items_stored_at ON work_items (stored_at);",
+];
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("Unable to access the work item store {0}: {1}")]
+    Database(PathBuf, rusqlite::Error),
+    #[error("Invalid work item store {0}: {1}")]
+    Format(PathBuf, String),
+}
+
+/// Where the store is kept and how large it can grow
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub path: PathBuf,
+    /// Work items kept, across the projects, the least recently stored evicted first
+    pub max_items: usize,
+}
+
+/// Criteria of the work items searched in the store, all of them matching
+#[derive(Debug, Default)]
+pub struct StoreFilter {
+    /// Text in the title, the description or the tags, case insensitive
+    pub text: Option<String>,
+    pub work_item_types: Vec<String>,
+    pub states: Vec<String>,
+    /// Name or email of the assignee, or part of it
+    pub assigned_to: Option<String>,
+    /// Area path the work items are in or under
+    pub area_path: Option<String>,
+    pub changed_since: Option<DateTime<Utc>>,
+}
+
+/// Local mirror of the work items fetched, complete for the projects synced since their
+/// watermark, that backs a search when Azure DevOps is slow or throttling.
+///
+/// Consistency rules:
+/// - only the work items fetched with all their fields are stored, replacing the
+///   stored ones unless of an older revision (responses can arrive out of order)
+/// - the watermark of a project only moves with a sync, which fetches every work item
+///   changed since, so the work items merely fetched don't make it look complete
+/// - the work items deleted upstream stay until they are evicted
+pub struct WorkItemStore {
+    config: StoreConfig,
+    /// SQLite database, only accessed from the blocking threads
+    connection: Arc<Mutex<Connection>>,
+}
+
+/// Work item as stored, with all its fields
+#[derive(Debug)]
+struct StoredWorkItem {
+    fields: Map<String, Value>,
+}
+
+impl StoredWorkItem {
+    fn field(&self, name: &str) -> &str {
+        self.fields.get(name).and_then(Value::as_str).unwrap_or("")
+    }
+
+    fn changed_date(&self) -> Option<DateTime<Utc>> {
+        self.fields
+            .get("System.ChangedDate")
+            .and_then(Value::as_str)
+            .and_then(parse_date)
+    }
+
+    fn matches(&self, filter: &StoreFilter) -> bool {
+        let contains = |name: &str, text: &str| field_contains(self.fields.get(name), text);
+        let is_any = |name: &str, values: &[String]| {
+            values.is_empty()
+                || values
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case(self.field(name)))
+        };
+        filter.text.as_deref().is_none_or(|text| {
+            ["System.Title", "System.Description", "System.Tags"]
+                .iter()
+                .any(|name| contains(name, text))
+        }) && is_any("System.WorkItemType", &filter.work_item_types)
+            && is_any("System.State", &filter.states)
+            && filter
+                .assigned_to
+                .as_deref()
+                .is_none_or(|assignee| contains("System.AssignedTo", assignee))
+            && filter.area_path.as_deref().is_none_or(|area_path| {
+                let path = self.field("System.AreaPath").to_lowercase();
+                let area_path = area_path.to_lowercase();
+                path == area_path || path.starts_with(&format!("{}\\", area_path))
+            })
+            && filter
+                .changed_since
+                .is_none_or(|since| self.changed_date().is_some_and(|date| date >= since))
+    }
+}
+
+/// Whether a field, a string or an identity, contains a text, case insensitive
+fn field_contains(field: Option<&Value>, text: &str) -> bool {
+    let text = text.to_lowercase();
+    match field {
+        Some(Value::String(value)) => value.to_lowercase().contains(&text),
+        Some(Value::Object(identity)) => ["displayName", "uniqueName"].iter().any(|name| {
+            identity
+                .get(*name)
+                .and_then(Value::as_str)
+                .is_some_and(|value| value.to_lowercase().contains(&text))
+        }),
+        _ => false,
+    }
+}
+
+fn changed_date(fields: &HashMap<String, Value>) -> Option<DateTime<Utc>> {
+    parse_date(fields.get("System.ChangedDate")?.as_str()?)
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|date| date.to_utc())
+}
+
+fn project_key(organization: &str, project: &str) -> String {
+    format!("{}/{}", organization, project).to_lowercase()
+}
+
+/// Date as stored, in UTC with a fixed precision so that the dates sort as text
+fn format_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Bring a database created by an older version to the current schema
+fn migrate(connection: &mut Connection) -> Result<(), String> {
+    let version: usize = connection
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if version > MIGRATIONS.len() {
+        return Err(format!(
+            "created by a newer version of the server (schema {})",
+            version
+        ));
+    }
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let transaction = connection.transaction().map_err(|e| e.to_string())?;
+        transaction
+            .execute_batch(migration)
+            .and_then(|_| transaction.pragma_update(None, "user_version", index + 1))
+            .and_then(|_| transaction.commit())
+            .map_err(|e| format!("migration to schema {} failed: {}", index + 1, e))?;
+    }
+    Ok(())
+}
+
+impl WorkItemStore {
+    /// Open the store, created when its database doesn't exist yet
+    pub fn open(config: StoreConfig) -> Result<Self, StoreError> {
+        let mut connection = Connection::open(&config.path)
+            .map_err(|e| StoreError::Database(config.path.clone(), e))?;
+        migrate(&mut connection).map_err(|e| StoreError::Format(config.path.clone(), e))?;
+        Ok(Self {
+            config,
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Run a task on the database from a blocking thread, not to hold up the runtime
+    async fn run<T: Send + 'static>(
+        &self,
+        task: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> Result<T, StoreError> {
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || task(&mut connection.lock().unwrap()))
+            .await
+            .expect("work item store task panicked")
+            .map_err(|e| StoreError::Database(self.config.path.clone(), e))
+    }
+
+    /// Store the work items fetched with all their fields
+    pub async fn record(&self, organization: &str, project: &str, work_items: &[WorkItem]) {
+        if work_items.is_empty() {
+            return;
+        }
+        let key = project_key(organization, project);
+        let rows: Vec<(u32, Option<u64>, Option<String>, String)> = work_items
+            .iter()
+            .map(|work_item| {
+                (
+                    work_item.id,
+                    work_item.fields.get("System.Rev").and_then(Value::as_u64),
+                    changed_date(&work_item.fields).map(format_date),
+                    serde_json::to_string(&work_item.fields).unwrap(),
+                )
+            })
+            .collect();
+        let max_items = self.config.max_items;
+        let recorded = self
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                let stored_at = Utc::now().timestamp_micros();
+                {
+                    // Replacing the stored work items unless of an older revision
+                    let mut upsert = transaction.prepare(
+                        "INSERT INTO work_items (project, id, rev, changed_date, fields, stored_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                         ON CONFLICT (project, id) DO UPDATE SET
+                             rev = excluded.rev,
+                             changed_date = excluded.changed_date,
+                             fields = excluded.fields,
+                             stored_at = excluded.stored_at
+                         WHERE excluded.rev IS NULL OR work_items.rev IS NULL
+                             OR excluded.rev >= work_items.rev",
+                    )?;
+                    for (id, rev, changed_date, fields) in &rows {
+                        upsert.execute(params![key, id, rev, changed_date, fields, stored_at])?;
+                    }
+                }
+                evict(&transaction, max_items)?;
+                transaction.commit()
+            })
+            .await;
+        if let Err(e) = recorded {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    /// Forget work items, e.g. deleted
+    pub async fn remove(&self, organization: &str, project: &str, ids: &[u32]) {
+        if ids.is_empty() {
+            return;
+        }
+        let key = project_key(organization, project);
+        let ids = ids.to_vec();
+        let removed = self
+            .run(move |connection| {
+                let transaction = connection.transaction()?;
+                {
+                    let mut delete = transaction
+                        .prepare("DELETE FROM work_items WHERE project = ?1 AND id = ?2")?;
+                    for id in &ids {
+                        delete.execute(params![key, id])?;
+                    }
+                }
+                transaction.commit()
+            })
+            .await;
+        if let Err(e) = removed {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    /// Work items of a project matching a filter, the most recently changed first
+    pub async fn search(
+        &self,
+        organization: &str,
+        project: &str,
+        filter: StoreFilter,
+    ) -> Result<Vec<WorkItem>, StoreError> {
+        let key = project_key(organization, project);
+        let since = filter.changed_since.map(format_date);
+        let stored = self
+            .run(move |connection| {
+                let mut select = connection.prepare(
+                    "SELECT id, fields FROM work_items
+                     WHERE project = ?1 AND (?2 IS NULL OR changed_date >= ?2)
+                     ORDER BY changed_date DESC, id DESC",
+                )?;
+                let rows = select.query_map(params![key, since], |row| {
+                    Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?))
+                })?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await?;
+
+        let mut found = Vec::new();
+        for (id, fields) in stored {
+            let fields: Map<String, Value> = serde_json::from_str(&fields)
+                .map_err(|e| StoreError::Format(self.config.path.clone(), e.to_string()))?;
+            let stored = StoredWorkItem { fields };
+            if stored.matches(&filter) {
+                found.push(WorkItem {
+                    id,
+                    fields: stored.fields.into_iter().collect::<HashMap<_, _>>(),
+                    url: None,
+                    comments: None,
+                    relations: None,
+                });
+            }
+        }
+        Ok(found)
+    }
+
+    /// Watermark of a project and when it was last synced
+    pub async fn sync_state(
+        &self,
+        organization: &str,
+        project: &str,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let key = project_key(organization, project);
+        let state = self
+            .run(move |connection| {
+                connection
+                    .query_row(
+                        "SELECT watermark, synced_at FROM projects WHERE key = ?1",
+                        [key],
+                        |row| {
+                            Ok((
+                                row.get::<_, Option<String>>(0)?,
+                                row.get::<_, Option<String>>(1)?,
+                            ))
+                        },
+                    )
+                    .optional()
+            })
+            .await;
+        match state {
+            Ok(Some((watermark, synced_at))) => (
+                watermark.as_deref().and_then(parse_date),
+                synced_at.as_deref().and_then(parse_date),
+            ),
+            Ok(None) => (None, None),
+            Err(e) => {
+                tracing::warn!("{}", e);
+                (None, None)
+            }
+        }
+    }
+
+    /// Record that a project is complete up to a watermark
+    async fn mark_synced(
+        &self,
+        organization: &str,
+        project: &str,
+        watermark: Option<DateTime<Utc>>,
+    ) {
+        let key = project_key(organization, project);
+        let watermark = watermark.map(format_date);
+        let synced_at = format_date(Utc::now());
+        let marked = self
+            .run(move |connection| {
+                connection.execute(
+                    "INSERT INTO projects (key, watermark, synced_at) VALUES (?1, ?2, ?3)
+                     ON CONFLICT (key) DO UPDATE SET
+                         watermark = CASE
+                             WHEN projects.watermark IS NULL
+                                 OR excluded.watermark > projects.watermark
+                             THEN coalesce(excluded.watermark, projects.watermark)
+                             ELSE projects.watermark
+                         END,
+                         synced_at = excluded.synced_at",
+                    params![key, watermark, synced_at],
+                )
+            })
+            .await;
+        if let Err(e) = marked {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// Drop the least recently stored work items over the maximum
+fn evict(connection: &Connection, max_items: usize) -> rusqlite::Result<()> {
+    let stored: usize = connection.query_row("SELECT count(*) FROM work_items", [], |row| {
+        row.get::<_, i64>(0).map(|count| count as usize)
+    })?;
+    if stored <= max_items {
+        return Ok(());
+    }
+    let oldest = "SELECT rowid FROM work_items ORDER BY stored_at, rowid LIMIT ?1";
+    let excess = (stored - max_items) as i64;
+    // The evicted changes are not mirrored anymore
+    connection.execute(
+        &format!(
+            "UPDATE projects SET watermark = NULL WHERE key IN
+             (SELECT project FROM work_items WHERE rowid IN ({}))",
+            oldest
+        ),
+        [excess],
+    )?;
+    connection.execute(
+        &format!("DELETE FROM work_items WHERE rowid IN ({})", oldest),
+        [excess],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn work_item(id: u32, rev: u64, title: &str, changed: &str) -> WorkItem {
+        serde_json::from_value(json!({
+            "id": id,
+            "fields": {
+                "System.Rev": rev,
+                "System.Title": title,
+                "System.State": "Active",
+                "System.AreaPath": "Fabrikam\\Web",
+                "System.AssignedTo": {"displayName": "Jane Doe", "uniqueName": "jane@contoso.com"},
+                "System.ChangedDate": changed,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_store() {
+        let path = std::env::temp_dir().join(format!("azdo-store-{}.db", uuid::Uuid::new_v4()));
+        let config = StoreConfig {
+            path: path.clone(),
+            max_items: 2,
+        };
+        let store = WorkItemStore::open(config.clone()).unwrap();
+        store
+            .record(
+                "Contoso",
+                "Fabrikam",
+                &[
+                    work_item(1, 2, "Fix login", "2025-01-10T10:00:00Z"),
+                    work_item(2, 1, "Add logout", "2025-01-11T10:00:00Z"),
+                ],
+            )
+            .await;
+        // Older revision, ignored
+        store
+            .record(
+                "contoso",
+                "fabrikam",
+                &[work_item(1, 1, "Stale", "2025-01-09T10:00:00Z")],
+            )
+            .await;
+
+        let filter = StoreFilter {
+            assigned_to: Some("JANE@".to_string()),
+            area_path: Some("fabrikam".to_string()),
+            ..Default::default()
+        };
+        let found = store.search("Contoso", "Fabrikam", filter).await.unwrap();
+        assert_eq!(found.iter().map(|w| w.id).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(found[1].fields["System.Title"], "Fix login");
+        let filter = StoreFilter {
+            text: Some("login".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            store
+                .search("Contoso", "Fabrikam", filter)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        let filter = StoreFilter {
+            changed_since: parse_date("2025-01-11T00:00:00+02:00"),
+            ..Default::default()
+        };
+        let found = store.search("Contoso", "Fabrikam", filter).await.unwrap();
+        assert_eq!(found.iter().map(|w| w.id).collect::<Vec<_>>(), [2]);
+
+        // Persisted, and the least recently stored evicted over the maximum
+        store
+            .mark_synced("Contoso", "Fabrikam", parse_date("2025-01-11T10:00:00Z"))
+            .await;
+        store.mark_synced("Contoso", "Fabrikam", None).await;
+        assert_eq!(
+            store.sync_state("Contoso", "Fabrikam").await.0,
+            parse_date("2025-01-11T10:00:00Z")
+        );
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        store
+            .record(
+                "Contoso",
+                "Fabrikam",
+                &[work_item(3, 1, "Add search", "2025-01-12T10:00:00Z")],
+            )
+            .await;
+        drop(store);
+        let reopened = WorkItemStore::open(config).unwrap();
+        let found = reopened
+            .search("Contoso", "Fabrikam", StoreFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].id, 3);
+        assert_eq!(reopened.sync_state("Contoso", "Fabrikam").await.0, None);
+
+        // Deleted
+        reopened.remove("contoso", "fabrikam", &[3]).await;
+        let found = reopened
+            .search("Contoso", "Fabrikam", StoreFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(found.iter().map(|w| w.id).collect::<Vec<_>>(), [2]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        migrate(&mut connection).unwrap();
+        // Migrated once
+        migrate(&mut connection).unwrap();
+        let version: usize = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+        connection
+            .pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .unwrap();
+        assert!(migrate(&mut connection).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::azure::api::AzureBoardsApi;
+use crate::azure::client::AzureError;
+use crate::azure::context;
+use crate::azure::work_items;
+use chrono::{DateTime, Duration, Utc};
+
+/// Days of changes the first sync of a project mirrors
+const INITIAL_SYNC_DAYS: i64 = 30;
+
+/// Work items queried at a time, the sync going on from the latest change fetched
+const SYNC_BATCH: usize = 1000;
+
+/// What a sync fetched
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub fetched: usize,
+    /// Changes mirrored, the next sync fetching the ones since
+    pub watermark: Option<DateTime<Utc>>,
+}
+
+/// Fetch the work items of a project changed since its watermark, or in the last days
+/// on the first sync, into the store of the client, if any
+pub async fn sync_project(
+    client: &dyn AzureBoardsApi,
+    organization: &str,
+    project: &str,
+) -> Result<SyncReport, AzureError> {
+    let Some(store) = context::current().work_item_store.clone() else {
+        return Ok(SyncReport::default());
+    };
+    let (watermark, _) = store.sync_state(organization, project).await;
+    let mut since = watermark.unwrap_or_else(|| Utc::now() - Duration::days(INITIAL_SYNC_DAYS));
+    let mut report = SyncReport::default();
+    loop {
+        let ids = work_items::query_changed_work_item_ids(
+            client,
+            organization,
+            project,
+            since,
+            SYNC_BATCH,
+        )
+        .await?;
+        // Every work item changed since is fetched (and stored) before the watermark
+        // moves past them
+        let fetched =
+            work_items::get_work_items(client, organization, project, &ids, None, &[]).await?;
+        report.fetched += fetched.len();
+        let latest = fetched
+            .iter()
+            .filter_map(|work_item| super::changed_date(&work_item.fields))
+            .max();
+        store.mark_synced(organization, project, latest).await;
+        report.watermark = store.sync_state(organization, project).await.0;
+
+        // A full batch can be followed by more changes, unless they all happened at once
+        match latest {
+            Some(latest) if ids.len() >= SYNC_BATCH && latest > since => since = latest,
+            _ => break,
+        }
+    }
+    Ok(report)
+}
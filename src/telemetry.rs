@@ -0,0 +1,74 @@
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Keeps the span exporter running, flushing the pending spans when dropped
+pub struct Telemetry {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to flush the exported spans: {}", e);
+        }
+    }
+}
+
+/// Install the tracing subscriber: events are written to stderr, filtered by
+/// `RUST_LOG`, and, with an OTLP endpoint, the spans of the tool invocations and
+/// Azure DevOps requests are exported over OTLP/HTTP
+pub fn init(otlp_endpoint: Option<&str>) -> Result<Telemetry, Box<dyn std::error::Error>> {
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(EnvFilter::from_default_env());
+
+    #[cfg(feature = "otlp")]
+    {
+        use opentelemetry::trace::TracerProvider;
+        use opentelemetry_otlp::WithExportConfig;
+        use tracing_subscriber::filter::LevelFilter;
+
+        let provider = otlp_endpoint
+            .map(|endpoint| {
+                let exporter = opentelemetry_otlp::SpanExporter::builder()
+                    .with_http()
+                    .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+                    .build()?;
+                Ok::<_, opentelemetry_otlp::ExporterBuildError>(
+                    opentelemetry_sdk::trace::SdkTracerProvider::builder()
+                        .with_batch_exporter(exporter)
+                        .with_resource(
+                            opentelemetry_sdk::Resource::builder()
+                                .with_service_name(env!("CARGO_PKG_NAME"))
+                                .build(),
+                        )
+                        .build(),
+                )
+            })
+            .transpose()?;
+        let otlp = provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer()
+                .with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+                .with_filter(LevelFilter::INFO)
+        });
+        tracing_subscriber::registry()
+            .with(fmt)
+            .with(otlp)
+            .try_init()?;
+        Ok(Telemetry { provider })
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        tracing_subscriber::registry().with(fmt).try_init()?;
+        if otlp_endpoint.is_some() {
+            tracing::warn!("OTLP export requires building with the `otlp` feature, ignoring");
+        }
+        Ok(Telemetry {})
+    }
+}
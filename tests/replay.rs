@@ -0,0 +1,117 @@
+//! The work item tools replayed against sanitized recordings of Azure DevOps, in
+//! `tests/recordings`, recorded with `--record`
+
+use mcp_for_azure_devops_boards::azure::client::AzureDevOpsClient;
+use mcp_for_azure_devops_boards::azure::transport::Replay;
+use mcp_for_azure_devops_boards::mcp::tools::work_items::{
+    create_work_item, get_work_item, query_work_items, update_work_item,
+};
+use rmcp::model::CallToolResult;
+use serde_json::{Value, json};
+use std::path::Path;
+use std::sync::Arc;
+
+fn client(recording: &str) -> AzureDevOpsClient {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/recordings")
+        .join(format!("{}.jsonl", recording));
+    AzureDevOpsClient::new().with_transport(Arc::new(Replay::open(&path).unwrap()))
+}
+
+fn args<T: serde::de::DeserializeOwned>(args: Value) -> T {
+    serde_json::from_value(args).unwrap()
+}
+
+fn structured(result: CallToolResult) -> Value {
+    assert_ne!(result.is_error, Some(true));
+    result.structured_content.unwrap()
+}
+
+#[tokio::test]
+async fn test_get_work_item() {
+    let client = client("get_work_item");
+    let result = Box::pin(get_work_item::get_work_item(
+        &client,
+        args(json!({"organization": "contoso", "project": "Web", "id": 42})),
+    ))
+    .await
+    .unwrap();
+    let work_item = &structured(result)["work_items"][0];
+    assert_eq!(work_item["Id"], 42);
+    assert_eq!(work_item["Title"], "Checkout fails with saved cards");
+    assert_eq!(work_item["AssignedTo"], "User 1 <user1@example.com>");
+}
+
+#[tokio::test]
+async fn test_query_work_items() {
+    let client = client("query_work_items");
+    let query = "SELECT [System.Id] FROM WorkItems WHERE [System.State] <> 'Closed' \
+                 ORDER BY [System.Id]";
+    let result = Box::pin(query_work_items::query_work_items(
+        &client,
+        args(json!({"organization": "contoso", "project": "Web", "query": query})),
+    ))
+    .await
+    .unwrap();
+    let result = structured(result);
+    assert_eq!(result["count"], 2);
+    let ids: Vec<&Value> = result["work_items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|work_item| &work_item["Id"])
+        .collect();
+    assert_eq!(ids, [42, 43]);
+}
+
+#[tokio::test]
+async fn test_create_work_item() {
+    let client = client("create_work_item");
+    let result = Box::pin(create_work_item::create_work_item(
+        &client,
+        args(json!({
+            "organization": "contoso",
+            "project": "Web",
+            "work_item_type": "Bug",
+            "title": "Refund button missing on mobile",
+        })),
+    ))
+    .await
+    .unwrap();
+    let work_item = &structured(result)["work_item"];
+    assert_eq!(work_item["Id"], 44);
+    assert_eq!(work_item["Rev"], 1);
+    assert_eq!(work_item["Title"], "Refund button missing on mobile");
+}
+
+#[tokio::test]
+async fn test_update_work_item() {
+    let client = client("update_work_item");
+    let result = Box::pin(update_work_item::update_work_item(
+        &client,
+        args(json!({
+            "organization": "contoso",
+            "project": "Web",
+            "id": 42,
+            "state": "Resolved",
+        })),
+    ))
+    .await
+    .unwrap();
+    let work_item = &structured(result)["work_item"];
+    assert_eq!(work_item["Rev"], 6);
+    assert_eq!(work_item["ChangedDate"], "2025-03-11T08:30:02.5Z");
+
+    // Not recorded, Azure DevOps is never reached
+    let result = Box::pin(update_work_item::update_work_item(
+        &client,
+        args(json!({
+            "organization": "contoso",
+            "project": "Web",
+            "id": 7,
+            "state": "Resolved",
+        })),
+    ))
+    .await;
+    assert!(result.is_err());
+}